@@ -106,6 +106,7 @@ describe('Staking Transaction', () => {
                 stakingAddress,
                 nonce: stakeStateAfterDeposit.nonce,
                 amount: cro.utils.toBigNumber(unbondAmount),
+                unbondingPeriod: 60,
                 network,
             },
         );
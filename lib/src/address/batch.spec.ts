@@ -0,0 +1,99 @@
+import 'mocha';
+import { expect } from 'chai';
+
+import {
+    validateTransferAddresses,
+    validateTransferAddressesAsync,
+    validateStakingAddresses,
+    validateStakingAddressesAsync,
+} from './batch';
+import { Mainnet, Testnet } from '../network';
+
+describe('batch', () => {
+    const TESTNET_ADDRESS =
+        'tcro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhqgnsq0x';
+    const MAINNET_ADDRESS =
+        'cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4';
+    const STAKING_ADDRESS = '0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0';
+
+    describe('validateTransferAddresses', () => {
+        it('should return the network of valid addresses', () => {
+            const validations = validateTransferAddresses([
+                TESTNET_ADDRESS,
+                MAINNET_ADDRESS,
+            ]);
+
+            expect(validations).to.deep.eq([
+                { address: TESTNET_ADDRESS, valid: true, network: 'Testnet' },
+                { address: MAINNET_ADDRESS, valid: true, network: 'Mainnet' },
+            ]);
+        });
+
+        it('should reject addresses of other networks when network is provided', () => {
+            const validations = validateTransferAddresses(
+                [TESTNET_ADDRESS, MAINNET_ADDRESS],
+                Mainnet,
+            );
+
+            expect(validations[0].valid).to.eq(false);
+            expect(validations[0].error).to.be.a('string');
+            expect(validations[1]).to.deep.eq({
+                address: MAINNET_ADDRESS,
+                valid: true,
+                network: 'Mainnet',
+            });
+        });
+
+        it('should report invalid addresses individually', () => {
+            const validations = validateTransferAddresses([
+                'invalid',
+                TESTNET_ADDRESS,
+            ]);
+
+            expect(validations[0].valid).to.eq(false);
+            expect(validations[0].error).to.be.a('string');
+            expect(validations[1].valid).to.eq(true);
+        });
+    });
+
+    describe('validateTransferAddressesAsync', () => {
+        it('should resolve to the same result as validateTransferAddresses', async () => {
+            const addresses = [TESTNET_ADDRESS, MAINNET_ADDRESS, 'invalid'];
+
+            const validations = await validateTransferAddressesAsync(
+                addresses,
+                Testnet,
+            );
+
+            expect(validations).to.deep.eq(
+                validateTransferAddresses(addresses, Testnet),
+            );
+        });
+    });
+
+    describe('validateStakingAddresses', () => {
+        it('should return per address result', () => {
+            const validations = validateStakingAddresses([
+                STAKING_ADDRESS,
+                '0xb5698e',
+            ]);
+
+            expect(validations[0]).to.deep.eq({
+                address: STAKING_ADDRESS,
+                valid: true,
+            });
+            expect(validations[1].valid).to.eq(false);
+            expect(validations[1].error).to.be.a('string');
+        });
+    });
+
+    describe('validateStakingAddressesAsync', () => {
+        it('should resolve to the same result as validateStakingAddresses', async () => {
+            const addresses = [STAKING_ADDRESS, 'invalid'];
+
+            const validations = await validateStakingAddressesAsync(addresses);
+
+            expect(validations).to.deep.eq(validateStakingAddresses(addresses));
+        });
+    });
+});
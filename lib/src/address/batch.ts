@@ -0,0 +1,95 @@
+import ow from 'ow';
+import { promisify } from 'util';
+
+import { owOptionalNetworkConfig, NetworkConfig } from '../network/types';
+
+const native = require('../../../native');
+
+/**
+ * Validation result of an address in a batch
+ * @typedef {object} AddressValidation
+ * @property {string} address Validated address
+ * @property {boolean} valid Whether the address is valid
+ * @property {string} [network] Name of the network the transfer address
+ * belongs to
+ * @property {string} [error] Reason of the address being invalid
+ */
+export interface AddressValidation {
+    address: string;
+    valid: boolean;
+    network?: string;
+    error?: string;
+}
+
+const validateTransferAddressesAsyncNative = promisify(
+    native.address.validateTransferAddressesAsync,
+);
+const validateStakingAddressesAsyncNative = promisify(
+    native.address.validateStakingAddressesAsync,
+);
+
+/**
+ * Validate a batch of transfer addresses
+ * @param {string[]} addresses Transfer addresses to validate
+ * @param {NetworkConfig} [network] Network the addresses should belong to.
+ * Any registered network is accepted when omitted
+ * @returns {AddressValidation[]}
+ */
+export function validateTransferAddresses(
+    addresses: string[],
+    network?: NetworkConfig,
+): AddressValidation[] {
+    ow(addresses, 'addresses', ow.array.ofType(ow.string));
+    ow(network, 'network', owOptionalNetworkConfig);
+
+    return native.address.validateTransferAddresses(
+        addresses,
+        network ? network.name : undefined,
+    );
+}
+
+/**
+ * Validate a batch of transfer addresses off the main thread
+ * @param {string[]} addresses Transfer addresses to validate
+ * @param {NetworkConfig} [network] Network the addresses should belong to.
+ * Any registered network is accepted when omitted
+ * @returns {Promise<AddressValidation[]>}
+ */
+export function validateTransferAddressesAsync(
+    addresses: string[],
+    network?: NetworkConfig,
+): Promise<AddressValidation[]> {
+    ow(addresses, 'addresses', ow.array.ofType(ow.string));
+    ow(network, 'network', owOptionalNetworkConfig);
+
+    return validateTransferAddressesAsyncNative(
+        addresses,
+        network ? network.name : undefined,
+    );
+}
+
+/**
+ * Validate a batch of staking addresses
+ * @param {string[]} addresses Staking addresses to validate
+ * @returns {AddressValidation[]}
+ */
+export function validateStakingAddresses(
+    addresses: string[],
+): AddressValidation[] {
+    ow(addresses, 'addresses', ow.array.ofType(ow.string));
+
+    return native.address.validateStakingAddresses(addresses);
+}
+
+/**
+ * Validate a batch of staking addresses off the main thread
+ * @param {string[]} addresses Staking addresses to validate
+ * @returns {Promise<AddressValidation[]>}
+ */
+export function validateStakingAddressesAsync(
+    addresses: string[],
+): Promise<AddressValidation[]> {
+    ow(addresses, 'addresses', ow.array.ofType(ow.string));
+
+    return validateStakingAddressesAsyncNative(addresses);
+}
@@ -1,3 +1,4 @@
+export * from './batch';
 export * from './staking';
 export * from './transfer';
 export { SINGLE_SIGN_ADDRESS } from './constant';
@@ -2,7 +2,7 @@ import 'mocha';
 import { expect } from 'chai';
 
 import { KeyPair } from '../key_pair';
-import { staking } from './staking';
+import { staking, parseStakingAddress } from './staking';
 
 describe('staking', () => {
     it('should throw TypeError when neither KeyPair nor PublicKey is provided', () => {
@@ -48,4 +48,20 @@ describe('staking', () => {
             }),
         ).to.eq('0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0');
     });
+
+    describe('parseStakingAddress', () => {
+        it('should throw Error when the address is invalid', () => {
+            expect(() => parseStakingAddress('0xinvalid')).to.throw(
+                'Unable to deserialize staking address',
+            );
+        });
+
+        it('should return the 20 bytes redeem address', () => {
+            expect(
+                parseStakingAddress(
+                    '0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0',
+                ).toString('hex'),
+            ).to.eq('b5698ee21f69a6184afbe59b3626ed9d4bd755b0');
+        });
+    });
 });
@@ -41,3 +41,14 @@ export interface StakingOptions {
     publicKey?: Buffer;
     keyPair?: KeyPair;
 }
+
+/**
+ * Decode a staking address into its 20 bytes redeem address
+ * @param {string} address Staking address to decode
+ * @returns {Buffer} redeem address
+ */
+export function parseStakingAddress(address: string): Buffer {
+    ow(address, 'address', ow.string);
+
+    return native.address.parseStakingAddress(address);
+}
@@ -2,8 +2,19 @@ import 'mocha';
 import { expect } from 'chai';
 
 import { KeyPair } from '../key_pair';
-import { transfer } from './transfer';
-import { Mainnet, Devnet } from '../network';
+import {
+    transfer,
+    parseTransferAddress,
+    getTransferAddressNetwork,
+    convertTransferAddress,
+} from './transfer';
+import {
+    Mainnet,
+    Testnet,
+    Devnet,
+    NetworkEnum,
+    registerNetwork,
+} from '../network';
 import { FeeConfig, FeeAlgorithm } from '../fee';
 import { BigNumber } from '../utils';
 
@@ -43,10 +54,12 @@ describe('transfer', () => {
             '041ff5820f619d51663efbb233eb03bd5d434f63c352a5633717d8b570daa43c190bddc906ed9b8601c10c2b58e347f6e1cc4035b391b98be1d9afa9c5ead9423b',
             'hex',
         );
-        const network = Devnet({
-            feeConfig: ANY_FEE_CONFIG,
-            chainHexId: 'AB',
-        });
+        const network = registerNetwork(
+            Devnet({
+                feeConfig: ANY_FEE_CONFIG,
+                chainHexId: 'AB',
+            }),
+        );
 
         expect(
             transfer({
@@ -65,10 +78,12 @@ describe('transfer', () => {
                 'hex',
             ),
         );
-        const network = Devnet({
-            feeConfig: ANY_FEE_CONFIG,
-            chainHexId: 'AB',
-        });
+        const network = registerNetwork(
+            Devnet({
+                feeConfig: ANY_FEE_CONFIG,
+                chainHexId: 'AB',
+            }),
+        );
 
         expect(
             transfer({
@@ -96,4 +111,60 @@ describe('transfer', () => {
             'cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4',
         );
     });
+
+    describe('parseTransferAddress', () => {
+        it('should throw Error when the address is invalid', () => {
+            expect(() => parseTransferAddress('cro1invalid')).to.throw(
+                'Invalid transfer address',
+            );
+        });
+
+        it('should throw Error when the address prefix is not registered', () => {
+            expect(() =>
+                parseTransferAddress(
+                    'xcro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4',
+                ),
+            ).to.throw('Unknown transfer address prefix xcro');
+        });
+
+        it('should return network, address prefix and root hash of the address', () => {
+            const parsed = parseTransferAddress(
+                'cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4',
+            );
+
+            expect(parsed.network).to.eq(NetworkEnum.Mainnet);
+            expect(parsed.addressPrefix).to.eq('cro');
+            expect(parsed.rootHash.length).to.eq(32);
+        });
+    });
+
+    describe('getTransferAddressNetwork', () => {
+        it('should return the network the address belongs to', () => {
+            expect(
+                getTransferAddressNetwork(
+                    'dcro1qkwn2jde2cq5e6ef6jd0s60y24vxc9zdv5ejp0kyy7d6td7n2kdqyq4n4v',
+                ),
+            ).to.eq(NetworkEnum.Devnet);
+        });
+    });
+
+    describe('convertTransferAddress', () => {
+        it('should convert the address to the target network', () => {
+            const mainnetAddress =
+                'cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4';
+
+            const testnetAddress = convertTransferAddress(
+                mainnetAddress,
+                Testnet,
+            );
+
+            expect(testnetAddress.startsWith('tcro1')).to.eq(true);
+            expect(parseTransferAddress(testnetAddress).rootHash).to.deep.eq(
+                parseTransferAddress(mainnetAddress).rootHash,
+            );
+            expect(convertTransferAddress(testnetAddress, Mainnet)).to.eq(
+                mainnetAddress,
+            );
+        });
+    });
 });
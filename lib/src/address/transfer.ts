@@ -49,3 +49,55 @@ export interface TransferOptions {
     keyPair?: KeyPair;
     network: NetworkConfig;
 }
+
+/**
+ * Decoded transfer address
+ * @typedef {object} ParsedTransferAddress
+ * @property {string} network Name of the network the address belongs to
+ * @property {string} addressPrefix Bech32 human readable part of the address
+ * @property {Buffer} rootHash Merkle root of the address public keys tree
+ */
+export interface ParsedTransferAddress {
+    network: string;
+    addressPrefix: string;
+    rootHash: Buffer;
+}
+
+/**
+ * Decode a transfer address of any registered network
+ * @param {string} address Transfer address to decode
+ * @returns {ParsedTransferAddress}
+ */
+export function parseTransferAddress(address: string): ParsedTransferAddress {
+    ow(address, 'address', ow.string);
+
+    return native.address.parseTransferAddress(address);
+}
+
+/**
+ * Returns the name of the network a transfer address belongs to, e.g.
+ * `Testnet` for a `tcro` address
+ * @param {string} address Transfer address
+ * @returns {string} network name
+ */
+export function getTransferAddressNetwork(address: string): string {
+    ow(address, 'address', ow.string);
+
+    return native.address.getTransferAddressNetwork(address);
+}
+
+/**
+ * Convert a transfer address to the same address on another network
+ * @param {string} address Transfer address to convert
+ * @param {NetworkConfig} network Network to convert to
+ * @returns {string} transfer address on the target network
+ */
+export function convertTransferAddress(
+    address: string,
+    network: NetworkConfig,
+): string {
+    ow(address, 'address', ow.string);
+    ow(network, 'network', owNetworkConfig);
+
+    return native.address.convertTransferAddress(address, network.name);
+}
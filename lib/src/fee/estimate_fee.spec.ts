@@ -0,0 +1,234 @@
+import 'mocha';
+import { expect } from 'chai';
+
+import { estimateFee, TransactionKind } from './estimate_fee';
+import { FeeAlgorithm, FeeConfig } from './types';
+import { DepositTransactionBuilder } from '../transaction/staking/deposit_transaction_builder';
+import { UnbondTransactionBuilder } from '../transaction/staking/unbond_transaction_builder';
+import { Devnet, registerNetwork } from '../network';
+import { KeyPair } from '../key_pair';
+import { transfer } from '../address';
+import { BigNumber } from '../utils';
+
+describe('estimateFee', () => {
+    const SAMPLE_FEE_CONFIG: FeeConfig = {
+        algorithm: FeeAlgorithm.LinearFee,
+        constant: new BigNumber(1.1),
+        coefficient: new BigNumber(1.25),
+    };
+    const SAMPLE_NETWORK = registerNetwork(
+        Devnet({
+            feeConfig: SAMPLE_FEE_CONFIG,
+            chainHexId: 'AB',
+        }),
+    );
+    const SAMPLE_KEY_PAIR = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+    const SAMPLE_STAKING_ADDRESS = '0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0';
+
+    const newUnbondTransactionBuilder = () =>
+        new UnbondTransactionBuilder({
+            stakingAddress: SAMPLE_STAKING_ADDRESS,
+            nonce: new BigNumber(1),
+            amount: new BigNumber(1000),
+            unbondingPeriod: 86400,
+            network: SAMPLE_NETWORK,
+        });
+    const newDepositTransactionBuilder = () =>
+        new DepositTransactionBuilder({
+            stakingAddress: SAMPLE_STAKING_ADDRESS,
+            network: SAMPLE_NETWORK,
+        }).addInput({
+            prevTxId:
+                '0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF',
+            prevIndex: 2,
+            prevOutput: {
+                address: transfer({
+                    keyPair: SAMPLE_KEY_PAIR,
+                    network: SAMPLE_NETWORK,
+                }),
+                value: new BigNumber('100000000'),
+            },
+        });
+
+    it('should throw Error when kind is unsupported', () => {
+        expect(() => {
+            estimateFee(
+                newUnbondTransactionBuilder().toUnsignedHex(),
+                'invalid' as any,
+                SAMPLE_FEE_CONFIG,
+            );
+        }).to.throw('Expected string to be one of');
+    });
+
+    it('should throw Error when unsigned transaction has no encoding prefix of the kind', () => {
+        expect(() => {
+            estimateFee(
+                newUnbondTransactionBuilder().toUnsignedHex(),
+                TransactionKind.Unjail,
+                SAMPLE_FEE_CONFIG,
+            );
+        }).to.throw('Expected unsigned unjail transaction to start with 0101');
+    });
+
+    it('should throw Error when witness shape is provided for staked state operation', () => {
+        expect(() => {
+            estimateFee(
+                newUnbondTransactionBuilder().toUnsignedHex(),
+                TransactionKind.Unbond,
+                SAMPLE_FEE_CONFIG,
+                [{ requiredSigners: 1, totalSigners: 1 }],
+            );
+        }).to.throw(
+            'Witness shape only applies to transfer and deposit transactions',
+        );
+    });
+
+    it('should throw Error when witness shape does not match the inputs', () => {
+        expect(() => {
+            estimateFee(
+                newDepositTransactionBuilder().toUnsignedHex(),
+                TransactionKind.Deposit,
+                SAMPLE_FEE_CONFIG,
+                [
+                    { requiredSigners: 1, totalSigners: 1 },
+                    { requiredSigners: 1, totalSigners: 1 },
+                ],
+            );
+        }).to.throw('Witness shape has 2 inputs but transaction has 1 inputs');
+    });
+
+    it('should return the size of the signed unbond transaction', () => {
+        const builder = newUnbondTransactionBuilder();
+
+        const estimation = estimateFee(
+            builder.toUnsignedHex(),
+            TransactionKind.Unbond,
+            SAMPLE_FEE_CONFIG,
+        );
+
+        const signedTxHex = builder.sign(SAMPLE_KEY_PAIR).toHex();
+        expect(estimation.size.total).to.eq(signedTxHex.length);
+        expect(
+            estimation.size.transaction +
+                estimation.size.witness +
+                estimation.size.overhead,
+        ).to.eq(estimation.size.total);
+        expect(estimation.fee.isGreaterThan(0)).to.eq(true);
+    });
+
+    it('should return the same fee as the deposit transaction builder', () => {
+        const builder = newDepositTransactionBuilder();
+
+        const estimation = estimateFee(
+            builder.toUnsignedHex(),
+            TransactionKind.Deposit,
+            SAMPLE_FEE_CONFIG,
+        );
+
+        expect(estimation.fee.toString(10)).to.eq(
+            builder.estimateFee().toString(10),
+        );
+    });
+
+    it('should return higher fee for multi-signature inputs', () => {
+        const unsignedTx = newDepositTransactionBuilder().toUnsignedHex();
+
+        const singleSigner = estimateFee(
+            unsignedTx,
+            TransactionKind.Deposit,
+            SAMPLE_FEE_CONFIG,
+        );
+        const multiSigner = estimateFee(
+            unsignedTx,
+            TransactionKind.Deposit,
+            SAMPLE_FEE_CONFIG,
+            [{ requiredSigners: 2, totalSigners: 5 }],
+        );
+
+        expect(multiSigner.size.witness).to.be.greaterThan(
+            singleSigner.size.witness,
+        );
+        expect(multiSigner.fee.isGreaterThan(singleSigner.fee)).to.eq(true);
+    });
+
+    describe('fee schedule', () => {
+        const estimateUnbondFee = (feeConfig: FeeConfig) =>
+            estimateFee(
+                newUnbondTransactionBuilder().toUnsignedHex(),
+                TransactionKind.Unbond,
+                feeConfig,
+            );
+
+        it('should throw Error when fee tiers are not sorted', () => {
+            expect(() => {
+                estimateUnbondFee({
+                    algorithm: FeeAlgorithm.TieredFee,
+                    tiers: [
+                        {
+                            maxSize: 1000,
+                            constant: new BigNumber(1),
+                            coefficient: new BigNumber(1),
+                        },
+                        {
+                            maxSize: 500,
+                            constant: new BigNumber(1),
+                            coefficient: new BigNumber(1),
+                        },
+                    ],
+                });
+            }).to.throw(
+                'Expected tiers to be sorted by increasing `maxSize` with only the last tier having no `maxSize`',
+            );
+        });
+
+        it('should charge the minimum fee when the linear fee is lower', () => {
+            const estimation = estimateUnbondFee({
+                ...SAMPLE_FEE_CONFIG,
+                minimumFee: new BigNumber('100000'),
+            } as FeeConfig);
+
+            expect(estimation.fee.toString(10)).to.eq('100000');
+        });
+
+        it('should charge the linear fee of the tier the size fits in', () => {
+            const linearEstimation = estimateUnbondFee(SAMPLE_FEE_CONFIG);
+            const { total } = linearEstimation.size;
+
+            const smallTierEstimation = estimateUnbondFee({
+                algorithm: FeeAlgorithm.TieredFee,
+                tiers: [
+                    {
+                        maxSize: total,
+                        constant: new BigNumber(1.1),
+                        coefficient: new BigNumber(1.25),
+                    },
+                    {
+                        constant: new BigNumber(1000),
+                        coefficient: new BigNumber(1000),
+                    },
+                ],
+            });
+            const largeTierEstimation = estimateUnbondFee({
+                algorithm: FeeAlgorithm.TieredFee,
+                tiers: [
+                    {
+                        maxSize: total - 1,
+                        constant: new BigNumber(0),
+                        coefficient: new BigNumber(0),
+                    },
+                    {
+                        constant: new BigNumber(1.1),
+                        coefficient: new BigNumber(1.25),
+                    },
+                ],
+            });
+
+            expect(smallTierEstimation.fee.toString(10)).to.eq(
+                linearEstimation.fee.toString(10),
+            );
+            expect(largeTierEstimation.fee.toString(10)).to.eq(
+                linearEstimation.fee.toString(10),
+            );
+        });
+    });
+});
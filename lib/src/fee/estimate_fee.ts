@@ -0,0 +1,118 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { FeeConfig, owFeeConfig, parseFeeConfigForNative } from './types';
+import { InputAddressParams, owInputAddressParams } from '../types';
+import { ENCODING_PREFIX } from '../transaction/encoding';
+
+const native = require('../../../native');
+
+/**
+ * Kind of the unsigned transaction to estimate fee
+ */
+export enum TransactionKind {
+    Transfer = 'transfer',
+    Deposit = 'deposit',
+    WithdrawUnbonded = 'withdrawUnbonded',
+    Unbond = 'unbond',
+    Unjail = 'unjail',
+    NodeJoin = 'nodeJoin',
+}
+
+/**
+ * Encoded size of the signed transaction in bytes
+ * @typedef {object} TransactionSizeBreakdown
+ * @property {number} transaction unsigned transaction
+ * @property {number} witness witnesses of all inputs, or the staked state
+ * operation witness
+ * @property {number} overhead transaction envelope and encryption overhead
+ * @property {number} total broadcast-able transaction
+ */
+export interface TransactionSizeBreakdown {
+    transaction: number;
+    witness: number;
+    overhead: number;
+    total: number;
+}
+
+/**
+ * @typedef {object} FeeEstimation
+ * @property {BigNumber} fee fee in basic unit
+ * @property {TransactionSizeBreakdown} size encoded size breakdown
+ */
+export interface FeeEstimation {
+    fee: BigNumber;
+    size: TransactionSizeBreakdown;
+}
+
+/**
+ * Prefix of the unsigned hex of the builders of public transactions
+ * @internal
+ */
+const UNSIGNED_HEX_PREFIX: { [kind: string]: Buffer } = {
+    [TransactionKind.WithdrawUnbonded]: ENCODING_PREFIX.WITHDRAW_UNBONDED,
+    [TransactionKind.Unbond]: ENCODING_PREFIX.UNBOND,
+    [TransactionKind.Unjail]: ENCODING_PREFIX.UNJAIL,
+    [TransactionKind.NodeJoin]: ENCODING_PREFIX.NODE_JOIN,
+};
+
+/**
+ * Estimate the exact fee of an unsigned transaction once it is signed. The
+ * transaction is completed with dummy witnesses of the same size as the
+ * real ones.
+ *
+ * @param {Buffer} unsignedTx unsigned transaction as returned by
+ * `toUnsignedHex()` of the builder of the kind, or the raw transfer
+ * transaction
+ * @param {TransactionKind} kind kind of the transaction
+ * @param {FeeConfig} feeConfig fee configuration of the network
+ * @param {InputAddressParams[]} [witnessShape] signers of the address of
+ * each input of transfer and deposit transactions. Default to single signer
+ * addresses
+ * @returns {FeeEstimation}
+ */
+export const estimateFee = (
+    unsignedTx: Buffer,
+    kind: TransactionKind,
+    feeConfig: FeeConfig,
+    witnessShape?: InputAddressParams[],
+): FeeEstimation => {
+    ow(unsignedTx, 'unsignedTx', ow.buffer);
+    ow(kind, 'kind', ow.string.oneOf(Object.values(TransactionKind)));
+    ow(feeConfig, 'feeConfig', owFeeConfig);
+    ow(
+        witnessShape,
+        'witnessShape',
+        ow.optional.array.ofType(owInputAddressParams),
+    );
+
+    const { fee, size } = native.feeEstimation.estimateFee({
+        unsignedRawTx: stripUnsignedHexPrefix(unsignedTx, kind),
+        kind,
+        feeConfig: parseFeeConfigForNative(feeConfig),
+        witnessShape,
+    });
+
+    return {
+        fee: new BigNumber(fee),
+        size,
+    };
+};
+
+const stripUnsignedHexPrefix = (
+    unsignedTx: Buffer,
+    kind: TransactionKind,
+): Buffer => {
+    const prefix = UNSIGNED_HEX_PREFIX[kind];
+    if (!prefix) {
+        return unsignedTx;
+    }
+    if (!unsignedTx.slice(0, prefix.length).equals(prefix)) {
+        const expectedPrefix = prefix.toString('hex');
+        throw new Error(
+            `Expected unsigned ${kind} transaction to start with ${expectedPrefix}`,
+        );
+    }
+
+    return unsignedTx.slice(prefix.length);
+};
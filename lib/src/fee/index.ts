@@ -1,2 +1,14 @@
 export { ZERO_LINEAR_FEE } from './constant';
-export { FeeAlgorithm, FeeConfig, LinearFeeConfig } from './types';
+export {
+    FeeAlgorithm,
+    FeeConfig,
+    LinearFeeConfig,
+    TieredFeeConfig,
+    FeeTier,
+} from './types';
+export {
+    estimateFee,
+    TransactionKind,
+    TransactionSizeBreakdown,
+    FeeEstimation,
+} from './estimate_fee';
@@ -1,19 +1,58 @@
 import ow from 'ow';
 import BigNumber from 'bignumber.js';
 
+import { owCoin } from '../types';
+
 export enum FeeAlgorithm {
     LinearFee = 'LinearFee',
+    TieredFee = 'TieredFee',
 }
 
 export type FeeConfig =
     | LinearFeeConfig
+    | TieredFeeConfig
     | {
           algorithm: FeeAlgorithm;
       };
+/**
+ * Fee of `constant + coefficient * size`, floored at `minimumFee`
+ * @typedef {object} LinearFeeConfig
+ * @property {FeeAlgorithm.LinearFee} algorithm
+ * @property {BigNumber} constant
+ * @property {BigNumber} coefficient
+ * @property {BigNumber} [minimumFee] minimum fee in basic unit
+ */
 export type LinearFeeConfig = {
     algorithm: FeeAlgorithm.LinearFee;
     constant: BigNumber;
     coefficient: BigNumber;
+    minimumFee?: BigNumber;
+};
+/**
+ * Linear fee of the first tier the transaction size fits in, floored at
+ * `minimumFee`
+ * @typedef {object} TieredFeeConfig
+ * @property {FeeAlgorithm.TieredFee} algorithm
+ * @property {FeeTier[]} tiers tiers sorted by increasing `maxSize`, the last
+ * of which has no `maxSize`
+ * @property {BigNumber} [minimumFee] minimum fee in basic unit
+ */
+export type TieredFeeConfig = {
+    algorithm: FeeAlgorithm.TieredFee;
+    tiers: FeeTier[];
+    minimumFee?: BigNumber;
+};
+/**
+ * @typedef {object} FeeTier
+ * @property {number} [maxSize] inclusive upper bound of the transaction
+ * size in bytes
+ * @property {BigNumber} constant
+ * @property {BigNumber} coefficient
+ */
+export type FeeTier = {
+    maxSize?: number;
+    constant: BigNumber;
+    coefficient: BigNumber;
 };
 
 /**
@@ -32,7 +71,16 @@ const owLinearFeeMilli = ow.object.validate((value: object) => ({
  * @internal
  */
 const owLinearFeeConfig = ow.object.exactShape({
-    algorithm: owFeeAlgorithm,
+    algorithm: ow.string.equals(FeeAlgorithm.LinearFee),
+    constant: owLinearFeeMilli,
+    coefficient: owLinearFeeMilli,
+    minimumFee: ow.optional.any(owCoin),
+});
+/**
+ * @internal
+ */
+const owFeeTier = ow.object.exactShape({
+    maxSize: ow.optional.number.integer.greaterThan(0),
     constant: owLinearFeeMilli,
     coefficient: owLinearFeeMilli,
 });
@@ -40,33 +88,87 @@ const owLinearFeeConfig = ow.object.exactShape({
 /**
  * @internal
  */
-export const owFeeConfig = ow.any(owLinearFeeConfig);
+const isFeeTiersSorted = (tiers: FeeTier[]): boolean =>
+    tiers.every((tier, i) => {
+        if (i === tiers.length - 1) {
+            return tier.maxSize === undefined;
+        }
+        if (tier.maxSize === undefined) {
+            return false;
+        }
+        return i === 0 || tier.maxSize > tiers[i - 1].maxSize!;
+    });
+
+/**
+ * @internal
+ */
+const owTieredFeeConfig = ow.object.exactShape({
+    algorithm: ow.string.equals(FeeAlgorithm.TieredFee),
+    tiers: ow.array.nonEmpty.ofType(owFeeTier).validate((value: unknown[]) => ({
+        validator: isFeeTiersSorted(value as FeeTier[]),
+        message:
+            'Expected tiers to be sorted by increasing `maxSize` with only the last tier having no `maxSize`',
+    })),
+    minimumFee: ow.optional.any(owCoin),
+});
+
+/**
+ * @internal
+ */
+export const owFeeConfig = ow.any(owLinearFeeConfig, owTieredFeeConfig);
 /**
  * @internal
  */
-export const owOptionalFeeConfig = ow.optional.any(owLinearFeeConfig);
+export const owOptionalFeeConfig = ow.optional.any(
+    owLinearFeeConfig,
+    owTieredFeeConfig,
+);
 
 export const parseFeeConfigForNative = (
     feeConfig: FeeConfig,
 ): NativeFeeConfig => {
     if (feeConfig.algorithm === FeeAlgorithm.LinearFee) {
+        const linearFeeConfig = feeConfig as LinearFeeConfig;
+        return {
+            algorithm: linearFeeConfig.algorithm,
+            constant: linearFeeConfig.constant.toString(10),
+            coefficient: linearFeeConfig.coefficient.toString(10),
+            minimumFee: parseMinimumFeeForNative(linearFeeConfig.minimumFee),
+        };
+    }
+    if (feeConfig.algorithm === FeeAlgorithm.TieredFee) {
+        const tieredFeeConfig = feeConfig as TieredFeeConfig;
         return {
-            ...feeConfig,
-            constant: (feeConfig as LinearFeeConfig).constant.toString(10),
-            coefficient: (feeConfig as LinearFeeConfig).coefficient.toString(
-                10,
-            ),
+            algorithm: tieredFeeConfig.algorithm,
+            tiers: tieredFeeConfig.tiers.map((tier) => ({
+                maxSize: tier.maxSize,
+                constant: tier.constant.toString(10),
+                coefficient: tier.coefficient.toString(10),
+            })),
+            minimumFee: parseMinimumFeeForNative(tieredFeeConfig.minimumFee),
         };
     }
     throw new Error(`Unsupported fee algorithm: ${feeConfig.algorithm}`);
 };
 
-type NativeFeeConfig = NativeLinearFeeConfig | NativeBaseFeeConfig;
+const parseMinimumFeeForNative = (
+    minimumFee?: BigNumber,
+): string | undefined => (minimumFee ? minimumFee.toString(10) : undefined);
+
+type NativeFeeConfig = NativeLinearFeeConfig | NativeTieredFeeConfig;
 type NativeLinearFeeConfig = {
     algorithm: FeeAlgorithm;
     constant: string;
     coefficient: string;
+    minimumFee?: string;
 };
-type NativeBaseFeeConfig = {
+type NativeTieredFeeConfig = {
     algorithm: FeeAlgorithm;
+    tiers: NativeFeeTier[];
+    minimumFee?: string;
+};
+type NativeFeeTier = {
+    maxSize?: number;
+    constant: string;
+    coefficient: string;
 };
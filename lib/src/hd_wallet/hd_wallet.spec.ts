@@ -3,7 +3,8 @@ import { expect } from 'chai';
 
 import { HDWallet } from './hd_wallet';
 import { KeyPair } from '../key_pair';
-import { NetworkEnum } from '../network';
+import { NetworkEnum, Testnet } from '../network';
+import { transfer, staking } from '../address';
 
 const native = require('../../../native');
 
@@ -132,6 +133,81 @@ describe('HDWallet', () => {
         });
     });
 
+    describe('deriveAddresses', () => {
+        it('should throw Error when the range exceeds the hardened index', () => {
+            const wallet = createWallet();
+
+            expect(() => {
+                wallet.deriveAddresses({
+                    network: Testnet,
+                    account: 0,
+                    startIndex: 2 ** 31 - 1,
+                    count: 2,
+                });
+            }).to.throw(
+                'Invalid options at `count`: index range should be below the hardened index 2^31',
+            );
+        });
+
+        it('should return key pairs and addresses of the range', () => {
+            const wallet = createWallet();
+
+            const derivedAddresses = wallet.deriveAddresses({
+                network: Testnet,
+                account: 2,
+                startIndex: 3,
+                count: 2,
+                includePrivateKey: true,
+            });
+
+            expect(derivedAddresses.length).to.eq(2);
+            derivedAddresses.forEach((derivedAddress, i) => {
+                const keyPair = wallet.derive(`m/44'/1'/2'/0/${3 + i}`);
+
+                expect(derivedAddress.index).to.eq(3 + i);
+                expect(derivedAddress.privateKey).to.deep.eq(
+                    keyPair.privateKey,
+                );
+                expect(derivedAddress.publicKey).to.deep.eq(keyPair.publicKey);
+                expect(derivedAddress.transferAddress).to.eq(
+                    transfer({ keyPair, network: Testnet }),
+                );
+                expect(derivedAddress.stakingAddress).to.eq(
+                    staking({ keyPair }),
+                );
+            });
+        });
+
+        it('should omit private keys by default', () => {
+            const wallet = createWallet();
+
+            const derivedAddresses = wallet.deriveAddresses({
+                network: Testnet,
+                account: 0,
+                startIndex: 0,
+                count: 1,
+            });
+
+            expect(derivedAddresses[0].privateKey).to.eq(undefined);
+        });
+    });
+
+    describe('deriveAddressesAsync', () => {
+        it('should resolve to the same result as deriveAddresses', async () => {
+            const wallet = createWallet();
+            const options = {
+                network: Testnet,
+                account: 1,
+                startIndex: 10,
+                count: 5,
+            };
+
+            const derivedAddresses = await wallet.deriveAddressesAsync(options);
+
+            expect(derivedAddresses).to.deep.eq(wallet.deriveAddresses(options));
+        });
+    });
+
     describe('generateMnemonic', () => {
         it('should generate unique Mnemonic', () => {
             const firstMnemonic = HDWallet.generateMnemonic();
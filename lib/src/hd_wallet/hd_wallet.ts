@@ -1,7 +1,15 @@
 import ow from 'ow';
 import * as bip32 from 'bip32';
 import * as bip39 from 'bip39';
+import { promisify } from 'util';
 import { KeyPair } from '../key_pair';
+import { owNetworkConfig, NetworkConfig } from '../network/types';
+
+const native = require('../../../native');
+
+const deriveAddressesFromSeedAsync = promisify(
+    native.hdWallet.deriveAddressesFromSeedAsync,
+);
 
 export class HDWallet {
     private seed: Buffer;
@@ -41,6 +49,48 @@ export class HDWallet {
         return this.derive(path);
     }
 
+    /**
+     * Derive the key pairs and addresses of a range of indexes of an account
+     * @param {DeriveAddressesOptions} options
+     * @returns {DerivedAddress[]}
+     */
+    public deriveAddresses(options: DeriveAddressesOptions): DerivedAddress[] {
+        ow(options, 'options', owDeriveAddressesOptions);
+
+        return native.hdWallet.deriveAddressesFromSeed(
+            this.toNativeDeriveAddressesOptions(options),
+        );
+    }
+
+    /**
+     * Derive the key pairs and addresses of a range of indexes of an account
+     * off the main thread. Prefer it over `deriveAddresses` for large batches
+     * @param {DeriveAddressesOptions} options
+     * @returns {Promise<DerivedAddress[]>}
+     */
+    public deriveAddressesAsync(
+        options: DeriveAddressesOptions,
+    ): Promise<DerivedAddress[]> {
+        ow(options, 'options', owDeriveAddressesOptions);
+
+        return deriveAddressesFromSeedAsync(
+            this.toNativeDeriveAddressesOptions(options),
+        );
+    }
+
+    private toNativeDeriveAddressesOptions(
+        options: DeriveAddressesOptions,
+    ): object {
+        return {
+            seed: this.seed,
+            network: options.network.name,
+            account: options.account,
+            startIndex: options.startIndex,
+            count: options.count,
+            includePrivateKey: options.includePrivateKey || false,
+        };
+    }
+
     public toSeed(): Buffer {
         return this.seed;
     }
@@ -76,3 +126,45 @@ export class HDWallet {
         Viewkey: 2,
     };
 }
+
+const owDeriveAddressesOptions = ow.object.exactShape({
+    network: owNetworkConfig,
+    account: ow.number.uint32,
+    startIndex: ow.number.uint32,
+    count: ow.number.uint32,
+    includePrivateKey: ow.optional.boolean,
+});
+
+/**
+ * @typedef {object} DeriveAddressesOptions
+ * @property {NetworkConfig} network Network of the derivation path and addresses
+ * @property {number} account BIP44 account
+ * @property {number} startIndex First address index to derive
+ * @property {number} count Number of addresses to derive
+ * @property {boolean} [includePrivateKey=false] Whether to return the private keys
+ */
+export interface DeriveAddressesOptions {
+    network: NetworkConfig;
+    account: number;
+    startIndex: number;
+    count: number;
+    includePrivateKey?: boolean;
+}
+
+/**
+ * @typedef {object} DerivedAddress
+ * @property {number} index Address index of the derivation path
+ * @property {Buffer} publicKey
+ * @property {Buffer} compressedPublicKey
+ * @property {Buffer} [privateKey] Only present when `includePrivateKey` is set
+ * @property {string} transferAddress
+ * @property {string} stakingAddress
+ */
+export interface DerivedAddress {
+    index: number;
+    publicKey: Buffer;
+    compressedPublicKey: Buffer;
+    privateKey?: Buffer;
+    transferAddress: string;
+    stakingAddress: string;
+}
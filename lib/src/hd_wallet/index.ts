@@ -1 +1 @@
-export { HDWallet } from './hd_wallet';
+export { HDWallet, DeriveAddressesOptions, DerivedAddress } from './hd_wallet';
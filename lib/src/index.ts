@@ -1,14 +1,17 @@
 import * as address from './address';
 import * as fee from './fee';
 import * as network from './network';
+import * as tendermint from './tendermint';
 import * as transaction from './transaction';
 import * as utils from './utils';
 
 export { Input, Output, Timespec } from './types';
 
-export { address, fee, network, transaction, utils };
+export { address, fee, network, tendermint, transaction, utils };
 
 export { HDWallet } from './hd_wallet';
 export { KeyPair } from './key_pair';
 export { TransferTransactionBuilder } from './transaction/transfer';
 export { MultiSigBuilder } from './multi_sig';
+export { ValidatorKey } from './validator_key';
+export { WalletDb } from './wallet_db';
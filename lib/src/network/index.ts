@@ -1,2 +1,9 @@
-export { NetworkEnum, Mainnet, Testnet, Devnet, fromChainId } from './network';
-export { NetworkConfig, DevnetOptions } from './types';
+export {
+    NetworkEnum,
+    Mainnet,
+    Testnet,
+    Devnet,
+    registerNetwork,
+    fromChainId,
+} from './network';
+export { NetworkConfig, DevnetOptions, CustomNetworkOptions } from './types';
@@ -1,7 +1,15 @@
 import 'mocha';
 import { expect } from 'chai';
 
-import { Mainnet, Testnet, Devnet, fromChainId, NetworkEnum } from './network';
+import {
+    Mainnet,
+    Testnet,
+    Devnet,
+    registerNetwork,
+    fromChainId,
+    NetworkEnum,
+} from './network';
+import { transfer } from '../address/transfer';
 import { FeeConfig, FeeAlgorithm } from '../fee';
 import { BigNumber } from '../utils';
 
@@ -105,7 +113,7 @@ describe('Network', () => {
                     chainHexId: 'AB',
                 });
             }).to.throw(
-                'Expected property property string `algorithm` to be one of `["LinearFee"]`, got `unsupported-algorithm` in object `feeConfig` in object `options`',
+                'Expected property property string `algorithm` to be `LinearFee`, got `unsupported-algorithm` in object `feeConfig` in object `options`',
             );
         });
 
@@ -132,8 +140,112 @@ describe('Network', () => {
         });
     });
 
+    describe('registerNetwork', () => {
+        const ANY_FEE_CONFIG: FeeConfig = {
+            algorithm: FeeAlgorithm.LinearFee,
+            constant: new BigNumber(1.1),
+            coefficient: new BigNumber(1.25),
+        };
+
+        it('should throw Error when option is invalid', () => {
+            expect(() => {
+                registerNetwork({
+                    name: 'Staging',
+                    chainHexId: 'C1',
+                    addressPrefix: 'Invalid-Prefix',
+                    bip44CoinType: 1,
+                    feeConfig: ANY_FEE_CONFIG,
+                });
+            }).to.throw(
+                'Expected property `addressPrefix` to match `/^[0-9a-z]{1,83}$/`, got `Invalid-Prefix` in object `options`',
+            );
+        });
+
+        it('should throw Error when the chainHexId is registered to another network', () => {
+            expect(() => {
+                registerNetwork({
+                    name: 'Staging',
+                    chainHexId: Mainnet.chainHexId,
+                    addressPrefix: 'scro',
+                    bip44CoinType: 1,
+                    feeConfig: ANY_FEE_CONFIG,
+                });
+            }).to.throw('Chain hex id 2A is already registered to network Mainnet');
+        });
+
+        it('should throw Error when the addressPrefix is registered to another network', () => {
+            expect(() => {
+                registerNetwork({
+                    name: 'Staging',
+                    chainHexId: 'C2',
+                    addressPrefix: 'tcro',
+                    bip44CoinType: 1,
+                    feeConfig: ANY_FEE_CONFIG,
+                });
+            }).to.throw(
+                'Address prefix tcro is already registered to network Testnet',
+            );
+        });
+
+        it('should return network config of the custom network', () => {
+            const network = registerNetwork({
+                name: 'Staging',
+                chainHexId: 'C1',
+                addressPrefix: 'scro',
+                bip44CoinType: 1,
+                feeConfig: ANY_FEE_CONFIG,
+            });
+
+            expect(network.name).to.eq('Staging');
+            expect(network.chainHexId).to.deep.eq(Buffer.from('C1', 'hex'));
+            expect(network.bip44Path).to.eq("m/44'/1'/{ACCOUNT}'/0/{INDEX}");
+        });
+
+        it('should register Devnet constants', () => {
+            const devnet = Devnet({
+                feeConfig: ANY_FEE_CONFIG,
+                chainHexId: 'AC',
+            });
+
+            const network = registerNetwork(devnet);
+
+            expect(network).to.deep.eq(devnet);
+            expect(fromChainId(Buffer.from('AC', 'hex'))).to.eq(
+                NetworkEnum.Devnet,
+            );
+        });
+
+        it('should create transfer address with the custom address prefix', () => {
+            const network = registerNetwork({
+                name: 'Staging',
+                chainHexId: 'C1',
+                addressPrefix: 'scro',
+                bip44CoinType: 1,
+                feeConfig: ANY_FEE_CONFIG,
+            });
+            const publicKey = Buffer.from(
+                '041ff5820f619d51663efbb233eb03bd5d434f63c352a5633717d8b570daa43c190bddc906ed9b8601c10c2b58e347f6e1cc4035b391b98be1d9afa9c5ead9423b',
+                'hex',
+            );
+
+            expect(
+                transfer({
+                    publicKey,
+                    network,
+                }).startsWith('scro1'),
+            ).to.eq(true);
+        });
+    });
+
     describe('fromChainId', () => {
         it('should return network type based on chainHexId', () => {
+            registerNetwork(
+                Devnet({
+                    feeConfig: Mainnet.feeConfig,
+                    chainHexId: 'AB',
+                }),
+            );
+
             expect(fromChainId(Mainnet.chainHexId)).to.deep.eq(
                 NetworkEnum.Mainnet,
             );
@@ -144,5 +256,22 @@ describe('Network', () => {
                 NetworkEnum.Devnet,
             );
         });
+
+        it('should not register the Devnet when only constructing it', () => {
+            Devnet({
+                feeConfig: Mainnet.feeConfig,
+                chainHexId: 'AD',
+            });
+
+            expect(() => fromChainId(Buffer.from('AD', 'hex'))).to.throw(
+                'Unknown chain hex id AD, register it as a network first',
+            );
+        });
+
+        it('should throw Error when chainHexId is not registered', () => {
+            expect(() => fromChainId(Buffer.from('FE', 'hex'))).to.throw(
+                'Unknown chain hex id FE, register it as a network first',
+            );
+        });
     });
 });
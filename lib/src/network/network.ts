@@ -1,9 +1,19 @@
 import ow from 'ow';
 import BigNumber from 'bignumber.js';
 
-import { owDevnetOptions, NetworkConfig, DevnetOptions } from './types';
+import {
+    owDevnetOptions,
+    owCustomNetworkOptions,
+    owUnregisteredNetworkConfig,
+    BIP44_PATH_REGEX,
+    NetworkConfig,
+    DevnetOptions,
+    CustomNetworkOptions,
+} from './types';
 import { FeeAlgorithm } from '../fee';
 
+const native = require('../../../native');
+
 /**
  * Network enums
  *
@@ -56,7 +66,11 @@ export const Testnet: NetworkConfig = {
 };
 
 /**
- * Generate Devnet constants with the provided chainHexId
+ * Generate Devnet constants with the provided chainHexId. The Devnet has to
+ * be registered with `registerNetwork()` before it is used by the builders.
+ *
+ * @example
+ * const network = registerNetwork(Devnet({ feeConfig, chainHexId: 'AB' }));
  *
  * @export
  * @param {DevnetOptions} options Devnet options
@@ -80,18 +94,80 @@ export const Devnet = (options: DevnetOptions): NetworkConfig => {
 };
 
 /**
- * Get network constants from given chainHexId
+ * Register a network with its own chain hex id, transfer address prefix and
+ * BIP44 coin type, from custom network options or from network constants
+ * such as `Devnet()`. Several chain hex ids can be registered under the same
+ * name as long as they share the same address prefix and coin type.
+ *
+ * @export
+ * @param {CustomNetworkOptions|NetworkConfig} options Custom network options
+ * or network constants
+ * @returns {NetworkConfig} Network constants
+ */
+export const registerNetwork = (
+    options: CustomNetworkOptions | NetworkConfig,
+): NetworkConfig => {
+    ow(
+        options,
+        'options',
+        ow.any(owCustomNetworkOptions, owUnregisteredNetworkConfig),
+    );
+
+    const customOptions = isNetworkConfig(options)
+        ? parseNetworkConfig(options)
+        : options;
+    const chainHexId =
+        typeof customOptions.chainHexId === 'string'
+            ? Buffer.from(customOptions.chainHexId, 'hex')
+            : customOptions.chainHexId;
+
+    native.network.registerNetwork({
+        name: customOptions.name,
+        chainHexId,
+        addressPrefix: customOptions.addressPrefix,
+        bip44CoinType: customOptions.bip44CoinType,
+    });
+
+    return {
+        name: customOptions.name,
+        chainHexId,
+        addressPrefix: customOptions.addressPrefix,
+        bip44Path: `m/44'/${customOptions.bip44CoinType}'/{ACCOUNT}'/0/{INDEX}`,
+        feeConfig: customOptions.feeConfig,
+    };
+};
+
+/**
+ * @internal
+ */
+const isNetworkConfig = (
+    options: CustomNetworkOptions | NetworkConfig,
+): options is NetworkConfig => {
+    return (options as NetworkConfig).bip44Path !== undefined;
+};
+
+/**
+ * @internal
+ */
+const parseNetworkConfig = (network: NetworkConfig): CustomNetworkOptions => {
+    const [, bip44CoinType] = BIP44_PATH_REGEX.exec(network.bip44Path)!;
+
+    return {
+        name: network.name,
+        chainHexId: network.chainHexId,
+        addressPrefix: network.addressPrefix,
+        bip44CoinType: parseInt(bip44CoinType, 10),
+        feeConfig: network.feeConfig,
+    };
+};
+
+/**
+ * Get network name from given chainHexId. Throws when the chainHexId is not
+ * registered by `registerNetwork()`
  *
  * @param {Buffer} chainHexId two hex characters chainHexId
- * @returns {NetworkEnum} Network type
+ * @returns {NetworkEnum|string} Network type or custom network name
  */
-export const fromChainId = (chainHexId: Buffer): NetworkEnum => {
-    switch (chainHexId.toString('hex').toUpperCase()) {
-        case '2A':
-            return NetworkEnum.Mainnet;
-        case '42':
-            return NetworkEnum.Testnet;
-        default:
-            return NetworkEnum.Devnet;
-    }
+export const fromChainId = (chainHexId: Buffer): NetworkEnum | string => {
+    return native.network.getNetworkByChainHexId(chainHexId).name;
 };
@@ -23,6 +23,16 @@ export interface DevnetOptions {
     chainHexId: Buffer | string;
 }
 
+export interface CustomNetworkOptions {
+    name: string;
+    chainHexId: Buffer | string;
+    addressPrefix: string;
+    bip44CoinType: number;
+    feeConfig: FeeConfig;
+}
+
+const native = require('../../../native');
+
 /**
  * @internal
  */
@@ -43,6 +53,21 @@ export const owOptionalNetworkEnum = ow.optional.string.validate(
     owNetworkEnumValidateFn,
 );
 
+/**
+ * @internal
+ */
+const isRegisteredNetwork = (value: any): boolean => {
+    try {
+        const network = native.network.getNetworkByChainHexId(value.chainHexId);
+        return (
+            network.name === value.name &&
+            network.addressPrefix === value.addressPrefix
+        );
+    } catch (err) {
+        return false;
+    }
+};
+
 /**
  * @internal
  */
@@ -87,12 +112,38 @@ const owDevnet = ow.object
     .validate((value: any) => ({
         validator: value.name === NetworkEnum.Devnet,
         message: `Expected network name to be ${NetworkEnum.Devnet}`,
+    }))
+    .validate((value: any) => ({
+        validator: isRegisteredNetwork(value),
+        message:
+            'Expected Devnet to be registered by `registerNetwork(Devnet(...))`',
+    }));
+
+/**
+ * @internal
+ */
+const owCustomNetwork = ow.object
+    .exactShape({
+        name: ow.string.nonEmpty,
+        chainHexId: ow.buffer,
+        addressPrefix: ow.string,
+        bip44Path: ow.string,
+        feeConfig: owFeeConfig,
+    })
+    .validate((value: any) => ({
+        validator: isRegisteredNetwork(value),
+        message: 'Expected network to be registered by `registerNetwork()`',
     }));
 
 /**
  * @internal
  */
-export const owNetworkConfig = ow.any(owMainnet, owTestnet, owDevnet);
+export const owNetworkConfig = ow.any(
+    owMainnet,
+    owTestnet,
+    owDevnet,
+    owCustomNetwork,
+);
 /**
  * @internal
  */
@@ -100,6 +151,7 @@ export const owOptionalNetworkConfig = ow.optional.any(
     owMainnet,
     owTestnet,
     owDevnet,
+    owCustomNetwork,
 );
 
 /**
@@ -128,6 +180,22 @@ export const owOptionalChainId = ow.optional.any(
     owChainHexIdBuffer,
 );
 
+/**
+ * @internal
+ */
+export const BIP44_PATH_REGEX = /^m\/44'\/(\d+)'\/\{ACCOUNT\}'\/0\/\{INDEX\}$/;
+
+/**
+ * @internal
+ */
+export const owUnregisteredNetworkConfig = ow.object.exactShape({
+    name: ow.string.nonEmpty,
+    chainHexId: owChainHexIdBuffer,
+    addressPrefix: ow.string.matches(/^[0-9a-z]{1,83}$/),
+    bip44Path: ow.string.matches(BIP44_PATH_REGEX),
+    feeConfig: owFeeConfig,
+});
+
 /**
  * @internal
  */
@@ -135,3 +203,14 @@ export const owDevnetOptions = ow.object.exactShape({
     feeConfig: owFeeConfig,
     chainHexId: owChainHexId,
 });
+
+/**
+ * @internal
+ */
+export const owCustomNetworkOptions = ow.object.exactShape({
+    name: ow.string.nonEmpty,
+    chainHexId: owChainHexId,
+    addressPrefix: ow.string.matches(/^[0-9a-z]{1,83}$/),
+    bip44CoinType: ow.number.integer.inRange(0, 0x7fffffff),
+    feeConfig: owFeeConfig,
+});
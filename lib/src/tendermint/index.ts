@@ -0,0 +1,10 @@
+export {
+    subscribe,
+    Subscription,
+    SubscribeOptions,
+    SubscriptionEvent,
+    BlockEvent,
+    TxEvent,
+    ReconnectingEvent,
+    ResumedEvent,
+} from './subscription';
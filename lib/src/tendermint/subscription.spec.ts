@@ -0,0 +1,54 @@
+import 'mocha';
+import { expect } from 'chai';
+
+import { subscribe } from './subscription';
+import { KeyPair } from '../key_pair';
+import { transfer } from '../address';
+import { Mainnet } from '../network';
+
+describe('subscribe', () => {
+    it('should throw Error when the Tendermint address is not websocket', () => {
+        expect(() => {
+            subscribe({
+                blocks: true,
+                tendermintAddress: 'http://localhost:26657',
+            });
+        }).to.throw('Expected value to be HTTP or WS tendermint address');
+    });
+
+    it('should throw Error when a staking address is invalid', () => {
+        expect(() => {
+            subscribe({
+                stakingAddresses: ['0xinvalid'],
+            });
+        }).to.throw('Expected value to be a valid staking address');
+    });
+
+    it('should throw Error when a transfer address is invalid', () => {
+        expect(() => {
+            subscribe({
+                transferAddresses: ['dcro1invalid'],
+                viewKeyPair: KeyPair.generateRandom(),
+            });
+        }).to.throw('Expected value to be a valid transfer address');
+    });
+
+    it('should throw Error when transfer addresses are given without view key pair', () => {
+        expect(() => {
+            subscribe({
+                transferAddresses: [
+                    transfer({
+                        keyPair: KeyPair.generateRandom(),
+                        network: Mainnet,
+                    }),
+                ],
+            });
+        }).to.throw('View key pair is required to filter transfer addresses');
+    });
+
+    it('should throw Error when no event is selected', () => {
+        expect(() => {
+            subscribe({});
+        }).to.throw('Filter should select blocks or transactions');
+    });
+});
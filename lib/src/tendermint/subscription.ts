@@ -0,0 +1,350 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import {
+    owTendermintAddress,
+    owStakingAddress,
+    owTransferAddress,
+} from '../types';
+import { KeyPair } from '../key_pair';
+import { owKeyPair } from '../key_pair/types';
+import { getRustFeaturesFromEnv } from '../native';
+
+const native = require('../../../native');
+
+/**
+ * New block of the chain
+ * @typedef {object} BlockEvent
+ * @property {string} type 'block'
+ * @property {BigNumber} height Block height
+ * @property {number} time Block time in seconds since the Unix epoch
+ * @property {number} txCount Number of transactions of the block
+ */
+export interface BlockEvent {
+    type: 'block';
+    height: BigNumber;
+    time: number;
+    txCount: number;
+}
+
+/**
+ * Transaction delivered in a block
+ * @typedef {object} TxEvent
+ * @property {string} type 'tx'
+ * @property {BigNumber} height Height of the block
+ * @property {number} index Index of the transaction in the block
+ * @property {string} txId Transaction id in hex
+ * @property {string} txType Transaction type, e.g. 'Deposit'
+ * @property {string} [stakingAddress] Staking address of staking and
+ * council node transactions
+ * @property {number} code Delivery result code, 0 when valid
+ * @property {string} log Delivery log
+ */
+export interface TxEvent {
+    type: 'tx';
+    height: BigNumber;
+    index: number;
+    txId: string;
+    txType: string;
+    stakingAddress?: string;
+    code: number;
+    log: string;
+}
+
+/**
+ * Connection to the node is lost and is about to be re-established
+ * @typedef {object} ReconnectingEvent
+ * @property {string} type 'reconnecting'
+ * @property {number} attempt Reconnection attempt, starting from 1
+ * @property {string} error Reason of the connection loss
+ */
+export interface ReconnectingEvent {
+    type: 'reconnecting';
+    attempt: number;
+    error: string;
+}
+
+/**
+ * Connection is re-established. Blocks from `fromHeight` to `toHeight` are
+ * replayed next, starting from the last received block whose transactions
+ * may not all have been received. Events already delivered are not
+ * delivered again
+ * @typedef {object} ResumedEvent
+ * @property {string} type 'resumed'
+ * @property {BigNumber} fromHeight First replayed block height
+ * @property {BigNumber} toHeight Last replayed block height
+ */
+export interface ResumedEvent {
+    type: 'resumed';
+    fromHeight: BigNumber;
+    toHeight: BigNumber;
+}
+
+export type SubscriptionEvent =
+    | BlockEvent
+    | TxEvent
+    | ReconnectingEvent
+    | ResumedEvent;
+
+export interface SubscribeOptions {
+    onEvent?: (event: SubscriptionEvent) => void;
+    onError?: (err: Error) => void;
+    tendermintAddress?: string;
+    blocks?: boolean;
+    txs?: boolean;
+    txIds?: string[];
+    stakingAddresses?: string[];
+    transferAddresses?: string[];
+    viewKeyPair?: KeyPair;
+}
+
+/**
+ * @internal
+ */
+const owSubscribeOptions = ow.object.exactShape({
+    onEvent: ow.optional.function,
+    onError: ow.optional.function,
+    tendermintAddress: ow.optional.any(owTendermintAddress),
+    blocks: ow.optional.boolean,
+    txs: ow.optional.boolean,
+    txIds: ow.optional.array.ofType(ow.string),
+    stakingAddresses: ow.optional.array.ofType(owStakingAddress),
+    transferAddresses: ow.optional.array.ofType(owTransferAddress),
+    viewKeyPair: ow.optional.any(owKeyPair),
+});
+
+/**
+ * Running subscription to the events of a Tendermint node. The connection
+ * is re-established automatically until the subscription is closed.
+ *
+ * Events are passed to the `onEvent` callback when given, otherwise they are
+ * queued for async iteration with `for await (const event of subscription)`.
+ */
+export class Subscription implements AsyncIterable<SubscriptionEvent> {
+    private id: number;
+
+    private closed: boolean = false;
+
+    private receiving: boolean = true;
+
+    private error?: Error;
+
+    private onEvent?: (event: SubscriptionEvent) => void;
+
+    private onError?: (err: Error) => void;
+
+    private queuedEvents: SubscriptionEvent[] = [];
+
+    private waitingReaders: Array<{
+        resolve: (result: IteratorResult<SubscriptionEvent>) => void;
+        reject: (err: Error) => void;
+    }> = [];
+
+    /**
+     * @internal
+     */
+    constructor(
+        start: (callback: NativeEventsCallback) => number,
+        onEvent?: (event: SubscriptionEvent) => void,
+        onError?: (err: Error) => void,
+    ) {
+        this.onEvent = onEvent;
+        this.onError = onError;
+
+        this.id = start((err, result) => this.receive(err, result));
+    }
+
+    /**
+     * Close the subscription. Events already received are still delivered
+     */
+    public close() {
+        if (this.closed) {
+            return;
+        }
+        this.closed = true;
+
+        native.subscription.unsubscribe(this.id);
+    }
+
+    /**
+     * Returns true once the subscription is closed
+     * @returns {boolean}
+     */
+    public isClosed(): boolean {
+        return this.closed;
+    }
+
+    /**
+     * Iterate over the events until the subscription is closed
+     * @returns {AsyncIterator<SubscriptionEvent>}
+     */
+    public [Symbol.asyncIterator](): AsyncIterator<SubscriptionEvent> {
+        return {
+            next: () => this.nextEvent(),
+            return: () => {
+                this.close();
+
+                return Promise.resolve({
+                    done: true,
+                    value: undefined,
+                });
+            },
+        };
+    }
+
+    private nextEvent(): Promise<IteratorResult<SubscriptionEvent>> {
+        const event = this.queuedEvents.shift();
+        if (event) {
+            return Promise.resolve({ done: false, value: event });
+        }
+        if (this.error) {
+            return Promise.reject(this.error);
+        }
+        if (this.closed && !this.receiving) {
+            return Promise.resolve({ done: true, value: undefined });
+        }
+
+        return new Promise((resolve, reject) => {
+            this.waitingReaders.push({ resolve, reject });
+        });
+    }
+
+    private receive(err: Error | null, result: NativeEvents) {
+        if (err) {
+            this.closed = true;
+            this.receiving = false;
+            this.fail(err);
+            return;
+        }
+
+        result.events.forEach((event) => {
+            this.deliver(parseNativeEvent(event));
+        });
+
+        if (result.closed) {
+            this.closed = true;
+            this.receiving = false;
+            this.finish();
+        }
+    }
+
+    private deliver(event: SubscriptionEvent) {
+        if (this.onEvent) {
+            this.onEvent(event);
+            return;
+        }
+
+        const reader = this.waitingReaders.shift();
+        if (reader) {
+            reader.resolve({ done: false, value: event });
+        } else {
+            this.queuedEvents.push(event);
+        }
+    }
+
+    private fail(err: Error) {
+        if (this.onError) {
+            this.onError(err);
+        }
+
+        this.error = err;
+        this.waitingReaders.splice(0).forEach((reader) => reader.reject(err));
+    }
+
+    private finish() {
+        this.waitingReaders.splice(0).forEach((reader) => {
+            reader.resolve({ done: true, value: undefined });
+        });
+    }
+}
+
+/**
+ * Subscribe to the new blocks and transactions of a Tendermint node.
+ * Transactions are delivered when `txs` is set or any transaction id,
+ * staking address or transfer address is given, in which case only the
+ * matching ones are. Transfer addresses are matched against the outputs of
+ * transfer and withdraw unbonded transactions, decrypted with the view key
+ * pair. Events are received natively and passed on as soon as they arrive.
+ * @param {object} options
+ * @param {Function} [options.onEvent] Called with every event. Events are
+ * queued for async iteration of the subscription when omitted
+ * @param {Function} [options.onError] Called when the subscription fails
+ * @param {string} [options.tendermintAddress='ws://localhost:26657/websocket']
+ * Tendermint websocket address
+ * @param {boolean} [options.blocks=false] Deliver the new blocks
+ * @param {boolean} [options.txs=false] Deliver every transaction
+ * @param {string[]} [options.txIds] Transaction ids to deliver
+ * @param {string[]} [options.stakingAddresses] Staking addresses of the
+ * transactions to deliver
+ * @param {string[]} [options.transferAddresses] Transfer addresses of the
+ * outputs of the transactions to deliver
+ * @param {KeyPair} [options.viewKeyPair] View key pair decrypting the
+ * transaction outputs, required with `transferAddresses`
+ * @returns {Subscription}
+ */
+export const subscribe = (options: SubscribeOptions): Subscription => {
+    ow(options, 'options', owSubscribeOptions);
+    const hasTransferAddresses =
+        !!options.transferAddresses && options.transferAddresses.length > 0;
+    if (hasTransferAddresses && !options.viewKeyPair) {
+        throw new Error(
+            'View key pair is required to filter transfer addresses',
+        );
+    }
+
+    return new Subscription(
+        (callback) =>
+            native.subscription.subscribe(
+                {
+                    blocks: options.blocks,
+                    txs: options.txs,
+                    txIds: options.txIds,
+                    stakingAddresses: options.stakingAddresses,
+                    transferAddresses: options.transferAddresses,
+                },
+                options.tendermintAddress || 'ws://localhost:26657/websocket',
+                callback,
+                hasTransferAddresses
+                    ? options.viewKeyPair!.toObject()
+                    : undefined,
+                getRustFeaturesFromEnv(process.env.NODE_ENV),
+            ),
+        options.onEvent,
+        options.onError,
+    );
+};
+
+/**
+ * @internal
+ */
+interface NativeEvents {
+    closed: boolean;
+    events: any[];
+}
+
+/**
+ * @internal
+ */
+type NativeEventsCallback = (err: Error | null, result: NativeEvents) => void;
+
+/**
+ * @internal
+ */
+const parseNativeEvent = (event: any): SubscriptionEvent => {
+    switch (event.type) {
+        case 'block':
+        case 'tx':
+            return {
+                ...event,
+                height: new BigNumber(event.height),
+            };
+        case 'resumed':
+            return {
+                ...event,
+                fromHeight: new BigNumber(event.fromHeight),
+                toHeight: new BigNumber(event.toHeight),
+            };
+        default:
+            return event;
+    }
+};
@@ -3,6 +3,8 @@ export { UnjailTransactionBuilder } from './unjail_transaction';
 
 export {
     NodeJoinTransactionBuilderOptions,
+    CouncilNodeOptions,
+    ConfidentialInit,
     NodeMetaData,
     NodePublicKey,
     NodePublicKeyType,
@@ -1,11 +1,15 @@
 import 'mocha';
 import { expect } from 'chai';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
 
 import { NodeJoinTransactionBuilder } from './node_join_transaction_builder';
 import { NodePublicKeyType, NodePublicKey } from './types';
 import { Mainnet, Testnet } from '../../network';
 import { KeyPair } from '../../key_pair';
 import { BigNumber } from '../../utils';
+import { ValidatorKey } from '../../validator_key';
 
 describe('NodeJoinTransactionBuilder', () => {
     const SAMPLE_PUBLIC_KEY: NodePublicKey = {
@@ -136,6 +140,195 @@ describe('NodeJoinTransactionBuilder', () => {
         });
     });
 
+    describe('with council node', () => {
+        const SAMPLE_COUNCIL_NODE = {
+            name: 'Council Node',
+            securityContact: 'security@councilnode.com',
+            consensusPublicKey: SAMPLE_PUBLIC_KEY.value,
+            confidentialInit: {
+                cert: Buffer.from('certificate'),
+            },
+        };
+
+        const writePrivValidatorKeyFile = (
+            publicKey: string,
+            privateKey: Buffer,
+        ): string => {
+            const keyFile = path.join(
+                fs.mkdtempSync(path.join(os.tmpdir(), 'node-join-')),
+                'priv_validator_key.json',
+            );
+            fs.writeFileSync(
+                keyFile,
+                JSON.stringify({
+                    address: '',
+                    pub_key: {
+                        type: 'tendermint/PubKeyEd25519',
+                        value: publicKey,
+                    },
+                    priv_key: {
+                        type: 'tendermint/PrivKeyEd25519',
+                        value: privateKey.toString('base64'),
+                    },
+                }),
+            );
+
+            return keyFile;
+        };
+
+        it('should throw Error when neither consensus public key nor key file is provided', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new NodeJoinTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    councilNode: {
+                        name: 'Council Node',
+                        confidentialInit: {
+                            cert: Buffer.from('certificate'),
+                        },
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Expected either `consensusPublicKey` or `privValidatorKeyFile` to be provided',
+            );
+        });
+
+        it('should throw Error when consensus public key is not 32 bytes', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new NodeJoinTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    councilNode: {
+                        ...SAMPLE_COUNCIL_NODE,
+                        consensusPublicKey: Buffer.alloc(31).toString('base64'),
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Invalid options at `councilNode.consensusPublicKey`',
+            );
+        });
+
+        it('should throw Error when consensus public key is not on the curve', () => {
+            const consensusPublicKey = Buffer.alloc(32);
+            consensusPublicKey[0] = 2;
+
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new NodeJoinTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    councilNode: {
+                        ...SAMPLE_COUNCIL_NODE,
+                        consensusPublicKey: consensusPublicKey.toString('base64'),
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw('Invalid ed25519 public key');
+        });
+
+        it('should throw Error when validator name is too long', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new NodeJoinTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    councilNode: {
+                        ...SAMPLE_COUNCIL_NODE,
+                        name: 'a'.repeat(65),
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Invalid options at `councilNode.name`: Validator name should be at most 64 bytes long',
+            );
+        });
+
+        it('should throw Error when confidential init certificate is empty', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new NodeJoinTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    councilNode: {
+                        ...SAMPLE_COUNCIL_NODE,
+                        confidentialInit: {
+                            cert: Buffer.alloc(0),
+                        },
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Invalid options at `councilNode.confidentialInit.cert`: Confidential init certificate should not be empty',
+            );
+        });
+
+        it('should create builder from base64 consensus public key', () => {
+            const builder = new NodeJoinTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                nonce: SAMPLE_NONCE,
+                councilNode: SAMPLE_COUNCIL_NODE,
+                network: Mainnet,
+            });
+
+            expect(builder.getNodeMetaData()).to.deep.eq(
+                SAMPLE_NODE_META_DATA,
+            );
+            expect(builder.getConfidentialInit()).to.deep.eq(
+                SAMPLE_COUNCIL_NODE.confidentialInit,
+            );
+        });
+
+        it('should create builder from priv_validator_key.json', () => {
+            const validatorKey = ValidatorKey.generate();
+            const privValidatorKeyFile = path.join(
+                fs.mkdtempSync(path.join(os.tmpdir(), 'node-join-')),
+                'priv_validator_key.json',
+            );
+            validatorKey.writePrivValidatorKeyFile(privValidatorKeyFile);
+
+            const builder = new NodeJoinTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                nonce: SAMPLE_NONCE,
+                councilNode: {
+                    name: SAMPLE_COUNCIL_NODE.name,
+                    securityContact: SAMPLE_COUNCIL_NODE.securityContact,
+                    privValidatorKeyFile,
+                    confidentialInit: SAMPLE_COUNCIL_NODE.confidentialInit,
+                },
+                network: Mainnet,
+            });
+
+            expect(builder.getNodeMetaData().consensusPublicKey).to.deep.eq(
+                validatorKey.toNodePublicKey(),
+            );
+        });
+
+        it('should throw Error when priv_validator_key.json public key does not match private key', () => {
+            const privValidatorKeyFile = writePrivValidatorKeyFile(
+                SAMPLE_PUBLIC_KEY.value,
+                Buffer.alloc(64, 1),
+            );
+
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new NodeJoinTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    councilNode: {
+                        name: SAMPLE_COUNCIL_NODE.name,
+                        privValidatorKeyFile,
+                        confidentialInit: SAMPLE_COUNCIL_NODE.confidentialInit,
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw('Public key does not belong to the private key');
+        });
+    });
+
     describe('isCompleted', () => {
         it('should return false when the transaction is not signed', () => {
             const builder = new NodeJoinTransactionBuilder({
@@ -1,9 +1,14 @@
+import * as fs from 'fs';
 import ow from 'ow';
 
 import { TransactionBuilder } from '../transaction_builder';
 import {
+    ConfidentialInit,
+    CouncilNodeOptions,
     NodeMetaData,
+    NodePublicKeyType,
     NodeJoinTransactionBuilderOptions,
+    owCouncilNodeJoinTransactionBuilderOptions,
     owNodeJoinTransactionBuilderOptions,
     parseNodeMetaDataForNative,
 } from './types';
@@ -19,7 +24,9 @@ export class NodeJoinTransactionBuilder extends TransactionBuilder {
 
     private nonce: BigNumber;
 
-    private nodeMetaData: NodeMetaData;
+    private nodeMetaData!: NodeMetaData;
+
+    private councilNode?: CouncilNodeOptions;
 
     private unsignedRawTx!: Buffer;
 
@@ -32,22 +39,34 @@ export class NodeJoinTransactionBuilder extends TransactionBuilder {
      * @param {UnbondTransactionBuilderOptions} [options] Builder options
      * @param {string} options.stakingAddress Staking address to unbond from
      * @param {BigNumber} options.nonce Staking address nonce
-     * @param {NodeMetaData} options.nodeMetaData Node meta data
+     * @param {NodeMetaData} [options.nodeMetaData] Node meta data.
+     * Deprecated in favour of `councilNode`
+     * @param {CouncilNodeOptions} [options.councilNode] Typed council node
+     * fields with the consensus public key and confidential init data
      * @param {Network} [options.network] Network the transaction belongs to
      * @memberof NodeJoinTransactionBuilder
      */
     public constructor(options: NodeJoinTransactionBuilderOptions) {
         super();
 
-        ow(options, 'options', owNodeJoinTransactionBuilderOptions);
+        if (options && options.councilNode !== undefined) {
+            ow(options, 'options', owCouncilNodeJoinTransactionBuilderOptions);
+        } else {
+            ow(options, 'options', owNodeJoinTransactionBuilderOptions);
+        }
 
         this.stakingAddress = options.stakingAddress;
         this.nonce = options.nonce;
-        this.nodeMetaData = options.nodeMetaData;
+        this.councilNode = options.councilNode;
 
         this.initNetwork(options.network);
 
-        this.prepareRawTx();
+        if (this.councilNode) {
+            this.prepareRawTxFromCouncilNode(this.councilNode);
+        } else {
+            this.nodeMetaData = options.nodeMetaData!;
+            this.prepareRawTx();
+        }
     }
 
     private prepareRawTx() {
@@ -67,6 +86,41 @@ export class NodeJoinTransactionBuilder extends TransactionBuilder {
         this.innertTxId = txId;
     }
 
+    private prepareRawTxFromCouncilNode(councilNode: CouncilNodeOptions) {
+        const privValidatorKey =
+            councilNode.privValidatorKeyFile !== undefined
+                ? fs.readFileSync(councilNode.privValidatorKeyFile, 'utf8')
+                : undefined;
+
+        const {
+            unsignedRawTx,
+            txId,
+            consensusPublicKey,
+        } = native.councilNodeTransaction.buildRawNodeJoinTransaction({
+            stakingAddress: this.stakingAddress,
+            nonce: this.nonce.toString(10),
+            councilNode: {
+                name: councilNode.name,
+                securityContact: councilNode.securityContact,
+                consensusPublicKey: councilNode.consensusPublicKey,
+                privValidatorKey,
+                confidentialInit: councilNode.confidentialInit,
+            },
+            chainHexId: this.getNetwork().chainHexId,
+        });
+
+        this.unsignedRawTx = unsignedRawTx;
+        this.innertTxId = txId;
+        this.nodeMetaData = {
+            name: councilNode.name,
+            securityContact: councilNode.securityContact,
+            consensusPublicKey: {
+                type: NodePublicKeyType.Ed25519,
+                value: consensusPublicKey,
+            },
+        };
+    }
+
     /**
      * Returns staking address holding the stake to participate as council node
      * @returns {string} stakingAddress
@@ -94,6 +148,16 @@ export class NodeJoinTransactionBuilder extends TransactionBuilder {
         return this.nodeMetaData;
     }
 
+    /**
+     * Returns the confidential init data of the council node. Returns
+     * undefined when the builder is created from node metadata
+     * @returns {ConfidentialInit|undefined} confidential init data
+     * @memberof NodeJoinTransactionBuilder
+     */
+    public getConfidentialInit(): Readonly<ConfidentialInit> | undefined {
+        return this.councilNode ? this.councilNode.confidentialInit : undefined;
+    }
+
     /**
      * Determine if the transaction is completed and can be exported
      *
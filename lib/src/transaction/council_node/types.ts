@@ -7,10 +7,34 @@ import { BigNumber } from '../../utils';
 export interface NodeJoinTransactionBuilderOptions {
     stakingAddress: string;
     nonce: BigNumber;
-    nodeMetaData: NodeMetaData;
+    nodeMetaData?: NodeMetaData;
+    councilNode?: CouncilNodeOptions;
     network?: NetworkConfig;
 }
 
+/**
+ * Typed council node fields
+ * @typedef {object} CouncilNodeOptions
+ * @property {string} name Validator name
+ * @property {string} [securityContact] Validator security contact
+ * @property {string} [consensusPublicKey] Base64 encoded Tendermint ed25519
+ * consensus public key
+ * @property {string} [privValidatorKeyFile] Path to Tendermint
+ * `priv_validator_key.json` to read the consensus public key from
+ * @property {ConfidentialInit} confidentialInit Confidential init data
+ */
+export interface CouncilNodeOptions {
+    name: string;
+    securityContact?: string;
+    consensusPublicKey?: string;
+    privValidatorKeyFile?: string;
+    confidentialInit: ConfidentialInit;
+}
+
+export interface ConfidentialInit {
+    cert: Buffer;
+}
+
 export interface NodeMetaData {
     name: string;
     securityContact?: string;
@@ -60,6 +84,39 @@ export const owNodeJoinTransactionBuilderOptions = ow.object.exactShape({
     network: owOptionalNetworkConfig,
 });
 
+/**
+ * @internal
+ */
+export const owCouncilNodeOptions = ow.object
+    .exactShape({
+        name: ow.string.nonEmpty,
+        securityContact: ow.optional.string.nonEmpty,
+        consensusPublicKey: ow.optional.string.nonEmpty,
+        privValidatorKeyFile: ow.optional.string.nonEmpty,
+        confidentialInit: ow.object.exactShape({
+            cert: ow.buffer,
+        }),
+    })
+    .validate((value: any) => ({
+        validator:
+            (value.consensusPublicKey === undefined) !==
+            (value.privValidatorKeyFile === undefined),
+        message:
+            'Expected either `consensusPublicKey` or `privValidatorKeyFile` to be provided',
+    }));
+
+/**
+ * @internal
+ */
+export const owCouncilNodeJoinTransactionBuilderOptions = ow.object.exactShape(
+    {
+        stakingAddress: owStakingAddress,
+        nonce: owAccountNonce,
+        councilNode: owCouncilNodeOptions,
+        network: owOptionalNetworkConfig,
+    },
+);
+
 export interface NativeNodeMetaData {
     name: string;
     // eslint-disable-next-line camelcase
@@ -10,6 +10,12 @@ import { owKeyPair } from '../../key_pair/types';
 import { NetworkConfig } from '../../network';
 import { BigNumber } from '../../utils';
 import { ENCODING_PREFIX } from '../encoding';
+import {
+    LocalValidation,
+    owValidationContext,
+    validateLocally,
+    ValidationContext,
+} from '../local_validation';
 
 const native = require('../../../../native');
 
@@ -140,6 +146,26 @@ export class UnjailTransactionBuilder extends TransactionBuilder {
             this.keyPair!.toObject(),
         );
     }
+
+    /**
+     * Run the unjail validation rules of the chain against the context,
+     * returning the reason the node would reject the transaction with
+     *
+     * @param {ValidationContext} context chain state to validate against,
+     * which should have the staked state
+     * @returns {LocalValidation}
+     * @throws {Error} error when the transaction is not completed
+     * @memberof UnjailTransactionBuilder
+     */
+    public validateLocally(context: ValidationContext): LocalValidation {
+        ow(context, 'context', owValidationContext);
+
+        return validateLocally(
+            { kind: 'unjail', txAux: this.toHex() },
+            this.getNetwork(),
+            context,
+        );
+    }
 }
 
 /**
@@ -2,4 +2,5 @@ export const ENCODING_PREFIX = {
     UNBOND: Buffer.from('0100', 'hex'),
     UNJAIL: Buffer.from('0101', 'hex'),
     NODE_JOIN: Buffer.from('0102', 'hex'),
+    WITHDRAW_UNBONDED: Buffer.from('0002', 'hex'),
 };
@@ -3,3 +3,17 @@ import * as staking from './staking';
 import * as transfer from './transfer';
 
 export { councilNode, staking, transfer };
+export {
+    verifyTxHex,
+    verifySignedWithdrawUnbondedPlainHex,
+    TxAssertions,
+    TxVerification,
+    TxMismatch,
+    FeeBounds,
+} from './verification';
+export {
+    OutputPolicy,
+    PolicyViolation,
+    PolicyViolationCode,
+} from './output_policy';
+export { ValidationContext, LocalValidation } from './local_validation';
@@ -0,0 +1,106 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import {
+    Output,
+    owCoin,
+    owOutput,
+    parseOutputForNative,
+    Timespec,
+} from '../types';
+import { owTimespec } from '../types/timespec';
+import { NetworkConfig } from '../network';
+import { parseFeeConfigForNative } from '../fee/types';
+import { State, owState } from './staking/types';
+
+const native = require('../../../native');
+
+/**
+ * Chain state a transaction is validated against
+ * @typedef {object} ValidationContext
+ * @property {Timespec} blockTime time of the latest committed block
+ * @property {BigNumber} [minFee] minimum fee of the transaction, default to
+ * the fee of the network fee config on the transaction size
+ * @property {number} [unbondingPeriod] unbonding period of the network in
+ * seconds, required to validate unbond transactions
+ * @property {Output[]} [spentOutputs] outputs spent by each input of
+ * transfer and deposit transactions, default to the previous outputs of
+ * the builder inputs
+ * @property {State} [stakedState] staked state of unbond, withdraw unbonded
+ * and unjail transactions
+ */
+export interface ValidationContext {
+    blockTime: Timespec;
+    minFee?: BigNumber;
+    unbondingPeriod?: number;
+    spentOutputs?: Output[];
+    stakedState?: State;
+}
+
+/**
+ * Result of the chain validation rules
+ * @typedef {object} LocalValidation
+ * @property {boolean} valid whether the chain accepts the transaction
+ * @property {string} [reason] reason the chain rejects the transaction with
+ */
+export interface LocalValidation {
+    valid: boolean;
+    reason?: string;
+}
+
+/**
+ * @internal
+ */
+export const owValidationContext = ow.object.exactShape({
+    blockTime: owTimespec,
+    minFee: ow.optional.any(owCoin),
+    unbondingPeriod: ow.optional.number.integer.greaterThanOrEqual(0),
+    spentOutputs: ow.optional.array.ofType(owOutput),
+    stakedState: ow.optional.any(owState),
+});
+
+/**
+ * Signed transaction to validate
+ * @internal
+ */
+export type LocalTransaction =
+    | { kind: 'transfer'; incompleteHex: Buffer }
+    | { kind: 'deposit'; unsignedRawTx: Buffer; witnesses: Buffer[] }
+    | { kind: 'withdrawUnbonded'; unsignedRawTx: Buffer; witness: Buffer }
+    | { kind: 'unbond' | 'unjail'; txAux: Buffer };
+
+/**
+ * Run the validation rules of the chain against the context
+ * @internal
+ */
+export const validateLocally = (
+    transaction: LocalTransaction,
+    network: Readonly<NetworkConfig>,
+    context: ValidationContext,
+): LocalValidation => {
+    const { stakedState } = context;
+
+    return native.localValidation.validate({
+        transaction,
+        chainHexId: network.chainHexId,
+        feeConfig: parseFeeConfigForNative(network.feeConfig),
+        context: {
+            blockTime: context.blockTime.toNumber(),
+            minFee: context.minFee ? context.minFee.toString(10) : undefined,
+            unbondingPeriod: context.unbondingPeriod,
+            spentOutputs: context.spentOutputs
+                ? context.spentOutputs.map(parseOutputForNative)
+                : undefined,
+            stakedState: stakedState
+                ? {
+                      nonce: stakedState.nonce.toString(10),
+                      bonded: stakedState.bonded.toString(10),
+                      unbonded: stakedState.unbonded.toString(10),
+                      unbondedFrom: stakedState.unbondedFrom,
+                      address: stakedState.address,
+                      jailedUntil: stakedState.jailedUntil,
+                  }
+                : undefined,
+        },
+    });
+};
@@ -0,0 +1,116 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { Output, owCoin, parseOutputForNative, Timespec } from '../types';
+import { owOptionalTimespec } from '../types/timespec';
+
+const native = require('../../../native');
+
+/**
+ * Policy the outputs of a transaction should satisfy before signing
+ * @typedef {object} OutputPolicy
+ * @property {BigNumber} [minOutputValue=1] minimum value of each output in
+ * basic unit, zero value outputs are not allowed by default
+ * @property {number} [maxOutputs] maximum number of outputs
+ * @property {boolean} [allowSelfSend=false] allow outputs to the addresses
+ * of the inputs
+ * @property {number} [changeOutput] index of the output returning the
+ * change to an input address, which is not reported as a self-send
+ * @property {boolean} [allowDuplicateOutputs=false] allow outputs identical
+ * to another output
+ * @property {number} [maxTimeLock] maximum seconds from the block time an
+ * output `validFrom` can be
+ * @property {Timespec} [blockTime] block time the time-lock is compared to,
+ * default to now
+ */
+export interface OutputPolicy {
+    minOutputValue?: BigNumber;
+    maxOutputs?: number;
+    allowSelfSend?: boolean;
+    changeOutput?: number;
+    allowDuplicateOutputs?: boolean;
+    maxTimeLock?: number;
+    blockTime?: Timespec;
+}
+
+/**
+ * Kind of policy violation
+ */
+export enum PolicyViolationCode {
+    BelowMinOutputValue = 'belowMinOutputValue',
+    TooManyOutputs = 'tooManyOutputs',
+    SelfSend = 'selfSend',
+    DuplicateOutput = 'duplicateOutput',
+    ValidFromInMilliseconds = 'validFromInMilliseconds',
+    TimeLockTooFar = 'timeLockTooFar',
+    InsufficientInput = 'insufficientInput',
+}
+
+/**
+ * A rule of the policy the transaction does not satisfy
+ * @typedef {object} PolicyViolation
+ * @property {PolicyViolationCode} code kind of violation
+ * @property {string} path path of the offending field, e.g.
+ * `outputs[1].validFrom`
+ * @property {string} message
+ */
+export interface PolicyViolation {
+    code: PolicyViolationCode;
+    path: string;
+    message: string;
+}
+
+/**
+ * @internal
+ */
+export const owOutputPolicy = ow.object.exactShape({
+    minOutputValue: ow.optional.any(owCoin),
+    maxOutputs: ow.optional.number.integer.greaterThan(0),
+    allowSelfSend: ow.optional.boolean,
+    changeOutput: ow.optional.number.integer.greaterThanOrEqual(0),
+    allowDuplicateOutputs: ow.optional.boolean,
+    maxTimeLock: ow.optional.number.integer.greaterThanOrEqual(0),
+    blockTime: owOptionalTimespec,
+});
+
+/**
+ * Transaction the policy is checked against
+ * @internal
+ */
+export interface PolicyContext {
+    chainHexId: Buffer;
+    inputs: Output[];
+    inputAmount?: BigNumber;
+    outputs: Output[];
+    fee?: BigNumber;
+}
+
+/**
+ * Check the outputs of a transaction against the policy
+ * @internal
+ */
+export const checkOutputPolicy = (
+    policy: OutputPolicy,
+    context: PolicyContext,
+): PolicyViolation[] => {
+    const { minOutputValue, blockTime, ...others } = policy;
+
+    return native.outputPolicy.checkPolicy({
+        chainHexId: context.chainHexId,
+        inputs: context.inputs.map(parseOutputForNative),
+        inputAmount: context.inputAmount
+            ? context.inputAmount.toString(10)
+            : undefined,
+        outputs: context.outputs.map(parseOutputForNative),
+        fee: context.fee ? context.fee.toString(10) : undefined,
+        blockTime: blockTime
+            ? blockTime.toNumber()
+            : Math.floor(Date.now() / 1000),
+        policy: {
+            ...others,
+            minOutputValue: minOutputValue
+                ? minOutputValue.toString(10)
+                : undefined,
+        },
+    });
+};
@@ -2,11 +2,12 @@ import 'mocha';
 import { expect } from 'chai';
 
 import { DepositTransactionBuilder } from './deposit_transaction_builder';
-import { Mainnet, Devnet } from '../../network';
+import { Mainnet, Devnet, registerNetwork } from '../../network';
 import { KeyPair } from '../../key_pair';
 import { PrevOutputPointer } from './types';
 import { FeeConfig, FeeAlgorithm } from '../../fee';
 import { BigNumber } from '../../utils';
+import { transfer } from '../../address';
 
 describe('DepositTransactionBuilder', () => {
     const SAMPLE_FEE_CONFIG: FeeConfig = {
@@ -56,10 +57,12 @@ describe('DepositTransactionBuilder', () => {
 
         it('should create builder with the provided config', () => {
             const stakingAddress = SAMPLE_STAKING_ADDRESS;
-            const network = Devnet({
-                feeConfig: SAMPLE_FEE_CONFIG,
-                chainHexId: 'AB',
-            });
+            const network = registerNetwork(
+                Devnet({
+                    feeConfig: SAMPLE_FEE_CONFIG,
+                    chainHexId: 'AB',
+                }),
+            );
             const builder = new DepositTransactionBuilder({
                 stakingAddress,
                 network,
@@ -651,4 +654,150 @@ describe('DepositTransactionBuilder', () => {
             );
         });
     });
+
+    describe('with previous outputs', () => {
+        const network = registerNetwork(
+            Devnet({
+                feeConfig: SAMPLE_FEE_CONFIG,
+                chainHexId: 'AB',
+            }),
+        );
+        const sampleDepositInput = (keyPair: KeyPair) => ({
+            ...SAMPLE_INPUT,
+            prevOutput: {
+                address: transfer({ keyPair, network }),
+                value: new BigNumber('100000000'),
+            },
+        });
+
+        it('should throw Error when signing a multi-signature input', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder.addInput({
+                ...sampleDepositInput(SAMPLE_KEY_PAIR),
+                addressParams: {
+                    requiredSigners: 1,
+                    totalSigners: 2,
+                },
+            });
+
+            expect(() => {
+                builder.signInput(0, SAMPLE_KEY_PAIR);
+            }).to.throw('Multi-signature input cannot be signed by a KeyPair');
+        });
+
+        it('should throw Error when inputs mix with and without previous output', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder.addInput(SAMPLE_INPUT);
+
+            expect(() => {
+                builder.addInput(sampleDepositInput(SAMPLE_KEY_PAIR));
+            }).to.throw(
+                'Previous output should be provided for all inputs or none',
+            );
+        });
+
+        it('should throw Error when previous output address is in another network', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+
+            expect(() => {
+                builder.addInput({
+                    ...SAMPLE_INPUT,
+                    prevOutput: {
+                        address: transfer({
+                            keyPair: SAMPLE_KEY_PAIR,
+                            network: Mainnet,
+                        }),
+                        value: new BigNumber('100000000'),
+                    },
+                });
+            }).to.throw(
+                'Previous output address does not belongs to the builder network',
+            );
+        });
+
+        it('should throw Error when getting deposit amount without previous outputs', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder.addInput(SAMPLE_INPUT);
+
+            expect(() => {
+                builder.getDepositAmount();
+            }).to.throw('Builder inputs have no previous output');
+        });
+
+        it('should return deposit amount net of fee', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder.addInput(sampleDepositInput(SAMPLE_KEY_PAIR));
+
+            const fee = builder.estimateFee();
+            expect(fee.isGreaterThan(0)).to.eq(true);
+            expect(builder.getInputAmount().toString(10)).to.eq('100000000');
+            expect(builder.getDepositAmount().toString(10)).to.eq(
+                new BigNumber('100000000').minus(fee).toString(10),
+            );
+        });
+
+        it('should throw Error when the key pair does not own the input', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder.addInput(sampleDepositInput(SAMPLE_KEY_PAIR));
+
+            expect(() => {
+                builder.signInput(
+                    0,
+                    KeyPair.fromPrivateKey(Buffer.alloc(32, 2)),
+                );
+            }).to.throw('Input address is not signable by the key pair');
+        });
+
+        it('should sign the input owned by the key pair', () => {
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder
+                .addInput(sampleDepositInput(SAMPLE_KEY_PAIR))
+                .signInput(0, SAMPLE_KEY_PAIR);
+
+            expect(builder.isCompleted()).to.eq(true);
+        });
+
+        it('should throw Error when the witness does not unlock the input', () => {
+            const otherBuilder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            const otherKeyPair = KeyPair.fromPrivateKey(Buffer.alloc(32, 2));
+            otherBuilder
+                .addInput(sampleDepositInput(otherKeyPair))
+                .signInput(0, otherKeyPair);
+            const otherWitness = (otherBuilder as any).inputs[0].witness;
+
+            const builder = new DepositTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                network,
+            });
+            builder.addInput(sampleDepositInput(SAMPLE_KEY_PAIR));
+
+            expect(() => {
+                builder.addWitness(0, otherWitness);
+            }).to.throw('Witness does not unlock the input address');
+        });
+    });
 });
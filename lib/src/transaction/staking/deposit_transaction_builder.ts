@@ -1,17 +1,28 @@
 import ow from 'ow';
 
+import BigNumber from 'bignumber.js';
+
 import {
     PrevOutputPointer,
+    DepositInput,
     DepositTransactionBuilderOptions,
     owPrevOutputPointer,
+    owDepositInput,
     owDepositTransactionOptions,
     WitnessedPrevOutputPointer,
 } from './types';
 import { TransactionBuilder } from '../transaction_builder';
 import { KeyPair } from '../../key_pair';
 import { owKeyPair } from '../../key_pair/types';
-import { owTendermintAddress } from '../../types';
+import { owTendermintAddress, parseOutputForNative } from '../../types';
 import { getRustFeaturesFromEnv } from '../../native';
+import { parseFeeConfigForNative } from '../../fee/types';
+import {
+    LocalValidation,
+    owValidationContext,
+    validateLocally,
+    ValidationContext,
+} from '../local_validation';
 
 const native = require('../../../../native');
 
@@ -27,6 +38,8 @@ export class DepositTransactionBuilder extends TransactionBuilder {
 
     private innertTxId?: string;
 
+    private amounts?: DepositAmounts;
+
     /**
      * Creates an instance of DepositTransactionBuilder.
      * @param {DepositTransactionBuilderOptions} [options] Builder options
@@ -56,25 +69,119 @@ export class DepositTransactionBuilder extends TransactionBuilder {
      * Append a previous transaction output as a input. All previous signatures
      * will be cleared when new input is added to builder.
      *
-     * @param {PrevOutputPointer} prevOutputPointer input to be added
-     * @returns {TransferTransactionBuilder}
-     * @memberof TransferTransactionBuilder
+     * When the spent output is provided, the builder computes the deposit
+     * amount net of fee and verifies the input signers. The spent output
+     * should then be provided for all inputs.
+     *
+     * @param {PrevOutputPointer|DepositInput} input input to be added
+     * @returns {DepositTransactionBuilder}
+     * @memberof DepositTransactionBuilder
      */
     public addInput(
-        prevOutputPointer: PrevOutputPointer,
+        input: PrevOutputPointer | DepositInput,
     ): DepositTransactionBuilder {
-        ow(prevOutputPointer, 'prevOutputPointer', owPrevOutputPointer);
+        let witnessedInput: WitnessedPrevOutputPointer;
+        if (input && (input as DepositInput).prevOutput !== undefined) {
+            const depositInput = input as DepositInput;
+            ow(depositInput, 'input', owDepositInput);
+
+            if (
+                !this.isTransferAddressInNetwork(depositInput.prevOutput.address)
+            ) {
+                throw new Error(
+                    'Previous output address does not belongs to the builder network',
+                );
+            }
+
+            witnessedInput = {
+                prevOutputPointer: {
+                    prevTxId: depositInput.prevTxId,
+                    prevIndex: depositInput.prevIndex,
+                },
+                prevOutput: depositInput.prevOutput,
+                addressParams: depositInput.addressParams,
+            };
+        } else {
+            ow(input, 'prevOutputPointer', owPrevOutputPointer);
+
+            witnessedInput = {
+                prevOutputPointer: input,
+            };
+        }
+        if (
+            this.hasInput() &&
+            this.hasPrevOutputs() !== !!witnessedInput.prevOutput
+        ) {
+            throw new Error(
+                'Previous output should be provided for all inputs or none',
+            );
+        }
 
         this.clearCachedData();
 
-        const input: WitnessedPrevOutputPointer = {
-            prevOutputPointer,
-        };
-        this.inputs.push(input);
+        this.inputs.push(witnessedInput);
 
         return this;
     }
 
+    private isTransferAddressInNetwork(address: string): boolean {
+        return native.address.isTransferAddressValid(
+            address,
+            this.getNetwork().name,
+        );
+    }
+
+    private hasPrevOutputs(): boolean {
+        return this.hasInput() && !!this.inputs[0].prevOutput;
+    }
+
+    /**
+     * Returns the total amount of the spent outputs
+     *
+     * @returns {BigNumber} input amount in basic unit
+     * @throws {Error} error when the spent outputs are not provided
+     * @memberof DepositTransactionBuilder
+     */
+    public getInputAmount(): BigNumber {
+        return this.getAmounts().inputAmount;
+    }
+
+    /**
+     * Returns the fee of the transaction with all inputs signed
+     *
+     * @returns {BigNumber} fee in basic unit
+     * @throws {Error} error when the spent outputs are not provided
+     * @memberof DepositTransactionBuilder
+     */
+    public estimateFee(): BigNumber {
+        return this.getAmounts().fee;
+    }
+
+    /**
+     * Returns the amount deposited to the staking address, which is the input
+     * amount net of fee
+     *
+     * @returns {BigNumber} deposit amount in basic unit
+     * @throws {Error} error when the spent outputs are not provided or they
+     * are insufficient to pay the fee
+     * @memberof DepositTransactionBuilder
+     */
+    public getDepositAmount(): BigNumber {
+        return this.getAmounts().depositAmount;
+    }
+
+    private getAmounts(): DepositAmounts {
+        this.verifyHasInput();
+        if (!this.hasPrevOutputs()) {
+            throw new Error('Builder inputs have no previous output');
+        }
+        if (!this.isRawTxPrepared()) {
+            this.prepareRawTx();
+        }
+
+        return this.amounts!;
+    }
+
     private clearCachedData() {
         this.clearWitnesses();
         this.clearPreparedRawTx();
@@ -89,6 +196,7 @@ export class DepositTransactionBuilder extends TransactionBuilder {
     private clearPreparedRawTx() {
         this.unsignedRawTx = undefined;
         this.innertTxId = undefined;
+        this.amounts = undefined;
     }
 
     /**
@@ -109,9 +217,14 @@ export class DepositTransactionBuilder extends TransactionBuilder {
     /**
      * Sign a particular input with the provided KeyPair
      *
+     * When the spent output is provided, the KeyPair should own its address.
+     * Inputs of multi-signature addresses cannot be signed by a KeyPair, their
+     * witness should be added with `addWitness()`.
+     *
      * @param {number} index input index
      * @param {KeyPair} keyPair key pair which can unlock the input
-     * @throws {Error} error when input index does not exist
+     * @throws {Error} error when input index does not exist or the input
+     * spends a multi-signature address
      * @memberof DepositTransactionBuilder
      */
     public signInput(
@@ -127,8 +240,15 @@ export class DepositTransactionBuilder extends TransactionBuilder {
         }
         this.verifyInputIndex(index);
 
-        const witness = native.signer.schnorrSignTxId(
-            Buffer.from(this.innertTxId!, 'hex'),
+        const { prevOutput, addressParams } = this.inputs[index];
+        if (addressParams && addressParams.totalSigners !== 1) {
+            throw new Error(
+                'Multi-signature input cannot be signed by a KeyPair',
+            );
+        }
+        const witness = native.stakingTransaction.signDepositTransactionInput(
+            this.unsignedRawTx,
+            prevOutput ? parseOutputForNative(prevOutput) : undefined,
             keyPair.toObject(),
         );
         this.inputs[index].witness = witness;
@@ -139,6 +259,9 @@ export class DepositTransactionBuilder extends TransactionBuilder {
     /**
      * Add witness data to input
      *
+     * When the spent output is provided, the witness should unlock its
+     * address.
+     *
      * @param {number} index input index
      * @param {Buffer} witness witness which can unlock the input
      * @throws {Error} error when input index does not exist
@@ -154,6 +277,18 @@ export class DepositTransactionBuilder extends TransactionBuilder {
         this.verifyTxIsSignable();
         this.verifyInputIndex(index);
 
+        const { prevOutput } = this.inputs[index];
+        if (prevOutput) {
+            if (!this.isRawTxPrepared()) {
+                this.prepareRawTx();
+            }
+            native.stakingTransaction.verifyDepositTransactionInputWitness(
+                this.unsignedRawTx,
+                parseOutputForNative(prevOutput),
+                witness,
+            );
+        }
+
         this.inputs[index].witness = witness;
 
         return this;
@@ -271,17 +406,88 @@ export class DepositTransactionBuilder extends TransactionBuilder {
     }
 
     private prepareRawTx() {
-        const inputs = this.inputs.map((input) => input.prevOutputPointer);
+        const inputs = this.inputs.map((input) => {
+            if (!input.prevOutput) {
+                return input.prevOutputPointer;
+            }
+            return {
+                ...input.prevOutputPointer,
+                prevOutput: parseOutputForNative(input.prevOutput),
+                addressParams: input.addressParams,
+            };
+        });
         const {
             unsignedRawTx,
             txId,
+            inputAmount,
+            fee,
+            depositAmount,
         } = native.stakingTransaction.buildRawDepositTransaction({
             inputs,
             toAddress: this.getStakingAddress(),
             chainHexId: this.getNetwork().chainHexId,
+            feeConfig: this.hasPrevOutputs()
+                ? parseFeeConfigForNative(this.getNetwork().feeConfig)
+                : undefined,
         });
 
         this.unsignedRawTx = unsignedRawTx;
         this.innertTxId = txId;
+        if (inputAmount !== undefined) {
+            this.amounts = {
+                inputAmount: new BigNumber(inputAmount),
+                fee: new BigNumber(fee),
+                depositAmount: new BigNumber(depositAmount),
+            };
+        }
     }
+
+    /**
+     * Run the deposit validation rules of the chain against the context,
+     * returning the reason the node would reject the transaction with
+     *
+     * @param {ValidationContext} context chain state to validate against,
+     * spent outputs default to the previous outputs of the inputs
+     * @returns {LocalValidation}
+     * @throws {Error} error when the transaction is not completed or the
+     * spent outputs are unknown
+     * @memberof DepositTransactionBuilder
+     */
+    public validateLocally(context: ValidationContext): LocalValidation {
+        ow(context, 'context', owValidationContext);
+
+        if (!this.isRawTxPrepared()) {
+            this.verifyTxIsSignable();
+            this.prepareRawTx();
+        }
+        if (!this.isCompleted()) {
+            throw new Error('Transaction builder is not completed');
+        }
+        if (!context.spentOutputs && !this.hasPrevOutputs()) {
+            throw new Error(
+                'Spent outputs are required when inputs have no previous output',
+            );
+        }
+
+        return validateLocally(
+            {
+                kind: 'deposit',
+                unsignedRawTx: this.unsignedRawTx!,
+                witnesses: this.inputs.map((input) => input.witness!),
+            },
+            this.getNetwork(),
+            {
+                ...context,
+                spentOutputs:
+                    context.spentOutputs ||
+                    this.inputs.map((input) => input.prevOutput!),
+            },
+        );
+    }
+}
+
+interface DepositAmounts {
+    inputAmount: BigNumber;
+    fee: BigNumber;
+    depositAmount: BigNumber;
 }
@@ -1,8 +1,26 @@
 export { DepositTransactionBuilder } from './deposit_transaction_builder';
 export { UnbondTransactionBuilder } from './unbond_transaction_builder';
 export { WithdrawUnbondedTransactionBuilder } from './withdraw_unbonded_transaction_builder';
+export { queryStakedState } from './staked_state';
+export {
+    StakingLifecycle,
+    StakingLifecycleOptions,
+    PlanStakingStepOptions,
+    StakingGoal,
+    StakingStep,
+    StakingStepType,
+    StakingStepTransaction,
+} from './staking_lifecycle';
+export {
+    StakingTransactionQueue,
+    StakingTransactionQueueOptions,
+    QueuedOperation,
+    QueuedOperationType,
+    QueuedStakingTransaction,
+} from './staking_queue';
 
 export {
+    State,
     PrevOutputPointer,
     DepositTransactionBuilderOptions,
     UnbondTransactionBuilderOptions,
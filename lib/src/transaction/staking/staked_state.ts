@@ -0,0 +1,42 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { State } from './types';
+import { owStakingAddress, owTendermintAddress } from '../../types';
+
+const native = require('../../../../native');
+
+/**
+ * Query the staked state of the staking address from the Tendermint node
+ *
+ * @param {string} stakingAddress Staking address to query
+ * @param {string} tendermintAddress Tendermint websocket address
+ * @returns {State|undefined} staked state, or undefined if the staking
+ * address has no staked state
+ */
+export const queryStakedState = (
+    stakingAddress: string,
+    tendermintAddress: string,
+): State | undefined => {
+    ow(stakingAddress, 'stakingAddress', owStakingAddress);
+    ow(tendermintAddress, 'tendermintAddress', owTendermintAddress);
+
+    const state = native.stakingTransaction.queryStakedState(
+        stakingAddress,
+        tendermintAddress,
+    );
+    if (state === null) {
+        return undefined;
+    }
+
+    return {
+        nonce: state.nonce,
+        bonded: new BigNumber(state.bonded),
+        unbonded: new BigNumber(state.unbonded),
+        unbondedFrom: state.unbondedFrom,
+        address: state.address,
+        ...(state.jailedUntil !== undefined && {
+            jailedUntil: state.jailedUntil,
+        }),
+    };
+};
@@ -0,0 +1,225 @@
+import 'mocha';
+import { expect } from 'chai';
+import BigNumber from 'bignumber.js';
+
+import {
+    StakingLifecycle,
+    StakingGoal,
+    StakingStepType,
+} from './staking_lifecycle';
+import { State } from './types';
+import { KeyPair } from '../../key_pair';
+import { Mainnet } from '../../network';
+import { Timespec } from '../../types';
+
+describe('StakingLifecycle', () => {
+    const SAMPLE_KEY_PAIR = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+    const SAMPLE_TO_ADDRESS =
+        'cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4';
+    const SAMPLE_BLOCK_TIME = Timespec.fromSeconds(1587071014);
+
+    const newWithdrawAllLifecycle = () =>
+        new StakingLifecycle({
+            keyPair: SAMPLE_KEY_PAIR,
+            goal: StakingGoal.WithdrawAll,
+            toAddress: SAMPLE_TO_ADDRESS,
+            network: Mainnet,
+        });
+    const stakedState = (overrides: Partial<State> = {}): State => ({
+        nonce: 1,
+        bonded: new BigNumber('0'),
+        unbonded: new BigNumber('0'),
+        unbondedFrom: 0,
+        address: newWithdrawAllLifecycle().getStakingAddress(),
+        ...overrides,
+    });
+
+    describe('constructor', () => {
+        it('should throw Error when withdrawAll goal has no destination address', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new StakingLifecycle({
+                    keyPair: SAMPLE_KEY_PAIR,
+                    goal: StakingGoal.WithdrawAll,
+                });
+            }).to.throw(
+                'Expected `toAddress` to be provided for withdrawAll goal',
+            );
+        });
+    });
+
+    describe('planNextStep', () => {
+        it('should plan deposit when bond goal has no staked state', () => {
+            const lifecycle = new StakingLifecycle({
+                keyPair: SAMPLE_KEY_PAIR,
+                goal: StakingGoal.Bond,
+            });
+
+            expect(
+                lifecycle.planNextStep({
+                    stakedState: null,
+                    blockTime: SAMPLE_BLOCK_TIME,
+                }),
+            ).to.deep.eq({
+                type: StakingStepType.Deposit,
+                nonce: new BigNumber(0),
+            });
+        });
+
+        it('should plan unbond of all bonded amount', () => {
+            const step = newWithdrawAllLifecycle().planNextStep({
+                stakedState: stakedState({ bonded: new BigNumber('5000') }),
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+
+            expect(step.type).to.eq(StakingStepType.Unbond);
+            expect(step.nonce!.toString(10)).to.eq('1');
+            expect(step.amount!.toString(10)).to.eq('5000');
+        });
+
+        it('should plan wait when unbonded amount is not yet withdrawable', () => {
+            const step = newWithdrawAllLifecycle().planNextStep({
+                stakedState: stakedState({
+                    unbonded: new BigNumber('5000'),
+                    unbondedFrom: 1587071015,
+                }),
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+
+            expect(step).to.deep.eq({
+                type: StakingStepType.WaitUnbonding,
+                until: 1587071015,
+            });
+        });
+
+        it('should plan withdraw when unbonding period is over', () => {
+            const step = newWithdrawAllLifecycle().planNextStep({
+                stakedState: stakedState({
+                    unbonded: new BigNumber('5000'),
+                    unbondedFrom: 1587071014,
+                }),
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+
+            expect(step.type).to.eq(StakingStepType.Withdraw);
+            expect(step.amount!.toString(10)).to.eq('5000');
+        });
+
+        it('should plan unjail before unbonding a jailed staked state', () => {
+            const lifecycle = newWithdrawAllLifecycle();
+
+            expect(
+                lifecycle.planNextStep({
+                    stakedState: stakedState({
+                        bonded: new BigNumber('5000'),
+                        jailedUntil: 1587071015,
+                    }),
+                    blockTime: SAMPLE_BLOCK_TIME,
+                }).type,
+            ).to.eq(StakingStepType.WaitJailed);
+            expect(
+                lifecycle.planNextStep({
+                    stakedState: stakedState({
+                        bonded: new BigNumber('5000'),
+                        jailedUntil: 1587071014,
+                    }),
+                    blockTime: SAMPLE_BLOCK_TIME,
+                }).type,
+            ).to.eq(StakingStepType.Unjail);
+        });
+
+        it('should plan withdraw before unbonding the bonded amount', () => {
+            const step = newWithdrawAllLifecycle().planNextStep({
+                stakedState: stakedState({
+                    bonded: new BigNumber('5000'),
+                    unbonded: new BigNumber('3000'),
+                    unbondedFrom: 1587071014,
+                }),
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+
+            expect(step.type).to.eq(StakingStepType.Withdraw);
+            expect(step.amount!.toString(10)).to.eq('3000');
+        });
+
+        it('should plan done when everything is withdrawn', () => {
+            expect(
+                newWithdrawAllLifecycle().planNextStep({
+                    stakedState: stakedState(),
+                    blockTime: SAMPLE_BLOCK_TIME,
+                }),
+            ).to.deep.eq({ type: StakingStepType.Done });
+        });
+    });
+
+    describe('buildStepTransaction', () => {
+        it('should throw Error when the step has no transaction', () => {
+            expect(() => {
+                newWithdrawAllLifecycle().buildStepTransaction({
+                    type: StakingStepType.Done,
+                });
+            }).to.throw('Step done has no transaction to build');
+        });
+
+        it('should throw Error when deposit step has no input', () => {
+            const lifecycle = new StakingLifecycle({
+                keyPair: SAMPLE_KEY_PAIR,
+                goal: StakingGoal.Bond,
+            });
+
+            expect(() => {
+                lifecycle.buildStepTransaction({
+                    type: StakingStepType.Deposit,
+                    nonce: new BigNumber(0),
+                });
+            }).to.throw('Expected `inputs` to be of type `array`');
+        });
+
+        it('should wait for the nonce of the built transaction', () => {
+            const lifecycle = newWithdrawAllLifecycle();
+            const state = stakedState({ bonded: new BigNumber('5000') });
+
+            const step = lifecycle.planNextStep({
+                stakedState: state,
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+            const transaction = lifecycle.buildStepTransaction(step);
+
+            expect(transaction.txHex.length).to.be.greaterThan(0);
+            expect(transaction.amount!.toString(10)).to.eq('5000');
+            expect(transaction.expectedNonce.toString(10)).to.eq('2');
+
+            const nextStep = lifecycle.planNextStep({
+                stakedState: state,
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+            expect(nextStep.type).to.eq(StakingStepType.WaitForNonce);
+            expect(nextStep.expectedNonce!.toString(10)).to.eq('2');
+
+            const committedStep = lifecycle.planNextStep({
+                stakedState: stakedState({
+                    nonce: 2,
+                    unbonded: new BigNumber('5000'),
+                    unbondedFrom: 1587071014,
+                }),
+                blockTime: SAMPLE_BLOCK_TIME,
+            });
+            expect(committedStep.type).to.eq(StakingStepType.Withdraw);
+        });
+
+        it('should deduct the fee from the withdrawn amount', () => {
+            const lifecycle = newWithdrawAllLifecycle();
+
+            const transaction = lifecycle.buildStepTransaction({
+                type: StakingStepType.Withdraw,
+                nonce: new BigNumber(2),
+                amount: new BigNumber('100000000'),
+            });
+
+            expect(
+                transaction.amount!.isLessThan(new BigNumber('100000000')),
+            ).to.eq(true);
+            expect(transaction.expectedNonce.toString(10)).to.eq('3');
+        });
+    });
+});
@@ -0,0 +1,312 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { DepositInput, State, owDepositInput, owState } from './types';
+import { queryStakedState } from './staked_state';
+import { NetworkConfig, Mainnet } from '../../network';
+import { owOptionalNetworkConfig } from '../../network/types';
+import { KeyPair } from '../../key_pair';
+import { owKeyPair } from '../../key_pair/types';
+import { staking } from '../../address/staking';
+import {
+    owTendermintAddress,
+    owTransferAddress,
+    owViewKey,
+    parseOutputForNative,
+    Timespec,
+} from '../../types';
+import { owOptionalTimespec } from '../../types/timespec';
+import { getRustFeaturesFromEnv } from '../../native';
+import { parseFeeConfigForNative } from '../../fee/types';
+
+const native = require('../../../../native');
+
+/**
+ * Target of the staking lifecycle
+ */
+export enum StakingGoal {
+    Bond = 'bond',
+    WithdrawAll = 'withdrawAll',
+}
+
+/**
+ * Type of the next staking step
+ */
+export enum StakingStepType {
+    Deposit = 'deposit',
+    WaitForNonce = 'waitForNonce',
+    WaitJailed = 'waitJailed',
+    Unjail = 'unjail',
+    Unbond = 'unbond',
+    WaitUnbonding = 'waitUnbonding',
+    Withdraw = 'withdraw',
+    Done = 'done',
+}
+
+/**
+ * Next step of the staking lifecycle
+ * @typedef {object} StakingStep
+ * @property {StakingStepType} type step type
+ * @property {BigNumber} [nonce] nonce of the step transaction
+ * @property {BigNumber} [amount] amount to unbond or withdraw
+ * @property {number} [until] time to wait until
+ * @property {BigNumber} [expectedNonce] nonce the staked state should reach
+ */
+export interface StakingStep {
+    type: StakingStepType;
+    nonce?: BigNumber;
+    amount?: BigNumber;
+    until?: number;
+    expectedNonce?: BigNumber;
+}
+
+/**
+ * Signed transaction of a staking step
+ * @typedef {object} StakingStepTransaction
+ * @property {StakingStep} step step of the transaction
+ * @property {string} txId transaction Id
+ * @property {Buffer} txHex broadcast-able transaction hex
+ * @property {BigNumber} [amount] deposited or withdrawn amount net of fee,
+ * or unbonded amount
+ * @property {BigNumber} expectedNonce staked state nonce after the
+ * transaction is committed
+ */
+export interface StakingStepTransaction {
+    step: StakingStep;
+    txId: string;
+    txHex: Buffer;
+    amount?: BigNumber;
+    expectedNonce: BigNumber;
+}
+
+export interface StakingLifecycleOptions {
+    keyPair: KeyPair;
+    goal: StakingGoal;
+    toAddress?: string;
+    viewKeys?: Buffer[];
+    tendermintAddress?: string;
+    network?: NetworkConfig;
+}
+
+export interface PlanStakingStepOptions {
+    stakedState?: State | null;
+    blockTime?: Timespec;
+}
+
+/**
+ * @internal
+ */
+const owStakingLifecycleOptions = ow.object.exactShape({
+    keyPair: owKeyPair,
+    goal: ow.string.oneOf(Object.values(StakingGoal)),
+    toAddress: ow.optional.any(owTransferAddress),
+    viewKeys: ow.optional.array.ofType(owViewKey),
+    tendermintAddress: ow.optional.any(owTendermintAddress),
+    network: owOptionalNetworkConfig,
+});
+
+/**
+ * @internal
+ */
+const owPlanStakingStepOptions = ow.object.exactShape({
+    stakedState: ow.optional.any(ow.null, owState),
+    blockTime: owOptionalTimespec,
+});
+
+/**
+ * Orchestrates the staking lifecycle of a staking address, i.e. deposit to
+ * bond, unbond and withdraw the unbonded amount once the unbonding period
+ * is over. The expected staked state nonce is tracked across steps so that
+ * a step is only planned after the previous transaction is committed.
+ */
+export class StakingLifecycle {
+    private keyPair: KeyPair;
+
+    private stakingAddress: string;
+
+    private goal: StakingGoal;
+
+    private toAddress?: string;
+
+    private viewKeys: Buffer[];
+
+    private tendermintAddress: string;
+
+    private network: NetworkConfig;
+
+    private expectedNonce?: BigNumber;
+
+    /**
+     * Creates an instance of StakingLifecycle
+     * @param {StakingLifecycleOptions} options Lifecycle options
+     * @param {KeyPair} options.keyPair Key pair of the staking address
+     * @param {StakingGoal} options.goal Target of the lifecycle
+     * @param {string} [options.toAddress] Transfer address to withdraw to.
+     * Required for `withdrawAll` goal
+     * @param {Buffer[]} [options.viewKeys] View keys of the withdraw
+     * transaction
+     * @param {string} [options.tendermintAddress='ws://localhost:26657/websocket']
+     * Tendermint address to query staked state and obfuscate transactions
+     * @param {NetworkConfig} [options.network] Network of the staking address
+     */
+    constructor(options: StakingLifecycleOptions) {
+        ow(options, 'options', owStakingLifecycleOptions);
+        if (options.goal === StakingGoal.WithdrawAll && !options.toAddress) {
+            throw new Error(
+                'Expected `toAddress` to be provided for withdrawAll goal',
+            );
+        }
+
+        this.keyPair = options.keyPair;
+        this.stakingAddress = staking({ keyPair: options.keyPair });
+        this.goal = options.goal;
+        this.toAddress = options.toAddress;
+        this.viewKeys = options.viewKeys || [];
+        this.tendermintAddress =
+            options.tendermintAddress || 'ws://localhost:26657/websocket';
+        this.network = options.network || Mainnet;
+    }
+
+    /**
+     * Returns the staking address of the lifecycle
+     * @returns {string}
+     * @memberof StakingLifecycle
+     */
+    public getStakingAddress(): Readonly<string> {
+        return this.stakingAddress;
+    }
+
+    /**
+     * Returns the staked state nonce expected after the last built
+     * transaction
+     * @returns {BigNumber|undefined}
+     * @memberof StakingLifecycle
+     */
+    public getExpectedNonce(): Readonly<BigNumber> | undefined {
+        return this.expectedNonce;
+    }
+
+    /**
+     * Plan the next valid step towards the goal. The staked state is queried
+     * from the Tendermint node unless provided
+     * @param {PlanStakingStepOptions} [options]
+     * @param {State|null} [options.stakedState] Current staked state, null
+     * if the staking address has no staked state
+     * @param {Timespec} [options.blockTime] Latest block time, default to now
+     * @returns {StakingStep}
+     * @memberof StakingLifecycle
+     */
+    public planNextStep(options: PlanStakingStepOptions = {}): StakingStep {
+        ow(options, 'options', owPlanStakingStepOptions);
+
+        const stakedState =
+            options.stakedState !== undefined
+                ? options.stakedState
+                : queryStakedState(this.stakingAddress, this.tendermintAddress);
+        const blockTime = options.blockTime
+            ? options.blockTime.toNumber()
+            : Math.floor(Date.now() / 1000);
+
+        const step = native.stakingLifecycle.planNextStakingStep({
+            goal: this.goal,
+            stakedState: stakedState
+                ? {
+                      nonce: stakedState.nonce.toString(10),
+                      bonded: stakedState.bonded.toString(10),
+                      unbonded: stakedState.unbonded.toString(10),
+                      unbondedFrom: stakedState.unbondedFrom,
+                      jailedUntil: stakedState.jailedUntil,
+                  }
+                : undefined,
+            blockTime,
+            expectedNonce: this.expectedNonce
+                ? this.expectedNonce.toString(10)
+                : undefined,
+        });
+
+        return parseStakingStepFromNative(step);
+    }
+
+    /**
+     * Build the signed transaction of a deposit, unjail, unbond or withdraw
+     * step and track the nonce expected after it is committed
+     * @param {StakingStep} step planned step
+     * @param {DepositInput[]} [inputs] 1-of-1 inputs of the key pair to
+     * deposit. Required for deposit step
+     * @returns {StakingStepTransaction}
+     * @memberof StakingLifecycle
+     */
+    public buildStepTransaction(
+        step: StakingStep,
+        inputs?: DepositInput[],
+    ): StakingStepTransaction {
+        if (
+            step.type !== StakingStepType.Deposit &&
+            step.type !== StakingStepType.Unjail &&
+            step.type !== StakingStepType.Unbond &&
+            step.type !== StakingStepType.Withdraw
+        ) {
+            throw new Error(`Step ${step.type} has no transaction to build`);
+        }
+        if (step.type === StakingStepType.Deposit) {
+            ow(inputs, 'inputs', ow.array.nonEmpty.ofType(owDepositInput));
+        }
+
+        const {
+            txId,
+            txHex,
+            amount,
+            expectedNonce,
+        } = native.stakingLifecycle.buildStakingStepTransaction(
+            {
+                step: {
+                    type: step.type,
+                    nonce: step.nonce!.toString(10),
+                    amount: step.amount ? step.amount.toString(10) : undefined,
+                    inputs: inputs
+                        ? inputs.map((input) => ({
+                              prevTxId: input.prevTxId,
+                              prevIndex: input.prevIndex,
+                              prevOutput: parseOutputForNative(
+                                  input.prevOutput,
+                              ),
+                              addressParams: input.addressParams,
+                          }))
+                        : undefined,
+                },
+                stakingAddress: this.stakingAddress,
+                chainHexId: this.network.chainHexId,
+                toAddress: this.toAddress,
+                viewKeys: this.viewKeys,
+                feeConfig: parseFeeConfigForNative(this.network.feeConfig),
+            },
+            this.keyPair.toObject(),
+            this.tendermintAddress,
+            getRustFeaturesFromEnv(process.env.NODE_ENV),
+        );
+
+        this.expectedNonce = new BigNumber(expectedNonce);
+
+        return {
+            step,
+            txId,
+            txHex,
+            amount: amount !== undefined ? new BigNumber(amount) : undefined,
+            expectedNonce: this.expectedNonce,
+        };
+    }
+}
+
+const parseStakingStepFromNative = (step: any): StakingStep => {
+    return {
+        type: step.type,
+        ...(step.nonce !== undefined && { nonce: new BigNumber(step.nonce) }),
+        ...(step.amount !== undefined && {
+            amount: new BigNumber(step.amount),
+        }),
+        ...(step.until !== undefined && { until: step.until }),
+        ...(step.expectedNonce !== undefined && {
+            expectedNonce: new BigNumber(step.expectedNonce),
+        }),
+    };
+};
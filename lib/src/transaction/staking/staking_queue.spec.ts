@@ -0,0 +1,106 @@
+import 'mocha';
+import { expect } from 'chai';
+import BigNumber from 'bignumber.js';
+
+import { StakingTransactionQueue, QueuedOperationType } from './staking_queue';
+import { KeyPair } from '../../key_pair';
+import { Mainnet } from '../../network';
+
+describe('StakingTransactionQueue', () => {
+    const SAMPLE_KEY_PAIR = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+
+    const newQueue = () =>
+        new StakingTransactionQueue({
+            keyPair: SAMPLE_KEY_PAIR,
+            committedNonce: new BigNumber(3),
+            network: Mainnet,
+        });
+    const unbond = (amount: string) => ({
+        type: QueuedOperationType.Unbond,
+        amount: new BigNumber(amount),
+    });
+
+    describe('enqueue', () => {
+        it('should throw Error when unbond operation has no amount', () => {
+            expect(() => {
+                newQueue().enqueue({ type: QueuedOperationType.Unbond });
+            }).to.throw(
+                'Expected `amount` only for unbond and `councilNode` only for nodeJoin operation',
+            );
+        });
+
+        it('should assign sequential nonces', () => {
+            const queue = newQueue();
+
+            const first = queue.enqueue(unbond('1000'));
+            const second = queue.enqueue({ type: QueuedOperationType.Unjail });
+
+            expect(first.nonce.toString(10)).to.eq('3');
+            expect(second.nonce.toString(10)).to.eq('4');
+            expect(second.txHex.length).to.be.greaterThan(0);
+        });
+    });
+
+    describe('confirm', () => {
+        it('should throw Error when the transaction is not the next one to commit', () => {
+            const queue = newQueue();
+            queue.enqueue(unbond('1000'));
+            const second = queue.enqueue(unbond('2000'));
+
+            expect(() => {
+                queue.confirm(second.txId);
+            }).to.throw(
+                `Transaction ${second.txId} is not the next transaction to commit`,
+            );
+        });
+
+        it('should increase the committed nonce', () => {
+            const queue = newQueue();
+            const first = queue.enqueue(unbond('1000'));
+            const second = queue.enqueue(unbond('2000'));
+
+            queue.confirm(first.txId);
+
+            expect(queue.getCommittedNonce().toString(10)).to.eq('4');
+            expect(queue.getPendingTransactions()).to.deep.eq([second]);
+        });
+    });
+
+    describe('fail', () => {
+        it('should re-base the following transactions', () => {
+            const queue = newQueue();
+            queue.enqueue(unbond('1000'));
+            const failed = queue.enqueue(unbond('2000'));
+            const third = queue.enqueue(unbond('3000'));
+
+            const rebased = queue.fail(failed.txId);
+
+            expect(rebased.length).to.eq(1);
+            expect(rebased[0].nonce.toString(10)).to.eq('4');
+            expect(rebased[0].operation).to.deep.eq(third.operation);
+            expect(rebased[0].txId).to.not.eq(third.txId);
+            expect(queue.getPendingTransactions()[1]).to.deep.eq(rebased[0]);
+        });
+    });
+
+    describe('getPendingTransactions', () => {
+        it('should return the transactions signed when queued', () => {
+            const queue = newQueue();
+            const first = queue.enqueue(unbond('1000'));
+            const second = queue.enqueue({ type: QueuedOperationType.Unjail });
+
+            expect(queue.getPendingTransactions()).to.deep.eq([first, second]);
+        });
+    });
+
+    describe('free', () => {
+        it('should throw Error when the queue is used after free', () => {
+            const queue = newQueue();
+            queue.free();
+
+            expect(() => {
+                queue.enqueue(unbond('1000'));
+            }).to.throw(/Staking queue \d+ is freed/);
+        });
+    });
+});
@@ -0,0 +1,323 @@
+import fs from 'fs';
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { NetworkConfig, Mainnet } from '../../network';
+import { owOptionalNetworkConfig } from '../../network/types';
+import { KeyPair } from '../../key_pair';
+import { owKeyPair } from '../../key_pair/types';
+import { staking } from '../../address/staking';
+import { owAccountNonce, owCoin, owTendermintAddress } from '../../types';
+import {
+    CouncilNodeOptions,
+    owCouncilNodeOptions,
+} from '../council_node/types';
+
+const native = require('../../../../native');
+
+/**
+ * Type of the queued staked state operation
+ */
+export enum QueuedOperationType {
+    Unbond = 'unbond',
+    Unjail = 'unjail',
+    NodeJoin = 'nodeJoin',
+}
+
+/**
+ * Staked state operation to queue
+ * @typedef {object} QueuedOperation
+ * @property {QueuedOperationType} type operation type
+ * @property {BigNumber} [amount] amount to unbond. Required for `unbond`
+ * @property {CouncilNodeOptions} [councilNode] council node to join.
+ * Required for `nodeJoin`
+ */
+export interface QueuedOperation {
+    type: QueuedOperationType;
+    amount?: BigNumber;
+    councilNode?: CouncilNodeOptions;
+}
+
+/**
+ * Signed transaction of a queued operation
+ * @typedef {object} QueuedStakingTransaction
+ * @property {QueuedOperation} operation queued operation
+ * @property {BigNumber} nonce nonce assigned to the operation
+ * @property {string} txId transaction Id
+ * @property {Buffer} txHex broadcast-able transaction hex
+ */
+export interface QueuedStakingTransaction {
+    operation: QueuedOperation;
+    nonce: BigNumber;
+    txId: string;
+    txHex: Buffer;
+}
+
+export interface StakingTransactionQueueOptions {
+    keyPair: KeyPair;
+    committedNonce?: BigNumber;
+    tendermintAddress?: string;
+    network?: NetworkConfig;
+}
+
+/**
+ * @internal
+ */
+const owStakingTransactionQueueOptions = ow.object.exactShape({
+    keyPair: owKeyPair,
+    committedNonce: ow.optional.any(owAccountNonce),
+    tendermintAddress: ow.optional.any(owTendermintAddress),
+    network: owOptionalNetworkConfig,
+});
+
+/**
+ * @internal
+ */
+const owQueuedOperation = ow.object
+    .exactShape({
+        type: ow.string.oneOf(Object.values(QueuedOperationType)),
+        amount: ow.optional.any(owCoin),
+        councilNode: ow.optional.any(owCouncilNodeOptions),
+    })
+    .validate((value: any) => ({
+        validator:
+            (value.type === QueuedOperationType.Unbond) ===
+                (value.amount !== undefined) &&
+            (value.type === QueuedOperationType.NodeJoin) ===
+                (value.councilNode !== undefined),
+        message:
+            'Expected `amount` only for unbond and `councilNode` only for nodeJoin operation',
+    }));
+
+/**
+ * Queues the unbond, unjail and node join transactions of a staking
+ * address. Each operation is signed with the next nonce after the pending
+ * ones, so transactions can be broadcasted before the previous ones are
+ * committed. When a transaction is confirmed or fails, or when the committed
+ * nonce is synced from the Tendermint node, the remaining transactions are
+ * re-based and have to be broadcasted again with their new transaction Id.
+ * The queue is kept natively until `free()` is called.
+ */
+export class StakingTransactionQueue {
+    private id: number;
+
+    private stakingAddress: string;
+
+    private tendermintAddress: string;
+
+    private operations = new Map<number, QueuedOperation>();
+
+    /**
+     * Creates an instance of StakingTransactionQueue
+     * @param {StakingTransactionQueueOptions} options Queue options
+     * @param {KeyPair} options.keyPair Key pair of the staking address
+     * @param {BigNumber} [options.committedNonce=0] Nonce of the committed
+     * staked state. Call `sync()` to read it from the Tendermint node
+     * @param {string} [options.tendermintAddress='ws://localhost:26657/websocket']
+     * Tendermint address to sync the committed nonce from
+     * @param {NetworkConfig} [options.network] Network of the staking address
+     */
+    constructor(options: StakingTransactionQueueOptions) {
+        ow(options, 'options', owStakingTransactionQueueOptions);
+
+        const network = options.network || Mainnet;
+        const committedNonce = options.committedNonce || new BigNumber(0);
+
+        this.stakingAddress = staking({ keyPair: options.keyPair });
+        this.tendermintAddress =
+            options.tendermintAddress || 'ws://localhost:26657/websocket';
+        this.id = native.stakingQueue.create(
+            {
+                stakingAddress: this.stakingAddress,
+                chainHexId: network.chainHexId,
+                committedNonce: committedNonce.toString(10),
+            },
+            options.keyPair.toObject(),
+        );
+    }
+
+    /**
+     * Returns the staking address of the queue
+     * @returns {string}
+     * @memberof StakingTransactionQueue
+     */
+    public getStakingAddress(): Readonly<string> {
+        return this.stakingAddress;
+    }
+
+    /**
+     * Returns the nonce of the committed staked state
+     * @returns {BigNumber}
+     * @memberof StakingTransactionQueue
+     */
+    public getCommittedNonce(): Readonly<BigNumber> {
+        return new BigNumber(native.stakingQueue.committedNonce(this.id));
+    }
+
+    /**
+     * Sign the operation with the next nonce and queue it
+     * @param {QueuedOperation} operation operation to queue
+     * @returns {QueuedStakingTransaction} signed transaction of the operation
+     * @memberof StakingTransactionQueue
+     */
+    public enqueue(operation: QueuedOperation): QueuedStakingTransaction {
+        ow(operation, 'operation', owQueuedOperation);
+
+        const transaction = native.stakingQueue.push(
+            this.id,
+            parseQueuedOperationForNative(operation),
+        );
+        this.operations.set(transaction.id, operation);
+
+        return this.parseTransaction(transaction);
+    }
+
+    /**
+     * Returns the signed transactions of the pending operations in nonce
+     * order
+     * @returns {QueuedStakingTransaction[]}
+     * @memberof StakingTransactionQueue
+     */
+    public getPendingTransactions(): QueuedStakingTransaction[] {
+        return this.parseTransactions(
+            native.stakingQueue.transactions(this.id),
+        );
+    }
+
+    /**
+     * Mark the transaction as committed. Only the first pending transaction
+     * can be committed since the chain applies nonces in order
+     * @param {string} txId Id of the committed transaction
+     * @memberof StakingTransactionQueue
+     */
+    public confirm(txId: string) {
+        ow(txId, 'txId', ow.string);
+
+        const pending = native.stakingQueue.transactions(this.id);
+        if (pending.length === 0 || pending[0].txId !== txId) {
+            throw new Error(
+                `Transaction ${txId} is not the next transaction to commit`,
+            );
+        }
+
+        native.stakingQueue.confirm(this.id, pending[0].id);
+        this.operations.delete(pending[0].id);
+    }
+
+    /**
+     * Remove the failed transaction and re-base the following ones on its
+     * nonce
+     * @param {string} txId Id of the failed transaction
+     * @returns {QueuedStakingTransaction[]} re-based transactions to
+     * broadcast again
+     * @memberof StakingTransactionQueue
+     */
+    public fail(txId: string): QueuedStakingTransaction[] {
+        ow(txId, 'txId', ow.string);
+
+        const failed = native.stakingQueue
+            .transactions(this.id)
+            .find(
+                (transaction: NativeQueuedTransaction) =>
+                    transaction.txId === txId,
+            );
+        if (failed === undefined) {
+            throw new Error(`Transaction ${txId} is not pending`);
+        }
+
+        const rebased = native.stakingQueue.fail(this.id, failed.id);
+        this.operations.delete(failed.id);
+
+        return this.parseTransactions(rebased);
+    }
+
+    /**
+     * Read the committed nonce of the staked state from the Tendermint node
+     * and re-base the queue on it. Operations below the committed nonce are
+     * removed as committed
+     * @returns {QueuedStakingTransaction[]} pending transactions after
+     * re-base
+     * @memberof StakingTransactionQueue
+     */
+    public sync(): QueuedStakingTransaction[] {
+        const committed: number[] = native.stakingQueue.sync(
+            this.id,
+            this.tendermintAddress,
+        );
+        committed.forEach((id) => this.operations.delete(id));
+
+        return this.getPendingTransactions();
+    }
+
+    /**
+     * Release the native queue. The queue cannot be used afterwards
+     * @memberof StakingTransactionQueue
+     */
+    public free() {
+        native.stakingQueue.free(this.id);
+        this.operations.clear();
+    }
+
+    private parseTransactions(
+        transactions: NativeQueuedTransaction[],
+    ): QueuedStakingTransaction[] {
+        return transactions.map((transaction) =>
+            this.parseTransaction(transaction),
+        );
+    }
+
+    private parseTransaction(
+        transaction: NativeQueuedTransaction,
+    ): QueuedStakingTransaction {
+        return {
+            operation: this.operations.get(transaction.id)!,
+            nonce: new BigNumber(transaction.nonce),
+            txId: transaction.txId,
+            txHex: transaction.txHex,
+        };
+    }
+}
+
+/**
+ * @internal
+ */
+interface NativeQueuedTransaction {
+    id: number;
+    nonce: string;
+    txId: string;
+    txHex: Buffer;
+}
+
+/**
+ * Operation for native, with the private validator key file read once
+ * @internal
+ */
+const parseQueuedOperationForNative = (operation: QueuedOperation): any => {
+    switch (operation.type) {
+        case QueuedOperationType.Unbond:
+            return {
+                type: operation.type,
+                amount: operation.amount!.toString(10),
+            };
+        case QueuedOperationType.NodeJoin: {
+            const councilNode = operation.councilNode!;
+            const privValidatorKey =
+                councilNode.privValidatorKeyFile !== undefined
+                    ? fs.readFileSync(councilNode.privValidatorKeyFile, 'utf8')
+                    : undefined;
+
+            return {
+                type: operation.type,
+                councilNode: {
+                    name: councilNode.name,
+                    securityContact: councilNode.securityContact,
+                    consensusPublicKey: councilNode.consensusPublicKey,
+                    privValidatorKey,
+                    confidentialInit: councilNode.confidentialInit,
+                },
+            };
+        }
+        default:
+            return { type: operation.type };
+    }
+};
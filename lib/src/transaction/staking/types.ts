@@ -6,6 +6,9 @@ import { owOptionalNetworkConfig } from '../../network/types';
 import {
     owCoin,
     owTxId,
+    owOutput,
+    owInputAddressParams,
+    InputAddressParams,
     Output,
     Timespec,
     owAccountNonce,
@@ -14,7 +17,7 @@ import {
     owUnixTimestamp,
     owBigNumber,
 } from '../../types';
-import { owTimespec } from '../../types/timespec';
+import { owTimespec, owOptionalTimespec } from '../../types/timespec';
 
 // Simplified staked state
 export interface State {
@@ -23,6 +26,7 @@ export interface State {
     unbonded: BigNumber;
     unbondedFrom: number;
     address: string;
+    jailedUntil?: number;
 }
 
 /**
@@ -34,6 +38,7 @@ export const owState = ow.object.exactShape({
     unbonded: owBigNumber,
     unbondedFrom: owUnixTimestamp,
     address: owStakingAddress,
+    jailedUntil: ow.optional.number.integer,
 });
 
 // TODO: Change transfer transaction builder to use this interface
@@ -48,14 +53,33 @@ export interface PrevOutputPointer {
     prevIndex: number;
 }
 
+/**
+ * Deposit transaction input with the spent output
+ * @typedef {object} DepositInput
+ * @property {string} prevTxId previous transaction Id
+ * @property {number} prevIndex previous transaction output index
+ * @property {Output} prevOutput previous transaction output
+ * @property {InputAddressParams} [addressParams] signers of the previous
+ * output address, default to 1-of-1
+ */
+export interface DepositInput extends PrevOutputPointer {
+    prevOutput: Output;
+    addressParams?: InputAddressParams;
+}
+
 /**
  * Deposit Transaction input
  * @typedef {object} Input
  * @property {PrevOutputPointer} prevOutputPointer previous output pointer
+ * @property {Output} [prevOutput] previous transaction output
+ * @property {InputAddressParams} [addressParams] signers of the previous
+ * output address
  * @property {Buffer} witness witness associated to the input
  */
 export interface WitnessedPrevOutputPointer {
     prevOutputPointer: PrevOutputPointer;
+    prevOutput?: Output;
+    addressParams?: InputAddressParams;
     witness?: Buffer;
 }
 
@@ -67,6 +91,16 @@ export const owPrevOutputPointer = ow.object.exactShape({
     prevIndex: ow.number.uint16,
 });
 
+/**
+ * @internal
+ */
+export const owDepositInput = ow.object.exactShape({
+    prevTxId: owTxId,
+    prevIndex: ow.number.uint16,
+    prevOutput: owOutput,
+    addressParams: ow.optional.any(owInputAddressParams),
+});
+
 export interface DepositTransactionBuilderOptions {
     stakingAddress: string;
     network?: NetworkConfig;
@@ -83,7 +117,11 @@ export const owDepositTransactionOptions = ow.object.exactShape({
 export interface UnbondTransactionBuilderOptions {
     stakingAddress: string;
     nonce: BigNumber;
-    amount: BigNumber;
+    amount?: BigNumber;
+    unbondAll?: boolean;
+    stakedState?: State;
+    unbondingPeriod: number;
+    blockTime?: Timespec;
     network?: NetworkConfig;
 }
 
@@ -94,6 +132,23 @@ export const owUnbondTransactionBuilderOptions = ow.object.exactShape({
     stakingAddress: owStakingAddress,
     nonce: owAccountNonce,
     amount: owCoin,
+    unbondAll: ow.optional.boolean.false,
+    stakedState: ow.optional.any(owState),
+    unbondingPeriod: ow.number.uint32,
+    blockTime: owOptionalTimespec,
+    network: owOptionalNetworkConfig,
+});
+
+/**
+ * @internal
+ */
+export const owUnbondAllTransactionBuilderOptions = ow.object.exactShape({
+    stakingAddress: owStakingAddress,
+    nonce: owAccountNonce,
+    unbondAll: ow.boolean.true,
+    stakedState: owState,
+    unbondingPeriod: ow.number.uint32,
+    blockTime: owOptionalTimespec,
     network: owOptionalNetworkConfig,
 });
 
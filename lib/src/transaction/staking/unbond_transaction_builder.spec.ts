@@ -3,10 +3,12 @@ import { expect } from 'chai';
 
 import BigNumber from 'bignumber.js';
 import { UnbondTransactionBuilder } from './unbond_transaction_builder';
-import { Mainnet, Devnet } from '../../network';
+import { Mainnet, Devnet, registerNetwork } from '../../network';
 import { MAX_COIN_BN } from '../../init';
 import { KeyPair } from '../../key_pair';
 import { FeeAlgorithm, FeeConfig } from '../../fee';
+import { Timespec } from '../../types';
+import { State } from './types';
 
 describe('UnbondTransactionBuilder', () => {
     const SAMPLE_FEE_CONFIG: FeeConfig = {
@@ -16,6 +18,7 @@ describe('UnbondTransactionBuilder', () => {
     };
     const SAMPLE_NONCE = new BigNumber(1);
     const SAMPLE_STAKING_ADDRESS = '0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0';
+    const SAMPLE_UNBONDING_PERIOD = 86400;
 
     describe('constructor', () => {
         it('should throw Error when staking address is missing', () => {
@@ -24,6 +27,7 @@ describe('UnbondTransactionBuilder', () => {
                 new UnbondTransactionBuilder({
                     nonce: SAMPLE_NONCE,
                     amount: new BigNumber(1000),
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 } as any);
             }).to.throw(
@@ -38,6 +42,7 @@ describe('UnbondTransactionBuilder', () => {
                     nonce: SAMPLE_NONCE,
                     stakingAddress: '0xInvalid',
                     amount: new BigNumber(1000),
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 });
             }).to.throw(
@@ -51,6 +56,7 @@ describe('UnbondTransactionBuilder', () => {
                 new UnbondTransactionBuilder({
                     stakingAddress: SAMPLE_STAKING_ADDRESS,
                     amount: new BigNumber(1000),
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 } as any);
             }).to.throw(
@@ -64,6 +70,7 @@ describe('UnbondTransactionBuilder', () => {
                 new UnbondTransactionBuilder({
                     stakingAddress: SAMPLE_STAKING_ADDRESS,
                     nonce: SAMPLE_NONCE,
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 } as any);
             }).to.throw(
@@ -78,6 +85,7 @@ describe('UnbondTransactionBuilder', () => {
                     stakingAddress: SAMPLE_STAKING_ADDRESS,
                     nonce: SAMPLE_NONCE,
                     amount: 1000,
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 } as any);
             }).to.throw(
@@ -92,6 +100,7 @@ describe('UnbondTransactionBuilder', () => {
                     stakingAddress: SAMPLE_STAKING_ADDRESS,
                     nonce: SAMPLE_NONCE,
                     amount: new BigNumber('-12'),
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 });
             }).to.throw(
@@ -106,6 +115,7 @@ describe('UnbondTransactionBuilder', () => {
                     stakingAddress: SAMPLE_STAKING_ADDRESS,
                     nonce: SAMPLE_NONCE,
                     amount: MAX_COIN_BN.plus(1),
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                     network: Mainnet,
                 });
             }).to.throw(
@@ -118,6 +128,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber(1000),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
             });
 
             expect(builder.getNetwork()).to.deep.eq(Mainnet);
@@ -127,14 +138,17 @@ describe('UnbondTransactionBuilder', () => {
             const stakingAddress = SAMPLE_STAKING_ADDRESS;
             const nonce = SAMPLE_NONCE;
             const amount = new BigNumber('1000');
-            const network = Devnet({
-                feeConfig: SAMPLE_FEE_CONFIG,
-                chainHexId: 'AB',
-            });
+            const network = registerNetwork(
+                Devnet({
+                    feeConfig: SAMPLE_FEE_CONFIG,
+                    chainHexId: 'AB',
+                }),
+            );
             const builder = new UnbondTransactionBuilder({
                 stakingAddress,
                 nonce,
                 amount,
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network,
             });
 
@@ -145,12 +159,146 @@ describe('UnbondTransactionBuilder', () => {
         });
     });
 
+    describe('with staked state', () => {
+        const SAMPLE_STAKED_STATE: State = {
+            nonce: 1,
+            bonded: new BigNumber('5000'),
+            unbonded: new BigNumber('0'),
+            unbondedFrom: 0,
+            address: SAMPLE_STAKING_ADDRESS,
+        };
+
+        it('should throw Error when amount exceeds bonded amount', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new UnbondTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    amount: new BigNumber('5001'),
+                    stakedState: SAMPLE_STAKED_STATE,
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Invalid options at `amount`: Unbond amount 5001 exceeds bonded amount 5000',
+            );
+        });
+
+        it('should throw Error when staked state is jailed', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new UnbondTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    amount: new BigNumber('1000'),
+                    stakedState: {
+                        ...SAMPLE_STAKED_STATE,
+                        jailedUntil: 1587071014,
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                    },
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Invalid options at `stakedState.jailedUntil`: Staked state is jailed until 1587071014',
+            );
+        });
+
+        it('should throw Error when staked state nonce does not match', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new UnbondTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: new BigNumber(2),
+                    amount: new BigNumber('1000'),
+                    stakedState: SAMPLE_STAKED_STATE,
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Invalid options at `stakedState.nonce`: Staked state nonce 1 does not match transaction nonce 2',
+            );
+        });
+
+        it('should throw Error when unbonding all without staked state', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new UnbondTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    unbondAll: true,
+                    unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                    network: Mainnet,
+                });
+            }).to.throw(
+                'Expected property `stakedState` to be of type `object` but received type `undefined` in object `options`',
+            );
+        });
+
+        it('should unbond all bonded amount', () => {
+            const builder = new UnbondTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                nonce: SAMPLE_NONCE,
+                unbondAll: true,
+                stakedState: SAMPLE_STAKED_STATE,
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                network: Mainnet,
+            });
+
+            expect(builder.isUnbondAll()).to.eq(true);
+            expect(builder.getAmount().toString(10)).to.eq('5000');
+        });
+
+        it('should report unbonded from time from the unbonding period', () => {
+            const builder = new UnbondTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                nonce: SAMPLE_NONCE,
+                amount: new BigNumber('1000'),
+                stakedState: SAMPLE_STAKED_STATE,
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                blockTime: Timespec.fromSeconds(1587071014),
+                network: Mainnet,
+            });
+
+            expect(builder.getUnbondedFrom().toNumber()).to.eq(1587157414);
+        });
+
+        it('should report unbonded from time from now when block time is missing', () => {
+            const now = Math.floor(Date.now() / 1000);
+            const builder = new UnbondTransactionBuilder({
+                stakingAddress: SAMPLE_STAKING_ADDRESS,
+                nonce: SAMPLE_NONCE,
+                amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                network: Mainnet,
+            });
+
+            expect(
+                builder.getUnbondedFrom().toNumber(),
+            ).to.be.greaterThanOrEqual(now + SAMPLE_UNBONDING_PERIOD);
+        });
+
+        it('should throw Error when unbonding period is missing', () => {
+            expect(() => {
+                // eslint-disable-next-line no-new
+                new UnbondTransactionBuilder({
+                    stakingAddress: SAMPLE_STAKING_ADDRESS,
+                    nonce: SAMPLE_NONCE,
+                    amount: new BigNumber('1000'),
+                    network: Mainnet,
+                } as any);
+            }).to.throw(
+                'Expected property `unbondingPeriod` to be of type `number` but received type `undefined` in object `options`',
+            );
+        });
+    });
+
     describe('sign', () => {
         it('should throw Error when KeyPair is missing', () => {
             const builder = new UnbondTransactionBuilder({
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -166,6 +314,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -179,6 +328,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -194,6 +344,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -205,6 +356,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -220,10 +372,13 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
-                network: Devnet({
-                    feeConfig: SAMPLE_FEE_CONFIG,
-                    chainHexId: 'AB',
-                }),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
+                network: registerNetwork(
+                    Devnet({
+                        feeConfig: SAMPLE_FEE_CONFIG,
+                        chainHexId: 'AB',
+                    }),
+                ),
             });
 
             expect(builder.txId()).to.eq(
@@ -238,6 +393,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -253,6 +409,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -266,6 +423,7 @@ describe('UnbondTransactionBuilder', () => {
                 stakingAddress: SAMPLE_STAKING_ADDRESS,
                 nonce: SAMPLE_NONCE,
                 amount: new BigNumber('1000'),
+                unbondingPeriod: SAMPLE_UNBONDING_PERIOD,
                 network: Mainnet,
             });
 
@@ -2,13 +2,22 @@ import BigNumber from 'bignumber.js';
 import ow from 'ow';
 
 import {
+    State,
     UnbondTransactionBuilderOptions,
     owUnbondTransactionBuilderOptions,
+    owUnbondAllTransactionBuilderOptions,
 } from './types';
 import { TransactionBuilder } from '../transaction_builder';
 import { KeyPair } from '../../key_pair';
 import { owKeyPair } from '../../key_pair/types';
 import { ENCODING_PREFIX } from '../encoding';
+import { Timespec } from '../../types';
+import {
+    LocalValidation,
+    owValidationContext,
+    validateLocally,
+    ValidationContext,
+} from '../local_validation';
 
 const native = require('../../../../native');
 
@@ -22,6 +31,16 @@ export class UnbondTransactionBuilder extends TransactionBuilder {
 
     private amount!: BigNumber;
 
+    private unbondAll: boolean;
+
+    private stakedState?: State;
+
+    private unbondingPeriod: number;
+
+    private blockTime?: Timespec;
+
+    private unbondedFrom!: Timespec;
+
     private unsignedRawTx!: Buffer;
 
     private innertTxId!: string;
@@ -33,35 +52,71 @@ export class UnbondTransactionBuilder extends TransactionBuilder {
      * @param {UnbondTransactionBuilderOptions} [options] Builder options
      * @param {string} options.stakingAddress Staking address to unbond from
      * @param {BigNumber} options.nonce Staking address nonce
-     * @param {string} options.amount Amount in basic unit to unbond
+     * @param {string} [options.amount] Amount in basic unit to unbond.
+     * Required unless unbonding all bonded
+     * @param {boolean} [options.unbondAll] Unbond all bonded amount of the
+     * staked state
+     * @param {State} [options.stakedState] Staked state of the staking
+     * address. When provided the amount is verified to be within the bonded
+     * amount and the staked state is verified to be not jailed
+     * @param {number} options.unbondingPeriod Unbonding period of the
+     * network in seconds
+     * @param {Timespec} [options.blockTime] Expected block time the
+     * transaction is included, default to now
      * @param {Network} [options.network] Network the transaction belongs to
      * @memberof UnbondTransactionBuilder
      */
     constructor(options: UnbondTransactionBuilderOptions) {
         super();
 
-        ow(options, 'options', owUnbondTransactionBuilderOptions);
+        if (options && options.unbondAll) {
+            ow(options, 'options', owUnbondAllTransactionBuilderOptions);
+        } else {
+            ow(options, 'options', owUnbondTransactionBuilderOptions);
+        }
 
         this.stakingAddress = options.stakingAddress;
         this.nonce = options.nonce;
-        this.amount = options.amount;
+        this.unbondAll = !!options.unbondAll;
+        this.stakedState = options.stakedState;
+        this.unbondingPeriod = options.unbondingPeriod;
+        this.blockTime = options.blockTime;
         this.initNetwork(options.network);
-        this.prepareRawTx();
+        this.prepareRawTx(options.amount);
     }
 
-    private prepareRawTx() {
+    private prepareRawTx(amount?: BigNumber) {
+        const blockTime =
+            this.blockTime ||
+            Timespec.fromSeconds(Math.floor(Date.now() / 1000));
+
         const {
             unsignedRawTx,
             txId,
+            amount: unbondAmount,
+            unbondedFrom,
         } = native.stakingTransaction.buildRawUnbondTransaction({
             stakingAddress: this.stakingAddress,
             nonce: this.nonce.toString(10),
-            amount: this.amount.toString(10),
+            amount: amount ? amount.toString(10) : undefined,
+            unbondAll: this.unbondAll,
+            stakedState: this.stakedState
+                ? {
+                      nonce: this.stakedState.nonce.toString(10),
+                      bonded: this.stakedState.bonded.toString(10),
+                      address: this.stakedState.address,
+                      jailedUntil: this.stakedState.jailedUntil,
+                  }
+                : undefined,
+            unbondingPeriod: this.unbondingPeriod,
+            blockTime: blockTime.toNumber(),
             chainHexId: this.getNetwork().chainHexId,
         });
 
         this.unsignedRawTx = unsignedRawTx;
         this.innertTxId = txId;
+        this.amount = new BigNumber(unbondAmount);
+        this.unbondedFrom = Timespec.fromSeconds(unbondedFrom);
     }
 
     /**
@@ -91,6 +146,25 @@ export class UnbondTransactionBuilder extends TransactionBuilder {
         return this.amount;
     }
 
+    /**
+     * Returns whether the builder unbonds all bonded amount
+     * @returns {boolean}
+     * @memberof UnbondTransactionBuilder
+     */
+    public isUnbondAll(): boolean {
+        return this.unbondAll;
+    }
+
+    /**
+     * Returns the time the unbonded amount can be withdrawn, calculated from
+     * the unbonding period and the expected block time
+     * @returns {Timespec} unbonded from time
+     * @memberof UnbondTransactionBuilder
+     */
+    public getUnbondedFrom(): Readonly<Timespec> {
+        return this.unbondedFrom;
+    }
+
     /**
      * Sign the transaction with the KeyPair
      * @param {KeyPair} keyPair KeyPair to sign the transaction
@@ -153,4 +227,32 @@ export class UnbondTransactionBuilder extends TransactionBuilder {
             this.keyPair!.toObject(),
         );
     }
+
+    /**
+     * Run the unbond validation rules of the chain against the context,
+     * returning the reason the node would reject the transaction with
+     *
+     * @param {ValidationContext} context chain state to validate against,
+     * staked state and unbonding period default to the ones of the builder
+     * @returns {LocalValidation}
+     * @throws {Error} error when the transaction is not completed or the
+     * staked state is unknown
+     * @memberof UnbondTransactionBuilder
+     */
+    public validateLocally(context: ValidationContext): LocalValidation {
+        ow(context, 'context', owValidationContext);
+
+        return validateLocally(
+            { kind: 'unbond', txAux: this.toHex() },
+            this.getNetwork(),
+            {
+                ...context,
+                stakedState: context.stakedState || this.stakedState,
+                unbondingPeriod:
+                    context.unbondingPeriod !== undefined
+                        ? context.unbondingPeriod
+                        : this.unbondingPeriod,
+            },
+        );
+    }
 }
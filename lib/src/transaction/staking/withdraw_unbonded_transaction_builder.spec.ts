@@ -315,6 +315,36 @@ describe('WithdrawUnbondedTransactionBuilder', () => {
         });
     });
 
+    describe('checkPolicy', () => {
+        it('should return no violation when outputs satisfy the policy', () => {
+            const builder = new WithdrawUnbondedTransactionBuilder({
+                nonce: SAMPLE_NONCE,
+                network: Mainnet,
+            });
+            builder.addOutput(SAMPLE_OUTPUT);
+
+            expect(
+                builder.checkPolicy({
+                    maxTimeLock: 0,
+                    blockTime: Timespec.fromSeconds(SAMPLE_UNBONDED_FROM),
+                }),
+            ).to.deep.eq([]);
+        });
+
+        it('should return violation when unbonded amount cannot cover outputs and fee', () => {
+            const builder = new WithdrawUnbondedTransactionBuilder({
+                nonce: SAMPLE_NONCE,
+                network: Mainnet,
+            });
+            builder.addOutput(SAMPLE_OUTPUT);
+
+            const violations = builder.checkPolicy({}, new BigNumber('1000'));
+            expect(violations.map((violation) => violation.code)).to.deep.eq([
+                'insufficientInput',
+            ]);
+        });
+    });
+
     describe('sign', () => {
         it('should throw Error when KeyPair is missing', () => {
             const builder = new WithdrawUnbondedTransactionBuilder({
@@ -12,6 +12,7 @@ import {
 } from './types';
 import {
     Output,
+    owCoin,
     owViewKey,
     owTendermintAddress,
     parseOutputForNative,
@@ -21,6 +22,19 @@ import { owKeyPair } from '../../key_pair/types';
 import { FeeConfig } from '../../fee';
 import { parseFeeConfigForNative } from '../../fee/types';
 import { getRustFeaturesFromEnv } from '../../native';
+import { ENCODING_PREFIX } from '../encoding';
+import {
+    LocalValidation,
+    owValidationContext,
+    validateLocally,
+    ValidationContext,
+} from '../local_validation';
+import {
+    checkOutputPolicy,
+    OutputPolicy,
+    owOutputPolicy,
+    PolicyViolation,
+} from '../output_policy';
 
 const native = require('../../../../native');
 
@@ -122,6 +136,31 @@ export class WithdrawUnbondedTransactionBuilder extends TransactionBuilder {
         );
     }
 
+    /**
+     * Check the outputs against the policy before signing. When the unbonded
+     * amount is provided, it should cover the outputs and the estimated fee.
+     *
+     * @param {OutputPolicy} [policy={}] output policy
+     * @param {BigNumber} [unbondedAmount] unbonded amount of the staked state
+     * @returns {PolicyViolation[]} rules the transaction does not satisfy
+     * @memberof WithdrawUnbondedTransactionBuilder
+     */
+    public checkPolicy(
+        policy: OutputPolicy = {},
+        unbondedAmount?: BigNumber,
+    ): PolicyViolation[] {
+        ow(policy, 'policy', owOutputPolicy);
+        ow(unbondedAmount, 'unbondedAmount', ow.optional.any(owCoin));
+
+        return checkOutputPolicy(policy, {
+            chainHexId: this.getNetwork().chainHexId,
+            inputs: [],
+            inputAmount: unbondedAmount,
+            outputs: this.outputs,
+            fee: unbondedAmount ? new BigNumber(this.estimateFee()) : undefined,
+        });
+    }
+
     private isTransferAddressInNetwork(address: string): boolean {
         return native.address.isTransferAddressValid(
             address,
@@ -226,7 +265,10 @@ export class WithdrawUnbondedTransactionBuilder extends TransactionBuilder {
             this.prepareRawTx();
         }
 
-        return Buffer.concat([Buffer.from('0002', 'hex'), this.unsignedRawTx!]);
+        return Buffer.concat([
+            ENCODING_PREFIX.WITHDRAW_UNBONDED,
+            this.unsignedRawTx!,
+        ]);
     }
 
     private isRawTxPrepared(): boolean {
@@ -399,4 +441,33 @@ export class WithdrawUnbondedTransactionBuilder extends TransactionBuilder {
     public clone(): WithdrawUnbondedTransactionBuilder {
         return cloneDeep(this);
     }
+
+    /**
+     * Run the withdraw unbonded validation rules of the chain against the
+     * context, returning the reason the node would reject the transaction
+     * with
+     *
+     * @param {ValidationContext} context chain state to validate against,
+     * which should have the staked state
+     * @returns {LocalValidation}
+     * @throws {Error} error when the transaction is not completed
+     * @memberof WithdrawUnbondedTransactionBuilder
+     */
+    public validateLocally(context: ValidationContext): LocalValidation {
+        ow(context, 'context', owValidationContext);
+
+        if (!this.isCompleted()) {
+            throw new Error('Transaction builder is not completed');
+        }
+
+        return validateLocally(
+            {
+                kind: 'withdrawUnbonded',
+                unsignedRawTx: this.unsignedRawTx!,
+                witness: this.witness!,
+            },
+            this.getNetwork(),
+            context,
+        );
+    }
 }
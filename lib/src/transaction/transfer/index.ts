@@ -1,2 +1,6 @@
 export { TransferTransactionBuilder } from './transfer_transaction_builder';
-export { TransferTransactionBuilderOptions } from './types';
+export {
+    TransferTransactionBuilderOptions,
+    InputInspection,
+    IncompleteInspection,
+} from './types';
@@ -7,7 +7,7 @@ import { KeyPair } from '../../key_pair';
 import { transfer, SINGLE_SIGN_ADDRESS } from '../../address';
 import { MAX_COIN_BN } from '../../init';
 import { FeeAlgorithm, ZERO_LINEAR_FEE, FeeConfig } from '../../fee';
-import { Mainnet, Devnet, Testnet } from '../../network';
+import { Mainnet, Devnet, Testnet, registerNetwork } from '../../network';
 import { Timespec } from '../../types/timespec';
 
 const native = require('../../../../native/index.node');
@@ -369,10 +369,12 @@ describe('TransferTransactionBuilder', () => {
 
         it('should throw Error when value is invalid', () => {
             const builder = new TransferTransactionBuilder({
-                network: Devnet({
-                    feeConfig: SAMPLE_FEE_CONFIG,
-                    chainHexId: 'AB',
-                }),
+                network: registerNetwork(
+                    Devnet({
+                        feeConfig: SAMPLE_FEE_CONFIG,
+                        chainHexId: 'AB',
+                    }),
+                ),
             });
 
             expect(() => {
@@ -388,10 +390,12 @@ describe('TransferTransactionBuilder', () => {
 
         it('should throw Error when value is greater than maximum coin', () => {
             const builder = new TransferTransactionBuilder({
-                network: Devnet({
-                    feeConfig: SAMPLE_FEE_CONFIG,
-                    chainHexId: 'AB',
-                }),
+                network: registerNetwork(
+                    Devnet({
+                        feeConfig: SAMPLE_FEE_CONFIG,
+                        chainHexId: 'AB',
+                    }),
+                ),
             });
 
             expect(() => {
@@ -407,10 +411,12 @@ describe('TransferTransactionBuilder', () => {
 
         it('should throw Error when valid from is invalid', () => {
             const builder = new TransferTransactionBuilder({
-                network: Devnet({
-                    feeConfig: SAMPLE_FEE_CONFIG,
-                    chainHexId: 'AB',
-                }),
+                network: registerNetwork(
+                    Devnet({
+                        feeConfig: SAMPLE_FEE_CONFIG,
+                        chainHexId: 'AB',
+                    }),
+                ),
             });
 
             expect(() => {
@@ -648,6 +654,147 @@ describe('TransferTransactionBuilder', () => {
         });
     });
 
+    describe('checkPolicy', () => {
+        const keyPair = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+        const transferAddress = transfer({
+            keyPair,
+            network: Mainnet,
+        });
+        const OTHER_ADDRESS =
+            'cro1p8c38xgv26c0wlzf0m8gugnn3fpaucrf5p98zhfaqvj4xr8mf97sp54ap3';
+        const newBuilder = (inputValue: string) =>
+            new TransferTransactionBuilder({
+                network: Mainnet,
+            }).addInput({
+                prevTxId:
+                    '0000000000000000000000000000000000000000000000000000000000000000',
+                prevIndex: 0,
+                prevOutput: {
+                    address: transferAddress,
+                    value: new BigNumber(inputValue),
+                },
+                addressParams: SINGLE_SIGN_ADDRESS,
+            });
+
+        it('should throw Error when policy is invalid', () => {
+            const builder = newBuilder('2000').addOutput({
+                address: OTHER_ADDRESS,
+                value: new BigNumber('1000'),
+            });
+
+            expect(() =>
+                builder.checkPolicy({ maxOutputs: 0 }),
+            ).to.throw(
+                'Expected property number `maxOutputs` to be greater than 0',
+            );
+        });
+
+        it('should return no violation when outputs satisfy the policy', () => {
+            const builder = newBuilder('2000').addOutput({
+                address: OTHER_ADDRESS,
+                value: new BigNumber('1000'),
+            });
+
+            expect(builder.checkPolicy()).to.deep.eq([]);
+        });
+
+        it('should return violations of the outputs', () => {
+            const builder = newBuilder('2000')
+                .addOutput({
+                    address: OTHER_ADDRESS,
+                    value: new BigNumber('0'),
+                })
+                .addOutput({
+                    address: transferAddress,
+                    value: new BigNumber('500'),
+                })
+                .addOutput({
+                    address: transferAddress,
+                    value: new BigNumber('500'),
+                });
+
+            expect(
+                builder
+                    .checkPolicy({ maxOutputs: 2 })
+                    .map((violation) => [violation.code, violation.path]),
+            ).to.deep.eq([
+                ['tooManyOutputs', 'outputs'],
+                ['belowMinOutputValue', 'outputs[0].value'],
+                ['selfSend', 'outputs[1].address'],
+                ['selfSend', 'outputs[2].address'],
+                ['duplicateOutput', 'outputs[2]'],
+            ]);
+            expect(
+                builder.checkPolicy({
+                    minOutputValue: new BigNumber(0),
+                    allowSelfSend: true,
+                    allowDuplicateOutputs: true,
+                }),
+            ).to.deep.eq([]);
+        });
+
+        it('should not return self-send violation of the change output', () => {
+            const builder = newBuilder('2000')
+                .addOutput({
+                    address: OTHER_ADDRESS,
+                    value: new BigNumber('1000'),
+                })
+                .addOutput({
+                    address: transferAddress,
+                    value: new BigNumber('500'),
+                });
+
+            expect(
+                builder
+                    .checkPolicy()
+                    .map((violation) => [violation.code, violation.path]),
+            ).to.deep.eq([['selfSend', 'outputs[1].address']]);
+            expect(builder.checkPolicy({ changeOutput: 1 })).to.deep.eq([]);
+        });
+
+        it('should return violation when validFrom is in milliseconds or too far', () => {
+            const blockTime = Timespec.fromSeconds(1587071014);
+            const builder = newBuilder('3000')
+                .addOutput({
+                    address: OTHER_ADDRESS,
+                    value: new BigNumber('1000'),
+                    validFrom: Timespec.fromSeconds(1587071014000),
+                })
+                .addOutput({
+                    address: OTHER_ADDRESS,
+                    value: new BigNumber('1001'),
+                    validFrom: Timespec.fromSeconds(1587071014 + 86401),
+                });
+
+            expect(
+                builder
+                    .checkPolicy({ maxTimeLock: 86400, blockTime })
+                    .map((violation) => [violation.code, violation.path]),
+            ).to.deep.eq([
+                ['validFromInMilliseconds', 'outputs[0].validFrom'],
+                ['timeLockTooFar', 'outputs[1].validFrom'],
+            ]);
+        });
+
+        it('should return violation when inputs cannot cover outputs and fee', () => {
+            const builder = newBuilder('1100').addOutput({
+                address: OTHER_ADDRESS,
+                value: new BigNumber('1000'),
+            });
+
+            const violations = builder.checkPolicy();
+            expect(violations.length).to.eq(1);
+            expect(violations[0].code).to.eq('insufficientInput');
+            expect(violations[0].message).to.eq(
+                `Input amount 1100 is less than the output amount and fee ${new BigNumber(
+                    1000,
+                )
+                    .plus(builder.estimateFee())
+                    .toString(10)}`,
+            );
+        });
+    });
+
     describe('signInput', () => {
         it('should throw Error when the input index is negative', () => {
             const builder = new TransferTransactionBuilder();
@@ -1101,6 +1248,172 @@ describe('TransferTransactionBuilder', () => {
         });
     });
 
+    describe('inspectIncomplete', () => {
+        it('should throw Error when the builder has no input', () => {
+            const builder = new TransferTransactionBuilder();
+
+            expect(() => {
+                builder.inspectIncomplete();
+            }).to.throw('Builder has no input');
+        });
+
+        it('should report witness status and signers of each input', () => {
+            const builder = new TransferTransactionBuilder();
+
+            const keyPair = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+            const address = transfer({
+                keyPair,
+                network: Mainnet,
+            });
+            builder
+                .addInput({
+                    prevTxId:
+                        '0000000000000000000000000000000000000000000000000000000000000000',
+                    prevIndex: 0,
+                    prevOutput: {
+                        address,
+                        value: new BigNumber('1000'),
+                    },
+                    addressParams: SINGLE_SIGN_ADDRESS,
+                })
+                .addInput({
+                    prevTxId:
+                        '0000000000000000000000000000000000000000000000000000000000000000',
+                    prevIndex: 1,
+                    prevOutput: {
+                        address,
+                        value: new BigNumber('2000'),
+                        validFrom: Timespec.fromSeconds(1586094976),
+                    },
+                    addressParams: SINGLE_SIGN_ADDRESS,
+                })
+                .addOutput({
+                    address:
+                        'cro1p8c38xgv26c0wlzf0m8gugnn3fpaucrf5p98zhfaqvj4xr8mf97sp54ap3',
+                    value: new BigNumber('2500'),
+                });
+
+            builder.signInput(1, keyPair);
+
+            const inspection = builder.inspectIncomplete();
+
+            expect(inspection.txId).to.eq(builder.txId());
+            expect(inspection.inputs.length).to.eq(2);
+
+            expect(inspection.inputs[0].prevIndex).to.eq(0);
+            expect(inspection.inputs[0].prevOutput.address).to.eq(address);
+            expect(inspection.inputs[0].prevOutput.value.toString(10)).to.eq(
+                '1000',
+            );
+            expect(inspection.inputs[0].addressLeaves).to.eq(1);
+            expect(inspection.inputs[0].addressParams).to.deep.eq(
+                SINGLE_SIGN_ADDRESS,
+            );
+            expect(inspection.inputs[0].hasWitness).to.eq(false);
+            expect(inspection.inputs[0].isWitnessValid).to.eq(false);
+
+            expect(inspection.inputs[1].prevIndex).to.eq(1);
+            expect(
+                inspection.inputs[1].prevOutput.validFrom!.toNumber(),
+            ).to.eq(1586094976);
+            expect(inspection.inputs[1].hasWitness).to.eq(true);
+            expect(inspection.inputs[1].isWitnessValid).to.eq(true);
+        });
+    });
+
+    describe('validateLocally', () => {
+        const keyPair = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+        const buildSignedBuilder = (): TransferTransactionBuilder => {
+            const address = transfer({
+                keyPair,
+                network: Mainnet,
+            });
+            const builder = new TransferTransactionBuilder();
+            builder
+                .addInput({
+                    prevTxId:
+                        '0000000000000000000000000000000000000000000000000000000000000000',
+                    prevIndex: 0,
+                    prevOutput: {
+                        address,
+                        value: new BigNumber('3000'),
+                        validFrom: Timespec.fromSeconds(1586094976),
+                    },
+                    addressParams: SINGLE_SIGN_ADDRESS,
+                })
+                .addOutput({
+                    address:
+                        'cro1p8c38xgv26c0wlzf0m8gugnn3fpaucrf5p98zhfaqvj4xr8mf97sp54ap3',
+                    value: new BigNumber('2500'),
+                });
+
+            return builder.signInput(0, keyPair);
+        };
+
+        it('should throw Error when the transaction is not completed', () => {
+            const builder = buildSignedBuilder();
+            builder.addOutput({
+                address:
+                    'cro1p8c38xgv26c0wlzf0m8gugnn3fpaucrf5p98zhfaqvj4xr8mf97sp54ap3',
+                value: new BigNumber('100'),
+            });
+
+            expect(() => {
+                builder.validateLocally({
+                    blockTime: Timespec.fromSeconds(1586094976),
+                });
+            }).to.throw('Transaction is not completed');
+        });
+
+        it('should throw Error when spent outputs do not match the inputs', () => {
+            const builder = buildSignedBuilder();
+
+            expect(() => {
+                builder.validateLocally({
+                    blockTime: Timespec.fromSeconds(1586094976),
+                    spentOutputs: [],
+                });
+            }).to.throw(
+                'Context has 0 spent outputs but transaction has 1 inputs',
+            );
+        });
+
+        it('should return valid when the chain accepts the transaction', () => {
+            const builder = buildSignedBuilder();
+
+            expect(
+                builder.validateLocally({
+                    blockTime: Timespec.fromSeconds(1586094976),
+                    minFee: new BigNumber('500'),
+                }),
+            ).to.deep.eq({ valid: true });
+        });
+
+        it('should return the rejection reason when fee is below the minimum fee', () => {
+            const builder = buildSignedBuilder();
+
+            const validation = builder.validateLocally({
+                blockTime: Timespec.fromSeconds(1586094976),
+                minFee: new BigNumber('501'),
+            });
+
+            expect(validation.valid).to.eq(false);
+            expect(validation.reason).to.be.a('string');
+        });
+
+        it('should return the rejection reason when a spent output is time-locked', () => {
+            const builder = buildSignedBuilder();
+
+            const validation = builder.validateLocally({
+                blockTime: Timespec.fromSeconds(1586094975),
+                minFee: new BigNumber('500'),
+            });
+
+            expect(validation.valid).to.eq(false);
+            expect(validation.reason).to.be.a('string');
+        });
+    });
+
     describe('txId', () => {
         it('should throw Error when the build has no input', () => {
             const builder = new TransferTransactionBuilder();
@@ -1184,10 +1497,12 @@ describe('TransferTransactionBuilder', () => {
     });
 
     describe('toHex', () => {
-        const ZERO_FEE_DEVNET = Devnet({
-            feeConfig: ZERO_LINEAR_FEE,
-            chainHexId: 'AB',
-        });
+        const ZERO_FEE_DEVNET = registerNetwork(
+            Devnet({
+                feeConfig: ZERO_LINEAR_FEE,
+                chainHexId: 'AB',
+            }),
+        );
 
         it('should throw Error when the tendermint address is not ws', () => {
             const builder = new TransferTransactionBuilder();
@@ -1,7 +1,9 @@
 import ow, { NumberPredicate } from 'ow';
 import cloneDeep from 'lodash/cloneDeep';
+import BigNumber from 'bignumber.js';
 
 import {
+    IncompleteInspection,
     owTransferTransactionBuilderOptions,
     TransferTransactionBuilderOptions,
 } from './types';
@@ -14,14 +16,27 @@ import {
     owViewKey,
     parseInputForNative,
     parseOutputForNative,
+    parseOutputFromNative,
 } from '../../types';
 import { KeyPair } from '../../key_pair/key_pair';
 import { owKeyPair } from '../../key_pair/types';
 import { getRustFeaturesFromEnv } from '../../native';
-import { FeeConfig, FeeAlgorithm } from '../../fee';
+import { FeeConfig } from '../../fee';
 import { parseFeeConfigForNative } from '../../fee/types';
 import { TransactionBuilder } from '../transaction_builder';
 import { transfer } from '../../address/transfer';
+import {
+    checkOutputPolicy,
+    OutputPolicy,
+    owOutputPolicy,
+    PolicyViolation,
+} from '../output_policy';
+import {
+    LocalValidation,
+    owValidationContext,
+    validateLocally,
+    ValidationContext,
+} from '../local_validation';
 
 const native = require('../../../../native');
 
@@ -146,20 +161,15 @@ export class TransferTransactionBuilder extends TransactionBuilder {
      * @returns {string} Estimated fee in basic unit
      */
     public estimateFee(): string {
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            this.verifyHasInput();
-            this.verifyHasOutput();
+        this.verifyHasInput();
+        this.verifyHasOutput();
 
-            const incompleteHex = this.buildIncompleteHex();
+        const incompleteHex = this.buildIncompleteHex();
 
-            return native.transferTransaction.estimateFeeLinearFee({
-                incompleteHex,
-                feeConfig: parseFeeConfigForNative(this.feeConfig),
-            });
-        }
-        throw new Error(
-            `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
-        );
+        return native.transferTransaction.estimateFee({
+            incompleteHex,
+            feeConfig: parseFeeConfigForNative(this.feeConfig),
+        });
     }
 
     /**
@@ -192,6 +202,30 @@ export class TransferTransactionBuilder extends TransactionBuilder {
         return this.viewKeys.length;
     }
 
+    /**
+     * Check the outputs against the policy before signing. The total input
+     * amount should cover the outputs and the estimated fee. Outputs to an
+     * input address are self-sends unless allowed, mark the change output
+     * with `changeOutput` to exempt it.
+     *
+     * @param {OutputPolicy} [policy={}] output policy
+     * @returns {PolicyViolation[]} rules the transaction does not satisfy
+     * @memberof TransferTransactionBuilder
+     */
+    public checkPolicy(policy: OutputPolicy = {}): PolicyViolation[] {
+        ow(policy, 'policy', owOutputPolicy);
+
+        this.verifyHasInput();
+        this.verifyHasOutput();
+
+        return checkOutputPolicy(policy, {
+            chainHexId: this.getNetwork().chainHexId,
+            inputs: this.inputs.map((input) => input.prevOutput),
+            outputs: this.outputs,
+            fee: new BigNumber(this.estimateFee()),
+        });
+    }
+
     /**
      * Returns transaction Id
      *
@@ -202,17 +236,12 @@ export class TransferTransactionBuilder extends TransactionBuilder {
     public txId(): string {
         this.verifyHasInput();
 
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            const incompleteHex = this.buildIncompleteHex();
+        const incompleteHex = this.buildIncompleteHex();
 
-            return native.transferTransaction.txIdLinearFee({
-                incompleteHex,
-                feeConfig: parseFeeConfigForNative(this.feeConfig),
-            });
-        }
-        throw new Error(
-            `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
-        );
+        return native.transferTransaction.txId({
+            incompleteHex,
+            feeConfig: parseFeeConfigForNative(this.feeConfig),
+        });
     }
 
     /**
@@ -240,23 +269,14 @@ export class TransferTransactionBuilder extends TransactionBuilder {
 
         const incompleteSigningHex = this.prepareIncompleteSigningHex();
 
-        let updatedIncompleteSigningHex: Buffer;
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            updatedIncompleteSigningHex = native.transferTransaction.signInputLinearFee(
-                {
-                    incompleteHex: incompleteSigningHex,
-                    feeConfig: parseFeeConfigForNative(this.feeConfig),
-                },
-                index,
-                keyPair.toObject(),
-            );
-        } else {
-            throw new Error(
-                `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
-            );
-        }
-
-        this.incompleteHex = updatedIncompleteSigningHex;
+        this.incompleteHex = native.transferTransaction.signInput(
+            {
+                incompleteHex: incompleteSigningHex,
+                feeConfig: parseFeeConfigForNative(this.feeConfig),
+            },
+            index,
+            keyPair.toObject(),
+        );
     }
 
     /**
@@ -272,24 +292,15 @@ export class TransferTransactionBuilder extends TransactionBuilder {
 
         const incompleteSigningHex = this.prepareIncompleteSigningHex();
 
-        let updatedIncompleteSigningHex: Buffer;
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            // FIXME: properly handle witness verification
-            updatedIncompleteSigningHex = native.transferTransaction.addInputWitnessLinearFee(
-                {
-                    incompleteHex: incompleteSigningHex,
-                    feeConfig: parseFeeConfigForNative(this.feeConfig),
-                },
-                index,
-                witness,
-            );
-        } else {
-            throw new Error(
-                `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
-            );
-        }
-
-        this.incompleteHex = updatedIncompleteSigningHex;
+        // FIXME: properly handle witness verification
+        this.incompleteHex = native.transferTransaction.addInputWitness(
+            {
+                incompleteHex: incompleteSigningHex,
+                feeConfig: parseFeeConfigForNative(this.feeConfig),
+            },
+            index,
+            witness,
+        );
     }
 
     private owIndex(): NumberPredicate {
@@ -306,14 +317,73 @@ export class TransferTransactionBuilder extends TransactionBuilder {
         this.verifyHasInput();
         this.verifyHasOutput();
 
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            return native.transferTransaction.verifyLinearFee({
-                incompleteHex: this.incompleteHex,
-                feeConfig: parseFeeConfigForNative(this.feeConfig),
-            });
+        return native.transferTransaction.verify({
+            incompleteHex: this.incompleteHex,
+            feeConfig: parseFeeConfigForNative(this.feeConfig),
+        });
+    }
+
+    /**
+     * Report the witness status and signer requirements of each input. It
+     * tells which inputs are still missing a witness and whether the
+     * collected witnesses verify against the current transaction id. The
+     * signers of each input are the address params kept by the builder,
+     * verified against the address leaves of the incomplete transaction.
+     *
+     * @returns {IncompleteInspection}
+     * @memberof TransferTransactionBuilder
+     */
+    public inspectIncomplete(): IncompleteInspection {
+        this.verifyHasInput();
+
+        const { txId, inputs } = native.transferTransaction.inspectIncomplete({
+            incompleteHex: this.toIncompleteHex(),
+            feeConfig: parseFeeConfigForNative(this.feeConfig),
+            addressParams: this.inputs.map((input) => input.addressParams),
+        });
+
+        return {
+            txId,
+            inputs: inputs.map((input: any) => ({
+                prevTxId: input.prevTxId,
+                prevIndex: input.prevIndex,
+                prevOutput: parseOutputFromNative(input.prevOutput),
+                addressLeaves: input.addressLeaves,
+                addressParams: input.addressParams,
+                hasWitness: input.hasWitness,
+                isWitnessValid: input.isWitnessValid,
+            })),
+        };
+    }
+
+    /**
+     * Run the transfer validation rules of the chain against the context,
+     * returning the reason the node would reject the transaction with
+     *
+     * @param {ValidationContext} context chain state to validate against,
+     * spent outputs default to the previous outputs of the inputs
+     * @returns {LocalValidation}
+     * @throws {Error} error when the transaction is not completed
+     * @memberof TransferTransactionBuilder
+     */
+    public validateLocally(context: ValidationContext): LocalValidation {
+        ow(context, 'context', owValidationContext);
+
+        this.verifyHasInput();
+        this.verifyHasOutput();
+        if (!this.isCompleted()) {
+            throw new Error('Transaction is not completed');
         }
-        throw new Error(
-            `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
+
+        return validateLocally(
+            { kind: 'transfer', incompleteHex: this.incompleteHex! },
+            this.getNetwork(),
+            {
+                ...context,
+                spentOutputs:
+                    context.spentOutputs ||
+                    this.inputs.map((input) => input.prevOutput),
+            },
         );
     }
 
@@ -343,16 +413,10 @@ export class TransferTransactionBuilder extends TransactionBuilder {
             return false;
         }
 
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            return native.transferTransaction.isCompletedLinearFee({
-                incompleteHex: this.incompleteHex,
-                feeConfig: parseFeeConfigForNative(this.feeConfig),
-            });
-        }
-
-        throw new Error(
-            `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
-        );
+        return native.transferTransaction.isCompleted({
+            incompleteHex: this.incompleteHex,
+            feeConfig: parseFeeConfigForNative(this.feeConfig),
+        });
     }
 
     /**
@@ -377,7 +441,7 @@ export class TransferTransactionBuilder extends TransactionBuilder {
         // FIXME: divide this.verify() suite to more specific error checks
         this.verify();
 
-        return native.transferTransaction.toHexLinearFee(
+        return native.transferTransaction.toHex(
             {
                 incompleteHex: this.incompleteHex,
                 feeConfig: parseFeeConfigForNative(this.feeConfig),
@@ -418,16 +482,7 @@ export class TransferTransactionBuilder extends TransactionBuilder {
     }
 
     private buildIncompleteHex(): Buffer {
-        if (this.feeConfig.algorithm === FeeAlgorithm.LinearFee) {
-            return this.buildIncompleteHexLinearFee();
-        }
-        throw new Error(
-            `Unsupported fee algorithm ${this.feeConfig.algorithm}`,
-        );
-    }
-
-    private buildIncompleteHexLinearFee(): Buffer {
-        return native.transferTransaction.buildIncompleteHexLinearFee({
+        return native.transferTransaction.buildIncompleteHex({
             chainHexId: this.getNetwork().chainHexId,
             inputs: this.inputs.map(parseInputForNative),
             outputs: this.outputs.map(parseOutputForNative),
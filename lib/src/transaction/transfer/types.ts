@@ -2,6 +2,7 @@ import ow from 'ow';
 import { NetworkConfig } from '../../network';
 import { owOptionalNetworkConfig } from '../../network/types';
 import { owOptionalFeeConfig } from '../../fee/types';
+import { InputAddressParams, Output } from '../../types';
 
 export type TransferTransactionBuilderOptions = {
     network?: NetworkConfig;
@@ -16,3 +17,36 @@ export const owTransferTransactionBuilderOptions = ow.optional.object.exactShape
         feeConfig: owOptionalFeeConfig,
     },
 );
+
+/**
+ * Witness status and signer requirements of an input
+ * @typedef {object} InputInspection
+ * @property {string} prevTxId previous transaction id
+ * @property {number} prevIndex previous transaction output index
+ * @property {Output} prevOutput previous transaction output
+ * @property {number} addressLeaves number of signer combinations of the input
+ * address, as stored in the incomplete transaction
+ * @property {InputAddressParams} addressParams signers of the input address
+ * @property {boolean} hasWitness whether the input has a witness
+ * @property {boolean} isWitnessValid whether the witness verifies against the
+ * current transaction id
+ */
+export interface InputInspection {
+    prevTxId: string;
+    prevIndex: number;
+    prevOutput: Output;
+    addressLeaves: number;
+    addressParams: InputAddressParams;
+    hasWitness: boolean;
+    isWitnessValid: boolean;
+}
+
+/**
+ * @typedef {object} IncompleteInspection
+ * @property {string} txId current transaction id
+ * @property {InputInspection[]} inputs inspection of each input
+ */
+export interface IncompleteInspection {
+    txId: string;
+    inputs: InputInspection[];
+}
@@ -0,0 +1,111 @@
+import 'mocha';
+import { expect } from 'chai';
+
+import { verifyTxHex } from './verification';
+import { UnbondTransactionBuilder } from './staking/unbond_transaction_builder';
+import { Mainnet, Testnet } from '../network';
+import { KeyPair } from '../key_pair';
+import { staking } from '../address';
+import { BigNumber } from '../utils';
+
+const SAMPLE_KEY_PAIR = KeyPair.fromPrivateKey(Buffer.alloc(32, 1));
+const SAMPLE_STAKING_ADDRESS = staking({
+    keyPair: SAMPLE_KEY_PAIR,
+});
+
+const buildUnbondTxHex = (keyPair: KeyPair = SAMPLE_KEY_PAIR): Buffer => {
+    return new UnbondTransactionBuilder({
+        stakingAddress: SAMPLE_STAKING_ADDRESS,
+        nonce: new BigNumber(1),
+        amount: new BigNumber(1000),
+        unbondingPeriod: 86400,
+        network: Mainnet,
+    })
+        .sign(keyPair)
+        .toHex();
+};
+
+describe('verifyTxHex', () => {
+    it('should throw Error when fee bounds are provided without network', () => {
+        expect(() => {
+            verifyTxHex(buildUnbondTxHex(), {
+                fee: {
+                    max: new BigNumber(1),
+                },
+            });
+        }).to.throw('Expected `network` to be provided with fee bounds');
+    });
+
+    it('should return valid report when all assertions are satisfied', () => {
+        const verification = verifyTxHex(buildUnbondTxHex(), {
+            network: Mainnet,
+            stakingAddress: SAMPLE_STAKING_ADDRESS,
+            nonce: new BigNumber(1),
+            amount: new BigNumber(1000),
+        });
+
+        expect(verification.txType).to.eq('Unbond');
+        expect(verification.valid).to.eq(true);
+        expect(verification.mismatches).to.deep.eq([]);
+        expect(verification.unverifiable).to.deep.eq([]);
+    });
+
+    it('should report every mismatched field', () => {
+        const verification = verifyTxHex(buildUnbondTxHex(), {
+            network: Testnet,
+            nonce: new BigNumber(2),
+            amount: new BigNumber(1000),
+        });
+
+        expect(verification.valid).to.eq(false);
+        expect(verification.mismatches).to.deep.eq([
+            {
+                field: 'chainHexId',
+                expected: '42',
+                actual: '2A',
+            },
+            {
+                field: 'nonce',
+                expected: '2',
+                actual: '1',
+            },
+        ]);
+    });
+
+    it('should report signature not signed by the staking address', () => {
+        const verification = verifyTxHex(
+            buildUnbondTxHex(KeyPair.fromPrivateKey(Buffer.alloc(32, 2))),
+            {},
+        );
+
+        expect(verification.valid).to.eq(false);
+        expect(verification.mismatches[0].field).to.eq('signature');
+        expect(verification.mismatches[0].expected).to.eq(
+            SAMPLE_STAKING_ADDRESS,
+        );
+    });
+
+    it('should report assertions which cannot be verified for the type', () => {
+        const verification = verifyTxHex(buildUnbondTxHex(), {
+            stakingAddress: SAMPLE_STAKING_ADDRESS,
+            outputsLength: 1,
+        });
+
+        expect(verification.valid).to.eq(false);
+        expect(verification.mismatches).to.deep.eq([]);
+        expect(verification.unverifiable).to.deep.eq(['outputsLength']);
+    });
+
+    it('should report fee above the maximum', () => {
+        const verification = verifyTxHex(buildUnbondTxHex(), {
+            network: Mainnet,
+            fee: {
+                max: new BigNumber(0),
+            },
+        });
+
+        expect(
+            verification.mismatches.map((mismatch) => mismatch.field),
+        ).to.deep.eq(['fee']);
+    });
+});
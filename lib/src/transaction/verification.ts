@@ -0,0 +1,180 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { NetworkConfig } from '../network';
+import { owOptionalNetworkConfig } from '../network/types';
+import {
+    Output,
+    owAccountNonce,
+    owCoin,
+    owOutput,
+    owStakingAddress,
+    owTxId,
+    parseOutputForNative,
+} from '../types';
+import { parseFeeConfigForNative } from '../fee/types';
+import { PrevOutputPointer, owPrevOutputPointer } from './staking/types';
+import { NodePublicKey, owNodePublicKey } from './council_node/types';
+
+const native = require('../../../native');
+
+/**
+ * Expected fields of a transaction. Provided fields which cannot be verified
+ * for the transaction type are reported as unverifiable.
+ * @typedef {object} TxAssertions
+ * @property {NetworkConfig} [network] Network of the transaction, its chain
+ * hex id is verified and its fee config is used by fee bounds
+ * @property {string} [txId] Transaction id
+ * @property {string} [stakingAddress] Staking address of the staking
+ * operation, or the signer of withdraw unbonded transactions
+ * @property {BigNumber} [nonce] Staking account nonce
+ * @property {BigNumber} [amount] Unbond amount
+ * @property {PrevOutputPointer[]} [inputs] Transfer and deposit inputs
+ * @property {Output[]} [outputs] Withdraw unbonded outputs in plain form
+ * @property {number} [outputsLength] Number of outputs
+ * @property {string} [validatorName] Node-join validator name
+ * @property {string} [securityContact] Node-join security contact
+ * @property {NodePublicKey} [consensusPublicKey] Node-join consensus key
+ * @property {FeeBounds} [fee] Bounds of the fee charged for the transaction
+ * size, requires `network`
+ */
+export interface TxAssertions {
+    network?: NetworkConfig;
+    txId?: string;
+    stakingAddress?: string;
+    nonce?: BigNumber;
+    amount?: BigNumber;
+    inputs?: PrevOutputPointer[];
+    outputs?: Output[];
+    outputsLength?: number;
+    validatorName?: string;
+    securityContact?: string;
+    consensusPublicKey?: NodePublicKey;
+    fee?: FeeBounds;
+}
+
+export interface FeeBounds {
+    min?: BigNumber;
+    max?: BigNumber;
+}
+
+/**
+ * A field of the transaction which does not match the expected value
+ * @typedef {object} TxMismatch
+ * @property {string} field Path of the field, e.g. `outputs[1].value`
+ * @property {string} expected
+ * @property {string} actual
+ */
+export interface TxMismatch {
+    field: string;
+    expected: string;
+    actual: string;
+}
+
+/**
+ * @typedef {object} TxVerification
+ * @property {string} txType Type of the transaction, e.g. `Unbond`
+ * @property {string} txId Transaction id
+ * @property {boolean} valid Whether all assertions are verified and
+ * satisfied
+ * @property {TxMismatch[]} mismatches Fields which do not match
+ * @property {string[]} unverifiable Provided fields which cannot be verified
+ * for the transaction type
+ */
+export interface TxVerification {
+    txType: string;
+    txId: string;
+    valid: boolean;
+    mismatches: TxMismatch[];
+    unverifiable: string[];
+}
+
+const owFeeBounds = ow.object.exactShape({
+    min: ow.optional.any(owCoin),
+    max: ow.optional.any(owCoin),
+});
+
+const owTxAssertions = ow.object
+    .exactShape({
+        network: owOptionalNetworkConfig,
+        txId: ow.optional.any(owTxId),
+        stakingAddress: ow.optional.any(owStakingAddress),
+        nonce: ow.optional.any(owAccountNonce),
+        amount: ow.optional.any(owCoin),
+        inputs: ow.optional.array.ofType(owPrevOutputPointer),
+        outputs: ow.optional.array.ofType(owOutput),
+        outputsLength: ow.optional.number.uint16,
+        validatorName: ow.optional.string,
+        securityContact: ow.optional.string,
+        consensusPublicKey: ow.optional.any(owNodePublicKey),
+        fee: ow.optional.any(owFeeBounds),
+    })
+    .validate((value: object) => ({
+        validator:
+            !(value as TxAssertions).fee || !!(value as TxAssertions).network,
+        message: 'Expected `network` to be provided with fee bounds',
+    }));
+
+const parseAssertionsForNative = (assertions: TxAssertions): object => {
+    const { network, nonce, amount, outputs, fee, ...others } = assertions;
+
+    return {
+        ...others,
+        chainHexId: network ? network.chainHexId : undefined,
+        nonce: nonce ? nonce.toString(10) : undefined,
+        amount: amount ? amount.toString(10) : undefined,
+        outputs: outputs ? outputs.map(parseOutputForNative) : undefined,
+        fee:
+            fee && network
+                ? {
+                      feeConfig: parseFeeConfigForNative(network.feeConfig),
+                      min: fee.min ? fee.min.toString(10) : undefined,
+                      max: fee.max ? fee.max.toString(10) : undefined,
+                  }
+                : undefined,
+    };
+};
+
+/**
+ * Verify a broadcast-able transaction hex prepared by another party against
+ * the expected fields. Obfuscated fields of enclave transactions, such as
+ * transfer outputs, cannot be verified and are reported as unverifiable.
+ *
+ * @param {Buffer} txHex Broadcast-able transaction hex
+ * @param {TxAssertions} assertions Expected fields
+ * @returns {TxVerification} report of the fields which do not match
+ */
+export const verifyTxHex = (
+    txHex: Buffer,
+    assertions: TxAssertions,
+): TxVerification => {
+    ow(txHex, 'txHex', ow.buffer);
+    ow(assertions, 'assertions', owTxAssertions);
+
+    return native.txAssertion.verifyTxAux(
+        txHex,
+        parseAssertionsForNative(assertions),
+    );
+};
+
+/**
+ * Verify a signed withdraw unbonded transaction in plain form, as returned
+ * by `WithdrawUnbondedTransactionBuilder.toSignedPlainHex()`, against the
+ * expected fields
+ *
+ * @param {Buffer} signedPlainHex Signed withdraw unbonded transaction
+ * @param {TxAssertions} assertions Expected fields
+ * @returns {TxVerification} report of the fields which do not match
+ */
+export const verifySignedWithdrawUnbondedPlainHex = (
+    signedPlainHex: Buffer,
+    assertions: TxAssertions,
+): TxVerification => {
+    ow(signedPlainHex, 'signedPlainHex', ow.buffer);
+    ow(assertions, 'assertions', owTxAssertions);
+
+    return native.txAssertion.verifySignedWithdrawUnbondedTx(
+        signedPlainHex,
+        parseAssertionsForNative(assertions),
+    );
+};
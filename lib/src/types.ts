@@ -7,7 +7,6 @@ import ow from 'ow';
 import { URL } from 'url';
 
 import { MAX_COIN_BN, MAX_COIN_FORMATTED } from './init';
-import { NetworkEnum } from './network/network';
 import { Timespec, owOptionalTimespec } from './types/timespec';
 
 const native = require('../../native');
@@ -35,18 +34,12 @@ export const owCoin = ow.object.validate((value: object) => ({
 }));
 
 const validateTransferAddress = (value: string): boolean => {
-    let network: NetworkEnum;
-    if (value.startsWith('cro')) {
-        network = NetworkEnum.Mainnet;
-    } else if (value.startsWith('tcro')) {
-        network = NetworkEnum.Testnet;
-    } else if (value.startsWith('dcro')) {
-        network = NetworkEnum.Devnet;
-    } else {
+    try {
+        native.address.getTransferAddressNetwork(value);
+        return true;
+    } catch (err) {
         return false;
     }
-
-    return native.address.isTransferAddressValid(value, network);
 };
 
 /**
@@ -126,6 +119,21 @@ export const parseOutputForNative = (output: Output): NativeOutput => {
     return nativeOutput;
 };
 
+/**
+ * @internal
+ */
+export const parseOutputFromNative = (nativeOutput: NativeOutput): Output => {
+    const output: Output = {
+        address: nativeOutput.address,
+        value: new BigNumber(nativeOutput.value),
+    };
+    if (nativeOutput.validFrom !== undefined) {
+        output.validFrom = Timespec.fromSeconds(nativeOutput.validFrom);
+    }
+
+    return output;
+};
+
 /**
  * @internal
  */
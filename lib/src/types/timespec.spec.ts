@@ -26,6 +26,58 @@ describe('Timespec', () => {
         });
     });
 
+    describe('fromISOString', () => {
+        it('should throw Error when date time is not ISO-8601', () => {
+            expect(() => {
+                Timespec.fromISOString('2020-04-05 13:56:16');
+            }).to.throw(
+                'expected YYYY-MM-DDTHH:MM:SS followed by Z or offset',
+            );
+        });
+
+        it('should throw Error when date time has sub-second precision', () => {
+            expect(() => {
+                Timespec.fromISOString('2020-04-05T13:56:16.5Z');
+            }).to.throw('Timespec does not support sub-second precision');
+        });
+
+        it('should throw Error when date does not exist', () => {
+            expect(() => {
+                Timespec.fromISOString('2019-02-29T00:00:00Z');
+            }).to.throw('date does not exist');
+        });
+
+        it('should return Timespec of UTC date time', () => {
+            const timespec = Timespec.fromISOString('2020-04-05T13:56:16Z');
+
+            expect(timespec.toNumber()).to.eq(1586094976);
+        });
+
+        it('should return Timespec of date time with offset', () => {
+            const timespec = Timespec.fromISOString(
+                '2020-04-05T21:56:16+08:00',
+            );
+
+            expect(timespec.toNumber()).to.eq(1586094976);
+        });
+    });
+
+    describe('toISOString', () => {
+        it('should return ISO-8601 UTC date time of the Timespec', () => {
+            const timespec = Timespec.fromSeconds(1586094976);
+
+            expect(timespec.toISOString()).to.eq('2020-04-05T13:56:16Z');
+        });
+
+        it('should throw Error when the Timespec is beyond year 9999', () => {
+            const timespec = Timespec.fromSeconds(253402300800);
+
+            expect(() => timespec.toISOString()).to.throw(
+                'Unable to format ISO-8601 date time',
+            );
+        });
+    });
+
     describe('toNumber', () => {
         it('should return number representation of the Timespec', () => {
             const timespec = Timespec.fromSeconds(1586094976);
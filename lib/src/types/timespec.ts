@@ -1,5 +1,7 @@
 import ow from 'ow';
 
+const native = require('../../../native');
+
 /**
  * Rust compatible time types
  */
@@ -21,12 +23,31 @@ export class Timespec {
         return new Timespec(seconds);
     }
 
+    /**
+     * Creates an instance of Timespec from ISO-8601 date time of second
+     * precision, e.g. `2020-04-05T13:56:16Z` or `2020-04-05T21:56:16+08:00`
+     * @param {string} dateTime ISO-8601 date time
+     * @memberof Timespec
+     */
+    public static fromISOString(dateTime: string): Timespec {
+        ow(dateTime, 'dateTime', ow.string);
+
+        return new Timespec(native.timespec.fromISO8601(dateTime));
+    }
+
     /**
      * Returns Timespec in number representation
      */
     public toNumber(): number {
         return this.seconds;
     }
+
+    /**
+     * Returns Timespec in ISO-8601 UTC date time representation
+     */
+    public toISOString(): string {
+        return native.timespec.toISO8601(this.seconds);
+    }
 }
 
 /**
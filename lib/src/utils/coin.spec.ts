@@ -0,0 +1,80 @@
+import 'mocha';
+import { expect } from 'chai';
+
+import { formatCRO, parseCRO, addCoin, subCoin } from './coin';
+import { BigNumber } from './utils';
+import { MAX_COIN } from '../init';
+
+describe('Coin', () => {
+    describe('formatCRO', () => {
+        it('should throw Error when amount is not an integer', () => {
+            expect(() => {
+                formatCRO(new BigNumber('1.5'));
+            }).to.throw('Expected value to be within maximum coin');
+        });
+
+        it('should return CRO decimal string with trailing zeros trimmed', () => {
+            expect(formatCRO(new BigNumber('150000000'))).to.eq('1.5');
+            expect(formatCRO(new BigNumber('1'))).to.eq('0.00000001');
+            expect(formatCRO(new BigNumber('0'))).to.eq('0');
+            expect(formatCRO(new BigNumber(MAX_COIN))).to.eq('100000000000');
+        });
+    });
+
+    describe('parseCRO', () => {
+        it('should throw Error when string is not a decimal', () => {
+            expect(() => {
+                parseCRO('1e5');
+            }).to.throw('Invalid CRO decimal string: 1e5');
+            expect(() => {
+                parseCRO('-1');
+            }).to.throw('Invalid CRO decimal string: -1');
+        });
+
+        it('should throw Error when string has more than 8 decimal places', () => {
+            expect(() => {
+                parseCRO('0.000000001');
+            }).to.throw('CRO amount 0.000000001 has more than 8 decimal places');
+        });
+
+        it('should throw Error when amount exceeds maximum coin supply', () => {
+            expect(() => {
+                parseCRO('100000000000.00000001');
+            }).to.throw('exceeds maximum coin supply');
+        });
+
+        it('should return basic unit amount', () => {
+            expect(parseCRO('1.5').toString(10)).to.eq('150000000');
+            expect(parseCRO('0.1').toString(10)).to.eq('10000000');
+            expect(parseCRO('1.500000000').toString(10)).to.eq('150000000');
+        });
+    });
+
+    describe('addCoin', () => {
+        it('should throw Error when sum exceeds maximum coin supply', () => {
+            expect(() => {
+                addCoin(new BigNumber(MAX_COIN), new BigNumber(1));
+            }).to.throw('Coin addition result exceeds maximum coin supply');
+        });
+
+        it('should return sum of the amounts', () => {
+            expect(
+                addCoin(new BigNumber(1), new BigNumber(2)).toString(10),
+            ).to.eq('3');
+        });
+    });
+
+    describe('subCoin', () => {
+        it('should throw Error when difference is negative', () => {
+            expect(() => {
+                subCoin(new BigNumber(1), new BigNumber(2));
+            }).to.throw('Coin subtraction result is negative');
+        });
+
+        it('should return difference of the amounts', () => {
+            expect(
+                subCoin(new BigNumber(3), new BigNumber(2)).toString(10),
+            ).to.eq('1');
+        });
+    });
+});
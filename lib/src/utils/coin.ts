@@ -0,0 +1,70 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import { owCoin } from '../types';
+
+const native = require('../../../native');
+
+/**
+ * Format basic unit amount as exact CRO decimal string
+ *
+ * @param {BigNumber} amount amount in basic unit
+ * @returns {string} CRO decimal string with trailing zeros trimmed
+ */
+export const formatCRO = (amount: BigNumber): string => {
+    ow(amount, 'amount', owCoin);
+
+    return native.coin.baseUnitToCRO(amount.toString(10));
+};
+
+/**
+ * Parse CRO decimal string into basic unit amount without floating point
+ * arithmetic
+ *
+ * @param {string} cro CRO decimal string, e.g. `1.5`
+ * @returns {BigNumber} amount in basic unit
+ * @throws {Error} Throws Error when the string has more than 8 decimal
+ * places or exceeds maximum coin supply
+ */
+export const parseCRO = (cro: string): BigNumber => {
+    ow(cro, 'cro', ow.string);
+
+    return new BigNumber(native.coin.croToBaseUnit(cro));
+};
+
+/**
+ * Add two basic unit amounts
+ *
+ * @param {BigNumber} augend amount in basic unit
+ * @param {BigNumber} addend amount in basic unit
+ * @returns {BigNumber} sum in basic unit
+ * @throws {Error} Throws Error when the sum exceeds maximum coin supply
+ */
+export const addCoin = (augend: BigNumber, addend: BigNumber): BigNumber => {
+    ow(augend, 'augend', owCoin);
+    ow(addend, 'addend', owCoin);
+
+    return new BigNumber(
+        native.coin.add(augend.toString(10), addend.toString(10)),
+    );
+};
+
+/**
+ * Subtract two basic unit amounts
+ *
+ * @param {BigNumber} minuend amount in basic unit
+ * @param {BigNumber} subtrahend amount in basic unit
+ * @returns {BigNumber} difference in basic unit
+ * @throws {Error} Throws Error when the difference is negative
+ */
+export const subCoin = (
+    minuend: BigNumber,
+    subtrahend: BigNumber,
+): BigNumber => {
+    ow(minuend, 'minuend', owCoin);
+    ow(subtrahend, 'subtrahend', owCoin);
+
+    return new BigNumber(
+        native.coin.sub(minuend.toString(10), subtrahend.toString(10)),
+    );
+};
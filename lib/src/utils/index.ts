@@ -6,3 +6,4 @@ export {
     BigNumber,
     UnitEnum,
 } from './utils';
+export { formatCRO, parseCRO, addCoin, subCoin } from './coin';
@@ -0,0 +1 @@
+export { ValidatorKey } from './validator_key';
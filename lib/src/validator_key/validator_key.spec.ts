@@ -0,0 +1,133 @@
+import 'mocha';
+import { expect } from 'chai';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+
+import { ValidatorKey } from './validator_key';
+import { NodePublicKeyType } from '../transaction/council_node/types';
+
+describe('ValidatorKey', () => {
+    // RFC 8032 ed25519 test vector 1
+    const SAMPLE_SEED = Buffer.from(
+        '9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60',
+        'hex',
+    );
+    const SAMPLE_CONSENSUS_PUBLIC_KEY =
+        '11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=';
+    const SAMPLE_CONSENSUS_ADDRESS = '21FE31DFA154A261626BF854046FD2271B7BED4B';
+    const SAMPLE_PRIV_KEY =
+        'nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2DXWpgBgrEKt9VL/tPJZAc6DuFy89qmIyWvAhpo9wdRGg==';
+
+    const privValidatorKeyJson = (overrides: object = {}): string =>
+        JSON.stringify({
+            address: SAMPLE_CONSENSUS_ADDRESS,
+            pub_key: {
+                type: 'tendermint/PubKeyEd25519',
+                value: SAMPLE_CONSENSUS_PUBLIC_KEY,
+            },
+            priv_key: {
+                type: 'tendermint/PrivKeyEd25519',
+                value: SAMPLE_PRIV_KEY,
+            },
+            ...overrides,
+        });
+
+    describe('generate', () => {
+        it('should generate a random validator key', () => {
+            const validatorKey = ValidatorKey.generate();
+            const anotherValidatorKey = ValidatorKey.generate();
+
+            expect(validatorKey.getSeed().length).to.eq(32);
+            expect(validatorKey.getConsensusPublicKey()).not.to.eq(
+                anotherValidatorKey.getConsensusPublicKey(),
+            );
+        });
+    });
+
+    describe('fromSeed', () => {
+        it('should throw Error when seed is not 32 bytes', () => {
+            expect(() => {
+                ValidatorKey.fromSeed(Buffer.alloc(31));
+            }).to.throw('Expected ed25519 seed to be 32 bytes but got 31 bytes');
+        });
+
+        it('should derive the consensus public key and address', () => {
+            const validatorKey = ValidatorKey.fromSeed(SAMPLE_SEED);
+
+            expect(validatorKey.getConsensusPublicKey()).to.eq(
+                SAMPLE_CONSENSUS_PUBLIC_KEY,
+            );
+            expect(validatorKey.getConsensusAddress()).to.eq(
+                SAMPLE_CONSENSUS_ADDRESS,
+            );
+            expect(validatorKey.toNodePublicKey()).to.deep.eq({
+                type: NodePublicKeyType.Ed25519,
+                value: SAMPLE_CONSENSUS_PUBLIC_KEY,
+            });
+        });
+    });
+
+    describe('fromPrivValidatorKey', () => {
+        it('should import priv_validator_key.json', () => {
+            const validatorKey = ValidatorKey.fromPrivValidatorKey(
+                privValidatorKeyJson(),
+            );
+
+            expect(validatorKey.getSeed()).to.deep.eq(SAMPLE_SEED);
+            expect(validatorKey.getConsensusAddress()).to.eq(
+                SAMPLE_CONSENSUS_ADDRESS,
+            );
+        });
+
+        it('should throw Error when address does not belong to the public key', () => {
+            expect(() => {
+                ValidatorKey.fromPrivValidatorKey(
+                    privValidatorKeyJson({
+                        address: '0000000000000000000000000000000000000000',
+                    }),
+                );
+            }).to.throw('Address does not belong to the public key');
+        });
+
+        it('should throw Error when public key does not belong to the private key', () => {
+            expect(() => {
+                ValidatorKey.fromPrivValidatorKey(
+                    privValidatorKeyJson({
+                        address: '',
+                        pub_key: {
+                            type: 'tendermint/PubKeyEd25519',
+                            value: Buffer.alloc(32, 1).toString('base64'),
+                        },
+                    }),
+                );
+            }).to.throw('Public key does not belong to the private key');
+        });
+
+        it('should round trip through priv_validator_key.json file', () => {
+            const keyFile = path.join(
+                fs.mkdtempSync(path.join(os.tmpdir(), 'validator-key-')),
+                'priv_validator_key.json',
+            );
+            const validatorKey = ValidatorKey.fromSeed(SAMPLE_SEED);
+            validatorKey.writePrivValidatorKeyFile(keyFile);
+
+            const imported = ValidatorKey.fromPrivValidatorKeyFile(keyFile);
+
+            expect(imported.getConsensusPublicKey()).to.eq(
+                SAMPLE_CONSENSUS_PUBLIC_KEY,
+            );
+            expect(JSON.parse(imported.toPrivValidatorKey())).to.deep.eq(
+                JSON.parse(privValidatorKeyJson()),
+            );
+        });
+    });
+
+    describe('getConsensusAddress', () => {
+        it('should return the consensus address of the public key', () => {
+            expect(
+                ValidatorKey.getConsensusAddress(SAMPLE_CONSENSUS_PUBLIC_KEY),
+            ).to.eq(SAMPLE_CONSENSUS_ADDRESS);
+        });
+    });
+});
@@ -0,0 +1,156 @@
+import * as fs from 'fs';
+import ow from 'ow';
+
+import {
+    NodePublicKey,
+    NodePublicKeyType,
+} from '../transaction/council_node/types';
+
+const native = require('../../../native');
+
+/**
+ * Tendermint ed25519 validator key of a council node
+ * @class ValidatorKey
+ */
+export class ValidatorKey {
+    private seed: Buffer;
+
+    private consensusPublicKey: string;
+
+    private consensusAddress: string;
+
+    private privValidatorKey: string;
+
+    private constructor(nativeValidatorKey: NativeValidatorKey) {
+        this.seed = nativeValidatorKey.seed;
+        this.consensusPublicKey = nativeValidatorKey.consensusPublicKey;
+        this.consensusAddress = nativeValidatorKey.consensusAddress;
+        this.privValidatorKey = nativeValidatorKey.privValidatorKey;
+    }
+
+    /**
+     * Generate a random validator key
+     * @returns {ValidatorKey}
+     */
+    public static generate(): ValidatorKey {
+        return new ValidatorKey(native.validatorKey.generateValidatorKey());
+    }
+
+    /**
+     * Import validator key from the 32 bytes ed25519 seed
+     * @param {Buffer} seed ed25519 seed
+     * @returns {ValidatorKey}
+     */
+    public static fromSeed(seed: Buffer): ValidatorKey {
+        ow(seed, 'seed', ow.buffer);
+
+        return new ValidatorKey(native.validatorKey.validatorKeyFromSeed(seed));
+    }
+
+    /**
+     * Import validator key from the content of Tendermint
+     * `priv_validator_key.json`. The public key and address are verified
+     * against the private key
+     * @param {string} privValidatorKey content of `priv_validator_key.json`
+     * @returns {ValidatorKey}
+     */
+    public static fromPrivValidatorKey(privValidatorKey: string): ValidatorKey {
+        ow(privValidatorKey, 'privValidatorKey', ow.string.nonEmpty);
+
+        return new ValidatorKey(
+            native.validatorKey.validatorKeyFromPrivValidatorKey(
+                privValidatorKey,
+            ),
+        );
+    }
+
+    /**
+     * Import validator key from Tendermint `priv_validator_key.json` file
+     * @param {string} path path to `priv_validator_key.json`
+     * @returns {ValidatorKey}
+     */
+    public static fromPrivValidatorKeyFile(path: string): ValidatorKey {
+        ow(path, 'path', ow.string.nonEmpty);
+
+        return ValidatorKey.fromPrivValidatorKey(fs.readFileSync(path, 'utf8'));
+    }
+
+    /**
+     * Returns the Tendermint validator address of a base64 encoded consensus
+     * public key
+     * @param {string} consensusPublicKey base64 encoded ed25519 public key
+     * @returns {string} validator address in uppercase hex
+     */
+    public static getConsensusAddress(consensusPublicKey: string): string {
+        ow(consensusPublicKey, 'consensusPublicKey', ow.string.nonEmpty);
+
+        return native.validatorKey.getConsensusAddress(consensusPublicKey);
+    }
+
+    /**
+     * Returns the 32 bytes ed25519 seed
+     * @returns {Buffer}
+     * @memberof ValidatorKey
+     */
+    public getSeed(): Readonly<Buffer> {
+        return this.seed;
+    }
+
+    /**
+     * Returns the base64 encoded consensus public key
+     * @returns {string}
+     * @memberof ValidatorKey
+     */
+    public getConsensusPublicKey(): Readonly<string> {
+        return this.consensusPublicKey;
+    }
+
+    /**
+     * Returns the consensus public key in node metadata format
+     * @returns {NodePublicKey}
+     * @memberof ValidatorKey
+     */
+    public toNodePublicKey(): NodePublicKey {
+        return {
+            type: NodePublicKeyType.Ed25519,
+            value: this.consensusPublicKey,
+        };
+    }
+
+    /**
+     * Returns the Tendermint validator address in uppercase hex
+     * @returns {string}
+     * @memberof ValidatorKey
+     */
+    public getConsensusAddress(): Readonly<string> {
+        return this.consensusAddress;
+    }
+
+    /**
+     * Returns the content of `priv_validator_key.json` of the key
+     * @returns {string}
+     * @memberof ValidatorKey
+     */
+    public toPrivValidatorKey(): string {
+        return this.privValidatorKey;
+    }
+
+    /**
+     * Write the key to `priv_validator_key.json` file readable by the owner
+     * only
+     * @param {string} path path to write `priv_validator_key.json`
+     * @memberof ValidatorKey
+     */
+    public writePrivValidatorKeyFile(path: string) {
+        ow(path, 'path', ow.string.nonEmpty);
+
+        fs.writeFileSync(path, this.privValidatorKey, { mode: 0o600 });
+    }
+}
+
+interface NativeValidatorKey {
+    seed: Buffer;
+    consensusPublicKey: string;
+    consensusAddress: string;
+    privValidatorKey: string;
+}
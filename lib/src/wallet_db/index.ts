@@ -0,0 +1,12 @@
+export {
+    WalletDb,
+    WalletDbOptions,
+    WalletTransferOptions,
+    WalletTransfer,
+    WalletBalance,
+    TrackedOutput,
+    OutputStatus,
+    IngestionSummary,
+    RawTransaction,
+    Block,
+} from './wallet_db';
@@ -0,0 +1,37 @@
+import 'mocha';
+import { expect } from 'chai';
+
+import { WalletDb } from './wallet_db';
+
+describe('WalletDb', () => {
+    describe('create', () => {
+        it('should throw Error when the path is empty', () => {
+            expect(() => {
+                WalletDb.create({
+                    path: '',
+                });
+            }).to.throw('Expected property string `path` to not be empty');
+        });
+
+        it('should throw Error when the passphrase is empty', () => {
+            expect(() => {
+                WalletDb.create({
+                    path: '/tmp/wallet.db',
+                    passphrase: '',
+                });
+            }).to.throw(
+                'Expected property string `passphrase` to not be empty',
+            );
+        });
+    });
+
+    describe('open', () => {
+        it('should throw Error when the path is empty', () => {
+            expect(() => {
+                WalletDb.open({
+                    path: '',
+                });
+            }).to.throw('Expected property string `path` to not be empty');
+        });
+    });
+});
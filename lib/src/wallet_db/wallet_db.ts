@@ -0,0 +1,425 @@
+import ow from 'ow';
+import BigNumber from 'bignumber.js';
+
+import {
+    Input,
+    InputAddressParams,
+    NativeOutput,
+    Output,
+    owBigNumber,
+    owInputAddressParams,
+    owOutput,
+    owTransferAddress,
+    owTxId,
+    owViewKey,
+    parseOutputForNative,
+    parseOutputFromNative,
+    Timespec,
+} from '../types';
+import { owTimespec } from '../types/timespec';
+import { Mainnet, NetworkConfig } from '../network';
+import { owOptionalNetworkConfig } from '../network/types';
+import { parseFeeConfigForNative } from '../fee/types';
+import { TransactionKind } from '../fee';
+import { TransferTransactionBuilder } from '../transaction/transfer';
+
+const native = require('../../../native');
+
+/**
+ * Decrypted raw transaction of a kind moving UTXOs
+ * @typedef {object} RawTransaction
+ * @property {TransactionKind} kind transfer, deposit or withdrawUnbonded
+ * @property {Buffer} rawTx decrypted raw transaction
+ */
+export interface RawTransaction {
+    kind:
+        | TransactionKind.Transfer
+        | TransactionKind.Deposit
+        | TransactionKind.WithdrawUnbonded;
+    rawTx: Buffer;
+}
+
+/**
+ * @typedef {object} Block
+ * @property {BigNumber} height block height
+ * @property {Timespec} time block time
+ * @property {RawTransaction[]} txs decrypted raw transactions of the block
+ * concerning the wallet
+ */
+export interface Block {
+    height: BigNumber;
+    time: Timespec;
+    txs: RawTransaction[];
+}
+
+/**
+ * Number of outputs affected by an ingestion
+ * @typedef {object} IngestionSummary
+ * @property {number} received outputs paid to owned addresses
+ * @property {number} spent tracked outputs spent
+ */
+export interface IngestionSummary {
+    received: number;
+    spent: number;
+}
+
+export enum OutputStatus {
+    Unspent = 'unspent',
+    Pending = 'pending',
+    Spent = 'spent',
+}
+
+/**
+ * Output paid to an owned address, in the shape of a transfer input
+ * @typedef {object} TrackedOutput
+ * @property {BigNumber} [height] height of the block of the transaction
+ * @property {OutputStatus} status spending status
+ * @property {string} [spentBy] id of the transaction spending the output
+ */
+export interface TrackedOutput extends Input {
+    height?: BigNumber;
+    status: OutputStatus;
+    spentBy?: string;
+}
+
+/**
+ * @typedef {object} WalletBalance
+ * @property {BigNumber} available unspent outputs spendable at the last
+ * block time
+ * @property {BigNumber} locked unspent outputs valid from after the last
+ * block time
+ * @property {BigNumber} pending inputs of the transfers built by the wallet
+ * which are not ingested yet
+ * @property {BigNumber} lastHeight height of the last ingested block
+ */
+export interface WalletBalance {
+    available: BigNumber;
+    locked: BigNumber;
+    pending: BigNumber;
+    lastHeight: BigNumber;
+}
+
+/**
+ * @typedef {object} WalletTransfer
+ * @property {TransferTransactionBuilder} builder builder with the inputs and
+ * outputs of the transfer, ready to sign
+ * @property {string} txId transaction id
+ * @property {BigNumber} fee fee in basic unit
+ * @property {Input[]} inputs spent outputs
+ * @property {Output} [change] change output, the last output of the builder
+ */
+export interface WalletTransfer {
+    builder: TransferTransactionBuilder;
+    txId: string;
+    fee: BigNumber;
+    inputs: Input[];
+    change?: Output;
+}
+
+export interface WalletDbOptions {
+    path: string;
+    passphrase?: string;
+    network?: NetworkConfig;
+}
+
+export interface WalletTransferOptions {
+    outputs: Output[];
+    viewKeys?: Buffer[];
+    changeAddress?: string;
+}
+
+/**
+ * @internal
+ */
+const owWalletDbOptions = ow.object.exactShape({
+    path: ow.string.nonEmpty,
+    passphrase: ow.optional.string.nonEmpty,
+    network: owOptionalNetworkConfig,
+});
+
+/**
+ * @internal
+ */
+const owRawTransaction = ow.object.exactShape({
+    kind: ow.string.oneOf([
+        TransactionKind.Transfer,
+        TransactionKind.Deposit,
+        TransactionKind.WithdrawUnbonded,
+    ]),
+    rawTx: ow.buffer,
+});
+
+/**
+ * @internal
+ */
+const owBlock = ow.object.exactShape({
+    height: owBigNumber,
+    time: owTimespec,
+    txs: ow.array.ofType(owRawTransaction),
+});
+
+/**
+ * @internal
+ */
+const owWalletTransferOptions = ow.object.exactShape({
+    outputs: ow.array.nonEmpty.ofType(owOutput),
+    viewKeys: ow.optional.array.ofType(owViewKey),
+    changeAddress: ow.optional.any(owTransferAddress),
+});
+
+/**
+ * Embedded wallet database tracking the unspent outputs of owned transfer
+ * addresses. The database file is encrypted when a passphrase is provided.
+ * Every call reads and writes the file, which should not be shared between
+ * processes.
+ */
+export class WalletDb {
+    private store: NativeWalletStore;
+
+    private network: NetworkConfig;
+
+    private constructor(options: WalletDbOptions) {
+        this.store = {
+            path: options.path,
+            passphrase: options.passphrase,
+        };
+        this.network = options.network || Mainnet;
+    }
+
+    /**
+     * Create the wallet database file, which should not exist yet
+     * @param {WalletDbOptions} options
+     * @param {string} options.path path of the database file
+     * @param {string} [options.passphrase] passphrase to encrypt the file with
+     * @param {NetworkConfig} [options.network=Mainnet] network of the wallet
+     * @returns {WalletDb}
+     */
+    public static create(options: WalletDbOptions): WalletDb {
+        ow(options, 'options', owWalletDbOptions);
+
+        const walletDb = new WalletDb(options);
+        native.walletDb.create(walletDb.store, walletDb.network.chainHexId);
+
+        return walletDb;
+    }
+
+    /**
+     * Open an existing wallet database file
+     * @param {WalletDbOptions} options
+     * @param {string} options.path path of the database file
+     * @param {string} [options.passphrase] passphrase the file is encrypted
+     * with
+     * @param {NetworkConfig} [options.network=Mainnet] network of the wallet
+     * @returns {WalletDb}
+     */
+    public static open(options: WalletDbOptions): WalletDb {
+        ow(options, 'options', owWalletDbOptions);
+
+        const walletDb = new WalletDb(options);
+        // Fails early on incorrect passphrase
+        walletDb.getBalance();
+
+        return walletDb;
+    }
+
+    /**
+     * Record the transfer address as owned, or update its signers. Outputs
+     * of transactions ingested before are not tracked retroactively.
+     * @param {string} address transfer address
+     * @param {InputAddressParams} [addressParams] signers of the address.
+     * Default to single signer
+     * @returns {boolean} true when the address is new
+     */
+    public addAddress(
+        address: string,
+        addressParams?: InputAddressParams,
+    ): boolean {
+        ow(address, 'address', owTransferAddress);
+        ow(
+            addressParams,
+            'addressParams',
+            ow.optional.any(owInputAddressParams),
+        );
+
+        return native.walletDb.addAddress(
+            this.store,
+            addressParams ? { address, addressParams } : { address },
+        );
+    }
+
+    /**
+     * Ingest the decrypted raw transactions of the next block
+     * @param {Block} block
+     * @returns {IngestionSummary}
+     */
+    public ingestBlock(block: Block): IngestionSummary {
+        ow(block, 'block', owBlock);
+
+        return native.walletDb.ingestBlock(this.store, {
+            height: block.height.toString(10),
+            time: block.time.toNumber(),
+            txs: block.txs,
+        });
+    }
+
+    /**
+     * Ingest a decrypted raw transaction on its own, e.g. as soon as it is
+     * broadcast
+     * @param {RawTransaction} tx
+     * @param {BigNumber} [height] height of the block of the transaction
+     * @returns {IngestionSummary}
+     */
+    public ingestTransaction(
+        tx: RawTransaction,
+        height?: BigNumber,
+    ): IngestionSummary {
+        ow(tx, 'tx', owRawTransaction);
+        ow(height, 'height', ow.optional.any(owBigNumber));
+
+        return native.walletDb.ingestTransaction(this.store, {
+            ...tx,
+            height: height ? height.toString(10) : undefined,
+        });
+    }
+
+    /**
+     * Returns every tracked output, including the spent ones
+     * @returns {TrackedOutput[]}
+     */
+    public getOutputs(): TrackedOutput[] {
+        return native.walletDb
+            .getOutputs(this.store)
+            .map((output: NativeTrackedOutput) => ({
+                ...parseInputFromNative(output),
+                height: output.height
+                    ? new BigNumber(output.height)
+                    : undefined,
+                status: output.status,
+                spentBy: output.spentBy,
+            }));
+    }
+
+    /**
+     * Returns the unspent outputs, as transfer inputs
+     * @returns {Input[]}
+     */
+    public getUnspentOutputs(): Input[] {
+        return native.walletDb
+            .getOutputs(this.store)
+            .filter(
+                (output: NativeTrackedOutput) =>
+                    output.status === OutputStatus.Unspent,
+            )
+            .map(parseInputFromNative);
+    }
+
+    /**
+     * @returns {WalletBalance}
+     */
+    public getBalance(): WalletBalance {
+        const balance = native.walletDb.getBalance(this.store);
+
+        return {
+            available: new BigNumber(balance.available),
+            locked: new BigNumber(balance.locked),
+            pending: new BigNumber(balance.pending),
+            lastHeight: new BigNumber(balance.lastHeight),
+        };
+    }
+
+    /**
+     * Build a transfer to the outputs from the unspent outputs, largest
+     * first, with the change to the change address. The spent outputs are
+     * pending until the transaction is ingested or released. A change below
+     * the fee of its own output is left to the fee instead.
+     * @param {WalletTransferOptions} options
+     * @param {Output[]} options.outputs transfer outputs
+     * @param {Buffer[]} [options.viewKeys] view keys of the transaction
+     * @param {string} [options.changeAddress] owned address receiving the
+     * change. Default to the first owned address
+     * @returns {WalletTransfer}
+     */
+    public buildTransfer(options: WalletTransferOptions): WalletTransfer {
+        ow(options, 'options', owWalletTransferOptions);
+
+        const viewKeys = options.viewKeys || [];
+        const transfer = native.walletDb.buildTransfer(this.store, {
+            outputs: options.outputs.map(parseOutputForNative),
+            viewKeys,
+            changeAddress: options.changeAddress,
+            feeConfig: parseFeeConfigForNative(this.network.feeConfig),
+        });
+
+        const inputs: Input[] = transfer.inputs.map(parseInputFromNative);
+        const change = transfer.change
+            ? parseOutputFromNative(transfer.change)
+            : undefined;
+
+        const builder = new TransferTransactionBuilder({
+            network: this.network,
+        });
+        inputs.forEach((input) => builder.addInput(input));
+        options.outputs.forEach((output) => builder.addOutput(output));
+        if (change) {
+            builder.addOutput(change);
+        }
+        viewKeys.forEach((viewKey) => builder.addViewKey(viewKey));
+
+        return {
+            builder,
+            txId: transfer.txId,
+            fee: new BigNumber(transfer.fee),
+            inputs,
+            change,
+        };
+    }
+
+    /**
+     * Release the outputs pending for the transaction, e.g. when it is
+     * rejected, so that they can be spent again
+     * @param {string} txId transaction id
+     * @returns {number} number of released outputs
+     */
+    public releasePending(txId: string): number {
+        ow(txId, 'txId', owTxId);
+
+        return native.walletDb.releasePending(this.store, txId);
+    }
+}
+
+/**
+ * @internal
+ */
+interface NativeWalletStore {
+    path: string;
+    passphrase?: string;
+}
+
+/**
+ * @internal
+ */
+interface NativeInput {
+    prevTxId: string;
+    prevIndex: number;
+    prevOutput: NativeOutput;
+    addressParams: InputAddressParams;
+}
+
+/**
+ * @internal
+ */
+interface NativeTrackedOutput extends NativeInput {
+    height?: string;
+    status: OutputStatus;
+    spentBy?: string;
+}
+
+/**
+ * @internal
+ */
+const parseInputFromNative = (nativeInput: NativeInput): Input => ({
+    prevTxId: nativeInput.prevTxId,
+    prevIndex: nativeInput.prevIndex,
+    prevOutput: parseOutputFromNative(nativeInput.prevOutput),
+    addressParams: nativeInput.addressParams,
+});
@@ -0,0 +1,156 @@
+//! Address and HD wallet key derivation
+use std::path::PathBuf;
+
+use client_common::{Error, ErrorKind, PublicKey, Result, ResultExt};
+use cro_nodelib_core::address::{
+    staking_address_from_public_key, transfer_address_from_public_key,
+};
+use cro_nodelib_core::hd_wallet::{seed_from_mnemonic, AddressDerivation, DeriveAddressesOptions};
+use cro_nodelib_core::network::network_by_name;
+use serde_json::{json, Value};
+use structopt::StructOpt;
+
+use crate::io::{decode_hex, read_input, schema_error};
+
+#[derive(Debug, StructOpt)]
+pub enum AddressCommand {
+    /// Transfer and staking addresses of a public key
+    FromPublicKey {
+        /// Hex encoded public key
+        #[structopt(long)]
+        public_key: String,
+        /// Network of the transfer address
+        #[structopt(long, default_value = "Mainnet")]
+        network: String,
+    },
+    /// Key pairs and addresses of a range of indexes of an HD wallet
+    Derive {
+        /// File of the mnemonic words, `-` for the standard input
+        #[structopt(long, parse(from_os_str))]
+        mnemonic_file: PathBuf,
+        /// Network of the addresses and of the BIP44 coin type
+        #[structopt(long, default_value = "Mainnet")]
+        network: String,
+        #[structopt(long, default_value = "0")]
+        account: u32,
+        #[structopt(long, default_value = "0")]
+        start_index: u32,
+        #[structopt(long, default_value = "1")]
+        count: u32,
+        /// Output the private key of each index
+        #[structopt(long)]
+        include_private_key: bool,
+    },
+}
+
+impl AddressCommand {
+    pub fn execute(&self) -> Result<Value> {
+        match self {
+            AddressCommand::FromPublicKey {
+                public_key,
+                network,
+            } => from_public_key(public_key, network),
+            AddressCommand::Derive {
+                mnemonic_file,
+                network,
+                account,
+                start_index,
+                count,
+                include_private_key,
+            } => {
+                let mnemonic = read_input(mnemonic_file)?;
+                let seed = seed_from_mnemonic(mnemonic.trim())?;
+
+                derive(DeriveAddressesOptions {
+                    seed,
+                    network: network.to_owned(),
+                    account: *account,
+                    start_index: *start_index,
+                    count: *count,
+                    include_private_key: *include_private_key,
+                })
+            }
+        }
+    }
+}
+
+fn from_public_key(public_key: &str, network: &str) -> Result<Value> {
+    let public_key = PublicKey::deserialize_from(&decode_hex(public_key, "public key")?)
+        .chain(|| (ErrorKind::InvalidInput, "Invalid public key"))?;
+    let network = network_by_name(network)?;
+
+    Ok(json!({
+        "transferAddress": transfer_address_from_public_key(&network, &public_key)?,
+        "stakingAddress": staking_address_from_public_key(&public_key).to_string(),
+    }))
+}
+
+fn derive(options: DeriveAddressesOptions) -> Result<Value> {
+    let derivation = AddressDerivation::new(options).map_err(schema_error)?;
+    let derived_addresses = derivation
+        .derive()
+        .map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+
+    Ok(derived_addresses
+        .into_iter()
+        .map(|derived_address| {
+            let mut json = json!({
+                "index": derived_address.index,
+                "publicKey": hex::encode(derived_address.public_key.serialize_compressed()),
+                "transferAddress": derived_address.transfer_address,
+                "stakingAddress": derived_address.staking_address,
+            });
+            if let Some(private_key) = derived_address.private_key {
+                json["privateKey"] = Value::String(hex::encode(private_key.serialize()));
+            }
+
+            json
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use client_common::PrivateKey;
+
+    #[test]
+    fn from_public_key_should_return_addresses_of_network() {
+        let private_key = PrivateKey::deserialize_from(&[1; 32]).unwrap();
+        let public_key = hex::encode(PublicKey::from(&private_key).serialize_compressed());
+
+        let mainnet = from_public_key(&public_key, "Mainnet").unwrap();
+        let testnet = from_public_key(&public_key, "Testnet").unwrap();
+
+        assert!(mainnet["transferAddress"]
+            .as_str()
+            .unwrap()
+            .starts_with("cro1"));
+        assert!(testnet["transferAddress"]
+            .as_str()
+            .unwrap()
+            .starts_with("tcro1"));
+        assert_eq!(mainnet["stakingAddress"], testnet["stakingAddress"]);
+    }
+
+    #[test]
+    fn derive_should_include_private_key_only_when_requested() {
+        let options = |include_private_key| DeriveAddressesOptions {
+            seed: vec![1; 64],
+            network: "Testnet".to_owned(),
+            account: 0,
+            start_index: 2,
+            count: 2,
+            include_private_key,
+        };
+
+        let without_private_key = derive(options(false)).unwrap();
+        let with_private_key = derive(options(true)).unwrap();
+
+        assert_eq!(without_private_key.as_array().unwrap().len(), 2);
+        assert_eq!(without_private_key[0]["index"], 2);
+        assert!(without_private_key[0].get("privateKey").is_none());
+        assert!(with_private_key[1]["privateKey"].is_string());
+    }
+}
@@ -0,0 +1,56 @@
+//! Node join and unjail transactions of council nodes
+use std::path::PathBuf;
+
+use client_common::Result;
+use cro_nodelib_core::council_node_transaction::node_join::{
+    sign_node_join_transaction, BuildNodeJoinTransactionOptions,
+};
+use cro_nodelib_core::council_node_transaction::unjail::{
+    sign_unjail_transaction, UnjailTxOptions,
+};
+use serde_json::Value;
+use structopt::StructOpt;
+
+use crate::io::{read_options, schema_error, KeyArgs};
+use crate::tx_aux::tx_aux_to_json;
+
+#[derive(Debug, StructOpt)]
+pub enum CouncilNodeCommand {
+    /// Join the staking address of the key as a council node
+    NodeJoin {
+        /// File of the node join options JSON, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        #[structopt(flatten)]
+        key: KeyArgs,
+    },
+    /// Unjail the council node of the staking address of the key
+    Unjail {
+        /// File of the unjail options JSON, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        #[structopt(flatten)]
+        key: KeyArgs,
+    },
+}
+
+impl CouncilNodeCommand {
+    pub fn execute(&self) -> Result<Value> {
+        match self {
+            CouncilNodeCommand::NodeJoin { options, key } => {
+                let options: BuildNodeJoinTransactionOptions = read_options(options)?;
+                let council_node = options.council_node().map_err(schema_error)?;
+                let tx = options.to_tx(council_node);
+                let tx_aux = sign_node_join_transaction(tx, &key.signer()?)?;
+
+                Ok(tx_aux_to_json(&tx_aux))
+            }
+            CouncilNodeCommand::Unjail { options, key } => {
+                let options: UnjailTxOptions = read_options(options)?;
+                let tx_aux = sign_unjail_transaction(options.to_tx(), &key.signer()?)?;
+
+                Ok(tx_aux_to_json(&tx_aux))
+            }
+        }
+    }
+}
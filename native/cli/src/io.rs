@@ -0,0 +1,138 @@
+//! Options, hex and keys read from files or the standard input
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use client_common::{Error, ErrorKind, PrivateKey, PublicKey, Result, ResultExt};
+use cro_nodelib_core::schema::{from_json_value, SchemaError};
+use cro_nodelib_core::signer::KeyPairSigner;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use structopt::StructOpt;
+
+/// Path read from the standard input
+const STDIN_PATH: &str = "-";
+
+/// Read the whole content of the file, or of the standard input for `-`
+pub fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new(STDIN_PATH) {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .chain(|| (ErrorKind::InvalidInput, "Unable to read standard input"))?;
+
+        return Ok(input);
+    }
+
+    fs::read_to_string(path).chain(|| {
+        (
+            ErrorKind::InvalidInput,
+            format!("Unable to read {}", path.display()),
+        )
+    })
+}
+
+/// Read the options JSON of the file, in the same shape as the options
+/// objects of the JS library
+pub fn read_options<T>(path: &Path) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let options: Value = serde_json::from_str(&read_input(path)?).chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            "Unable to parse options JSON",
+        )
+    })?;
+
+    from_json_value(options).map_err(schema_error)
+}
+
+/// Invalid options error of the schema error
+pub fn schema_error(err: SchemaError) -> Error {
+    Error::new(ErrorKind::InvalidInput, err.to_string())
+}
+
+/// Decode a hex string, ignoring surrounding whitespaces and `0x` prefix
+pub fn decode_hex(value: &str, name: &str) -> Result<Vec<u8>> {
+    let value = value.trim();
+    let value = value.trim_start_matches("0x");
+
+    hex::decode(value).chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            format!("Unable to decode {} hex", name),
+        )
+    })
+}
+
+/// Hex string of the argument, or of the standard input when omitted
+pub fn hex_argument(value: Option<&str>, name: &str) -> Result<Vec<u8>> {
+    match value {
+        Some(value) => decode_hex(value, name),
+        None => decode_hex(&read_input(Path::new(STDIN_PATH))?, name),
+    }
+}
+
+/// Private key of the signer
+#[derive(Debug, StructOpt)]
+pub struct KeyArgs {
+    /// File of the hex encoded private key, `-` for the standard input
+    #[structopt(long, parse(from_os_str))]
+    pub private_key_file: PathBuf,
+}
+
+impl KeyArgs {
+    /// Read the private key file
+    pub fn load(&self) -> Result<KeyFile> {
+        let private_key = decode_hex(&read_input(&self.private_key_file)?, "private key")?;
+
+        Ok(KeyFile { private_key })
+    }
+
+    /// Read the private key as a signer
+    pub fn signer(&self) -> Result<KeyPairSigner> {
+        self.load()?.signer()
+    }
+}
+
+/// Private key read from the key file, which can be turned into key pairs
+/// as many times as needed
+pub struct KeyFile {
+    private_key: Vec<u8>,
+}
+
+impl KeyFile {
+    /// Private key and its public key
+    pub fn key_pair(&self) -> Result<(PrivateKey, PublicKey)> {
+        let private_key = PrivateKey::deserialize_from(&self.private_key)
+            .chain(|| (ErrorKind::InvalidInput, "Invalid private key"))?;
+        let public_key = PublicKey::from(&private_key);
+
+        Ok((private_key, public_key))
+    }
+
+    pub fn signer(&self) -> Result<KeyPairSigner> {
+        let (private_key, public_key) = self.key_pair()?;
+
+        KeyPairSigner::new(private_key, public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_hex_should_trim_whitespaces_and_prefix() {
+        assert_eq!(decode_hex(" 0x2aff\n", "value").unwrap(), vec![0x2A, 0xFF]);
+        assert_eq!(decode_hex("2AFF", "value").unwrap(), vec![0x2A, 0xFF]);
+    }
+
+    #[test]
+    fn decode_hex_should_name_the_invalid_value() {
+        let err = decode_hex("2g", "transaction").unwrap_err();
+
+        assert!(err.to_string().contains("Unable to decode transaction hex"));
+    }
+}
@@ -0,0 +1,100 @@
+//! Command line tool building, signing, decoding and verifying transactions
+//! with the node library core, without a Node.js runtime
+//!
+//! Options are JSON files in the same shape as the options objects of the
+//! JS library, with buffers as hex strings. Results are written to the
+//! standard output as JSON.
+mod address;
+mod council_node;
+mod io;
+#[cfg(feature = "mock-node")]
+mod mock_node;
+mod staking;
+mod transfer;
+mod tx_aux;
+
+use std::path::PathBuf;
+use std::process;
+
+use client_common::Result;
+use cro_nodelib_core::network::{insert_network, NetworkDefinition};
+use cro_nodelib_core::tx_aux::Features;
+use serde_json::Value;
+use structopt::StructOpt;
+
+use address::AddressCommand;
+use council_node::CouncilNodeCommand;
+#[cfg(feature = "mock-node")]
+use mock_node::MockNodeCommand;
+use staking::StakingCommand;
+use transfer::TransferCommand;
+use tx_aux::TxAuxCommand;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "cro-nodelib", about = "Crypto.com Chain transaction tool")]
+struct Options {
+    /// File of the JSON array of custom networks to register, e.g. devnets
+    #[structopt(long, global = true, parse(from_os_str))]
+    networks: Option<PathBuf>,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Address derivation
+    Address(AddressCommand),
+    /// Transfer transaction
+    Transfer(TransferCommand),
+    #[structopt(flatten)]
+    Staking(StakingCommand),
+    #[structopt(flatten)]
+    CouncilNode(CouncilNodeCommand),
+    #[structopt(flatten)]
+    TxAux(TxAuxCommand),
+    /// Run a mock Tendermint node for offline integration tests
+    #[cfg(feature = "mock-node")]
+    MockNode(MockNodeCommand),
+}
+
+/// Obfuscation of the transactions sent to the enclave
+#[derive(Debug, StructOpt)]
+pub struct ObfuscationArgs {
+    /// Tendermint websocket address of the node obfuscating the transaction
+    #[structopt(long, default_value = "ws://localhost:26657/websocket")]
+    pub tendermint_address: String,
+    /// Obfuscation features: AllDefault, MockAbci or MockObfuscation
+    #[structopt(long, default_value = "AllDefault")]
+    pub features: Features,
+}
+
+fn execute(options: &Options) -> Result<Value> {
+    if let Some(ref networks) = options.networks {
+        let networks: Vec<NetworkDefinition> = io::read_options(networks)?;
+        for network in networks {
+            insert_network(network)?;
+        }
+    }
+
+    match &options.command {
+        Command::Address(command) => command.execute(),
+        Command::Transfer(command) => command.execute(),
+        Command::Staking(command) => command.execute(),
+        Command::CouncilNode(command) => command.execute(),
+        Command::TxAux(command) => command.execute(),
+        #[cfg(feature = "mock-node")]
+        Command::MockNode(command) => command.execute(),
+    }
+}
+
+fn main() {
+    let options = Options::from_args();
+
+    match execute(&options) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
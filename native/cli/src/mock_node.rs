@@ -0,0 +1,42 @@
+//! Mock Tendermint node for offline integration tests
+use std::io::{stdout, Write};
+use std::thread;
+
+use client_common::Result;
+use cro_nodelib_core::mock_node::MockNode;
+use serde_json::{json, Value};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct MockNodeCommand {
+    /// Local address to listen on
+    #[structopt(long, default_value = "127.0.0.1:26657")]
+    listen: String,
+    /// Tx-query enclave address returned to the `txquery` query
+    #[structopt(long)]
+    tx_query_address: Option<String>,
+}
+
+impl MockNodeCommand {
+    /// Run the node until the process is terminated. The addresses of the
+    /// node are written once it is listening.
+    pub fn execute(&self) -> Result<Value> {
+        let node = MockNode::bind(&self.listen)?;
+        if let Some(ref tx_query_address) = self.tx_query_address {
+            node.set_tx_query_address(tx_query_address);
+        }
+
+        println!(
+            "{}",
+            json!({
+                "websocketAddress": node.websocket_address(),
+                "httpAddress": node.http_address(),
+            })
+        );
+        let _ = stdout().flush();
+
+        loop {
+            thread::park();
+        }
+    }
+}
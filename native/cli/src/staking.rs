@@ -0,0 +1,120 @@
+//! Deposit, unbond and withdraw unbonded transactions of a staked state
+use std::path::PathBuf;
+
+use chain_core::tx::witness::TxInWitness;
+use chain_core::tx::TransactionId;
+use client_common::{Result, SignedTransaction};
+use cro_nodelib_core::staking_transaction::deposit::{
+    sign_deposit_input, verify_key_pair_inputs, BuildDepositTransactionOptions,
+};
+use cro_nodelib_core::staking_transaction::staked_state_op_witness;
+use cro_nodelib_core::staking_transaction::unbond::{
+    sign_unbond_transaction, BuildUnbondTransactionOptions,
+};
+use cro_nodelib_core::staking_transaction::withdraw_unbonded::BuildWithdrawUnbondedTransactionOptions;
+use cro_nodelib_core::tx_aux::signed_transaction_to_tx_aux;
+use serde_json::Value;
+use structopt::StructOpt;
+
+use crate::io::{read_options, schema_error, KeyArgs};
+use crate::tx_aux::tx_aux_to_json;
+use crate::ObfuscationArgs;
+
+#[derive(Debug, StructOpt)]
+pub enum StakingCommand {
+    /// Deposit the inputs, all signed by the key, to a staking address
+    Deposit {
+        /// File of the deposit options JSON, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        #[structopt(flatten)]
+        key: KeyArgs,
+        #[structopt(flatten)]
+        obfuscation: ObfuscationArgs,
+    },
+    /// Unbond bonded amount of the staking address of the key
+    Unbond {
+        /// File of the unbond options JSON, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        #[structopt(flatten)]
+        key: KeyArgs,
+    },
+    /// Withdraw unbonded amount of the staking address of the key
+    WithdrawUnbonded {
+        /// File of the withdraw unbonded options JSON, `-` for the standard
+        /// input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        #[structopt(flatten)]
+        key: KeyArgs,
+        #[structopt(flatten)]
+        obfuscation: ObfuscationArgs,
+    },
+}
+
+impl StakingCommand {
+    pub fn execute(&self) -> Result<Value> {
+        match self {
+            StakingCommand::Deposit {
+                options,
+                key,
+                obfuscation,
+            } => {
+                let options: BuildDepositTransactionOptions = read_options(options)?;
+                verify_key_pair_inputs(&options.inputs, "inputs").map_err(schema_error)?;
+                let tx = options.to_tx();
+                let spent_outputs = options.spent_outputs().map_err(schema_error)?;
+                let key = key.load()?;
+
+                let witnesses = (0..tx.inputs.len())
+                    .map(|i| {
+                        let prev_output = spent_outputs
+                            .as_ref()
+                            .map(|spent_outputs| &spent_outputs[i].0);
+                        let (private_key, public_key) = key.key_pair()?;
+
+                        sign_deposit_input(&tx, prev_output, private_key, public_key)
+                    })
+                    .collect::<Result<Vec<TxInWitness>>>()?;
+
+                let signed_transaction =
+                    SignedTransaction::DepositStakeTransaction(tx, witnesses.into());
+                let tx_aux = signed_transaction_to_tx_aux(
+                    signed_transaction,
+                    &obfuscation.tendermint_address,
+                    obfuscation.features,
+                )?;
+
+                Ok(tx_aux_to_json(&tx_aux))
+            }
+            StakingCommand::Unbond { options, key } => {
+                let options: BuildUnbondTransactionOptions = read_options(options)?;
+                let amount = options.unbond_amount().map_err(schema_error)?;
+                let tx = options.to_tx(amount);
+                let tx_aux = sign_unbond_transaction(tx, &key.signer()?)?;
+
+                Ok(tx_aux_to_json(&tx_aux))
+            }
+            StakingCommand::WithdrawUnbonded {
+                options,
+                key,
+                obfuscation,
+            } => {
+                let options: BuildWithdrawUnbondedTransactionOptions = read_options(options)?;
+                let tx = options.to_tx().map_err(schema_error)?;
+                let witness = staked_state_op_witness(&key.signer()?, &tx.id())?;
+
+                let signed_transaction =
+                    SignedTransaction::WithdrawUnbondedStakeTransaction(tx, witness);
+                let tx_aux = signed_transaction_to_tx_aux(
+                    signed_transaction,
+                    &obfuscation.tendermint_address,
+                    obfuscation.features,
+                )?;
+
+                Ok(tx_aux_to_json(&tx_aux))
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Transfer transaction built, signed and finished across signers through
+//! the incomplete hex
+use std::path::PathBuf;
+
+use client_common::Result;
+use cro_nodelib_core::transfer_transaction::{
+    build_incomplete, sign_input, to_tx_aux, BuilderOptions,
+    FeeScheduleRawTransferTransactionBuilder, IncompleteBuilderOptions,
+};
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use structopt::StructOpt;
+
+use crate::io::{read_options, schema_error, KeyArgs};
+use crate::ObfuscationArgs;
+
+#[derive(Debug, StructOpt)]
+pub enum TransferCommand {
+    /// Build the incomplete hex of a transfer transaction without witnesses
+    Build {
+        /// File of the builder options JSON, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+    },
+    /// Add the witness of an input to the incomplete hex
+    Sign {
+        /// File of `{ incompleteHex, feeConfig }`, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        /// Index of the input signed by the key
+        #[structopt(long)]
+        input_index: usize,
+        #[structopt(flatten)]
+        key: KeyArgs,
+    },
+    /// Obfuscate the completed incomplete hex into a broadcast-able TxAux
+    Finish {
+        /// File of `{ incompleteHex, feeConfig }`, `-` for the standard input
+        #[structopt(parse(from_os_str))]
+        options: PathBuf,
+        #[structopt(flatten)]
+        obfuscation: ObfuscationArgs,
+    },
+}
+
+impl TransferCommand {
+    pub fn execute(&self) -> Result<Value> {
+        match self {
+            TransferCommand::Build { options } => {
+                let options: BuilderOptions = read_options(options)?;
+                let raw_tx_options = options.to_raw_tx_options().map_err(schema_error)?;
+                let builder =
+                    build_incomplete(&raw_tx_options, options.fee_config.to_fee_algorithm())?;
+
+                Ok(incomplete_to_json(&builder))
+            }
+            TransferCommand::Sign {
+                options,
+                input_index,
+                key,
+            } => {
+                let options: IncompleteBuilderOptions = read_options(options)?;
+                let mut builder = options.to_builder()?;
+                sign_input(&mut builder, *input_index, &key.signer()?)?;
+
+                Ok(incomplete_to_json(&builder))
+            }
+            TransferCommand::Finish {
+                options,
+                obfuscation,
+            } => {
+                let options: IncompleteBuilderOptions = read_options(options)?;
+                let builder = options.to_builder()?;
+                let tx_aux = to_tx_aux(
+                    &builder,
+                    &obfuscation.tendermint_address,
+                    obfuscation.features,
+                )?;
+
+                Ok(json!({
+                    "txId": hex::encode(tx_aux.tx_id()),
+                    "txAuxHex": hex::encode(tx_aux.encode()),
+                }))
+            }
+        }
+    }
+}
+
+fn incomplete_to_json(builder: &FeeScheduleRawTransferTransactionBuilder) -> Value {
+    json!({
+        "txId": hex::encode(builder.tx_id()),
+        "incompleteHex": hex::encode(builder.to_incomplete()),
+    })
+}
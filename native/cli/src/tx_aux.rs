@@ -0,0 +1,213 @@
+//! Decoding and verification of broadcast-able transactions
+use std::path::PathBuf;
+
+use chain_core::tx::data::input::TxoPointer;
+use chain_core::tx::{TransactionId, TxAux, TxEnclaveAux, TxPublicAux};
+use client_common::Result;
+use cro_nodelib_core::codec::decode_transaction;
+use cro_nodelib_core::tx_assertion::{assert_tx_aux, TxAssertions};
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use structopt::StructOpt;
+
+use crate::io::{hex_argument, read_options};
+
+#[derive(Debug, StructOpt)]
+pub enum TxAuxCommand {
+    /// Decode the fields of a TxAux. The output can be edited into the
+    /// assertions of `verify`
+    Decode {
+        /// Hex encoded TxAux, read from the standard input when omitted
+        tx_aux_hex: Option<String>,
+    },
+    /// Verify the fields of a TxAux against the expected ones
+    Verify {
+        /// File of the assertions JSON, `-` for the standard input
+        #[structopt(long, parse(from_os_str))]
+        assertions: PathBuf,
+        /// Hex encoded TxAux, read from the standard input when omitted
+        tx_aux_hex: Option<String>,
+    },
+}
+
+impl TxAuxCommand {
+    pub fn execute(&self) -> Result<Value> {
+        match self {
+            TxAuxCommand::Decode { tx_aux_hex } => {
+                let tx_aux = decode_tx_aux(tx_aux_hex.as_deref())?;
+
+                Ok(describe_tx_aux(&tx_aux))
+            }
+            TxAuxCommand::Verify {
+                assertions,
+                tx_aux_hex,
+            } => {
+                let assertions: TxAssertions = read_options(assertions)?;
+                let tx_aux = decode_tx_aux(tx_aux_hex.as_deref())?;
+                let verification = assert_tx_aux(&tx_aux, &assertions)?;
+
+                let mismatches: Vec<Value> = verification
+                    .report
+                    .mismatches()
+                    .iter()
+                    .map(|mismatch| {
+                        json!({
+                            "field": mismatch.field,
+                            "expected": mismatch.expected,
+                            "actual": mismatch.actual,
+                        })
+                    })
+                    .collect();
+
+                Ok(json!({
+                    "txType": verification.tx_type,
+                    "txId": hex::encode(verification.tx_id),
+                    "valid": verification.report.is_valid(),
+                    "mismatches": mismatches,
+                    "unverifiable": verification.report.unverifiable(),
+                }))
+            }
+        }
+    }
+}
+
+/// Transaction id and hex of a broadcast-able transaction
+pub fn tx_aux_to_json(tx_aux: &TxAux) -> Value {
+    json!({
+        "txId": hex::encode(tx_aux.tx_id()),
+        "txAuxHex": hex::encode(tx_aux.encode()),
+    })
+}
+
+fn decode_tx_aux(tx_aux_hex: Option<&str>) -> Result<TxAux> {
+    let tx_aux = hex_argument(tx_aux_hex, "transaction")?;
+
+    decode_transaction(&tx_aux, "transaction")
+}
+
+/// Fields of the transaction, named after the fields of the assertions.
+/// Obfuscated fields of enclave transactions are not available.
+fn describe_tx_aux(tx_aux: &TxAux) -> Value {
+    match tx_aux {
+        TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, _)) => json!({
+            "txType": "Unbond",
+            "txId": hex::encode(tx.id()),
+            "chainHexId": chain_hex_id_to_hex(tx.attributes.chain_hex_id),
+            "stakingAddress": tx.from_staked_account.to_string(),
+            "nonce": tx.nonce.to_string(),
+            "amount": u64::from(tx.value).to_string(),
+        }),
+        TxAux::PublicTx(TxPublicAux::NodeJoinTx(tx, _)) => json!({
+            "txType": "NodeJoin",
+            "txId": hex::encode(tx.id()),
+            "chainHexId": chain_hex_id_to_hex(tx.attributes.chain_hex_id),
+            "stakingAddress": tx.address.to_string(),
+            "nonce": tx.nonce.to_string(),
+            "validatorName": tx.node_meta.name,
+            "securityContact": tx.node_meta.security_contact,
+            "consensusPublicKey": tx.node_meta.consensus_pubkey,
+        }),
+        TxAux::PublicTx(TxPublicAux::UnjailTx(tx, _)) => json!({
+            "txType": "Unjail",
+            "txId": hex::encode(tx.id()),
+            "chainHexId": chain_hex_id_to_hex(tx.attributes.chain_hex_id),
+            "stakingAddress": tx.address.to_string(),
+            "nonce": tx.nonce.to_string(),
+        }),
+        TxAux::EnclaveTx(TxEnclaveAux::TransferTx {
+            inputs,
+            no_of_outputs,
+            payload,
+        }) => json!({
+            "txType": "Transfer",
+            "txId": hex::encode(payload.txid),
+            "inputs": inputs_to_json(inputs),
+            "outputsLength": no_of_outputs,
+        }),
+        TxAux::EnclaveTx(TxEnclaveAux::DepositStakeTx { tx, .. }) => json!({
+            "txType": "Deposit",
+            "txId": hex::encode(tx.id()),
+            "chainHexId": chain_hex_id_to_hex(tx.attributes.chain_hex_id),
+            "inputs": inputs_to_json(&tx.inputs),
+            "stakingAddress": tx.to_staked_account.to_string(),
+        }),
+        TxAux::EnclaveTx(TxEnclaveAux::WithdrawUnbondedStakeTx {
+            no_of_outputs,
+            payload,
+            ..
+        }) => json!({
+            "txType": "WithdrawUnbonded",
+            "txId": hex::encode(payload.txid),
+            "outputsLength": no_of_outputs,
+        }),
+    }
+}
+
+fn chain_hex_id_to_hex(chain_hex_id: u8) -> String {
+    hex::encode([chain_hex_id])
+}
+
+fn inputs_to_json(inputs: &[TxoPointer]) -> Vec<Value> {
+    inputs
+        .iter()
+        .map(|input| {
+            json!({
+                "prevTxId": hex::encode(input.id),
+                "prevIndex": input.index,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use client_common::{PrivateKey, PublicKey};
+    use cro_nodelib_core::address::staking_address_from_public_key;
+    use cro_nodelib_core::council_node_transaction::unjail::{
+        sign_unjail_transaction, UnjailTxOptions,
+    };
+    use cro_nodelib_core::schema::from_json_value;
+    use cro_nodelib_core::signer::KeyPairSigner;
+
+    fn unjail_tx_aux() -> TxAux {
+        let private_key = PrivateKey::deserialize_from(&[1; 32]).unwrap();
+        let public_key = PublicKey::from(&private_key);
+        let tx = UnjailTxOptions {
+            staking_address: staking_address_from_public_key(&public_key),
+            nonce: 3,
+            chain_hex_id: 0x2A,
+        }
+        .to_tx();
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+
+        sign_unjail_transaction(tx, &signer).unwrap()
+    }
+
+    #[test]
+    fn decode_tx_aux_should_decode_hex_of_tx_aux() {
+        let tx_aux = unjail_tx_aux();
+        let tx_aux_hex = hex::encode(tx_aux.encode());
+
+        let decoded = decode_tx_aux(Some(&tx_aux_hex)).unwrap();
+
+        assert_eq!(decoded.tx_id(), tx_aux.tx_id());
+        assert!(decode_tx_aux(Some("00")).is_err());
+    }
+
+    #[test]
+    fn describe_tx_aux_should_be_accepted_as_assertions() {
+        let tx_aux = unjail_tx_aux();
+        let description = describe_tx_aux(&tx_aux);
+
+        assert_eq!(description["txType"], "Unjail");
+        assert_eq!(description["chainHexId"], "2a");
+        assert_eq!(description["nonce"], "3");
+
+        let assertions: TxAssertions = from_json_value(description).unwrap();
+        let verification = assert_tx_aux(&tx_aux, &assertions).unwrap();
+
+        assert!(verification.report.is_valid());
+    }
+}
@@ -0,0 +1,176 @@
+//! Transfer and staking addresses of public keys and their validation
+use std::str::FromStr;
+
+use chain_core::init::address::RedeemAddress;
+use chain_core::state::account::StakedStateAddress;
+use chain_core::tx::data::address::ExtendedAddr;
+use client_common::{MultiSigAddress, PublicKey, Result};
+
+use crate::network::{decode_transfer_address, NetworkDefinition};
+
+/// Maximum number of addresses validated in a single batch
+pub const MAX_VALIDATION_BATCH_SIZE: usize = 1_000_000;
+
+/// Returns the single signer extended address of the public key
+pub fn extended_address_from_public_key(public_key: &PublicKey) -> Result<ExtendedAddr> {
+    let required_signers = 1;
+    let multi_sig_address = MultiSigAddress::new(
+        vec![public_key.clone()],
+        public_key.clone(),
+        required_signers,
+    )?;
+
+    Ok(ExtendedAddr::from(multi_sig_address))
+}
+
+/// Returns the single signer transfer address of the public key
+pub fn transfer_address_from_public_key(
+    network: &NetworkDefinition,
+    public_key: &PublicKey,
+) -> Result<String> {
+    let extended_address = extended_address_from_public_key(public_key)?;
+
+    network.encode_transfer_address(&extended_address)
+}
+
+/// Returns the staking address of the public key
+pub fn staking_address_from_public_key(public_key: &PublicKey) -> StakedStateAddress {
+    StakedStateAddress::BasicRedeem(RedeemAddress::from(public_key))
+}
+
+/// Validation result of a single address in a batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressValidation {
+    pub address: String,
+    /// Name of the network of a valid transfer address
+    pub network: Option<String>,
+    pub error: Option<String>,
+}
+
+impl AddressValidation {
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Transfer,
+    Staking,
+}
+
+/// Validate each address of the batch. Transfer addresses are validated
+/// against `network` when provided, otherwise their network is detected
+/// from the address prefix.
+pub fn validate_addresses(
+    kind: AddressKind,
+    addresses: &[String],
+    network: Option<&NetworkDefinition>,
+) -> Vec<AddressValidation> {
+    addresses
+        .iter()
+        .map(|address| {
+            let result = match kind {
+                AddressKind::Transfer => validate_transfer_address(address, network),
+                AddressKind::Staking => StakedStateAddress::from_str(address)
+                    .map(|_| None)
+                    .map_err(|err| err.to_string()),
+            };
+
+            match result {
+                Ok(network) => AddressValidation {
+                    address: address.to_owned(),
+                    network,
+                    error: None,
+                },
+                Err(error) => AddressValidation {
+                    address: address.to_owned(),
+                    network: None,
+                    error: Some(error),
+                },
+            }
+        })
+        .collect()
+}
+
+fn validate_transfer_address(
+    address: &str,
+    network: Option<&NetworkDefinition>,
+) -> std::result::Result<Option<String>, String> {
+    let network = match network {
+        Some(network) => network
+            .decode_transfer_address(address)
+            .map(|_| network.name.clone()),
+        None => decode_transfer_address(address).map(|(network, _)| network.name),
+    };
+
+    network.map(Some).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::network::{network_by_name, MAINNET, TESTNET};
+
+    const MAINNET_ADDRESS: &str = "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4";
+    const STAKING_ADDRESS: &str = "0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0";
+
+    fn public_key(hex_public_key: &str) -> PublicKey {
+        PublicKey::deserialize_from(&hex::decode(hex_public_key).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn transfer_address_from_public_key_should_encode_network_address() {
+        let public_key = public_key("043f1d17afa4b881bfdbcee2d82c0f278f09b433b0ddb14ca93fb7cb8d1e16b4b74d9f83587966a9c20243b75b828535b180557302bfd68a0cb37c3fd906b456e8");
+        let mainnet = network_by_name(MAINNET).unwrap();
+
+        assert_eq!(
+            transfer_address_from_public_key(&mainnet, &public_key).unwrap(),
+            MAINNET_ADDRESS
+        );
+    }
+
+    #[test]
+    fn staking_address_from_public_key_should_return_redeem_address() {
+        let public_key = public_key("0492c14d055927997160e7db0842f1e58e6b5891871320a2df7082b931c3cf875a83ec3c22909c862c788b69988a89ed32e2d4819996020d8ebcddbe040da1a850");
+
+        assert_eq!(
+            staking_address_from_public_key(&public_key).to_string(),
+            STAKING_ADDRESS
+        );
+    }
+
+    #[test]
+    fn validate_addresses_should_detect_transfer_address_network() {
+        let addresses = vec![MAINNET_ADDRESS.to_owned(), "cro1invalid".to_owned()];
+
+        let validations = validate_addresses(AddressKind::Transfer, &addresses, None);
+
+        assert!(validations[0].is_valid());
+        assert_eq!(validations[0].network, Some(MAINNET.to_owned()));
+        assert!(!validations[1].is_valid());
+        assert_eq!(validations[1].network, None);
+    }
+
+    #[test]
+    fn validate_addresses_should_validate_against_network() {
+        let testnet = network_by_name(TESTNET).unwrap();
+        let addresses = vec![MAINNET_ADDRESS.to_owned()];
+
+        let validations = validate_addresses(AddressKind::Transfer, &addresses, Some(&testnet));
+
+        assert!(!validations[0].is_valid());
+    }
+
+    #[test]
+    fn validate_addresses_should_validate_staking_address() {
+        let addresses = vec![STAKING_ADDRESS.to_owned(), "0xb5698e".to_owned()];
+
+        let validations = validate_addresses(AddressKind::Staking, &addresses, None);
+
+        assert!(validations[0].is_valid());
+        assert_eq!(validations[0].network, None);
+        assert!(!validations[1].is_valid());
+    }
+}
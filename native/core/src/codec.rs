@@ -0,0 +1,165 @@
+//! Strict decoding of the SCALE encoded buffers handed over by callers
+//!
+//! Raw transactions and witnesses often come from other parties, e.g. the
+//! co-signers of a transfer. `Decode::decode` alone accepts trailing bytes
+//! and non-canonical encodings, and has no bound on the size of the buffer.
+//! A buffer is decoded here only when it is within the maximum size, is
+//! consumed entirely and encodes back to the same bytes.
+use client_common::{Error, ErrorKind, Result};
+use parity_scale_codec::{Decode, Encode};
+
+/// Maximum size of raw transactions, `TxAux` and incomplete transfer
+/// transactions
+pub const MAX_TRANSACTION_SIZE: usize = 60 * 1024;
+
+/// Maximum size of input and staked state operation witnesses
+pub const MAX_WITNESS_SIZE: usize = 4 * 1024;
+
+/// Decode the raw transaction, `TxAux` or incomplete transaction bytes
+pub fn decode_transaction<T>(bytes: &[u8], name: &str) -> Result<T>
+where
+    T: Decode + Encode,
+{
+    decode_strict(bytes, MAX_TRANSACTION_SIZE, name)
+}
+
+/// Decode the witness bytes
+pub fn decode_witness<T>(bytes: &[u8], name: &str) -> Result<T>
+where
+    T: Decode + Encode,
+{
+    decode_strict(bytes, MAX_WITNESS_SIZE, name)
+}
+
+/// Decode the bytes of at most `max_size` into exactly one canonically
+/// encoded value
+pub fn decode_strict<T>(bytes: &[u8], max_size: usize, name: &str) -> Result<T>
+where
+    T: Decode + Encode,
+{
+    if bytes.len() > max_size {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Size of {} is {} bytes, exceeding the maximum of {} bytes",
+                name,
+                bytes.len(),
+                max_size
+            ),
+        ));
+    }
+
+    let mut input = bytes;
+    let value = T::decode(&mut input).map_err(|err| {
+        Error::new(
+            ErrorKind::DeserializationError,
+            format!("Unable to decode {}: {}", name, err),
+        )
+    })?;
+    if !input.is_empty() {
+        return Err(Error::new(
+            ErrorKind::DeserializationError,
+            format!("Unexpected trailing bytes after {}", name),
+        ));
+    }
+    if value.encode() != bytes {
+        return Err(Error::new(
+            ErrorKind::DeserializationError,
+            format!("Non-canonical encoding of {}", name),
+        ));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::tx::witness::TxInWitness;
+    use parity_scale_codec::{Input, Output};
+
+    /// Flag decoded from any non-zero byte but encoded as 1, so that only
+    /// the re-encoding check rejects it
+    #[derive(Debug)]
+    struct LenientFlag(bool);
+
+    impl Encode for LenientFlag {
+        fn encode_to<W: Output>(&self, dest: &mut W) {
+            dest.push_byte(self.0 as u8);
+        }
+    }
+
+    impl Decode for LenientFlag {
+        fn decode<I: Input>(input: &mut I) -> std::result::Result<Self, parity_scale_codec::Error> {
+            Ok(LenientFlag(input.read_byte()? != 0))
+        }
+    }
+
+    #[test]
+    fn decode_strict_should_decode_canonical_bytes() {
+        let bytes = vec![1u8, 2, 3].encode();
+
+        assert_eq!(
+            decode_strict::<Vec<u8>>(&bytes, 16, "bytes").unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(decode_strict::<LenientFlag>(&[1], 16, "flag").unwrap().0);
+    }
+
+    #[test]
+    fn decode_strict_should_reject_trailing_bytes() {
+        let mut bytes = 42u64.encode();
+        bytes.push(0);
+
+        let err = decode_strict::<u64>(&bytes, 16, "value").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::DeserializationError);
+        assert!(err
+            .to_string()
+            .contains("Unexpected trailing bytes after value"));
+    }
+
+    #[test]
+    fn decode_strict_should_reject_oversized_bytes() {
+        let bytes = vec![0; MAX_WITNESS_SIZE + 1];
+
+        let err = decode_witness::<TxInWitness>(&bytes, "witness").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err
+            .to_string()
+            .contains("Size of witness is 4097 bytes, exceeding the maximum of 4096 bytes"));
+    }
+
+    #[test]
+    fn decode_strict_should_reject_non_canonical_encoding() {
+        let err = decode_strict::<LenientFlag>(&[2], 16, "flag").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::DeserializationError);
+        assert!(err.to_string().contains("Non-canonical encoding of flag"));
+    }
+
+    #[test]
+    fn decode_strict_should_reject_non_canonical_compact_length() {
+        // Length 1 in the two bytes compact mode instead of the single byte
+        // mode is already rejected by the codec
+        let bytes = [0x05, 0x00, 0x01];
+
+        let err = decode_strict::<Vec<u8>>(&bytes, 16, "bytes").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::DeserializationError);
+        assert!(err.to_string().contains("Unable to decode bytes: "));
+    }
+
+    #[test]
+    fn decode_strict_should_reject_length_beyond_buffer() {
+        // Vector claiming 2^30 - 1 elements with a single byte
+        let bytes = [0xfe, 0xff, 0xff, 0xff, 0x01];
+
+        let err = decode_strict::<Vec<u8>>(&bytes, 16, "bytes").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::DeserializationError);
+        assert!(err.to_string().contains("Unable to decode bytes: "));
+    }
+}
@@ -0,0 +1,168 @@
+//! Exact conversion and arithmetic of Coin amounts
+//!
+//! These helpers convert base unit amounts to and from CRO decimal strings
+//! without floating point and add or subtract them with the Coin supply
+//! bound checks.
+use std::str::FromStr;
+
+use chain_core::init::coin::{Coin, CoinError};
+use client_common::{Error, ErrorKind, Result};
+
+/// Number of decimal places of one CRO in base unit
+const CRO_DECIMAL_PLACES: usize = 8;
+/// Number of base units in one CRO
+const CRO_DECIMAL: u64 = 100_000_000;
+
+/// Format base unit amount as CRO decimal string with trailing zeros of the
+/// fractional part trimmed
+pub fn coin_to_cro(coin: Coin) -> String {
+    let base_unit = u64::from(coin);
+    let integer = base_unit / CRO_DECIMAL;
+    let fraction = base_unit % CRO_DECIMAL;
+    if fraction == 0 {
+        return integer.to_string();
+    }
+
+    let fraction = format!("{:0width$}", fraction, width = CRO_DECIMAL_PLACES);
+    format!("{}.{}", integer, fraction.trim_end_matches('0'))
+}
+
+/// Parse CRO decimal string into base unit amount. Rejects signs, exponents
+/// and more decimal places than the base unit can represent.
+pub fn coin_from_cro(cro: &str) -> Result<Coin> {
+    let (integer, fraction) = match cro.find('.') {
+        Some(index) => (&cro[..index], &cro[index + 1..]),
+        None => (cro, ""),
+    };
+    let is_decimal = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_decimal(integer) || (cro.contains('.') && !is_decimal(fraction)) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid CRO decimal string: {}", cro),
+        ));
+    }
+    // Trailing zeros do not add precision
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > CRO_DECIMAL_PLACES {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "CRO amount {} has more than {} decimal places",
+                cro, CRO_DECIMAL_PLACES
+            ),
+        ));
+    }
+
+    let out_of_bound = || {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("CRO amount {} exceeds maximum coin supply", cro),
+        )
+    };
+    let integer = u128::from_str(integer).map_err(|_| out_of_bound())?;
+    let fraction = format!("{:0<width$}", fraction, width = CRO_DECIMAL_PLACES);
+    let fraction = u128::from_str(&fraction).expect("Fraction is validated to be decimal digits");
+
+    let base_unit = integer
+        .checked_mul(u128::from(CRO_DECIMAL))
+        .and_then(|base_unit| base_unit.checked_add(fraction))
+        .ok_or_else(out_of_bound)?;
+    if base_unit > u128::from(u64::max_value()) {
+        return Err(out_of_bound());
+    }
+
+    Coin::new(base_unit as u64).map_err(|_| out_of_bound())
+}
+
+/// Add two amounts, failing when the sum exceeds the maximum coin supply
+pub fn add_coins(augend: Coin, addend: Coin) -> Result<Coin> {
+    (augend + addend).map_err(|err| coin_arithmetic_error("addition", err))
+}
+
+/// Subtract two amounts, failing when the difference is negative
+pub fn sub_coins(minuend: Coin, subtrahend: Coin) -> Result<Coin> {
+    (minuend - subtrahend).map_err(|err| coin_arithmetic_error("subtraction", err))
+}
+
+fn coin_arithmetic_error(operation: &str, err: CoinError) -> Error {
+    let message = match err {
+        CoinError::Negative => format!("Coin {} result is negative", operation),
+        CoinError::OutOfBound(_) => {
+            format!("Coin {} result exceeds maximum coin supply", operation)
+        }
+        err => format!("Coin {} failed: {}", operation, err),
+    };
+
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::init::coin::max_coin;
+
+    fn coin(base_unit: u64) -> Coin {
+        Coin::new(base_unit).unwrap()
+    }
+
+    #[test]
+    fn coin_to_cro_should_trim_trailing_zeros() {
+        assert_eq!(coin_to_cro(coin(150_000_000)), "1.5");
+        assert_eq!(coin_to_cro(coin(1)), "0.00000001");
+        assert_eq!(coin_to_cro(Coin::zero()), "0");
+        assert_eq!(coin_to_cro(max_coin()), "100000000000");
+    }
+
+    #[test]
+    fn coin_from_cro_should_return_base_unit_amount() {
+        assert_eq!(coin_from_cro("1.5").unwrap(), coin(150_000_000));
+        assert_eq!(coin_from_cro("0.1").unwrap(), coin(10_000_000));
+        assert_eq!(coin_from_cro("1.500000000").unwrap(), coin(150_000_000));
+        assert_eq!(coin_from_cro("100000000000").unwrap(), max_coin());
+    }
+
+    #[test]
+    fn coin_from_cro_should_reject_invalid_decimal() {
+        for cro in &["1e5", "-1", "+1", "", ".5", "1.", "1.2.3", " 1"] {
+            let err = coin_from_cro(cro).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", cro);
+        }
+    }
+
+    #[test]
+    fn coin_from_cro_should_reject_excess_precision() {
+        let err = coin_from_cro("0.000000001").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("CRO amount 0.000000001 has more than 8 decimal places"));
+    }
+
+    #[test]
+    fn coin_from_cro_should_reject_amount_over_maximum_coin_supply() {
+        for cro in &["100000000000.00000001", "99999999999999999999999999"] {
+            let err = coin_from_cro(cro).unwrap_err();
+            assert!(err.to_string().contains("exceeds maximum coin supply"));
+        }
+    }
+
+    #[test]
+    fn add_coins_should_check_maximum_coin_supply() {
+        assert_eq!(add_coins(coin(1), coin(2)).unwrap(), coin(3));
+
+        let err = add_coins(max_coin(), coin(1)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Coin addition result exceeds maximum coin supply"));
+    }
+
+    #[test]
+    fn sub_coins_should_reject_negative_difference() {
+        assert_eq!(sub_coins(coin(3), coin(2)).unwrap(), coin(1));
+
+        let err = sub_coins(coin(1), coin(2)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Coin subtraction result is negative"));
+    }
+}
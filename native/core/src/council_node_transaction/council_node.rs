@@ -0,0 +1,132 @@
+use chain_core::state::account::{ConfidentialInit, CouncilNode};
+use chain_core::state::tendermint::TendermintValidatorPubKey;
+use serde::Deserialize;
+
+use crate::schema::{de, SchemaError, SchemaResult};
+use crate::validator_key::{consensus_public_key_from_base64, ValidatorKey};
+
+/// Maximum length of the validator name in bytes
+const MAX_VALIDATOR_NAME_LENGTH: usize = 64;
+/// Maximum length of the validator security contact in bytes
+const MAX_SECURITY_CONTACT_LENGTH: usize = 64;
+
+/// Typed council node fields of a node join transaction
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CouncilNodeOptions {
+    name: String,
+    #[serde(default)]
+    security_contact: Option<String>,
+    /// Base64 encoded Tendermint ed25519 consensus public key
+    #[serde(default)]
+    consensus_public_key: Option<String>,
+    /// Content of Tendermint `priv_validator_key.json`
+    #[serde(default)]
+    priv_validator_key: Option<String>,
+    confidential_init: ConfidentialInitOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfidentialInitOptions {
+    #[serde(deserialize_with = "de::buffer")]
+    cert: Vec<u8>,
+}
+
+impl CouncilNodeOptions {
+    /// Validate each field and convert to `CouncilNode`. `path` is the
+    /// location of the council node in the options object.
+    pub fn to_council_node(&self, path: &str) -> SchemaResult<CouncilNode> {
+        let name = self.validated_name(path)?;
+        let security_contact = self.validated_security_contact(path)?;
+        let consensus_pubkey = self.consensus_pubkey(path)?;
+
+        if self.confidential_init.cert.is_empty() {
+            return Err(SchemaError::new(
+                format!("{}.confidentialInit.cert", path),
+                "Confidential init certificate should not be empty",
+            ));
+        }
+
+        Ok(CouncilNode {
+            name,
+            security_contact,
+            consensus_pubkey,
+            confidential_init: ConfidentialInit {
+                cert: self.confidential_init.cert.clone(),
+            },
+        })
+    }
+
+    fn validated_name(&self, path: &str) -> SchemaResult<String> {
+        let path = format!("{}.name", path);
+
+        if self.name.trim().is_empty() {
+            return Err(SchemaError::new(path, "Validator name should not be empty"));
+        }
+        if self.name.trim() != self.name {
+            return Err(SchemaError::new(
+                path,
+                "Validator name should not have leading or trailing whitespaces",
+            ));
+        }
+        if self.name.len() > MAX_VALIDATOR_NAME_LENGTH {
+            return Err(SchemaError::new(
+                path,
+                format!(
+                    "Validator name should be at most {} bytes long",
+                    MAX_VALIDATOR_NAME_LENGTH
+                ),
+            ));
+        }
+
+        Ok(self.name.clone())
+    }
+
+    fn validated_security_contact(&self, path: &str) -> SchemaResult<Option<String>> {
+        let security_contact = match &self.security_contact {
+            None => return Ok(None),
+            Some(security_contact) => security_contact,
+        };
+        let path = format!("{}.securityContact", path);
+
+        if security_contact.trim().is_empty() {
+            return Err(SchemaError::new(
+                path,
+                "Security contact should not be empty when provided",
+            ));
+        }
+        if security_contact.len() > MAX_SECURITY_CONTACT_LENGTH {
+            return Err(SchemaError::new(
+                path,
+                format!(
+                    "Security contact should be at most {} bytes long",
+                    MAX_SECURITY_CONTACT_LENGTH
+                ),
+            ));
+        }
+
+        Ok(Some(security_contact.clone()))
+    }
+
+    fn consensus_pubkey(&self, path: &str) -> SchemaResult<TendermintValidatorPubKey> {
+        match (&self.consensus_public_key, &self.priv_validator_key) {
+            (Some(consensus_public_key), None) => {
+                consensus_public_key_from_base64(consensus_public_key).map_err(|err| {
+                    SchemaError::new(format!("{}.consensusPublicKey", path), err.to_string())
+                })
+            }
+            (None, Some(priv_validator_key)) => {
+                ValidatorKey::from_priv_validator_key_json(priv_validator_key)
+                    .map(|key| key.consensus_public_key())
+                    .map_err(|err| {
+                        SchemaError::new(format!("{}.privValidatorKey", path), err.to_string())
+                    })
+            }
+            _ => Err(SchemaError::new(
+                path,
+                "Either consensusPublicKey or privValidatorKey should be provided",
+            )),
+        }
+    }
+}
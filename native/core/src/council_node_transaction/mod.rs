@@ -0,0 +1,6 @@
+//! Node join and unjail transactions of council nodes
+mod council_node;
+pub mod node_join;
+pub mod unjail;
+
+pub use council_node::CouncilNodeOptions;
@@ -0,0 +1,165 @@
+use serde::de::{Deserializer, Error};
+use serde::Deserialize;
+
+use chain_core::state::account::{CouncilNode, Nonce, StakedStateAddress, StakedStateOpAttributes};
+use chain_core::state::validator::NodeJoinRequestTx;
+use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
+use client_common::Result;
+
+use crate::schema::{de, SchemaError, SchemaResult};
+use crate::signer::KeyPairSigner;
+use crate::staking_transaction::staked_state_op_witness;
+
+use super::CouncilNodeOptions;
+
+/// Options of a node join transaction
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildNodeJoinTransactionOptions {
+    #[serde(deserialize_with = "de::staking_address")]
+    pub staking_address: StakedStateAddress,
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    #[serde(
+        rename = "nodeMetaData",
+        default,
+        deserialize_with = "optional_council_node"
+    )]
+    pub node_meta_data: Option<CouncilNode>,
+    #[serde(default)]
+    pub council_node: Option<CouncilNodeOptions>,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+}
+
+impl BuildNodeJoinTransactionOptions {
+    /// Returns the council node from either the typed council node fields or
+    /// the node metadata JSON string
+    pub fn council_node(&self) -> SchemaResult<CouncilNode> {
+        match (&self.node_meta_data, &self.council_node) {
+            (Some(node_meta_data), None) => Ok(node_meta_data.clone()),
+            (None, Some(council_node)) => council_node.to_council_node("councilNode"),
+            _ => Err(SchemaError::new(
+                "",
+                "Either nodeMetaData or councilNode should be provided",
+            )),
+        }
+    }
+
+    /// Returns the unsigned node join transaction of the council node
+    pub fn to_tx(&self, council_node: CouncilNode) -> NodeJoinRequestTx {
+        let attributes = StakedStateOpAttributes::new(self.chain_hex_id);
+
+        NodeJoinRequestTx::new(self.nonce, self.staking_address, attributes, council_node)
+    }
+}
+
+/// Optional council node metadata JSON string
+fn optional_council_node<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<CouncilNode>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let council_node = match Option::<String>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(council_node) => council_node,
+    };
+
+    serde_json::from_str::<CouncilNode>(&council_node)
+        .map(Some)
+        .map_err(|err| D::Error::custom(format!("Invalid council node: {}", err)))
+}
+
+/// Sign the node join transaction into a broadcast-able `TxAux`
+pub fn sign_node_join_transaction(tx: NodeJoinRequestTx, signer: &KeyPairSigner) -> Result<TxAux> {
+    let witness = staked_state_op_witness(signer, &tx.id())?;
+
+    Ok(TxAux::PublicTx(TxPublicAux::NodeJoinTx(tx, witness)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    use crate::schema::from_json_value;
+
+    const STAKING_ADDRESS: &str = "0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0";
+    const CONSENSUS_PUBLIC_KEY: &str = "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=";
+
+    fn options(council_node: Value) -> BuildNodeJoinTransactionOptions {
+        from_json_value(json!({
+            "stakingAddress": STAKING_ADDRESS,
+            "nonce": "0",
+            "councilNode": council_node,
+            "chainHexId": [0x2A],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn council_node_should_convert_typed_fields() {
+        let options = options(json!({
+            "name": "validator",
+            "consensusPublicKey": CONSENSUS_PUBLIC_KEY,
+            "confidentialInit": { "cert": [1, 2, 3] },
+        }));
+
+        let council_node = options.council_node().unwrap();
+
+        assert_eq!(council_node.name, "validator");
+        assert_eq!(council_node.security_contact, None);
+        assert_eq!(council_node.confidential_init.cert, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn council_node_should_report_path_of_invalid_field() {
+        let invalid_name = options(json!({
+            "name": " validator",
+            "consensusPublicKey": CONSENSUS_PUBLIC_KEY,
+            "confidentialInit": { "cert": [1] },
+        }));
+        let empty_cert = options(json!({
+            "name": "validator",
+            "consensusPublicKey": CONSENSUS_PUBLIC_KEY,
+            "confidentialInit": { "cert": [] },
+        }));
+        let missing_key = options(json!({
+            "name": "validator",
+            "confidentialInit": { "cert": [1] },
+        }));
+
+        assert!(invalid_name
+            .council_node()
+            .unwrap_err()
+            .to_string()
+            .contains("`councilNode.name`"));
+        assert!(empty_cert
+            .council_node()
+            .unwrap_err()
+            .to_string()
+            .contains("`councilNode.confidentialInit.cert`"));
+        assert!(missing_key
+            .council_node()
+            .unwrap_err()
+            .to_string()
+            .contains("`councilNode`"));
+    }
+
+    #[test]
+    fn options_should_report_nonce_error_at_nonce() {
+        let err = from_json_value::<BuildNodeJoinTransactionOptions>(json!({
+            "stakingAddress": STAKING_ADDRESS,
+            "nonce": "-1",
+            "chainHexId": [0x2A],
+        }))
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Invalid options at `nonce`: Invalid nonce: "));
+        assert!(!err.to_string().contains("stakingAddress"));
+    }
+}
@@ -0,0 +1,164 @@
+use serde::Deserialize;
+
+use chain_core::state::account::{
+    Nonce, StakedStateAddress, StakedStateOpAttributes, StakedStateOpWitness, UnjailTx,
+};
+use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
+use chain_tx_validation::witness::verify_tx_recover_address;
+use client_common::{Error, ErrorKind, Result, ResultExt};
+
+use crate::schema::de;
+use crate::signer::KeyPairSigner;
+use crate::staking_transaction::staked_state_op_witness;
+
+/// Fields of an unjail transaction
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnjailTxOptions {
+    #[serde(deserialize_with = "de::staking_address")]
+    pub staking_address: StakedStateAddress,
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+}
+
+impl UnjailTxOptions {
+    /// Returns the unsigned unjail transaction
+    pub fn to_tx(&self) -> UnjailTx {
+        let attributes = StakedStateOpAttributes::new(self.chain_hex_id);
+
+        UnjailTx::new(self.nonce, self.staking_address, attributes)
+    }
+}
+
+/// Sign the unjail transaction into a broadcast-able `TxAux`
+pub fn sign_unjail_transaction(tx: UnjailTx, signer: &KeyPairSigner) -> Result<TxAux> {
+    let witness = staked_state_op_witness(signer, &tx.id())?;
+
+    Ok(TxAux::PublicTx(TxPublicAux::UnjailTx(tx, witness)))
+}
+
+/// Returns the unjail transaction and its witness of the `TxAux`
+pub fn unjail_tx_of_tx_aux(tx_aux: TxAux) -> Result<(UnjailTx, StakedStateOpWitness)> {
+    match tx_aux {
+        TxAux::PublicTx(TxPublicAux::UnjailTx(unjail_tx, staked_state_op_witness)) => {
+            Ok((unjail_tx, staked_state_op_witness))
+        }
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Transaction is not an Unjail transaction",
+        )),
+    }
+}
+
+/// Verify the signed unjail transaction has the asserted fields and is
+/// signed by the staking address
+pub fn verify_unjail_transaction(
+    unjail_tx: &UnjailTx,
+    witness: &StakedStateOpWitness,
+    assertions: &UnjailTxOptions,
+) -> Result<()> {
+    if unjail_tx.address != assertions.staking_address {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Mismatch staking address",
+        ));
+    }
+    if unjail_tx.nonce != assertions.nonce {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Mismatch staking account nonce",
+        ));
+    }
+    if unjail_tx.attributes.chain_hex_id != assertions.chain_hex_id {
+        return Err(Error::new(ErrorKind::InvalidInput, "Mismatch chain hex id"));
+    }
+
+    let address = verify_tx_recover_address(witness, &unjail_tx.id())
+        .chain(|| (ErrorKind::InvalidInput, "Invalid signature"))?;
+    if address != assertions.staking_address {
+        return Err(Error::new(ErrorKind::InvalidInput, "Incorrect signature"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::init::coin::Coin;
+    use chain_core::state::account::UnbondTx;
+
+    use crate::address::staking_address_from_public_key;
+    use crate::staking_transaction::unbond::sign_unbond_transaction;
+    use crate::transfer_transaction::tests::key_pair;
+
+    fn options(secret: u8) -> UnjailTxOptions {
+        UnjailTxOptions {
+            staking_address: staking_address_from_public_key(&key_pair(secret).1),
+            nonce: 3,
+            chain_hex_id: 0x2A,
+        }
+    }
+
+    fn signed_unjail_tx(secret: u8) -> (UnjailTx, StakedStateOpWitness) {
+        let (private_key, public_key) = key_pair(secret);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        let tx_aux = sign_unjail_transaction(options(1).to_tx(), &signer).unwrap();
+
+        unjail_tx_of_tx_aux(tx_aux).unwrap()
+    }
+
+    #[test]
+    fn verify_unjail_transaction_should_accept_transaction_signed_by_staking_address() {
+        let (unjail_tx, witness) = signed_unjail_tx(1);
+
+        verify_unjail_transaction(&unjail_tx, &witness, &options(1)).unwrap();
+    }
+
+    #[test]
+    fn verify_unjail_transaction_should_reject_mismatched_fields() {
+        let (unjail_tx, witness) = signed_unjail_tx(1);
+        let mut other_nonce = options(1);
+        other_nonce.nonce = 4;
+        let mut other_chain = options(1);
+        other_chain.chain_hex_id = 0x42;
+
+        let error_of = |assertions: &UnjailTxOptions| {
+            verify_unjail_transaction(&unjail_tx, &witness, assertions)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(error_of(&options(2)).contains("Mismatch staking address"));
+        assert!(error_of(&other_nonce).contains("Mismatch staking account nonce"));
+        assert!(error_of(&other_chain).contains("Mismatch chain hex id"));
+    }
+
+    #[test]
+    fn verify_unjail_transaction_should_reject_signature_of_other_key() {
+        let (unjail_tx, witness) = signed_unjail_tx(2);
+
+        assert!(verify_unjail_transaction(&unjail_tx, &witness, &options(1))
+            .unwrap_err()
+            .to_string()
+            .contains("Incorrect signature"));
+    }
+
+    #[test]
+    fn unjail_tx_of_tx_aux_should_reject_other_transaction() {
+        let (private_key, public_key) = key_pair(1);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        let unbond_tx = UnbondTx::new(
+            options(1).staking_address,
+            0,
+            Coin::one(),
+            StakedStateOpAttributes::new(0x2A),
+        );
+        let tx_aux = sign_unbond_transaction(unbond_tx, &signer).unwrap();
+
+        assert!(unjail_tx_of_tx_aux(tx_aux).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+//! Fee algorithms configurable from the JS side
+use chain_core::init::coin::{Coin, CoinError};
+use chain_core::tx::fee::{Fee, FeeAlgorithm, LinearFee};
+use chain_core::tx::TxAux;
+use parity_scale_codec::Encode;
+
+/// Linear fee charged for transactions up to `max_size` bytes
+#[derive(Debug, Clone)]
+pub struct FeeTier {
+    /// Inclusive upper bound of the transaction size, `None` for the last
+    /// tier which has no bound
+    pub max_size: Option<usize>,
+    pub linear_fee: LinearFee,
+}
+
+/// Size-tiered linear fee with a minimum fee floor. A plain linear fee is
+/// a schedule of a single unbounded tier.
+#[derive(Debug, Clone)]
+pub struct FeeSchedule {
+    tiers: Vec<FeeTier>,
+    minimum_fee: Coin,
+}
+
+impl FeeSchedule {
+    /// Create a fee schedule from tiers sorted by size, the last of which
+    /// should have no bound
+    pub fn new(tiers: Vec<FeeTier>, minimum_fee: Option<Coin>) -> Self {
+        FeeSchedule {
+            tiers,
+            minimum_fee: minimum_fee.unwrap_or_else(Coin::zero),
+        }
+    }
+
+    /// Create a fee schedule of a single linear fee
+    pub fn linear(linear_fee: LinearFee, minimum_fee: Option<Coin>) -> Self {
+        FeeSchedule::new(
+            vec![FeeTier {
+                max_size: None,
+                linear_fee,
+            }],
+            minimum_fee,
+        )
+    }
+
+    fn tier_of_size(&self, num_bytes: usize) -> Option<&FeeTier> {
+        self.tiers.iter().find(|tier| match tier.max_size {
+            Some(max_size) => num_bytes <= max_size,
+            None => true,
+        })
+    }
+}
+
+impl FeeAlgorithm for FeeSchedule {
+    fn calculate_fee(&self, num_bytes: usize) -> Result<Fee, CoinError> {
+        let tier = self
+            .tier_of_size(num_bytes)
+            .ok_or(CoinError::OutOfBound(num_bytes as u64))?;
+        let fee = tier.linear_fee.calculate_fee(num_bytes)?.to_coin();
+
+        Ok(Fee::new(std::cmp::max(fee, self.minimum_fee)))
+    }
+
+    fn calculate_for_txaux(&self, txaux: &TxAux) -> Result<Fee, CoinError> {
+        self.calculate_fee(txaux.encode().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+
+    use chain_core::tx::fee::Milli;
+
+    fn linear_fee(constant: &str, coefficient: &str) -> LinearFee {
+        LinearFee::new(
+            Milli::from_str(constant).unwrap(),
+            Milli::from_str(coefficient).unwrap(),
+        )
+    }
+
+    fn fee_of_size(fee_algorithm: &FeeSchedule, num_bytes: usize) -> u64 {
+        u64::from(fee_algorithm.calculate_fee(num_bytes).unwrap().to_coin())
+    }
+
+    #[test]
+    fn linear_schedule_should_charge_linear_fee() {
+        let fee_algorithm = FeeSchedule::linear(linear_fee("1.1", "1.25"), None);
+
+        assert_eq!(
+            fee_of_size(&fee_algorithm, 100),
+            u64::from(
+                linear_fee("1.1", "1.25")
+                    .calculate_fee(100)
+                    .unwrap()
+                    .to_coin()
+            )
+        );
+    }
+
+    #[test]
+    fn schedule_should_charge_at_least_minimum_fee() {
+        let fee_algorithm =
+            FeeSchedule::linear(linear_fee("0", "0"), Some(Coin::new(1000).unwrap()));
+
+        assert_eq!(fee_of_size(&fee_algorithm, 100), 1000);
+    }
+
+    #[test]
+    fn schedule_should_charge_fee_of_the_size_tier() {
+        let fee_algorithm = FeeSchedule::new(
+            vec![
+                FeeTier {
+                    max_size: Some(100),
+                    linear_fee: linear_fee("10", "0"),
+                },
+                FeeTier {
+                    max_size: None,
+                    linear_fee: linear_fee("20", "0"),
+                },
+            ],
+            None,
+        );
+
+        assert_eq!(fee_of_size(&fee_algorithm, 100), 10);
+        assert_eq!(fee_of_size(&fee_algorithm, 101), 20);
+    }
+
+    #[test]
+    fn schedule_should_reject_size_over_every_tier() {
+        let fee_algorithm = FeeSchedule::new(
+            vec![FeeTier {
+                max_size: Some(100),
+                linear_fee: linear_fee("10", "0"),
+            }],
+            None,
+        );
+
+        assert!(fee_algorithm.calculate_fee(101).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+//! Witnesses and transaction envelopes of the same encoded size as the real
+//! ones, used to estimate fees before a transaction is signed
+use chain_core::state::account::{DepositBondTx, StakedStateOpWitness};
+use chain_core::state::tendermint::BlockHeight;
+use chain_core::tx::data::input::TxoSize;
+use chain_core::tx::data::{Tx, TxId};
+use chain_core::tx::witness::{TxInWitness, TxWitness};
+use chain_core::tx::{PlainTxAux, TransactionId, TxAux, TxEnclaveAux, TxObfuscated};
+use client_common::{Error, ErrorKind, MultiSigAddress, PrivateKey, PublicKey, Result, ResultExt};
+use parity_scale_codec::Encode;
+
+use crate::schema::types::AddressParamsOptions;
+use crate::signer::KeyPairSigner;
+
+/// Size of the authentication tag appended to the encrypted payload
+const ENCRYPTION_TAG_SIZE: usize = 16;
+
+/// Create a witness of the same size as the one of an input owned by an
+/// address of the provided signers
+pub fn mock_tx_in_witness(
+    message: &TxId,
+    address_params: &AddressParamsOptions,
+) -> Result<TxInWitness> {
+    address_params
+        .verify("addressParams")
+        .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))?;
+    let public_keys = (0..address_params.total_signers)
+        .map(|i| mock_private_key(i).map(|private_key| PublicKey::from(&private_key)))
+        .collect::<Result<Vec<PublicKey>>>()?;
+    let required_signers = address_params.required_signers as usize;
+
+    let multi_sig_address = MultiSigAddress::new(
+        public_keys.clone(),
+        public_keys[0].clone(),
+        required_signers,
+    )?;
+    let proof = multi_sig_address
+        .generate_proof(public_keys[..required_signers].to_vec())?
+        .chain(|| (ErrorKind::InvalidInput, "Unable to generate merkle proof"))?;
+
+    let signer = KeyPairSigner::new(mock_private_key(0)?, public_keys[0].clone())?;
+    let signature = signer.schnorr_sign(message)?;
+
+    Ok(TxInWitness::TreeSig(signature, proof))
+}
+
+/// Create a staked state operation witness of the same size as the one
+/// signed by the staking address
+pub fn mock_staked_state_op_witness(message: &TxId) -> Result<StakedStateOpWitness> {
+    let private_key = mock_private_key(0)?;
+    let public_key = PublicKey::from(&private_key);
+
+    let signer = KeyPairSigner::new(private_key, public_key)?;
+
+    signer.sign(message).map(StakedStateOpWitness::new)
+}
+
+fn mock_private_key(index: u64) -> Result<PrivateKey> {
+    let mut private_key = [0u8; 32];
+    private_key[0] = 1;
+    private_key[24..].copy_from_slice(&index.to_be_bytes());
+
+    PrivateKey::deserialize_from(&private_key)
+}
+
+/// Create a transfer `TxAux` whose obfuscated payload has the same size as
+/// the encrypted one
+pub fn mock_transfer_tx_aux(tx: &Tx, witness: TxWitness) -> TxAux {
+    let mut txpayload = PlainTxAux::TransferTx(tx.clone(), witness).encode();
+    txpayload.extend_from_slice(&[0u8; ENCRYPTION_TAG_SIZE]);
+
+    TxAux::EnclaveTx(TxEnclaveAux::TransferTx {
+        inputs: tx.inputs.clone(),
+        no_of_outputs: tx.outputs.len() as TxoSize,
+        payload: TxObfuscated {
+            txid: tx.id(),
+            key_from: BlockHeight::new(0),
+            init_vector: [0u8; 12],
+            txpayload,
+        },
+    })
+}
+
+/// Create a deposit `TxAux` whose obfuscated payload has the same size as
+/// the encrypted one
+pub fn mock_deposit_tx_aux(tx: &DepositBondTx, witness: TxWitness) -> TxAux {
+    let mut txpayload = PlainTxAux::DepositStakeTx(witness).encode();
+    txpayload.extend_from_slice(&[0u8; ENCRYPTION_TAG_SIZE]);
+
+    TxAux::EnclaveTx(TxEnclaveAux::DepositStakeTx {
+        tx: tx.clone(),
+        payload: TxObfuscated {
+            txid: tx.id(),
+            key_from: BlockHeight::new(0),
+            init_vector: [0u8; 12],
+            txpayload,
+        },
+    })
+}
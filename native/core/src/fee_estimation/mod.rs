@@ -0,0 +1,362 @@
+//! Fee estimation of unsigned transactions of every kind
+//!
+//! The unsigned transaction is completed with dummy witnesses of the same
+//! encoded size as the real ones, so the estimated fee is the exact fee
+//! charged once the transaction is signed.
+pub mod mock_witness;
+
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{DepositBondTx, UnbondTx, UnjailTx, WithdrawUnbondedTx};
+use chain_core::state::validator::NodeJoinRequestTx;
+use chain_core::tx::data::{Tx, TxId};
+use chain_core::tx::fee::FeeAlgorithm;
+use chain_core::tx::witness::TxInWitness;
+use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use client_core::signer::DummySigner;
+use parity_scale_codec::Encode;
+use serde::Deserialize;
+
+use crate::codec::decode_transaction;
+use crate::schema::types::{AddressParamsOptions, FeeConfigOptions};
+use crate::schema::{de, SchemaError, SchemaResult};
+
+use mock_witness::{
+    mock_deposit_tx_aux, mock_staked_state_op_witness, mock_transfer_tx_aux, mock_tx_in_witness,
+};
+
+/// Kind of the unsigned transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxKind {
+    Transfer,
+    Deposit,
+    WithdrawUnbonded,
+    Unbond,
+    Unjail,
+    NodeJoin,
+}
+
+impl TxKind {
+    /// Whether the transaction spends UTXOs and has one witness per input
+    fn has_inputs(self) -> bool {
+        match self {
+            TxKind::Transfer | TxKind::Deposit => true,
+            _ => false,
+        }
+    }
+}
+
+/// Encoded size of the signed transaction in bytes
+#[derive(Debug, Clone, Copy)]
+pub struct SizeBreakdown {
+    /// Unsigned transaction
+    pub transaction: usize,
+    /// Witnesses of all the inputs, or the staked state operation witness
+    pub witness: usize,
+    /// Broadcast-able `TxAux`, including the envelope and the encryption
+    /// overhead of enclave transactions
+    pub total: usize,
+}
+
+impl SizeBreakdown {
+    /// Size of the `TxAux` envelope and encryption overhead
+    pub fn overhead(&self) -> usize {
+        self.total - self.transaction - self.witness
+    }
+}
+
+/// Fee of a transaction once signed
+#[derive(Debug, Clone, Copy)]
+pub struct FeeEstimation {
+    pub fee: Coin,
+    pub size: SizeBreakdown,
+}
+
+/// Estimate the fee of the unsigned transaction of `kind`. `witness_shape`
+/// are the signers of the address of each input, and only applies to
+/// transfer and deposit transactions.
+pub fn estimate_tx_fee<F>(
+    unsigned_raw_tx: &[u8],
+    kind: TxKind,
+    witness_shape: &[AddressParamsOptions],
+    fee_algorithm: &F,
+) -> Result<FeeEstimation>
+where
+    F: FeeAlgorithm,
+{
+    let (tx_aux, witness_size) = match kind {
+        TxKind::Transfer => {
+            let tx: Tx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+            let witness = mock_tx_in_witnesses(&tx.id(), tx.inputs.len(), witness_shape)?;
+            let witness_size = witness.encode().len();
+
+            (mock_transfer_tx_aux(&tx, witness.into()), witness_size)
+        }
+        TxKind::Deposit => {
+            let tx: DepositBondTx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+            let witness = mock_tx_in_witnesses(&tx.id(), tx.inputs.len(), witness_shape)?;
+            let witness_size = witness.encode().len();
+
+            (mock_deposit_tx_aux(&tx, witness.into()), witness_size)
+        }
+        TxKind::WithdrawUnbonded => {
+            let tx: WithdrawUnbondedTx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+            let witness_size = mock_staked_state_op_witness(&tx.id())?.encode().len();
+
+            (DummySigner().mock_txaux_for_withdraw(tx), witness_size)
+        }
+        TxKind::Unbond => {
+            let tx: UnbondTx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+            let witness = mock_staked_state_op_witness(&tx.id())?;
+            let witness_size = witness.encode().len();
+
+            (
+                TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, witness)),
+                witness_size,
+            )
+        }
+        TxKind::Unjail => {
+            let tx: UnjailTx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+            let witness = mock_staked_state_op_witness(&tx.id())?;
+            let witness_size = witness.encode().len();
+
+            (
+                TxAux::PublicTx(TxPublicAux::UnjailTx(tx, witness)),
+                witness_size,
+            )
+        }
+        TxKind::NodeJoin => {
+            let tx: NodeJoinRequestTx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+            let witness = mock_staked_state_op_witness(&tx.id())?;
+            let witness_size = witness.encode().len();
+
+            (
+                TxAux::PublicTx(TxPublicAux::NodeJoinTx(tx, witness)),
+                witness_size,
+            )
+        }
+    };
+
+    let fee = fee_algorithm
+        .calculate_for_txaux(&tx_aux)
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Unable to calculate transaction fee",
+            )
+        })?
+        .to_coin();
+
+    Ok(FeeEstimation {
+        fee,
+        size: SizeBreakdown {
+            transaction: unsigned_raw_tx.len(),
+            witness: witness_size,
+            total: tx_aux.encode().len(),
+        },
+    })
+}
+
+/// Create the witnesses of the inputs, which are owned by single signer
+/// addresses unless the witness shape is provided
+fn mock_tx_in_witnesses(
+    tx_id: &TxId,
+    inputs_len: usize,
+    witness_shape: &[AddressParamsOptions],
+) -> Result<Vec<TxInWitness>> {
+    if witness_shape.is_empty() {
+        return (0..inputs_len)
+            .map(|_| mock_tx_in_witness(tx_id, &AddressParamsOptions::default()))
+            .collect();
+    }
+    if witness_shape.len() != inputs_len {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Witness shape has {} inputs but transaction has {} inputs",
+                witness_shape.len(),
+                inputs_len
+            ),
+        ));
+    }
+
+    witness_shape
+        .iter()
+        .map(|address_params| mock_tx_in_witness(tx_id, address_params))
+        .collect()
+}
+
+/// Options of a fee estimation
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateFeeOptions {
+    #[serde(deserialize_with = "de::buffer")]
+    pub unsigned_raw_tx: Vec<u8>,
+    pub kind: TxKind,
+    pub fee_config: FeeConfigOptions,
+    #[serde(default)]
+    pub witness_shape: Vec<AddressParamsOptions>,
+}
+
+impl EstimateFeeOptions {
+    pub fn verify(&self) -> SchemaResult<()> {
+        if !self.kind.has_inputs() && !self.witness_shape.is_empty() {
+            return Err(SchemaError::new(
+                "witnessShape",
+                "Witness shape only applies to transfer and deposit transactions",
+            ));
+        }
+        for (i, address_params) in self.witness_shape.iter().enumerate() {
+            address_params.verify(&format!("witnessShape[{}]", i))?;
+        }
+
+        Ok(())
+    }
+
+    /// Estimate the fee with the fee config of the options
+    pub fn estimate(&self) -> Result<FeeEstimation> {
+        estimate_tx_fee(
+            &self.unsigned_raw_tx,
+            self.kind,
+            &self.witness_shape,
+            &self.fee_config.to_fee_algorithm(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::tx::witness::TxWitness;
+
+    use crate::signer::KeyPairSigner;
+    use crate::transfer_transaction::tests::{builder_of, fee_config, key_pair};
+    use crate::transfer_transaction::{sign_input, signed_transfer_transaction};
+
+    fn signed_transfer() -> (Tx, TxWitness) {
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+
+        signed_transfer_transaction(&builder).unwrap()
+    }
+
+    #[test]
+    fn estimate_tx_fee_should_match_fee_of_signed_transfer() {
+        let (tx, witness) = signed_transfer();
+        let fee_algorithm = fee_config().to_fee_algorithm();
+        let witness_size = witness.encode().len();
+        let signed_tx_aux = mock_transfer_tx_aux(&tx, witness);
+
+        let estimation =
+            estimate_tx_fee(&tx.encode(), TxKind::Transfer, &[], &fee_algorithm).unwrap();
+
+        assert_eq!(
+            estimation.fee,
+            fee_algorithm
+                .calculate_for_txaux(&signed_tx_aux)
+                .unwrap()
+                .to_coin()
+        );
+        assert_eq!(estimation.size.transaction, tx.encode().len());
+        assert_eq!(estimation.size.witness, witness_size);
+        assert_eq!(estimation.size.total, signed_tx_aux.encode().len());
+    }
+
+    #[test]
+    fn estimate_tx_fee_should_grow_with_witness_shape() {
+        let (tx, _) = signed_transfer();
+        let fee_algorithm = fee_config().to_fee_algorithm();
+
+        let single_signer =
+            estimate_tx_fee(&tx.encode(), TxKind::Transfer, &[], &fee_algorithm).unwrap();
+        let multi_signers = estimate_tx_fee(
+            &tx.encode(),
+            TxKind::Transfer,
+            &[AddressParamsOptions {
+                required_signers: 2,
+                total_signers: 3,
+            }],
+            &fee_algorithm,
+        )
+        .unwrap();
+
+        assert!(multi_signers.size.witness > single_signer.size.witness);
+        assert!(multi_signers.fee > single_signer.fee);
+    }
+
+    #[test]
+    fn estimate_tx_fee_should_reject_mismatched_witness_shape() {
+        let (tx, _) = signed_transfer();
+        let witness_shape = vec![AddressParamsOptions::default(); 2];
+
+        assert!(estimate_tx_fee(
+            &tx.encode(),
+            TxKind::Transfer,
+            &witness_shape,
+            &fee_config().to_fee_algorithm(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn estimate_tx_fee_should_reject_witness_shape_above_signers_limit() {
+        let (tx, _) = signed_transfer();
+        let fee_algorithm = fee_config().to_fee_algorithm();
+        let witness_shape = |required_signers, total_signers| {
+            vec![AddressParamsOptions {
+                required_signers,
+                total_signers,
+            }]
+        };
+
+        assert!(estimate_tx_fee(
+            &tx.encode(),
+            TxKind::Transfer,
+            &witness_shape(1, u64::MAX),
+            &fee_algorithm,
+        )
+        .is_err());
+        assert!(estimate_tx_fee(
+            &tx.encode(),
+            TxKind::Transfer,
+            &witness_shape(10, 20),
+            &fee_algorithm,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn estimate_tx_fee_should_reject_trailing_bytes() {
+        let (tx, _) = signed_transfer();
+        let mut unsigned_raw_tx = tx.encode();
+        unsigned_raw_tx.push(0);
+
+        assert!(estimate_tx_fee(
+            &unsigned_raw_tx,
+            TxKind::Transfer,
+            &[],
+            &fee_config().to_fee_algorithm(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_should_reject_witness_shape_of_staked_state_operation() {
+        let options = EstimateFeeOptions {
+            unsigned_raw_tx: Vec::new(),
+            kind: TxKind::Unbond,
+            fee_config: fee_config(),
+            witness_shape: vec![AddressParamsOptions::default()],
+        };
+
+        assert!(options
+            .verify()
+            .unwrap_err()
+            .to_string()
+            .contains("`witnessShape`"));
+    }
+}
@@ -0,0 +1,217 @@
+//! BIP39 mnemonic seeds and batch derivation of BIP44 addresses
+use client_common::{PrivateKey, PublicKey, Result};
+use client_core::Mnemonic;
+use secstr::SecUtf8;
+use serde::Deserialize;
+
+use crate::address::{staking_address_from_public_key, transfer_address_from_public_key};
+use crate::network::{network_by_name, NetworkDefinition};
+use crate::schema::{de, SchemaError, SchemaResult};
+
+/// Maximum number of key pairs derived in a single batch
+pub const MAX_DERIVATION_BATCH_SIZE: u32 = 1_000_000;
+/// First hardened index, derived indexes must stay below it
+const HARDENED_INDEX: u32 = 0x8000_0000;
+
+/// Returns the seed of the mnemonic words
+pub fn seed_from_mnemonic(mnemonic: &str) -> Result<Vec<u8>> {
+    let mnemonic = SecUtf8::from(mnemonic);
+    let mnemonic = Mnemonic::from_secstr(&mnemonic)?;
+
+    Ok(mnemonic.seed().to_vec())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeriveAddressesOptions {
+    #[serde(deserialize_with = "de::buffer")]
+    pub seed: Vec<u8>,
+    pub network: String,
+    pub account: u32,
+    pub start_index: u32,
+    pub count: u32,
+    #[serde(default)]
+    pub include_private_key: bool,
+}
+
+impl DeriveAddressesOptions {
+    fn to_network(&self) -> SchemaResult<NetworkDefinition> {
+        network_by_name(&self.network).map_err(|err| SchemaError::new("network", err.to_string()))
+    }
+
+    fn verify_range(&self) -> SchemaResult<()> {
+        if self.count > MAX_DERIVATION_BATCH_SIZE {
+            return Err(SchemaError::new(
+                "count",
+                format!("should not exceed {}", MAX_DERIVATION_BATCH_SIZE),
+            ));
+        }
+
+        let end_index = self.start_index.checked_add(self.count);
+        match end_index {
+            Some(end_index) if end_index <= HARDENED_INDEX => Ok(()),
+            _ => Err(SchemaError::new(
+                "count",
+                "index range should be below the hardened index 2^31",
+            )),
+        }
+    }
+}
+
+/// Key pair and addresses derived at an index
+pub struct DerivedAddress {
+    pub index: u32,
+    pub public_key: PublicKey,
+    pub private_key: Option<PrivateKey>,
+    pub transfer_address: String,
+    pub staking_address: String,
+}
+
+/// Derivation of a range of indexes of an account
+pub struct AddressDerivation {
+    seed: Vec<u8>,
+    network: NetworkDefinition,
+    account: u32,
+    start_index: u32,
+    count: u32,
+    include_private_key: bool,
+}
+
+impl AddressDerivation {
+    /// Verify the network and the index range of the options
+    pub fn new(options: DeriveAddressesOptions) -> SchemaResult<AddressDerivation> {
+        let network = options.to_network()?;
+        options.verify_range()?;
+
+        Ok(AddressDerivation {
+            seed: options.seed,
+            network,
+            account: options.account,
+            start_index: options.start_index,
+            count: options.count,
+            include_private_key: options.include_private_key,
+        })
+    }
+
+    /// Derive the key pair and addresses of each index
+    pub fn derive(&self) -> std::result::Result<Vec<DerivedAddress>, String> {
+        (self.start_index..self.start_index + self.count)
+            .map(|index| {
+                let (public_key, private_key) = self
+                    .network
+                    .derive_key_pair(&self.seed, self.account, index)
+                    .map_err(|err| format!("Unable to derive key pair at {}: {}", index, err))?;
+                let transfer_address = transfer_address_from_public_key(&self.network, &public_key)
+                    .map_err(|err| {
+                        format!("Unable to create transfer address at {}: {}", index, err)
+                    })?;
+                let staking_address = staking_address_from_public_key(&public_key).to_string();
+
+                Ok(DerivedAddress {
+                    index,
+                    public_key,
+                    private_key: if self.include_private_key {
+                        Some(private_key)
+                    } else {
+                        None
+                    },
+                    transfer_address,
+                    staking_address,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::network::TESTNET;
+
+    const MNEMONIC: &str = "point shiver hurt flight fun online hub antenna engine pave chef fantasy front interest poem accident catch load frequent praise elite pet remove used";
+
+    fn derive_addresses_options(start_index: u32, count: u32) -> DeriveAddressesOptions {
+        DeriveAddressesOptions {
+            seed: seed_from_mnemonic(MNEMONIC).unwrap(),
+            network: TESTNET.to_owned(),
+            account: 2,
+            start_index,
+            count,
+            include_private_key: true,
+        }
+    }
+
+    #[test]
+    fn seed_from_mnemonic_should_reject_invalid_mnemonic() {
+        assert!(seed_from_mnemonic("hello from rust").is_err());
+        assert_eq!(seed_from_mnemonic(MNEMONIC).unwrap().len(), 64);
+    }
+
+    #[test]
+    fn derive_should_derive_bip44_key_pairs_and_addresses() {
+        let derivation = AddressDerivation::new(derive_addresses_options(3, 2)).unwrap();
+        let derived_addresses = derivation.derive().unwrap();
+
+        let seed = seed_from_mnemonic(MNEMONIC).unwrap();
+        let network = network_by_name(TESTNET).unwrap();
+        assert_eq!(derived_addresses.len(), 2);
+        for (i, derived_address) in derived_addresses.iter().enumerate() {
+            let index = 3 + i as u32;
+            let (public_key, private_key) = network.derive_key_pair(&seed, 2, index).unwrap();
+
+            assert_eq!(derived_address.index, index);
+            assert_eq!(derived_address.public_key, public_key);
+            assert_eq!(
+                derived_address
+                    .private_key
+                    .as_ref()
+                    .map(PrivateKey::serialize),
+                Some(private_key.serialize())
+            );
+            assert_eq!(
+                derived_address.transfer_address,
+                transfer_address_from_public_key(&network, &public_key).unwrap()
+            );
+            assert!(derived_address.transfer_address.starts_with("tcro1"));
+            assert_eq!(
+                derived_address.staking_address,
+                staking_address_from_public_key(&public_key).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn derive_should_omit_private_key_unless_requested() {
+        let mut options = derive_addresses_options(0, 1);
+        options.include_private_key = false;
+
+        let derived_addresses = AddressDerivation::new(options).unwrap().derive().unwrap();
+
+        assert!(derived_addresses[0].private_key.is_none());
+    }
+
+    #[test]
+    fn new_should_reject_range_over_hardened_index() {
+        assert!(AddressDerivation::new(derive_addresses_options(HARDENED_INDEX - 1, 1)).is_ok());
+
+        let err = AddressDerivation::new(derive_addresses_options(HARDENED_INDEX - 1, 2))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            SchemaError::new(
+                "count",
+                "index range should be below the hardened index 2^31"
+            )
+        );
+    }
+
+    #[test]
+    fn new_should_reject_unknown_network() {
+        let mut options = derive_addresses_options(0, 1);
+        options.network = "Unknown".to_owned();
+
+        assert!(AddressDerivation::new(options).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Key pair and view key helpers
+use std::str::FromStr;
+
+use client_common::PublicKey;
+
+/// Returns whether the buffer is a valid view key, which is a serialized
+/// secp256k1 public key
+pub fn is_valid_view_key(view_key: &[u8]) -> bool {
+    PublicKey::from_str(&hex::encode_upper(view_key)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use client_common::PrivateKey;
+
+    #[test]
+    fn public_key_should_be_derived_from_private_key() {
+        let private_key = PrivateKey::deserialize_from(&[1; 32]).unwrap();
+        let public_key = PublicKey::from(&private_key);
+
+        assert_eq!(
+            hex::encode(public_key.serialize()),
+            "041b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f70beaf8f588b541507fed6a642c5ab42dfdf8120a7f639de5122d47a69a8e8d1"
+        );
+        assert_eq!(
+            hex::encode(public_key.serialize_compressed()),
+            "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+        );
+    }
+
+    #[test]
+    fn private_key_should_reject_invalid_secret() {
+        assert!(PrivateKey::deserialize_from(&[0; 32]).is_err());
+        assert!(PrivateKey::deserialize_from(&[1; 33]).is_err());
+    }
+
+    #[test]
+    fn is_valid_view_key_should_accept_public_key() {
+        let private_key = PrivateKey::deserialize_from(&[1; 32]).unwrap();
+        let public_key = PublicKey::from(&private_key);
+
+        assert!(is_valid_view_key(&public_key.serialize()));
+        assert!(is_valid_view_key(&public_key.serialize_compressed()));
+        assert!(!is_valid_view_key(&[2; 33]));
+        assert!(!is_valid_view_key(&[]));
+    }
+}
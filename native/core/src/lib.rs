@@ -0,0 +1,34 @@
+//! Plain Rust core of the Crypto.com Chain node library
+//!
+//! Every capability exposed to JS by the neon bindings is implemented here
+//! on plain Rust types, so it can be reused by other front-ends. Options
+//! objects deserialize from the same camelCase JSON shape the JS layer sends
+//! to the native module.
+pub mod address;
+pub mod codec;
+pub mod coin;
+pub mod council_node_transaction;
+pub mod fee;
+pub mod fee_estimation;
+pub mod hd_wallet;
+pub mod key_pair;
+pub mod local_validation;
+#[cfg(feature = "mock-node")]
+pub mod mock_node;
+pub mod multi_sig;
+pub mod network;
+pub mod output_policy;
+pub mod schema;
+pub mod signer;
+pub mod staking_lifecycle;
+pub mod staking_queue;
+pub mod staking_transaction;
+#[cfg(feature = "websocket-rpc")]
+pub mod subscription;
+pub mod timespec;
+pub mod transfer_transaction;
+pub mod tx_assertion;
+pub mod tx_aux;
+pub mod validator_key;
+#[cfg(feature = "wallet-db")]
+pub mod wallet_db;
@@ -0,0 +1,337 @@
+//! Local validation of signed transactions before broadcast
+//!
+//! The chain validation rules are run against a context provided by the
+//! caller, so a transaction the node would reject is caught with the same
+//! rejection reason without reaching the node.
+mod rules;
+
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{
+    ConfidentialInit, CouncilNode, DepositBondTx, Nonce, StakedState, StakedStateAddress,
+    StakedStateOpWitness, Validator, WithdrawUnbondedTx,
+};
+use chain_core::state::tendermint::TendermintValidatorPubKey;
+use chain_core::tx::witness::{TxInWitness, TxWitness};
+use chain_core::tx::{TxAux, TxPublicAux};
+use client_common::{Error, ErrorKind, Result};
+use serde::Deserialize;
+
+use crate::codec::{decode_transaction, decode_witness};
+use crate::schema::types::{network_of_chain_hex_id, to_tx_outs, FeeConfigOptions, OutputOptions};
+use crate::schema::{de, SchemaError, SchemaResult};
+use crate::transfer_transaction::{from_incomplete, signed_transfer_transaction};
+
+pub use rules::{validate_locally, LocalTransaction, ValidationContext};
+
+/// Name of the placeholder validator of a jailed staked state. Only the
+/// jailed time of the validator is read by the chain rules.
+const PLACEHOLDER_VALIDATOR_NAME: &str = "local-validation";
+
+/// Signed transaction to validate, tagged by `kind`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TransactionOptions {
+    #[serde(rename_all = "camelCase")]
+    Transfer {
+        #[serde(deserialize_with = "de::buffer")]
+        incomplete_hex: Vec<u8>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Deposit {
+        #[serde(deserialize_with = "de::buffer")]
+        unsigned_raw_tx: Vec<u8>,
+        #[serde(deserialize_with = "de::buffers")]
+        witnesses: Vec<Vec<u8>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    WithdrawUnbonded {
+        #[serde(deserialize_with = "de::buffer")]
+        unsigned_raw_tx: Vec<u8>,
+        #[serde(deserialize_with = "de::buffer")]
+        witness: Vec<u8>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Unbond {
+        #[serde(deserialize_with = "de::buffer")]
+        tx_aux: Vec<u8>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Unjail {
+        #[serde(deserialize_with = "de::buffer")]
+        tx_aux: Vec<u8>,
+    },
+}
+
+impl TransactionOptions {
+    /// Decode the signed transaction
+    pub fn to_local_transaction(&self, fee_config: &FeeConfigOptions) -> Result<LocalTransaction> {
+        match self {
+            TransactionOptions::Transfer { incomplete_hex } => {
+                let builder =
+                    from_incomplete(incomplete_hex.clone(), fee_config.to_fee_algorithm())?;
+                let (tx, witness) = signed_transfer_transaction(&builder)?;
+
+                Ok(LocalTransaction::Transfer(tx, witness))
+            }
+            TransactionOptions::Deposit {
+                unsigned_raw_tx,
+                witnesses,
+            } => {
+                let tx: DepositBondTx = decode_transaction(unsigned_raw_tx, "raw transaction")?;
+                let witnesses = witnesses
+                    .iter()
+                    .map(|witness| decode_witness::<TxInWitness>(witness, "input witness"))
+                    .collect::<Result<Vec<TxInWitness>>>()?;
+
+                Ok(LocalTransaction::Deposit(tx, TxWitness::from(witnesses)))
+            }
+            TransactionOptions::WithdrawUnbonded {
+                unsigned_raw_tx,
+                witness,
+            } => {
+                let tx: WithdrawUnbondedTx =
+                    decode_transaction(unsigned_raw_tx, "raw transaction")?;
+                let witness: StakedStateOpWitness = decode_witness(witness, "witness")?;
+
+                Ok(LocalTransaction::WithdrawUnbonded(tx, witness))
+            }
+            TransactionOptions::Unbond { tx_aux } => {
+                match decode_transaction::<TxAux>(tx_aux, "transaction")? {
+                    TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, witness)) => {
+                        Ok(LocalTransaction::Unbond(tx, witness))
+                    }
+                    _ => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Transaction is not an unbond transaction",
+                    )),
+                }
+            }
+            TransactionOptions::Unjail { tx_aux } => {
+                match decode_transaction::<TxAux>(tx_aux, "transaction")? {
+                    TxAux::PublicTx(TxPublicAux::UnjailTx(tx, witness)) => {
+                        Ok(LocalTransaction::Unjail(tx, witness))
+                    }
+                    _ => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Transaction is not an unjail transaction",
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// Staked state of the signer of staked state operations
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakedStateOptions {
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    #[serde(deserialize_with = "de::coin")]
+    pub bonded: Coin,
+    #[serde(deserialize_with = "de::coin")]
+    pub unbonded: Coin,
+    pub unbonded_from: Timespec,
+    #[serde(deserialize_with = "de::staking_address")]
+    pub address: StakedStateAddress,
+    #[serde(default, deserialize_with = "de::optional_timespec")]
+    pub jailed_until: Option<Timespec>,
+}
+
+impl StakedStateOptions {
+    /// Staked state with a placeholder validator when jailed
+    pub fn to_staked_state(&self) -> StakedState {
+        let mut staked_state = StakedState::default(self.address);
+        staked_state.nonce = self.nonce;
+        staked_state.bonded = self.bonded;
+        staked_state.unbonded = self.unbonded;
+        staked_state.unbonded_from = self.unbonded_from;
+
+        if let Some(jailed_until) = self.jailed_until {
+            let mut validator = Validator::new(CouncilNode {
+                name: PLACEHOLDER_VALIDATOR_NAME.to_owned(),
+                security_contact: None,
+                consensus_pubkey: TendermintValidatorPubKey::Ed25519([0; 32]),
+                confidential_init: ConfidentialInit { cert: Vec::new() },
+            });
+            validator.jailed_until = Some(jailed_until);
+            staked_state.validator = Some(validator);
+        }
+
+        staked_state
+    }
+}
+
+/// Chain state the transaction is validated against
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextOptions {
+    pub block_time: Timespec,
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    pub min_fee: Option<Coin>,
+    #[serde(default)]
+    pub unbonding_period: Option<u32>,
+    #[serde(default)]
+    pub spent_outputs: Vec<OutputOptions>,
+    #[serde(default)]
+    pub staked_state: Option<StakedStateOptions>,
+}
+
+/// Signed transaction, network and chain state of a local validation
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateLocallyOptions {
+    pub transaction: TransactionOptions,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+    pub fee_config: FeeConfigOptions,
+    pub context: ContextOptions,
+}
+
+impl ValidateLocallyOptions {
+    /// Chain state of the options, with the spent outputs decoded on the
+    /// network of the chain hex id
+    pub fn to_validation_context(&self) -> SchemaResult<ValidationContext> {
+        let network = network_of_chain_hex_id(self.chain_hex_id, "chainHexId")?;
+        // Unbonding period is only read by the unbond transaction rules
+        let unbonding_period = match (&self.transaction, self.context.unbonding_period) {
+            (TransactionOptions::Unbond { .. }, None) => {
+                return Err(SchemaError::new(
+                    "context.unbondingPeriod",
+                    "Unbonding period is required to validate an unbond transaction",
+                ))
+            }
+            (_, unbonding_period) => unbonding_period.unwrap_or_default(),
+        };
+        let spent_outputs = to_tx_outs(
+            &self.context.spent_outputs,
+            &network,
+            "context.spentOutputs",
+        )?;
+
+        Ok(ValidationContext {
+            chain_hex_id: self.chain_hex_id,
+            block_time: self.context.block_time,
+            min_fee: self.context.min_fee,
+            unbonding_period,
+            spent_outputs,
+            staked_state: self
+                .context
+                .staked_state
+                .as_ref()
+                .map(StakedStateOptions::to_staked_state),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::state::account::{StakedStateOpAttributes, UnjailTx};
+    use chain_core::tx::TransactionId;
+    use parity_scale_codec::Encode;
+    use serde_json::json;
+
+    use crate::fee_estimation::mock_witness::mock_staked_state_op_witness;
+    use crate::schema::from_json_value;
+
+    const ADDRESS: &str = "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4";
+    const STAKING_ADDRESS: &str = "0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0";
+
+    #[test]
+    fn to_local_transaction_should_reject_tx_aux_of_other_kind() {
+        let tx = UnjailTx::new(
+            1,
+            STAKING_ADDRESS.parse().unwrap(),
+            StakedStateOpAttributes::new(0x2A),
+        );
+        let witness = mock_staked_state_op_witness(&tx.id()).unwrap();
+        let tx_aux = TxAux::PublicTx(TxPublicAux::UnjailTx(tx, witness));
+        let options: ValidateLocallyOptions = from_json_value(json!({
+            "transaction": { "kind": "unbond", "txAux": tx_aux.encode() },
+            "chainHexId": [0x2A],
+            "feeConfig": { "algorithm": "LinearFee", "constant": "1.1", "coefficient": "1.25" },
+            "context": { "blockTime": 0, "unbondingPeriod": 60 },
+        }))
+        .unwrap();
+
+        let err = options
+            .transaction
+            .to_local_transaction(&options.fee_config)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("is not an unbond transaction"));
+    }
+
+    #[test]
+    fn to_validation_context_should_require_unbonding_period_of_unbond_transaction() {
+        let options: ValidateLocallyOptions = from_json_value(json!({
+            "transaction": { "kind": "unbond", "txAux": [] },
+            "chainHexId": [0x2A],
+            "feeConfig": { "algorithm": "LinearFee", "constant": "1.1", "coefficient": "1.25" },
+            "context": { "blockTime": 0 },
+        }))
+        .unwrap();
+
+        let err = options.to_validation_context().unwrap_err();
+
+        assert!(err.to_string().contains("`context.unbondingPeriod`"));
+    }
+
+    #[test]
+    fn to_validation_context_should_decode_context() {
+        let options: ValidateLocallyOptions = from_json_value(json!({
+            "transaction": { "kind": "unjail", "txAux": [] },
+            "chainHexId": [0x2A],
+            "feeConfig": { "algorithm": "LinearFee", "constant": "1.1", "coefficient": "1.25" },
+            "context": {
+                "blockTime": 1000,
+                "minFee": "5",
+                "unbondingPeriod": 6,
+                "spentOutputs": [{ "address": ADDRESS, "value": "10" }],
+                "stakedState": {
+                    "nonce": "1",
+                    "bonded": "2",
+                    "unbonded": "3",
+                    "unbondedFrom": 4,
+                    "address": STAKING_ADDRESS,
+                    "jailedUntil": 5,
+                },
+            },
+        }))
+        .unwrap();
+
+        let context = options.to_validation_context().unwrap();
+
+        assert_eq!(context.block_time, 1000);
+        assert_eq!(context.min_fee, Some(Coin::new(5).unwrap()));
+        assert_eq!(context.unbonding_period, 6);
+        assert_eq!(context.spent_outputs.len(), 1);
+        let staked_state = context.staked_state.unwrap();
+        assert_eq!(staked_state.nonce, 1);
+        assert_eq!(staked_state.unbonded_from, 4);
+        assert_eq!(staked_state.validator.unwrap().jailed_until, Some(5));
+    }
+
+    #[test]
+    fn to_validation_context_should_reject_spent_output_of_other_network() {
+        let options: ValidateLocallyOptions = from_json_value(json!({
+            "transaction": { "kind": "unjail", "txAux": [] },
+            "chainHexId": [0x42],
+            "feeConfig": { "algorithm": "LinearFee", "constant": "1.1", "coefficient": "1.25" },
+            "context": {
+                "blockTime": 0,
+                "spentOutputs": [{ "address": ADDRESS, "value": "10" }],
+            },
+        }))
+        .unwrap();
+
+        let err = options.to_validation_context().unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("`context.spentOutputs[0].address`"));
+    }
+}
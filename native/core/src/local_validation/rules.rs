@@ -0,0 +1,359 @@
+//! Chain validation rules run against a caller-supplied context
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{
+    DepositBondTx, StakedState, StakedStateAddress, StakedStateOpWitness, UnbondTx, UnjailTx,
+    WithdrawUnbondedTx,
+};
+use chain_core::tx::data::attribute::TxAttributes;
+use chain_core::tx::data::input::TxoPointer;
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::{Tx, TxId};
+use chain_core::tx::fee::{Fee, FeeAlgorithm};
+use chain_core::tx::witness::TxWitness;
+use chain_core::tx::{TransactionId, TxAux, TxPublicAux, TxWithOutputs};
+use chain_tx_validation::witness::verify_tx_recover_address;
+use chain_tx_validation::{
+    verify_bonded_deposit_core, verify_transfer, verify_unbonded_withdraw_core, verify_unbonding,
+    verify_unjailed, ChainInfo, Error as TxValidationError,
+};
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use client_core::signer::DummySigner;
+
+use crate::fee_estimation::mock_witness::{mock_deposit_tx_aux, mock_transfer_tx_aux};
+
+/// Signed transaction to validate
+#[derive(Debug)]
+pub enum LocalTransaction {
+    Transfer(Tx, TxWitness),
+    Deposit(DepositBondTx, TxWitness),
+    WithdrawUnbonded(WithdrawUnbondedTx, StakedStateOpWitness),
+    Unbond(UnbondTx, StakedStateOpWitness),
+    Unjail(UnjailTx, StakedStateOpWitness),
+}
+
+impl LocalTransaction {
+    /// `TxAux` of the same encoded size as the broadcast transaction, which
+    /// the minimum fee is computed on
+    fn to_sized_tx_aux(&self) -> TxAux {
+        match self {
+            LocalTransaction::Transfer(tx, witness) => mock_transfer_tx_aux(tx, witness.clone()),
+            LocalTransaction::Deposit(tx, witness) => mock_deposit_tx_aux(tx, witness.clone()),
+            LocalTransaction::WithdrawUnbonded(tx, _) => {
+                DummySigner().mock_txaux_for_withdraw(tx.clone())
+            }
+            LocalTransaction::Unbond(tx, witness) => {
+                TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx.clone(), witness.clone()))
+            }
+            LocalTransaction::Unjail(tx, witness) => {
+                TxAux::PublicTx(TxPublicAux::UnjailTx(tx.clone(), witness.clone()))
+            }
+        }
+    }
+}
+
+/// Chain state the transaction is validated against
+#[derive(Debug)]
+pub struct ValidationContext {
+    pub chain_hex_id: u8,
+    /// Time of the latest committed block
+    pub block_time: Timespec,
+    /// Minimum fee, computed from the fee algorithm on the transaction size
+    /// when not provided
+    pub min_fee: Option<Coin>,
+    /// Unbonding period of the network in seconds
+    pub unbonding_period: u32,
+    /// Outputs spent by each input of transfer and deposit transactions
+    pub spent_outputs: Vec<TxOut>,
+    /// Staked state of unbond, withdraw unbonded and unjail transactions
+    pub staked_state: Option<StakedState>,
+}
+
+/// Run the validation rules of the chain. Returns the reason the chain
+/// would reject the transaction with, or `None` when it is accepted.
+pub fn validate_locally<F>(
+    transaction: &LocalTransaction,
+    context: &ValidationContext,
+    fee_algorithm: &F,
+) -> Result<Option<String>>
+where
+    F: FeeAlgorithm,
+{
+    let min_fee_computed = match context.min_fee {
+        Some(min_fee) => Fee::new(min_fee),
+        None => fee_algorithm
+            .calculate_for_txaux(&transaction.to_sized_tx_aux())
+            .chain(|| (ErrorKind::InvalidInput, "Unable to compute minimum fee"))?,
+    };
+    let chain_info = ChainInfo {
+        min_fee_computed,
+        chain_hex_id: context.chain_hex_id,
+        previous_block_time: context.block_time,
+        unbonding_period: context.unbonding_period,
+    };
+
+    let result = match transaction {
+        LocalTransaction::Transfer(tx, witness) => {
+            let spent_transactions = spent_transactions(context, &tx.inputs)?;
+            verify_transfer(tx, witness, chain_info, spent_transactions).map(|_| ())
+        }
+        LocalTransaction::Deposit(tx, witness) => {
+            let spent_transactions = spent_transactions(context, &tx.inputs)?;
+            verify_bonded_deposit_core(tx, witness, chain_info, spent_transactions).map(|_| ())
+        }
+        LocalTransaction::WithdrawUnbonded(tx, witness) => {
+            let staked_state = signer_staked_state(context, &tx.id(), witness)?;
+            staked_state.and_then(|staked_state| {
+                verify_unbonded_withdraw_core(tx, chain_info, &staked_state).map(|_| ())
+            })
+        }
+        LocalTransaction::Unbond(tx, witness) => {
+            let staked_state = signer_staked_state(context, &tx.id(), witness)?;
+            staked_state
+                .and_then(|staked_state| {
+                    verify_staked_state_address(staked_state, &tx.from_staked_account)
+                })
+                .and_then(|staked_state| verify_unbonding(tx, chain_info, staked_state).map(|_| ()))
+        }
+        LocalTransaction::Unjail(tx, witness) => {
+            let staked_state = signer_staked_state(context, &tx.id(), witness)?;
+            staked_state
+                .and_then(|staked_state| verify_staked_state_address(staked_state, &tx.address))
+                .and_then(|staked_state| verify_unjailed(tx, chain_info, staked_state).map(|_| ()))
+        }
+    };
+
+    Ok(result.err().map(|err| err.to_string()))
+}
+
+/// Transactions of the spent outputs, with each output at the index of the
+/// input pointing to it. Only the pointed output is read by the chain rules.
+fn spent_transactions(
+    context: &ValidationContext,
+    inputs: &[TxoPointer],
+) -> Result<Vec<TxWithOutputs>> {
+    if context.spent_outputs.len() != inputs.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Context has {} spent outputs but transaction has {} inputs",
+                context.spent_outputs.len(),
+                inputs.len()
+            ),
+        ));
+    }
+
+    Ok(context
+        .spent_outputs
+        .iter()
+        .zip(inputs)
+        .map(|(spent_output, input)| {
+            TxWithOutputs::Transfer(Tx {
+                inputs: Vec::new(),
+                outputs: vec![spent_output.clone(); usize::from(input.index) + 1],
+                attributes: TxAttributes::new(context.chain_hex_id),
+            })
+        })
+        .collect())
+}
+
+/// Staked state of the context, as long as the witness is signed by it
+fn signer_staked_state(
+    context: &ValidationContext,
+    tx_id: &TxId,
+    witness: &StakedStateOpWitness,
+) -> Result<std::result::Result<StakedState, TxValidationError>> {
+    let staked_state = context.staked_state.clone().ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "Staked state is required to validate staked state operations",
+        )
+    })?;
+
+    let signer = match verify_tx_recover_address(witness, tx_id) {
+        Ok(signer) => signer,
+        Err(err) => return Ok(Err(TxValidationError::EcdsaCrypto(err))),
+    };
+
+    Ok(verify_staked_state_address(staked_state, &signer))
+}
+
+fn verify_staked_state_address(
+    staked_state: StakedState,
+    address: &StakedStateAddress,
+) -> std::result::Result<StakedState, TxValidationError> {
+    if staked_state.address != *address {
+        return Err(TxValidationError::MismatchAccountAddress);
+    }
+
+    Ok(staked_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::address::{extended_address_from_public_key, staking_address_from_public_key};
+    use crate::council_node_transaction::unjail::{
+        sign_unjail_transaction, unjail_tx_of_tx_aux, UnjailTxOptions,
+    };
+    use crate::signer::KeyPairSigner;
+    use crate::transfer_transaction::tests::{builder_of, fee_config, key_pair, CHAIN_HEX_ID};
+    use crate::transfer_transaction::{sign_input, signed_transfer_transaction};
+
+    fn signed_transfer() -> (Tx, TxWitness) {
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+
+        signed_transfer_transaction(&builder).unwrap()
+    }
+
+    fn spent_output() -> TxOut {
+        let address = extended_address_from_public_key(&key_pair(1).1).unwrap();
+
+        TxOut::new(address, Coin::new(100_000_000).unwrap())
+    }
+
+    fn context() -> ValidationContext {
+        ValidationContext {
+            chain_hex_id: CHAIN_HEX_ID,
+            block_time: 1_000,
+            min_fee: None,
+            unbonding_period: 0,
+            spent_outputs: vec![spent_output()],
+            staked_state: None,
+        }
+    }
+
+    fn signed_unjail(secret: u8) -> LocalTransaction {
+        let (private_key, public_key) = key_pair(secret);
+        let tx = UnjailTxOptions {
+            staking_address: staking_address_from_public_key(&key_pair(1).1),
+            nonce: 0,
+            chain_hex_id: CHAIN_HEX_ID,
+        }
+        .to_tx();
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        let tx_aux = sign_unjail_transaction(tx, &signer).unwrap();
+        let (tx, witness) = unjail_tx_of_tx_aux(tx_aux).unwrap();
+
+        LocalTransaction::Unjail(tx, witness)
+    }
+
+    fn validate(transaction: &LocalTransaction, context: &ValidationContext) -> Option<String> {
+        validate_locally(transaction, context, &fee_config().to_fee_algorithm()).unwrap()
+    }
+
+    #[test]
+    fn validate_locally_should_accept_valid_transfer() {
+        let (tx, witness) = signed_transfer();
+
+        assert_eq!(
+            validate(&LocalTransaction::Transfer(tx, witness), &context()),
+            None
+        );
+    }
+
+    #[test]
+    fn validate_locally_should_reject_transfer_below_minimum_fee() {
+        let (tx, witness) = signed_transfer();
+        let context = ValidationContext {
+            min_fee: Some(Coin::new(50_000_001).unwrap()),
+            ..context()
+        };
+
+        assert!(validate(&LocalTransaction::Transfer(tx, witness), &context).is_some());
+    }
+
+    #[test]
+    fn validate_locally_should_reject_transfer_of_other_chain() {
+        let (tx, witness) = signed_transfer();
+        let context = ValidationContext {
+            chain_hex_id: 0x42,
+            ..context()
+        };
+
+        assert!(validate(&LocalTransaction::Transfer(tx, witness), &context).is_some());
+    }
+
+    #[test]
+    fn validate_locally_should_throw_when_spent_outputs_mismatch_inputs() {
+        let (tx, witness) = signed_transfer();
+        let context = ValidationContext {
+            spent_outputs: Vec::new(),
+            ..context()
+        };
+
+        let err = validate_locally(
+            &LocalTransaction::Transfer(tx, witness),
+            &context,
+            &fee_config().to_fee_algorithm(),
+        )
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Context has 0 spent outputs but transaction has 1 inputs"));
+    }
+
+    #[test]
+    fn validate_locally_should_throw_when_staked_state_is_missing() {
+        let err = validate_locally(
+            &signed_unjail(1),
+            &context(),
+            &fee_config().to_fee_algorithm(),
+        )
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Staked state is required to validate staked state operations"));
+    }
+
+    #[test]
+    fn validate_locally_should_reject_unjail_signed_by_other_key() {
+        let staking_address = staking_address_from_public_key(&key_pair(1).1);
+        let context = ValidationContext {
+            staked_state: Some(StakedState::default(staking_address)),
+            ..context()
+        };
+
+        let reason = validate(&signed_unjail(2), &context).unwrap();
+
+        assert_eq!(
+            reason,
+            TxValidationError::MismatchAccountAddress.to_string()
+        );
+    }
+
+    #[test]
+    fn validate_locally_should_reject_unjail_of_not_jailed_staked_state() {
+        let staking_address = staking_address_from_public_key(&key_pair(1).1);
+        let context = ValidationContext {
+            staked_state: Some(StakedState::default(staking_address)),
+            ..context()
+        };
+
+        assert!(validate(&signed_unjail(1), &context).is_some());
+    }
+
+    #[test]
+    fn spent_transactions_should_place_output_at_input_index() {
+        let inputs = [TxoPointer {
+            id: [0; 32],
+            index: 2,
+        }];
+
+        let spent_transactions = spent_transactions(&context(), &inputs).unwrap();
+
+        match &spent_transactions[0] {
+            TxWithOutputs::Transfer(tx) => {
+                assert_eq!(tx.outputs.len(), 3);
+                assert_eq!(tx.outputs[2], spent_output());
+            }
+            _ => panic!("Spent transaction is not a transfer transaction"),
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! In-process mock Tendermint node for offline integration tests
+//!
+//! The node speaks enough of the Tendermint JSON-RPC, over websocket and
+//! HTTP on the same port, for the library and the integration tests to run
+//! on a single machine without a chain:
+//! - `abci_query` of the staked states, the tx-query address and the
+//!   `mockencrypt` obfuscation used by the `MockAbci` features
+//! - `broadcast_tx_async`, `broadcast_tx_sync` and `broadcast_tx_commit`
+//! - `tx` and `tx_search` lookup of the broadcasted transactions
+//! - `abci_info` and `status`
+//! - `block` and `block_results` of the committed blocks
+//! - `subscribe` to the `NewBlock` and `Tx` events over websocket
+//!
+//! Transactions are not executed: every decodable `TxAux` is accepted and
+//! committed in a block of its own, and staked states are only changed by
+//! the test through `MockNode::set_staked_state()`.
+mod rpc;
+mod transport;
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use chain_core::common::Timespec;
+use chain_core::state::account::{StakedState, StakedStateAddress};
+use chain_core::tx::data::TxId;
+use chain_core::tx::TxAux;
+use client_common::{ErrorKind, Result, ResultExt};
+use serde_json::Value;
+
+/// Transaction committed by the mock node
+#[derive(Debug, Clone)]
+pub struct CommittedTransaction {
+    pub tx_aux: TxAux,
+    /// SHA-256 hash of the broadcasted bytes, as reported by Tendermint
+    pub hash: [u8; 32],
+    pub height: u64,
+    /// Time of the block of the transaction
+    pub time: Timespec,
+}
+
+/// Subscription of a websocket connection to the events of a query
+#[derive(Debug)]
+struct Subscriber {
+    connection: u64,
+    /// Id of the subscribe request, from which the event ids are derived
+    id: Value,
+    query: String,
+    events: Sender<Value>,
+}
+
+#[derive(Debug, Default)]
+struct NodeState {
+    /// Staked states by the redeem address of their staking address
+    staked_states: HashMap<[u8; 20], StakedState>,
+    tx_query_address: Option<String>,
+    transactions: Vec<CommittedTransaction>,
+    height: u64,
+    subscribers: Vec<Subscriber>,
+    next_connection: u64,
+    /// Incremented to close the websocket connections opened before
+    connection_generation: u64,
+}
+
+impl NodeState {
+    fn transaction_by_id(&self, tx_id: &TxId) -> Option<&CommittedTransaction> {
+        self.transactions
+            .iter()
+            .find(|transaction| &transaction.tx_aux.tx_id() == tx_id)
+    }
+
+    fn transaction_by_hash(&self, hash: &[u8]) -> Option<&CommittedTransaction> {
+        self.transactions
+            .iter()
+            .find(|transaction| transaction.hash[..] == *hash)
+    }
+
+    /// Committed transaction of the block of the height, each block having
+    /// a single transaction
+    fn transaction_by_height(&self, height: u64) -> Option<&CommittedTransaction> {
+        self.transactions
+            .iter()
+            .find(|transaction| transaction.height == height)
+    }
+}
+
+/// Mock Tendermint node serving on a local port until it is dropped
+pub struct MockNode {
+    address: SocketAddr,
+    state: Arc<Mutex<NodeState>>,
+    shutdown: Arc<AtomicBool>,
+    server: Option<JoinHandle<()>>,
+}
+
+impl MockNode {
+    /// Start the node on a random local port
+    pub fn start() -> Result<MockNode> {
+        MockNode::bind("127.0.0.1:0")
+    }
+
+    /// Start the node on the address, e.g. `127.0.0.1:26657`
+    pub fn bind(address: &str) -> Result<MockNode> {
+        let listener = TcpListener::bind(address).chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                format!("Unable to bind mock node to {}", address),
+            )
+        })?;
+        let address = listener.local_addr().chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Unable to get local address of mock node",
+            )
+        })?;
+
+        let state = Arc::new(Mutex::new(NodeState::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let server = {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || transport::serve(listener, state, shutdown))
+        };
+
+        Ok(MockNode {
+            address,
+            state,
+            shutdown,
+            server: Some(server),
+        })
+    }
+
+    /// Local address the node is listening on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Tendermint websocket address, as accepted by the transaction
+    /// obfuscation and the staked state query
+    pub fn websocket_address(&self) -> String {
+        format!("ws://{}/websocket", self.address)
+    }
+
+    /// Tendermint HTTP RPC address
+    pub fn http_address(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Set the staked state returned for its staking address
+    pub fn set_staked_state(&self, staked_state: StakedState) {
+        let StakedStateAddress::BasicRedeem(redeem_address) = staked_state.address;
+
+        self.state()
+            .staked_states
+            .insert(redeem_address.0, staked_state);
+    }
+
+    /// Set the tx-query enclave address returned by the `txquery` query
+    pub fn set_tx_query_address(&self, tx_query_address: &str) {
+        self.state().tx_query_address = Some(tx_query_address.to_owned());
+    }
+
+    /// Transactions committed so far, in broadcast order
+    pub fn transactions(&self) -> Vec<CommittedTransaction> {
+        self.state().transactions.clone()
+    }
+
+    /// Committed transaction of the transaction id
+    pub fn transaction(&self, tx_id: &TxId) -> Option<CommittedTransaction> {
+        self.state().transaction_by_id(tx_id).cloned()
+    }
+
+    /// Height of the last block
+    pub fn height(&self) -> u64 {
+        self.state().height
+    }
+
+    /// Number of event subscriptions of the websocket connections
+    pub fn subscriber_count(&self) -> usize {
+        self.state().subscribers.len()
+    }
+
+    /// Close the websocket connections, e.g. to test the reconnection of
+    /// subscriptions. Their subscriptions are removed at once so that they
+    /// miss the blocks committed until they reconnect.
+    pub fn disconnect_clients(&self) {
+        let mut state = self.state();
+
+        state.subscribers.clear();
+        state.connection_generation += 1;
+    }
+
+    fn state(&self) -> MutexGuard<NodeState> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake up the listener blocked on accept so that it sees the shutdown
+        let _ = TcpStream::connect(self.address);
+
+        if let Some(server) = self.server.take() {
+            let _ = server.join();
+        }
+    }
+}
+
+fn lock(state: &Mutex<NodeState>) -> MutexGuard<NodeState> {
+    state.lock().expect("Mock node state lock is poisoned")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+
+    use chain_core::init::coin::Coin;
+    use chain_core::tx::TransactionId;
+    use client_common::tendermint::{Client, WebsocketRpcClient};
+    use client_common::SignedTransaction;
+    use parity_scale_codec::Encode;
+    use serde_json::Value;
+
+    use crate::address::staking_address_from_public_key;
+    use crate::signer::KeyPairSigner;
+    use crate::staking_transaction::query_staked_state;
+    use crate::transfer_transaction::tests::{builder_of, key_pair};
+    use crate::transfer_transaction::{sign_input, signed_transfer_transaction, to_tx_aux};
+    use crate::tx_aux::{signed_transaction_to_tx_aux, Features};
+
+    fn http_get(node: &MockNode, path: &str) -> Value {
+        let mut stream = TcpStream::connect(node.address()).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        let body = response.splitn(2, "\r\n\r\n").nth(1).unwrap();
+
+        serde_json::from_str(body).unwrap()
+    }
+
+    #[test]
+    fn query_staked_state_should_return_staked_state_of_mock_node() {
+        let node = MockNode::start().unwrap();
+        let (_, public_key) = key_pair(1);
+        let address = staking_address_from_public_key(&public_key);
+
+        assert_eq!(
+            query_staked_state(&address, &node.websocket_address()).unwrap(),
+            None
+        );
+
+        let mut staked_state = StakedState::default(address);
+        staked_state.nonce = 3;
+        staked_state.bonded = Coin::new(100_000_000).unwrap();
+        node.set_staked_state(staked_state.clone());
+
+        assert_eq!(
+            query_staked_state(&address, &node.websocket_address()).unwrap(),
+            Some(staked_state)
+        );
+    }
+
+    #[test]
+    fn mock_abci_features_should_obfuscate_with_mock_node() {
+        let node = MockNode::start().unwrap();
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+
+        let tx_aux = to_tx_aux(&builder, &node.websocket_address(), Features::MockAbci).unwrap();
+
+        let (tx, witness) = signed_transfer_transaction(&builder).unwrap();
+        let expected = signed_transaction_to_tx_aux(
+            SignedTransaction::TransferTransaction(tx, witness),
+            "",
+            Features::MockObfuscation,
+        )
+        .unwrap();
+        assert_eq!(tx_aux.encode(), expected.encode());
+    }
+
+    #[test]
+    fn broadcasted_transaction_should_be_committed_and_found() {
+        let node = MockNode::start().unwrap();
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+        let tx_aux = to_tx_aux(&builder, "", Features::MockObfuscation).unwrap();
+        let tx_id = tx_aux.tx_id();
+
+        let client = WebsocketRpcClient::new(&node.websocket_address()).unwrap();
+        client.broadcast_transaction(&tx_aux.encode()).unwrap();
+
+        assert_eq!(node.height(), 1);
+        let committed = node.transaction(&tx_id).unwrap();
+        assert_eq!(committed.tx_aux.encode(), tx_aux.encode());
+        assert_eq!(committed.height, 1);
+
+        let search = http_get(
+            &node,
+            &format!(
+                "/tx_search?query=%22valid_txs.txid='{}'%22",
+                hex::encode(tx_id)
+            ),
+        );
+        assert_eq!(search["result"]["total_count"], "1");
+
+        let search = http_get(
+            &node,
+            &format!(
+                "/tx_search?query=\"valid_txs.txid='{}'\"",
+                hex::encode([0; 32])
+            ),
+        );
+        assert_eq!(search["result"]["total_count"], "0");
+
+        let tx = http_get(
+            &node,
+            &format!("/tx?hash=0x{}", hex::encode(committed.hash)),
+        );
+        assert_eq!(tx["result"]["height"], "1");
+        assert_eq!(
+            tx["result"]["tx"],
+            Value::String(base64::encode(&tx_aux.encode()))
+        );
+    }
+
+    #[test]
+    fn block_should_return_block_of_committed_transaction() {
+        let node = MockNode::start().unwrap();
+        let mut builder = builder_of(&[key_pair(1)]);
+        let (private_key, public_key) = key_pair(1);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+        let tx_aux = to_tx_aux(&builder, "", Features::MockObfuscation).unwrap();
+
+        let client = WebsocketRpcClient::new(&node.websocket_address()).unwrap();
+        client.broadcast_transaction(&tx_aux.encode()).unwrap();
+
+        let block = http_get(&node, "/block?height=1");
+        assert_eq!(block["result"]["block"]["header"]["height"], "1");
+        assert_eq!(
+            block["result"]["block"]["data"]["txs"][0],
+            Value::String(base64::encode(&tx_aux.encode()))
+        );
+
+        let block = http_get(&node, "/block?height=2");
+        assert_eq!(block["error"]["code"], -32603);
+    }
+
+    #[test]
+    fn broadcast_should_reject_undecodable_transaction() {
+        let node = MockNode::start().unwrap();
+
+        let client = WebsocketRpcClient::new(&node.websocket_address()).unwrap();
+        let _ = client.broadcast_transaction(&[0xff, 0xff]);
+
+        assert!(node.transactions().is_empty());
+        assert_eq!(
+            http_get(&node, "/status")["result"]["sync_info"]["latest_block_height"],
+            "0"
+        );
+    }
+
+    #[test]
+    fn unknown_method_should_return_json_rpc_error() {
+        let node = MockNode::start().unwrap();
+
+        let response = http_get(&node, "/unknown_method");
+
+        assert_eq!(response["error"]["code"], -32601);
+    }
+}
@@ -0,0 +1,496 @@
+//! JSON-RPC methods of the mock node, shared by the websocket and HTTP
+//! transports
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chain_core::tx::TxAux;
+use client_common::SignedTransaction;
+use client_core::cipher::TransactionObfuscation;
+use parity_scale_codec::{Decode, Encode};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::schema::de::h256_from_hex;
+use crate::timespec::timespec_to_iso8601;
+use crate::tx_aux::MockTransactionCipher;
+
+use super::{lock, CommittedTransaction, NodeState, Subscriber};
+
+const NEW_BLOCK_QUERY: &str = "tm.event='NewBlock'";
+const TX_QUERY: &str = "tm.event='Tx'";
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// JSON-RPC error object
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: &'static str,
+    data: String,
+}
+
+impl RpcError {
+    fn new<D: Into<String>>(code: i64, message: &'static str, data: D) -> RpcError {
+        RpcError {
+            code,
+            message,
+            data: data.into(),
+        }
+    }
+
+    fn invalid_params<D: Into<String>>(data: D) -> RpcError {
+        RpcError::new(INVALID_PARAMS, "Invalid params", data)
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "code": self.code,
+            "message": self.message,
+            "data": self.data,
+        })
+    }
+}
+
+/// Handle the JSON-RPC request and returns its response
+pub(super) fn handle_request(state: &Mutex<NodeState>, request: &Value) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    response(id, call(state, method, &params))
+}
+
+/// Handle the JSON-RPC request of the websocket connection, which can also
+/// subscribe to events
+pub(super) fn handle_websocket_request(
+    state: &Mutex<NodeState>,
+    connection: u64,
+    events: &Sender<Value>,
+    request: &Value,
+) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "subscribe" => param_str(&params, "query", 0).and_then(|query| {
+            let query = normalize_query(&query)?;
+            lock(state).subscribers.push(Subscriber {
+                connection,
+                id: id.clone(),
+                query,
+                events: events.clone(),
+            });
+
+            Ok(json!({}))
+        }),
+        "unsubscribe" => param_str(&params, "query", 0).and_then(|query| {
+            let query = normalize_query(&query)?;
+            lock(state).subscribers.retain(|subscriber| {
+                subscriber.connection != connection || subscriber.query != query
+            });
+
+            Ok(json!({}))
+        }),
+        "unsubscribe_all" => {
+            unsubscribe_all(state, connection);
+
+            Ok(json!({}))
+        }
+        _ => return handle_request(state, request),
+    };
+
+    response(id, result)
+}
+
+/// Remove the subscriptions of the closed websocket connection
+pub(super) fn unsubscribe_all(state: &Mutex<NodeState>, connection: u64) {
+    lock(state)
+        .subscribers
+        .retain(|subscriber| subscriber.connection != connection);
+}
+
+/// Response of a request which is not valid JSON
+pub(super) fn parse_error(data: String) -> Value {
+    response(
+        Value::Null,
+        Err(RpcError::new(PARSE_ERROR, "Parse error", data)),
+    )
+}
+
+/// JSON-RPC response of the result of a request
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json!({ "jsonrpc": "2.0", "id": id, "error": err.to_json() }),
+    }
+}
+
+/// Call the method with its params, either by position or by name
+fn call(state: &Mutex<NodeState>, method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "abci_query" => abci_query(state, params),
+        "abci_info" => Ok(abci_info(&lock(state))),
+        "status" => Ok(status(&lock(state))),
+        "broadcast_tx_async" | "broadcast_tx_sync" => {
+            let (transaction, check_tx, _) = broadcast(state, params)?;
+
+            Ok(json!({
+                "code": check_tx["code"],
+                "data": "",
+                "log": check_tx["log"],
+                "codespace": "",
+                "hash": hex::encode_upper(hash(&transaction)),
+            }))
+        }
+        "broadcast_tx_commit" => {
+            let (transaction, check_tx, height) = broadcast(state, params)?;
+
+            Ok(json!({
+                "check_tx": check_tx,
+                "deliver_tx": tx_result(0, ""),
+                "hash": hex::encode_upper(hash(&transaction)),
+                "height": height.unwrap_or_default().to_string(),
+            }))
+        }
+        "tx" => {
+            let hash = param_bytes(params, "hash", 0)?;
+            let state = lock(state);
+            let committed = state.transaction_by_hash(&hash).ok_or_else(|| {
+                RpcError::new(
+                    INTERNAL_ERROR,
+                    "Internal error",
+                    format!("tx ({}) not found", hex::encode_upper(&hash)),
+                )
+            })?;
+
+            Ok(committed_transaction(committed))
+        }
+        "block" => {
+            let state = lock(state);
+            let committed = committed_at(&state, params)?;
+
+            Ok(json!({
+                "block_id": { "hash": hex::encode_upper(committed.hash) },
+                "block": block(committed),
+            }))
+        }
+        "block_results" => {
+            let state = lock(state);
+            let committed = committed_at(&state, params)?;
+
+            Ok(json!({
+                "height": committed.height.to_string(),
+                "txs_results": [tx_result(0, "")],
+                "begin_block_events": null,
+                "end_block_events": null,
+                "validator_updates": null,
+                "consensus_param_updates": null,
+            }))
+        }
+        "tx_search" => {
+            let query = param_str(params, "query", 0)?;
+            let tx_id = query
+                .trim_matches('"')
+                .splitn(2, '=')
+                .collect::<Vec<&str>>();
+            let tx_id = match tx_id.as_slice() {
+                ["valid_txs.txid", tx_id] => tx_id.trim_matches('\''),
+                _ => {
+                    return Err(RpcError::invalid_params(
+                        "Only valid_txs.txid='<txid>' query is supported",
+                    ))
+                }
+            };
+            let tx_id = h256_from_hex(tx_id).map_err(RpcError::invalid_params)?;
+
+            let txs = lock(state)
+                .transaction_by_id(&tx_id)
+                .map(committed_transaction)
+                .into_iter()
+                .collect::<Vec<Value>>();
+
+            Ok(json!({
+                "total_count": txs.len().to_string(),
+                "txs": txs,
+            }))
+        }
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, "Method not found", method)),
+    }
+}
+
+fn abci_query(state: &Mutex<NodeState>, params: &Value) -> Result<Value, RpcError> {
+    let path = param_str(params, "path", 0)?;
+    let data = match param(params, "data", 1) {
+        Some(Value::String(data)) if !data.is_empty() => {
+            hex::decode(data).map_err(|err| RpcError::invalid_params(err.to_string()))?
+        }
+        _ => Vec::new(),
+    };
+    let state = lock(state);
+
+    let value = match path.as_str() {
+        "staking" => {
+            let staked_state = state
+                .staked_states
+                .iter()
+                .find(|(redeem_address, _)| redeem_address[..] == data[..])
+                .map(|(_, staked_state)| staked_state.clone());
+
+            Ok(staked_state.encode())
+        }
+        "txquery" => state
+            .tx_query_address
+            .clone()
+            .map(String::into_bytes)
+            .ok_or("Tx query address is not set"),
+        "mockencrypt" => SignedTransaction::decode(&mut data.as_slice())
+            .ok()
+            .and_then(|transaction| MockTransactionCipher.encrypt(transaction).ok())
+            .map(|tx_aux| tx_aux.encode())
+            .ok_or("Unable to decode signed transaction"),
+        _ => Err("Unknown query path"),
+    };
+
+    let (code, log, value) = match value {
+        Ok(value) => (0, "", Value::String(base64::encode(&value))),
+        Err(log) => (1, log, Value::Null),
+    };
+
+    Ok(json!({
+        "response": {
+            "code": code,
+            "log": log,
+            "info": "",
+            "index": "0",
+            "key": null,
+            "value": value,
+            "proof": null,
+            "height": state.height.to_string(),
+            "codespace": "",
+        }
+    }))
+}
+
+fn abci_info(state: &NodeState) -> Value {
+    json!({
+        "response": {
+            "data": "mock-node",
+            "version": "",
+            "app_version": "0",
+            "last_block_height": state.height.to_string(),
+            "last_block_app_hash": "",
+        }
+    })
+}
+
+fn status(state: &NodeState) -> Value {
+    json!({
+        "node_info": {
+            "network": "mock",
+            "moniker": "mock-node",
+            "version": "",
+        },
+        "sync_info": {
+            "latest_block_height": state.height.to_string(),
+            "catching_up": false,
+        },
+    })
+}
+
+/// Check the broadcasted transaction and commit it in a new block when it
+/// is a `TxAux`. Returns the transaction bytes, the check result and the
+/// height of the block of the committed transaction.
+fn broadcast(
+    state: &Mutex<NodeState>,
+    params: &Value,
+) -> Result<(Vec<u8>, Value, Option<u64>), RpcError> {
+    let transaction = param_str(params, "tx", 0)?;
+    let transaction =
+        base64::decode(&transaction).map_err(|err| RpcError::invalid_params(err.to_string()))?;
+
+    let tx_aux = match TxAux::decode(&mut transaction.as_slice()) {
+        Ok(tx_aux) => tx_aux,
+        Err(_) => return Ok((transaction, tx_result(1, "Unable to decode TxAux"), None)),
+    };
+
+    let mut state = lock(state);
+    state.height += 1;
+    let committed = CommittedTransaction {
+        tx_aux,
+        hash: hash(&transaction),
+        height: state.height,
+        time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+    };
+    publish(&mut state, &committed);
+    state.transactions.push(committed);
+
+    Ok((transaction, tx_result(0, ""), Some(state.height)))
+}
+
+/// Push the `NewBlock` and `Tx` events of the block of the committed
+/// transaction to the subscribers. Subscribers of closed connections are
+/// removed.
+fn publish(state: &mut NodeState, committed: &CommittedTransaction) {
+    let new_block = json!({
+        "type": "tendermint/event/NewBlock",
+        "value": { "block": block(committed) },
+    });
+    let tx = json!({
+        "type": "tendermint/event/Tx",
+        "value": {
+            "TxResult": {
+                "height": committed.height.to_string(),
+                "index": 0,
+                "tx": base64::encode(&committed.tx_aux.encode()),
+                "result": tx_result(0, ""),
+            },
+        },
+    });
+
+    state.subscribers.retain(|subscriber| {
+        let data = match subscriber.query.as_str() {
+            NEW_BLOCK_QUERY => &new_block,
+            _ => &tx,
+        };
+        let id = match subscriber.id {
+            Value::String(ref id) => format!("{}#event", id),
+            ref id => format!("{}#event", id),
+        };
+
+        subscriber
+            .events
+            .send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": {
+                    "query": subscriber.query,
+                    "data": data,
+                    "events": {},
+                },
+            }))
+            .is_ok()
+    });
+}
+
+/// Query without whitespaces, only the new block and transaction queries
+/// being supported
+fn normalize_query(query: &str) -> Result<String, RpcError> {
+    let query = query.split_whitespace().collect::<String>();
+
+    match query.as_str() {
+        NEW_BLOCK_QUERY | TX_QUERY => Ok(query),
+        _ => Err(RpcError::invalid_params(format!(
+            "Only {} and {} queries are supported",
+            NEW_BLOCK_QUERY, TX_QUERY
+        ))),
+    }
+}
+
+/// Committed transaction of the block of the height param, or of the last
+/// block without it
+fn committed_at<'a>(
+    state: &'a NodeState,
+    params: &Value,
+) -> Result<&'a CommittedTransaction, RpcError> {
+    let height = match param(params, "height", 0) {
+        None | Some(Value::Null) => state.height,
+        Some(Value::String(height)) => height
+            .parse()
+            .map_err(|_| RpcError::invalid_params("Invalid height param"))?,
+        Some(height) => height
+            .as_u64()
+            .ok_or_else(|| RpcError::invalid_params("Invalid height param"))?,
+    };
+
+    state.transaction_by_height(height).ok_or_else(|| {
+        RpcError::new(
+            INTERNAL_ERROR,
+            "Internal error",
+            format!(
+                "height {} must be less than or equal to the current blockchain height {}",
+                height, state.height
+            ),
+        )
+    })
+}
+
+/// Block of the committed transaction
+fn block(committed: &CommittedTransaction) -> Value {
+    json!({
+        "header": {
+            "chain_id": "mock",
+            "height": committed.height.to_string(),
+            "time": timespec_to_iso8601(committed.time)
+                .expect("Block time is the system time, within ISO-8601 date times"),
+        },
+        "data": {
+            "txs": [base64::encode(&committed.tx_aux.encode())],
+        },
+    })
+}
+
+fn committed_transaction(committed: &CommittedTransaction) -> Value {
+    json!({
+        "hash": hex::encode_upper(committed.hash),
+        "height": committed.height.to_string(),
+        "index": 0,
+        "tx_result": tx_result(0, ""),
+        "tx": base64::encode(&committed.tx_aux.encode()),
+    })
+}
+
+fn tx_result(code: u32, log: &str) -> Value {
+    json!({
+        "code": code,
+        "data": "",
+        "log": log,
+        "info": "",
+        "gas_wanted": "0",
+        "gas_used": "0",
+        "events": [],
+        "codespace": "",
+    })
+}
+
+fn hash(transaction: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&Sha256::digest(transaction));
+
+    hash
+}
+
+fn param<'a>(params: &'a Value, name: &str, index: usize) -> Option<&'a Value> {
+    match params {
+        Value::Object(params) => params.get(name),
+        Value::Array(params) => params.get(index),
+        _ => None,
+    }
+}
+
+fn param_str(params: &Value, name: &str, index: usize) -> Result<String, RpcError> {
+    param(params, name, index)
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| RpcError::invalid_params(format!("Missing {} param", name)))
+}
+
+/// Bytes param as hex with `0x` prefix in URIs, or base64 in JSON requests
+fn param_bytes(params: &Value, name: &str, index: usize) -> Result<Vec<u8>, RpcError> {
+    let value = param_str(params, name, index)?;
+
+    let bytes = if value.starts_with("0x") {
+        hex::decode(&value[2..]).ok()
+    } else {
+        base64::decode(&value).ok()
+    };
+
+    bytes.ok_or_else(|| RpcError::invalid_params(format!("Invalid {} param", name)))
+}
@@ -0,0 +1,334 @@
+//! Websocket and HTTP transports of the mock node on a single port
+//!
+//! Each connection is served by its own thread. Websocket connections are
+//! kept open, answer each text frame and push the events of their
+//! subscriptions. HTTP connections answer a single JSON-RPC `POST` or URI
+//! `GET` request and are closed.
+use std::io::{self, Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Map, Value};
+use tungstenite::Message;
+
+use super::rpc::{handle_request, handle_websocket_request, parse_error, unsubscribe_all};
+use super::{lock, NodeState};
+
+/// Maximum size of the head of an HTTP request
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// Interval at which websocket connections push the events and check
+/// whether they should be closed
+const WEBSOCKET_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+pub(super) fn serve(
+    listener: TcpListener,
+    state: Arc<Mutex<NodeState>>,
+    shutdown: Arc<AtomicBool>,
+) {
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+
+        if let Ok(stream) = stream {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || {
+                let _ = serve_connection(stream, &state, &shutdown);
+            });
+        }
+    }
+}
+
+fn serve_connection(
+    mut stream: TcpStream,
+    state: &Mutex<NodeState>,
+    shutdown: &AtomicBool,
+) -> io::Result<()> {
+    let (head, rest) = read_head(&mut stream)?;
+    let request = HttpRequest::parse(&head)?;
+
+    if request.is_websocket_upgrade() {
+        // The handshake is read again by tungstenite from the buffered head
+        let mut buffered = head;
+        buffered.extend_from_slice(&rest);
+
+        return serve_websocket(
+            Buffered {
+                buffered: Cursor::new(buffered),
+                stream,
+            },
+            state,
+            shutdown,
+        );
+    }
+
+    let response = match request.method.as_str() {
+        "POST" => {
+            let body = read_body(&mut stream, rest, request.content_length)?;
+            match serde_json::from_slice::<Value>(&body) {
+                Ok(body) => handle_request(state, &body),
+                Err(err) => parse_error(err.to_string()),
+            }
+        }
+        _ => handle_request(state, &request.uri_request()),
+    };
+
+    let body = response.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+
+    stream.flush()
+}
+
+fn serve_websocket(
+    stream: Buffered,
+    state: &Mutex<NodeState>,
+    shutdown: &AtomicBool,
+) -> io::Result<()> {
+    let mut websocket = tungstenite::accept(stream)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    // Reads time out to push the events in between
+    websocket
+        .get_ref()
+        .stream
+        .set_read_timeout(Some(WEBSOCKET_POLL_INTERVAL))?;
+
+    let (connection, generation) = {
+        let mut state = lock(state);
+        state.next_connection += 1;
+
+        (state.next_connection, state.connection_generation)
+    };
+    let (events, subscribed_events) = channel();
+
+    loop {
+        if shutdown.load(Ordering::SeqCst) || lock(state).connection_generation != generation {
+            let _ = websocket.close(None);
+            let _ = websocket.write_pending();
+            break;
+        }
+
+        let response = match websocket.read_message() {
+            Ok(Message::Text(text)) => Some(match serde_json::from_str::<Value>(&text) {
+                Ok(request) => handle_websocket_request(state, connection, &events, &request),
+                Err(err) => parse_error(err.to_string()),
+            }),
+            Ok(Message::Close(_)) => break,
+            Ok(_) => None,
+            Err(tungstenite::Error::Io(ref err))
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                None
+            }
+            Err(_) => break,
+        };
+
+        let messages = response.into_iter().chain(subscribed_events.try_iter());
+        if messages
+            .map(|message| websocket.write_message(Message::Text(message.to_string())))
+            .any(|result| result.is_err())
+        {
+            break;
+        }
+    }
+
+    unsubscribe_all(state, connection);
+
+    Ok(())
+}
+
+/// Read the request head up to the blank line. Returns the head and the
+/// bytes read past it.
+fn read_head(stream: &mut TcpStream) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 4096];
+
+    loop {
+        if let Some(end) = find(&buffer, b"\r\n\r\n") {
+            let rest = buffer.split_off(end + 4);
+            return Ok((buffer, rest));
+        }
+        if buffer.len() > MAX_HEAD_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Request head is too large",
+            ));
+        }
+
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn read_body(
+    stream: &mut TcpStream,
+    mut body: Vec<u8>,
+    content_length: usize,
+) -> io::Result<Vec<u8>> {
+    if body.len() < content_length {
+        let mut remaining = vec![0; content_length - body.len()];
+        stream.read_exact(&mut remaining)?;
+        body.extend_from_slice(&remaining);
+    }
+    body.truncate(content_length);
+
+    Ok(body)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+struct HttpRequest {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    content_length: usize,
+}
+
+impl HttpRequest {
+    fn parse(head: &[u8]) -> io::Result<HttpRequest> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "Invalid HTTP request");
+
+        let head = std::str::from_utf8(head).map_err(|_| invalid())?;
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().ok_or_else(invalid)?.split(' ');
+        let method = request_line.next().ok_or_else(invalid)?.to_owned();
+        let target = request_line.next().ok_or_else(invalid)?.to_owned();
+
+        let headers = lines
+            .filter_map(|line| {
+                let mut header = line.splitn(2, ':');
+                let name = header.next()?.trim().to_ascii_lowercase();
+                let value = header.next()?.trim().to_owned();
+
+                Some((name, value))
+            })
+            .collect::<Vec<(String, String)>>();
+        let content_length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .map(|(_, value)| value.parse().map_err(|_| invalid()))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(HttpRequest {
+            method,
+            target,
+            headers,
+            content_length,
+        })
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_websocket_upgrade(&self) -> bool {
+        self.header("upgrade")
+            .map(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+            .unwrap_or(false)
+    }
+
+    /// JSON-RPC request of a URI request, e.g.
+    /// `/tx_search?query="valid_txs.txid='...'"`
+    fn uri_request(&self) -> Value {
+        let mut target = self.target.splitn(2, '?');
+        let method = target.next().unwrap_or("").trim_start_matches('/');
+
+        let params = target
+            .next()
+            .unwrap_or("")
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let mut param = param.splitn(2, '=');
+                let name = percent_decode(param.next().unwrap_or(""));
+                let value = percent_decode(param.next().unwrap_or(""));
+
+                (name, Value::String(value.trim_matches('"').to_owned()))
+            })
+            .collect::<Map<String, Value>>();
+
+        json!({
+            "jsonrpc": "2.0",
+            "id": -1,
+            "method": method,
+            "params": params,
+        })
+    }
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = if bytes[i] == b'%' && i + 2 < bytes.len() {
+            std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Connection stream replaying the bytes already read from it
+struct Buffered {
+    buffered: Cursor<Vec<u8>>,
+    stream: TcpStream,
+}
+
+impl Read for Buffered {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if (self.buffered.position() as usize) < self.buffered.get_ref().len() {
+            self.buffered.read(buf)
+        } else {
+            self.stream.read(buf)
+        }
+    }
+}
+
+impl Write for Buffered {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
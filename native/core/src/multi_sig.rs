@@ -0,0 +1,182 @@
+//! Multi-signature transfer addresses and MuSig signatures
+//!
+//! A signing session is driven by `MultiSigBuilder`, which is restored from
+//! and saved to its incomplete bytes between each round so that co-signers
+//! can exchange their nonce commitments, nonces and partial signatures.
+use chain_core::common::H256;
+use chain_core::tx::data::address::ExtendedAddr;
+use client_common::{ErrorKind, MultiSigAddress, PublicKey, Result, ResultExt, SECP};
+use secp256k1::schnorrsig::{schnorr_verify, SchnorrSignature};
+use secp256k1::Message;
+
+use crate::network::NetworkDefinition;
+
+pub use client_core::multi_sig::MultiSigBuilder;
+
+/// Returns the transfer address of `required_signers` out of the public keys
+pub fn multi_sig_transfer_address(
+    network: &NetworkDefinition,
+    public_keys: Vec<PublicKey>,
+    self_public_key: PublicKey,
+    required_signers: usize,
+) -> Result<String> {
+    let multi_sig_address = MultiSigAddress::new(public_keys, self_public_key, required_signers)?;
+
+    network.encode_transfer_address(&ExtendedAddr::from(multi_sig_address))
+}
+
+/// Verify the final signature of the message against the combined public
+/// key of all co-signers
+pub fn verify_signature(
+    signature: &[u8],
+    message: &H256,
+    mut public_keys: Vec<PublicKey>,
+) -> Result<bool> {
+    let signature = SchnorrSignature::from_default(signature).chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            "Unable to restore signature",
+        )
+    })?;
+    let message = Message::from_slice(message)
+        .chain(|| (ErrorKind::DeserializationError, "Unable to form message"))?;
+
+    // Sort the public keys to keep the order consistency
+    public_keys.sort();
+    let combined_public_key = PublicKey::combine(&public_keys)?.0;
+
+    Ok(SECP.with(|secp| {
+        schnorr_verify(&secp, &message, &signature, &combined_public_key.into()).is_ok()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use client_common::PrivateKey;
+
+    use crate::network::{network_by_name, MAINNET};
+
+    const MESSAGE: H256 = [7; 32];
+
+    fn key_pair(secret: u8) -> (PublicKey, PrivateKey) {
+        let private_key = PrivateKey::deserialize_from(&[secret; 32]).unwrap();
+
+        (PublicKey::from(&private_key), private_key)
+    }
+
+    /// Nonce commitment, nonce or partial signature exchanged in a round
+    type Round = (
+        fn(&mut MultiSigBuilder) -> H256,
+        fn(&mut MultiSigBuilder, &PublicKey, H256),
+    );
+
+    /// Run a round of the session, each signer restoring its session from
+    /// the incomplete bytes
+    fn exchange(sessions: &mut [Vec<u8>], public_keys: &[PublicKey], round: Round) {
+        let (generate, add) = round;
+        let mut values = Vec::new();
+        for session in sessions.iter_mut() {
+            let mut builder = MultiSigBuilder::from_incomplete_insecure(session.clone()).unwrap();
+            values.push(generate(&mut builder));
+            *session = builder.to_incomplete();
+        }
+        for (i, session) in sessions.iter_mut().enumerate() {
+            let mut builder = MultiSigBuilder::from_incomplete_insecure(session.clone()).unwrap();
+            for (j, value) in values.iter().enumerate() {
+                if i != j {
+                    add(&mut builder, &public_keys[j], *value);
+                }
+            }
+            *session = builder.to_incomplete();
+        }
+    }
+
+    /// Run a full signing session of the signer secrets and returns the
+    /// final signature
+    fn sign_message(secrets: &[u8]) -> Vec<u8> {
+        let public_keys: Vec<PublicKey> =
+            secrets.iter().map(|secret| key_pair(*secret).0).collect();
+        let mut sessions: Vec<Vec<u8>> = secrets
+            .iter()
+            .map(|secret| {
+                let (public_key, private_key) = key_pair(*secret);
+                MultiSigBuilder::new(MESSAGE, public_keys.clone(), public_key, private_key)
+                    .unwrap()
+                    .to_incomplete()
+            })
+            .collect();
+
+        exchange(
+            &mut sessions,
+            &public_keys,
+            (
+                |builder| builder.nonce_commitment().unwrap(),
+                |builder, public_key, value| {
+                    builder.add_nonce_commitment(public_key, value).unwrap()
+                },
+            ),
+        );
+        exchange(
+            &mut sessions,
+            &public_keys,
+            (
+                |builder| builder.nonce().unwrap(),
+                |builder, public_key, value| builder.add_nonce(public_key, &value).unwrap(),
+            ),
+        );
+        exchange(
+            &mut sessions,
+            &public_keys,
+            (
+                |builder| builder.partial_signature().unwrap(),
+                |builder, public_key, value| {
+                    builder.add_partial_signature(public_key, value).unwrap()
+                },
+            ),
+        );
+
+        MultiSigBuilder::from_incomplete_insecure(sessions[0].clone())
+            .unwrap()
+            .signature()
+            .unwrap()
+            .serialize_default()
+            .to_vec()
+    }
+
+    #[test]
+    fn multi_sig_transfer_address_should_encode_network_address() {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let public_keys = vec![key_pair(1).0, key_pair(2).0];
+
+        let address =
+            multi_sig_transfer_address(&mainnet, public_keys.clone(), public_keys[0].clone(), 1)
+                .unwrap();
+
+        assert!(address.starts_with("cro1"));
+        assert!(multi_sig_transfer_address(
+            &mainnet,
+            public_keys.clone(),
+            public_keys[0].clone(),
+            3
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_signature_should_verify_session_signature() {
+        let public_keys = vec![key_pair(1).0, key_pair(2).0];
+
+        let signature = sign_message(&[1, 2]);
+
+        assert!(verify_signature(&signature, &MESSAGE, public_keys.clone()).unwrap());
+        assert!(!verify_signature(&signature, &[8; 32], public_keys).unwrap());
+        assert!(!verify_signature(&signature, &MESSAGE, vec![key_pair(3).0]).unwrap());
+    }
+
+    #[test]
+    fn verify_signature_should_reject_malformed_signature() {
+        assert!(verify_signature(&[1; 10], &MESSAGE, vec![key_pair(1).0]).is_err());
+    }
+}
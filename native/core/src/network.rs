@@ -0,0 +1,434 @@
+//! Registry of named networks
+//!
+//! A network decides the bech32 human readable part of transfer addresses and
+//! the BIP44 coin type used for key derivation. Besides the built-in Mainnet,
+//! Testnet and Devnet, private chains can be registered with their own chain
+//! hex id.
+use std::sync::RwLock;
+
+use bech32::{FromBase32, ToBase32};
+use chain_core::common::HASH_SIZE_256;
+use chain_core::tx::data::address::ExtendedAddr;
+use client_common::{Error, ErrorKind, PrivateKey, PublicKey, Result, ResultExt};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use tiny_hderive::bip32::ExtendedPrivKey;
+
+use crate::schema::de;
+
+pub const MAINNET: &str = "Mainnet";
+pub const TESTNET: &str = "Testnet";
+pub const DEVNET: &str = "Devnet";
+
+const MAINNET_CHAIN_HEX_ID: u8 = 0x2A;
+const TESTNET_CHAIN_HEX_ID: u8 = 0x42;
+
+const MAINNET_BIP44_COIN_TYPE: u32 = 394;
+const TESTNET_BIP44_COIN_TYPE: u32 = 1;
+
+/// Coin type is hardened in the derivation path so it must fit in 31 bits
+const MAX_BIP44_COIN_TYPE: u32 = 0x7FFF_FFFF;
+
+lazy_static! {
+    static ref REGISTRY: RwLock<Vec<NetworkDefinition>> = RwLock::new(vec![
+        NetworkDefinition {
+            name: MAINNET.to_owned(),
+            chain_hex_id: Some(MAINNET_CHAIN_HEX_ID),
+            address_prefix: "cro".to_owned(),
+            bip44_coin_type: MAINNET_BIP44_COIN_TYPE,
+        },
+        NetworkDefinition {
+            name: TESTNET.to_owned(),
+            chain_hex_id: Some(TESTNET_CHAIN_HEX_ID),
+            address_prefix: "tcro".to_owned(),
+            bip44_coin_type: TESTNET_BIP44_COIN_TYPE,
+        },
+        // Devnet has no fixed chain hex id, each devnet has to be registered
+        NetworkDefinition {
+            name: DEVNET.to_owned(),
+            chain_hex_id: None,
+            address_prefix: "dcro".to_owned(),
+            bip44_coin_type: TESTNET_BIP44_COIN_TYPE,
+        },
+    ]);
+}
+
+/// A named network with its address and key derivation parameters
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkDefinition {
+    pub name: String,
+    #[serde(default, deserialize_with = "de::optional_chain_hex_id")]
+    pub chain_hex_id: Option<u8>,
+    pub address_prefix: String,
+    pub bip44_coin_type: u32,
+}
+
+impl NetworkDefinition {
+    /// Returns the BIP44 derivation path of the account and index
+    pub fn bip44_path(&self, account: u32, index: u32) -> String {
+        format!("m/44'/{}'/{}'/0/{}", self.bip44_coin_type, account, index)
+    }
+
+    /// Encode the transfer address with the network address prefix
+    pub fn encode_transfer_address(&self, address: &ExtendedAddr) -> Result<String> {
+        match address {
+            ExtendedAddr::OrTree(root_hash) => {
+                bech32::encode(&self.address_prefix, root_hash.to_base32()).chain(|| {
+                    (
+                        ErrorKind::SerializationError,
+                        "Unable to encode transfer address",
+                    )
+                })
+            }
+        }
+    }
+
+    /// Decode a transfer address belonging to this network
+    pub fn decode_transfer_address(&self, address: &str) -> Result<ExtendedAddr> {
+        let (prefix, data) = bech32::decode(address).chain(|| {
+            (
+                ErrorKind::DeserializationError,
+                "Invalid bech32 transfer address",
+            )
+        })?;
+        if prefix != self.address_prefix {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Transfer address prefix {} does not belong to network {}",
+                    prefix, self.name
+                ),
+            ));
+        }
+
+        let root_hash = Vec::<u8>::from_base32(&data).chain(|| {
+            (
+                ErrorKind::DeserializationError,
+                "Invalid bech32 transfer address",
+            )
+        })?;
+        if root_hash.len() != HASH_SIZE_256 {
+            return Err(Error::new(
+                ErrorKind::DeserializationError,
+                "Transfer address should contain a 32 bytes root hash",
+            ));
+        }
+
+        let mut tree = [0u8; HASH_SIZE_256];
+        tree.copy_from_slice(&root_hash);
+
+        Ok(ExtendedAddr::OrTree(tree))
+    }
+
+    /// Derive the key pair of account and index from the seed
+    pub fn derive_key_pair(
+        &self,
+        seed: &[u8],
+        account: u32,
+        index: u32,
+    ) -> Result<(PublicKey, PrivateKey)> {
+        let path = self.bip44_path(account, index);
+        let extended_private_key = ExtendedPrivKey::derive(seed, path.as_str()).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unable to derive key pair of path {}", path),
+            )
+        })?;
+
+        let private_key = PrivateKey::deserialize_from(&extended_private_key.secret())?;
+        let public_key = PublicKey::from(&private_key);
+
+        Ok((public_key, private_key))
+    }
+
+    fn verify(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Network name cannot be empty",
+            ));
+        }
+        let is_valid_prefix = !self.address_prefix.is_empty()
+            && self.address_prefix.len() <= 83
+            && self
+                .address_prefix
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+        if !is_valid_prefix {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Address prefix should be 1 to 83 lowercase alphanumeric characters",
+            ));
+        }
+        if self.bip44_coin_type > MAX_BIP44_COIN_TYPE {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "BIP44 coin type should be less than 2^31",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn has_same_parameters(&self, other: &NetworkDefinition) -> bool {
+        self.address_prefix == other.address_prefix && self.bip44_coin_type == other.bip44_coin_type
+    }
+}
+
+/// Register a network. Several chain hex ids may be registered under the
+/// same name as long as they share the same address prefix and coin type.
+/// The address prefix identifies the network of a transfer address, so it
+/// cannot be shared by networks of different names.
+pub fn insert_network(network: NetworkDefinition) -> Result<()> {
+    network.verify()?;
+
+    let mut registry = REGISTRY.write().expect("Network registry lock is poisoned");
+
+    if let Some(existing) = registry.iter().find(|existing| {
+        existing.address_prefix == network.address_prefix && existing.name != network.name
+    }) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Address prefix {} is already registered to network {}",
+                network.address_prefix, existing.name
+            ),
+        ));
+    }
+
+    if let Some(existing) = registry
+        .iter()
+        .find(|existing| existing.name == network.name)
+    {
+        if !existing.has_same_parameters(&network) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Network {} is already registered with different address prefix or coin type",
+                    network.name
+                ),
+            ));
+        }
+    }
+    if let Some(chain_hex_id) = network.chain_hex_id {
+        if let Some(existing) = registry
+            .iter()
+            .find(|existing| existing.chain_hex_id == Some(chain_hex_id))
+        {
+            if existing.name == network.name {
+                return Ok(());
+            }
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Chain hex id {:02X} is already registered to network {}",
+                    chain_hex_id, existing.name
+                ),
+            ));
+        }
+    } else if registry
+        .iter()
+        .any(|existing| existing.name == network.name)
+    {
+        return Ok(());
+    }
+
+    registry.push(network);
+
+    Ok(())
+}
+
+/// Find the network of the chain hex id
+pub fn network_by_chain_hex_id(chain_hex_id: u8) -> Result<NetworkDefinition> {
+    REGISTRY
+        .read()
+        .expect("Network registry lock is poisoned")
+        .iter()
+        .find(|network| network.chain_hex_id == Some(chain_hex_id))
+        .cloned()
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unknown chain hex id {:02X}, register it as a network first",
+                    chain_hex_id
+                ),
+            )
+        })
+}
+
+/// Find the network by name
+pub fn network_by_name(name: &str) -> Result<NetworkDefinition> {
+    REGISTRY
+        .read()
+        .expect("Network registry lock is poisoned")
+        .iter()
+        .find(|network| network.name == name)
+        .cloned()
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                format!("Unrecognized network {}", name),
+            )
+        })
+}
+
+/// Find the network of the transfer address by its prefix and decode it.
+/// Bech32 addresses may be all uppercase, while registered prefixes are
+/// lowercase.
+pub fn decode_transfer_address(address: &str) -> Result<(NetworkDefinition, ExtendedAddr)> {
+    let separator = address.rfind('1').chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            "Invalid bech32 transfer address",
+        )
+    })?;
+    let prefix = address[..separator].to_ascii_lowercase();
+
+    let network = REGISTRY
+        .read()
+        .expect("Network registry lock is poisoned")
+        .iter()
+        .find(|network| network.address_prefix == prefix)
+        .cloned()
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                format!("Unknown transfer address prefix {}", prefix),
+            )
+        })?;
+    let extended_addr = network.decode_transfer_address(address)?;
+
+    Ok((network, extended_addr))
+}
+
+/// Returns all registered networks
+pub fn registered_networks() -> Vec<NetworkDefinition> {
+    REGISTRY
+        .read()
+        .expect("Network registry lock is poisoned")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn private_network(name: &str, chain_hex_id: u8, address_prefix: &str) -> NetworkDefinition {
+        NetworkDefinition {
+            name: name.to_owned(),
+            chain_hex_id: Some(chain_hex_id),
+            address_prefix: address_prefix.to_owned(),
+            bip44_coin_type: TESTNET_BIP44_COIN_TYPE,
+        }
+    }
+
+    #[test]
+    fn built_in_networks_should_be_registered() {
+        let mainnet = network_by_chain_hex_id(MAINNET_CHAIN_HEX_ID).unwrap();
+        assert_eq!(mainnet.name, MAINNET);
+        assert_eq!(mainnet.address_prefix, "cro");
+        assert_eq!(mainnet.bip44_path(0, 1), "m/44'/394'/0'/0/1");
+
+        let testnet = network_by_name(TESTNET).unwrap();
+        assert_eq!(testnet.chain_hex_id, Some(TESTNET_CHAIN_HEX_ID));
+
+        let devnet = network_by_name(DEVNET).unwrap();
+        assert_eq!(devnet.chain_hex_id, None);
+    }
+
+    #[test]
+    fn transfer_address_should_round_trip() {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address = "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4";
+
+        let extended_addr = mainnet.decode_transfer_address(address).unwrap();
+        assert_eq!(
+            mainnet.encode_transfer_address(&extended_addr).unwrap(),
+            address
+        );
+
+        let (network, decoded) = decode_transfer_address(address).unwrap();
+        assert_eq!(network.name, MAINNET);
+        assert_eq!(decoded, extended_addr);
+    }
+
+    #[test]
+    fn decode_transfer_address_should_reject_address_of_other_network() {
+        let testnet = network_by_name(TESTNET).unwrap();
+        let err = testnet
+            .decode_transfer_address(
+                "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4",
+            )
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Transfer address prefix cro does not belong to network Testnet"));
+    }
+
+    #[test]
+    fn decode_transfer_address_should_reject_unknown_prefix() {
+        assert!(decode_transfer_address("unknown1qqqqqq").is_err());
+        assert!(decode_transfer_address("cro1invalid").is_err());
+    }
+
+    #[test]
+    fn insert_network_should_register_private_network() {
+        insert_network(private_network("CoreTestPrivate", 0xA0, "pcro")).unwrap();
+        // Registering the same network twice is a no-op
+        insert_network(private_network("CoreTestPrivate", 0xA0, "pcro")).unwrap();
+        insert_network(private_network("CoreTestPrivate", 0xA1, "pcro")).unwrap();
+
+        assert_eq!(
+            network_by_chain_hex_id(0xA0).unwrap().name,
+            "CoreTestPrivate"
+        );
+        assert_eq!(
+            network_by_chain_hex_id(0xA1).unwrap().name,
+            "CoreTestPrivate"
+        );
+        assert!(registered_networks()
+            .iter()
+            .any(|network| network.chain_hex_id == Some(0xA1)));
+    }
+
+    #[test]
+    fn insert_network_should_reject_conflicting_network() {
+        insert_network(private_network("CoreTestConflict", 0xB0, "qcro")).unwrap();
+
+        assert!(insert_network(private_network("CoreTestConflict", 0xB1, "rcro")).is_err());
+        assert!(insert_network(private_network("CoreTestOther", 0xB0, "qcro")).is_err());
+        assert!(insert_network(private_network("CoreTestInvalid", 0xB2, "Q-CRO")).is_err());
+        assert!(insert_network(private_network("", 0xB3, "qcro")).is_err());
+    }
+
+    #[test]
+    fn insert_network_should_reject_address_prefix_of_other_network() {
+        let err = insert_network(private_network("CoreTestPrefix", 0xB4, "tcro")).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Address prefix tcro is already registered to network Testnet"));
+    }
+
+    #[test]
+    fn decode_transfer_address_should_find_network_of_uppercase_address() {
+        let address = "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4";
+
+        let (network, decoded) = decode_transfer_address(&address.to_uppercase()).unwrap();
+
+        assert_eq!(network.name, MAINNET);
+        assert_eq!(decoded, decode_transfer_address(address).unwrap().1);
+    }
+
+    #[test]
+    fn network_by_chain_hex_id_should_reject_unknown_chain_hex_id() {
+        let err = network_by_chain_hex_id(0xFF).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Unknown chain hex id FF, register it as a network first"));
+    }
+}
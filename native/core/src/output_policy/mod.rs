@@ -0,0 +1,180 @@
+//! Policy checks of transaction outputs before signing
+mod policy;
+
+use chain_core::common::Timespec;
+use chain_core::init::coin::{sum_coins, Coin};
+use serde::Deserialize;
+
+use crate::schema::types::{network_of_chain_hex_id, to_tx_outs, OutputOptions};
+use crate::schema::{de, SchemaError, SchemaResult};
+
+pub use policy::{
+    check_output_policy, OutputPolicy, PolicyContext, PolicyViolation, ViolationCode,
+};
+
+/// Outputs of a transaction and the policy to check them against
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckPolicyOptions {
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+    /// Outputs spent by the inputs
+    #[serde(default)]
+    pub inputs: Vec<OutputOptions>,
+    /// Amount available when there is no input
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    pub input_amount: Option<Coin>,
+    pub outputs: Vec<OutputOptions>,
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    pub fee: Option<Coin>,
+    pub block_time: Timespec,
+    #[serde(default)]
+    pub policy: OutputPolicyOptions,
+}
+
+impl CheckPolicyOptions {
+    /// Returns every rule of the policy the outputs do not satisfy
+    pub fn check(&self) -> SchemaResult<Vec<PolicyViolation>> {
+        let network = network_of_chain_hex_id(self.chain_hex_id, "chainHexId")?;
+        let inputs = to_tx_outs(&self.inputs, &network, "inputs")?;
+        let outputs = to_tx_outs(&self.outputs, &network, "outputs")?;
+        if let Some(change_output) = self.policy.change_output {
+            if change_output >= outputs.len() {
+                return Err(SchemaError::new(
+                    "policy.changeOutput",
+                    format!(
+                        "Change output {} is out of bound of {} outputs",
+                        change_output,
+                        outputs.len()
+                    ),
+                ));
+            }
+        }
+
+        let input_amount = match self.input_amount {
+            Some(input_amount) => Some(input_amount),
+            None if inputs.is_empty() => None,
+            None => Some(
+                sum_coins(inputs.iter().map(|input| input.value))
+                    .map_err(|_| SchemaError::new("inputs", "Input amount exceeds maximum coin"))?,
+            ),
+        };
+        let input_addresses: Vec<_> = inputs.into_iter().map(|input| input.address).collect();
+
+        Ok(check_output_policy(
+            &self.policy.to_output_policy(),
+            &PolicyContext {
+                input_addresses: &input_addresses,
+                input_amount,
+                outputs: &outputs,
+                fee: self.fee,
+                block_time: self.block_time,
+            },
+        ))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputPolicyOptions {
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    pub min_output_value: Option<Coin>,
+    #[serde(default)]
+    pub max_outputs: Option<usize>,
+    #[serde(default)]
+    pub allow_self_send: bool,
+    #[serde(default)]
+    pub change_output: Option<usize>,
+    #[serde(default)]
+    pub allow_duplicate_outputs: bool,
+    #[serde(default)]
+    pub max_time_lock: Option<Timespec>,
+}
+
+impl OutputPolicyOptions {
+    /// Zero value outputs are not allowed unless a minimum output value is
+    /// provided
+    pub fn to_output_policy(&self) -> OutputPolicy {
+        OutputPolicy {
+            min_output_value: self.min_output_value.unwrap_or_else(Coin::unit),
+            max_outputs: self.max_outputs,
+            allow_self_send: self.allow_self_send,
+            change_output: self.change_output,
+            allow_duplicate_outputs: self.allow_duplicate_outputs,
+            max_time_lock: self.max_time_lock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::schema::from_json_value;
+
+    const ADDRESS: &str = "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4";
+
+    #[test]
+    fn check_should_sum_input_amount_from_inputs() {
+        let options: CheckPolicyOptions = from_json_value(json!({
+            "chainHexId": [0x2A],
+            "inputs": [{ "address": ADDRESS, "value": "10" }],
+            "outputs": [{ "address": ADDRESS, "value": "11" }],
+            "blockTime": 0,
+            "policy": { "allowSelfSend": true },
+        }))
+        .unwrap();
+
+        let violations = options.check().unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, ViolationCode::InsufficientInput);
+    }
+
+    #[test]
+    fn check_should_reject_zero_value_output_by_default() {
+        let options: CheckPolicyOptions = from_json_value(json!({
+            "chainHexId": [0x2A],
+            "outputs": [{ "address": ADDRESS, "value": "0" }],
+            "blockTime": 0,
+        }))
+        .unwrap();
+
+        let violations = options.check().unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, ViolationCode::BelowMinOutputValue);
+        assert_eq!(violations[0].path, "outputs[0].value");
+    }
+
+    #[test]
+    fn check_should_reject_change_output_out_of_bound() {
+        let options: CheckPolicyOptions = from_json_value(json!({
+            "chainHexId": [0x2A],
+            "outputs": [{ "address": ADDRESS, "value": "1" }],
+            "blockTime": 0,
+            "policy": { "changeOutput": 1 },
+        }))
+        .unwrap();
+
+        let err = options.check().unwrap_err();
+
+        assert!(err.to_string().contains("`policy.changeOutput`"));
+    }
+
+    #[test]
+    fn check_should_reject_output_address_of_other_network() {
+        let options: CheckPolicyOptions = from_json_value(json!({
+            "chainHexId": [0x42],
+            "outputs": [{ "address": ADDRESS, "value": "1" }],
+            "blockTime": 0,
+        }))
+        .unwrap();
+
+        let err = options.check().unwrap_err();
+
+        assert!(err.to_string().contains("`outputs[0].address`"));
+    }
+}
@@ -0,0 +1,386 @@
+use chain_core::common::Timespec;
+use chain_core::init::coin::{sum_coins, Coin};
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::data::output::TxOut;
+
+/// `valid_from` beyond this bound, i.e. year 2286, is most likely a
+/// millisecond timestamp mistaken for seconds
+const MAX_SECONDS_TIMESPEC: Timespec = 10_000_000_000;
+
+/// Policy the outputs of a transaction should satisfy before signing
+#[derive(Debug, Clone)]
+pub struct OutputPolicy {
+    /// Minimum value of each output
+    pub min_output_value: Coin,
+    /// Maximum number of outputs
+    pub max_outputs: Option<usize>,
+    /// Allow outputs to the addresses of the inputs
+    pub allow_self_send: bool,
+    /// Index of the output returning the change to an input address, which
+    /// is not a self-send
+    pub change_output: Option<usize>,
+    /// Allow outputs identical to another output
+    pub allow_duplicate_outputs: bool,
+    /// Maximum seconds from the block time an output can be time-locked
+    pub max_time_lock: Option<Timespec>,
+}
+
+/// Transaction the policy is checked against
+pub struct PolicyContext<'a> {
+    /// Addresses of the outputs spent by the inputs
+    pub input_addresses: &'a [ExtendedAddr],
+    /// Amount available to the outputs and fee
+    pub input_amount: Option<Coin>,
+    pub outputs: &'a [TxOut],
+    pub fee: Option<Coin>,
+    pub block_time: Timespec,
+}
+
+/// Kind of policy violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationCode {
+    BelowMinOutputValue,
+    TooManyOutputs,
+    SelfSend,
+    DuplicateOutput,
+    ValidFromInMilliseconds,
+    TimeLockTooFar,
+    InsufficientInput,
+}
+
+impl ViolationCode {
+    pub fn name(self) -> &'static str {
+        match self {
+            ViolationCode::BelowMinOutputValue => "belowMinOutputValue",
+            ViolationCode::TooManyOutputs => "tooManyOutputs",
+            ViolationCode::SelfSend => "selfSend",
+            ViolationCode::DuplicateOutput => "duplicateOutput",
+            ViolationCode::ValidFromInMilliseconds => "validFromInMilliseconds",
+            ViolationCode::TimeLockTooFar => "timeLockTooFar",
+            ViolationCode::InsufficientInput => "insufficientInput",
+        }
+    }
+}
+
+/// A rule of the policy the transaction does not satisfy
+#[derive(Debug, Clone)]
+pub struct PolicyViolation {
+    pub code: ViolationCode,
+    /// Path of the offending field, e.g. `outputs[1].validFrom`
+    pub path: String,
+    pub message: String,
+}
+
+impl PolicyViolation {
+    fn new<P, M>(code: ViolationCode, path: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        PolicyViolation {
+            code,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Returns every rule of the policy the transaction does not satisfy
+pub fn check_output_policy(policy: &OutputPolicy, context: &PolicyContext) -> Vec<PolicyViolation> {
+    let mut violations = Vec::new();
+
+    if let Some(max_outputs) = policy.max_outputs {
+        if context.outputs.len() > max_outputs {
+            violations.push(PolicyViolation::new(
+                ViolationCode::TooManyOutputs,
+                "outputs",
+                format!(
+                    "Transaction has {} outputs, at most {} are allowed",
+                    context.outputs.len(),
+                    max_outputs
+                ),
+            ));
+        }
+    }
+
+    for (i, output) in context.outputs.iter().enumerate() {
+        check_output(policy, context, &mut violations, i, output);
+    }
+
+    check_balance(context, &mut violations);
+
+    violations
+}
+
+fn check_output(
+    policy: &OutputPolicy,
+    context: &PolicyContext,
+    violations: &mut Vec<PolicyViolation>,
+    index: usize,
+    output: &TxOut,
+) {
+    if output.value < policy.min_output_value {
+        violations.push(PolicyViolation::new(
+            ViolationCode::BelowMinOutputValue,
+            format!("outputs[{}].value", index),
+            format!(
+                "Output value {} is below the minimum output value {}",
+                u64::from(output.value),
+                u64::from(policy.min_output_value)
+            ),
+        ));
+    }
+
+    if !policy.allow_self_send
+        && policy.change_output != Some(index)
+        && context.input_addresses.contains(&output.address)
+    {
+        violations.push(PolicyViolation::new(
+            ViolationCode::SelfSend,
+            format!("outputs[{}].address", index),
+            "Output address is the address of an input",
+        ));
+    }
+
+    if !policy.allow_duplicate_outputs {
+        let duplicate = context.outputs[..index].iter().position(|prev_output| {
+            prev_output.address == output.address
+                && prev_output.value == output.value
+                && prev_output.valid_from == output.valid_from
+        });
+        if let Some(duplicate) = duplicate {
+            violations.push(PolicyViolation::new(
+                ViolationCode::DuplicateOutput,
+                format!("outputs[{}]", index),
+                format!("Output is identical to outputs[{}]", duplicate),
+            ));
+        }
+    }
+
+    if let Some(valid_from) = output.valid_from {
+        let path = format!("outputs[{}].validFrom", index);
+        if valid_from >= MAX_SECONDS_TIMESPEC {
+            violations.push(PolicyViolation::new(
+                ViolationCode::ValidFromInMilliseconds,
+                path,
+                format!(
+                    "Valid from {} looks like a timestamp in milliseconds, it should be in seconds",
+                    valid_from
+                ),
+            ));
+        } else if let Some(max_time_lock) = policy.max_time_lock {
+            let max_valid_from = context.block_time.saturating_add(max_time_lock);
+            if valid_from > max_valid_from {
+                violations.push(PolicyViolation::new(
+                    ViolationCode::TimeLockTooFar,
+                    path,
+                    format!(
+                        "Valid from {} is more than {} seconds after the block time {}",
+                        valid_from, max_time_lock, context.block_time
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn check_balance(context: &PolicyContext, violations: &mut Vec<PolicyViolation>) {
+    let input_amount = match context.input_amount {
+        Some(input_amount) => input_amount,
+        None => return,
+    };
+    let fee = context.fee.unwrap_or_else(Coin::zero);
+
+    let required_amount = sum_coins(
+        context
+            .outputs
+            .iter()
+            .map(|output| output.value)
+            .chain(std::iter::once(fee)),
+    );
+    match required_amount {
+        Ok(required_amount) if required_amount <= input_amount => {}
+        Ok(required_amount) => violations.push(PolicyViolation::new(
+            ViolationCode::InsufficientInput,
+            "outputs",
+            format!(
+                "Input amount {} is less than the output amount and fee {}",
+                u64::from(input_amount),
+                u64::from(required_amount)
+            ),
+        )),
+        Err(_) => violations.push(PolicyViolation::new(
+            ViolationCode::InsufficientInput,
+            "outputs",
+            "Output amount and fee exceed maximum coin",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(byte: u8) -> ExtendedAddr {
+        ExtendedAddr::OrTree([byte; 32])
+    }
+
+    fn output(byte: u8, value: u64) -> TxOut {
+        TxOut::new(address(byte), Coin::new(value).unwrap())
+    }
+
+    fn policy() -> OutputPolicy {
+        OutputPolicy {
+            min_output_value: Coin::unit(),
+            max_outputs: None,
+            allow_self_send: false,
+            change_output: None,
+            allow_duplicate_outputs: false,
+            max_time_lock: None,
+        }
+    }
+
+    fn codes(policy: &OutputPolicy, context: &PolicyContext) -> Vec<ViolationCode> {
+        check_output_policy(policy, context)
+            .into_iter()
+            .map(|violation| violation.code)
+            .collect()
+    }
+
+    #[test]
+    fn check_output_policy_should_accept_valid_outputs() {
+        let outputs = [output(2, 10), output(3, 20)];
+        let context = PolicyContext {
+            input_addresses: &[address(1)],
+            input_amount: Some(Coin::new(31).unwrap()),
+            outputs: &outputs,
+            fee: Some(Coin::unit()),
+            block_time: 0,
+        };
+
+        assert!(check_output_policy(&policy(), &context).is_empty());
+    }
+
+    #[test]
+    fn check_output_policy_should_report_each_violation_with_path() {
+        let mut outputs = [output(1, 0), output(2, 10), output(2, 10)];
+        outputs[1].valid_from = Some(1_600_000_000_000);
+        outputs[2].valid_from = Some(1_600_000_000_000);
+        let context = PolicyContext {
+            input_addresses: &[address(1)],
+            input_amount: None,
+            outputs: &outputs,
+            fee: None,
+            block_time: 0,
+        };
+        let policy = OutputPolicy {
+            max_outputs: Some(2),
+            ..policy()
+        };
+
+        let violations = check_output_policy(&policy, &context);
+        let paths: Vec<_> = violations
+            .iter()
+            .map(|violation| (violation.code, violation.path.as_str()))
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                (ViolationCode::TooManyOutputs, "outputs"),
+                (ViolationCode::BelowMinOutputValue, "outputs[0].value"),
+                (ViolationCode::SelfSend, "outputs[0].address"),
+                (
+                    ViolationCode::ValidFromInMilliseconds,
+                    "outputs[1].validFrom"
+                ),
+                (ViolationCode::DuplicateOutput, "outputs[2]"),
+                (
+                    ViolationCode::ValidFromInMilliseconds,
+                    "outputs[2].validFrom"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_output_policy_should_allow_self_send_and_duplicates_when_enabled() {
+        let outputs = [output(1, 10), output(1, 10)];
+        let context = PolicyContext {
+            input_addresses: &[address(1)],
+            input_amount: None,
+            outputs: &outputs,
+            fee: None,
+            block_time: 0,
+        };
+        let policy = OutputPolicy {
+            allow_self_send: true,
+            allow_duplicate_outputs: true,
+            ..policy()
+        };
+
+        assert!(codes(&policy, &context).is_empty());
+    }
+
+    #[test]
+    fn check_output_policy_should_not_report_change_output_as_self_send() {
+        let outputs = [output(2, 10), output(1, 5), output(1, 5)];
+        let context = PolicyContext {
+            input_addresses: &[address(1)],
+            input_amount: None,
+            outputs: &outputs,
+            fee: None,
+            block_time: 0,
+        };
+        let policy = OutputPolicy {
+            change_output: Some(1),
+            allow_duplicate_outputs: true,
+            ..policy()
+        };
+
+        let violations = check_output_policy(&policy, &context);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, ViolationCode::SelfSend);
+        assert_eq!(violations[0].path, "outputs[2].address");
+    }
+
+    #[test]
+    fn check_output_policy_should_report_time_lock_beyond_block_time() {
+        let mut outputs = [output(2, 10)];
+        outputs[0].valid_from = Some(1_000 + 3_601);
+        let context = PolicyContext {
+            input_addresses: &[],
+            input_amount: None,
+            outputs: &outputs,
+            fee: None,
+            block_time: 1_000,
+        };
+        let policy = OutputPolicy {
+            max_time_lock: Some(3_600),
+            ..policy()
+        };
+
+        assert_eq!(
+            codes(&policy, &context),
+            vec![ViolationCode::TimeLockTooFar]
+        );
+    }
+
+    #[test]
+    fn check_output_policy_should_report_insufficient_input() {
+        let outputs = [output(2, 10)];
+        let context = PolicyContext {
+            input_addresses: &[],
+            input_amount: Some(Coin::new(10).unwrap()),
+            outputs: &outputs,
+            fee: Some(Coin::unit()),
+            block_time: 0,
+        };
+
+        assert_eq!(
+            codes(&policy(), &context),
+            vec![ViolationCode::InsufficientInput]
+        );
+    }
+}
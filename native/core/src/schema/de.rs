@@ -0,0 +1,436 @@
+//! `deserialize_with` helpers for chain types crossing the JS boundary
+//!
+//! Buffers are received as objects tagged with `BUFFER_TAG` from JS, and
+//! as arrays of bytes or hex strings from JSON options. Amounts and nonces
+//! are base-unit decimal strings.
+use std::fmt;
+use std::str::FromStr;
+
+use chain_core::common::{Timespec, H256, HASH_SIZE_256};
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{Nonce, StakedStateAddress};
+use chain_core::tx::data::TxId;
+use chain_core::tx::fee::Milli;
+use client_common::{PrivateKey, PublicKey};
+use serde::de::{Deserializer, Error, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::is_tagged_buffers;
+
+/// Key of the object a JS buffer is converted to, whose value is the hex
+/// encoded buffer, e.g. `{ "$buffer": "2aff" }`
+pub const BUFFER_TAG: &str = "$buffer";
+
+/// Buffer of arbitrary length, as a tagged buffer object, or an array of
+/// bytes or a hex string outside of JS values
+pub fn buffer<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(BufferVisitor)
+}
+
+/// List of buffers
+pub fn buffers<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buffers = Vec::<Buffer>::deserialize(deserializer)?;
+
+    Ok(buffers.into_iter().map(|Buffer(buffer)| buffer).collect())
+}
+
+/// Element of a list of buffers
+#[derive(Deserialize)]
+struct Buffer(#[serde(deserialize_with = "buffer")] Vec<u8>);
+
+struct BufferVisitor;
+
+impl<'de> Visitor<'de> for BufferVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if is_tagged_buffers() {
+            formatter.write_str("a Buffer")
+        } else {
+            formatter.write_str("an array of bytes or a hex string")
+        }
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Vec<u8>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let hex = match map.next_entry::<String, String>()? {
+            Some((key, hex)) if key == BUFFER_TAG => hex,
+            _ => return Err(A::Error::invalid_type(Unexpected::Map, &self)),
+        };
+        if map.next_key::<String>()?.is_some() {
+            return Err(A::Error::invalid_type(Unexpected::Map, &self));
+        }
+
+        hex::decode(hex).map_err(|err| A::Error::custom(format!("Invalid hex buffer: {}", err)))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Vec<u8>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        if is_tagged_buffers() {
+            return Err(A::Error::invalid_type(Unexpected::Seq, &self));
+        }
+
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        Ok(value)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        if is_tagged_buffers() {
+            return Err(E::invalid_type(Unexpected::Str(value), &self));
+        }
+
+        hex::decode(value).map_err(|err| E::custom(format!("Invalid hex buffer: {}", err)))
+    }
+}
+
+/// Single byte buffer chain hex id
+pub fn chain_hex_id<'de, D>(deserializer: D) -> Result<u8, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let chain_hex_id = buffer(deserializer)?;
+    if chain_hex_id.len() != 1 {
+        return Err(D::Error::custom("Chain hex id must be 8 bit long"));
+    }
+
+    Ok(chain_hex_id[0])
+}
+
+/// Hex encoded 32 bytes transaction id
+pub fn tx_id<'de, D>(deserializer: D) -> Result<TxId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tx_id = String::deserialize(deserializer)?;
+
+    h256_from_hex(&tx_id).map_err(D::Error::custom)
+}
+
+/// Base unit Coin decimal string
+pub fn coin<'de, D>(deserializer: D) -> Result<Coin, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let coin = String::deserialize(deserializer)?;
+
+    Coin::from_str(&coin).map_err(|err| D::Error::custom(format!("Invalid Coin: {}", err)))
+}
+
+/// Milli decimal string used by fee configurations
+pub fn milli<'de, D>(deserializer: D) -> Result<Milli, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let milli = String::deserialize(deserializer)?;
+
+    Milli::from_str(&milli).map_err(|err| D::Error::custom(format!("Invalid Milli: {}", err)))
+}
+
+/// Account nonce decimal string
+pub fn nonce<'de, D>(deserializer: D) -> Result<Nonce, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let nonce = String::deserialize(deserializer)?;
+
+    nonce
+        .parse()
+        .map_err(|err| D::Error::custom(format!("Invalid nonce: {}", err)))
+}
+
+/// Block height decimal string
+pub fn height<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let height = String::deserialize(deserializer)?;
+
+    height
+        .parse()
+        .map_err(|err| D::Error::custom(format!("Invalid block height: {}", err)))
+}
+
+/// Optional timespec in seconds
+pub fn optional_timespec<'de, D>(deserializer: D) -> Result<Option<Timespec>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Timespec>::deserialize(deserializer)
+}
+
+/// Staking address string
+pub fn staking_address<'de, D>(deserializer: D) -> Result<StakedStateAddress, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let staking_address = String::deserialize(deserializer)?;
+
+    StakedStateAddress::from_str(&staking_address)
+        .map_err(|err| D::Error::custom(format!("Invalid staking address: {}", err)))
+}
+
+/// Serialized public key buffer
+pub fn public_key<'de, D>(deserializer: D) -> Result<PublicKey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let public_key = buffer(deserializer)?;
+
+    PublicKey::deserialize_from(&public_key)
+        .map_err(|err| D::Error::custom(format!("Invalid public key: {}", err)))
+}
+
+/// Serialized private key buffer
+pub fn private_key<'de, D>(deserializer: D) -> Result<PrivateKey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let private_key = buffer(deserializer)?;
+
+    PrivateKey::deserialize_from(&private_key)
+        .map_err(|err| D::Error::custom(format!("Invalid private key: {}", err)))
+}
+
+/// View key buffers
+pub fn view_keys<'de, D>(deserializer: D) -> Result<Vec<PublicKey>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let view_keys = Vec::<Buffer>::deserialize(deserializer)?;
+
+    view_keys
+        .into_iter()
+        .enumerate()
+        .map(|(i, Buffer(view_key))| {
+            PublicKey::from_str(&hex::encode_upper(view_key))
+                .map_err(|err| D::Error::custom(format!("Invalid view key at {}: {}", i, err)))
+        })
+        .collect()
+}
+
+/// Hex encoded 32 bytes transaction ids
+pub fn tx_ids<'de, D>(deserializer: D) -> Result<Vec<TxId>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tx_ids = Vec::<String>::deserialize(deserializer)?;
+
+    tx_ids
+        .iter()
+        .enumerate()
+        .map(|(i, tx_id)| {
+            h256_from_hex(tx_id)
+                .map_err(|err| D::Error::custom(format!("Invalid tx id at {}: {}", i, err)))
+        })
+        .collect()
+}
+
+/// Staking address strings
+pub fn staking_addresses<'de, D>(deserializer: D) -> Result<Vec<StakedStateAddress>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let staking_addresses = Vec::<String>::deserialize(deserializer)?;
+
+    staking_addresses
+        .iter()
+        .enumerate()
+        .map(|(i, staking_address)| {
+            StakedStateAddress::from_str(staking_address).map_err(|err| {
+                D::Error::custom(format!("Invalid staking address at {}: {}", i, err))
+            })
+        })
+        .collect()
+}
+
+/// Optional single byte buffer chain hex id
+pub fn optional_chain_hex_id<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    nullable(deserializer, chain_hex_id)
+}
+
+/// Optional hex encoded 32 bytes transaction id
+pub fn optional_tx_id<'de, D>(deserializer: D) -> Result<Option<TxId>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    nullable(deserializer, tx_id)
+}
+
+/// Optional base unit Coin decimal string
+pub fn optional_coin<'de, D>(deserializer: D) -> Result<Option<Coin>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    nullable(deserializer, coin)
+}
+
+/// Optional account nonce decimal string
+pub fn optional_nonce<'de, D>(deserializer: D) -> Result<Option<Nonce>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    nullable(deserializer, nonce)
+}
+
+/// Optional block height decimal string
+pub fn optional_height<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    nullable(deserializer, height)
+}
+
+/// Optional staking address string
+pub fn optional_staking_address<'de, D>(
+    deserializer: D,
+) -> Result<Option<StakedStateAddress>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    nullable(deserializer, staking_address)
+}
+
+/// Deserialize a value which can be null or undefined with the helper
+fn nullable<'de, D, T>(
+    deserializer: D,
+    helper: fn(Value) -> Result<T, serde_json::Error>,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Value>::deserialize(deserializer)?
+        .map(helper)
+        .transpose()
+        .map_err(D::Error::custom)
+}
+
+/// Parse a hex encoded 32 bytes hash
+pub fn h256_from_hex(hash: &str) -> Result<H256, String> {
+    let decoded = hex::decode(hash).map_err(|err| format!("Invalid hex string: {}", err))?;
+    if decoded.len() != HASH_SIZE_256 {
+        return Err(format!(
+            "Expected 32 bytes hash but got {} bytes",
+            decoded.len()
+        ));
+    }
+
+    let mut out: H256 = [0; HASH_SIZE_256];
+    out.copy_from_slice(&decoded);
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use chain_core::state::account::StakedStateAddress;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use crate::schema::{from_js_json_value, from_json_value};
+
+    #[derive(Debug, Deserialize)]
+    struct BufferOptions {
+        #[serde(deserialize_with = "super::buffer")]
+        buffer: Vec<u8>,
+    }
+
+    #[test]
+    fn buffer_should_accept_array_of_bytes_and_hex_string() {
+        let from_array: BufferOptions = from_json_value(json!({ "buffer": [0x2A, 0xFF] })).unwrap();
+        let from_hex: BufferOptions = from_json_value(json!({ "buffer": "2aff" })).unwrap();
+
+        assert_eq!(from_array.buffer, vec![0x2A, 0xFF]);
+        assert_eq!(from_hex.buffer, vec![0x2A, 0xFF]);
+    }
+
+    #[test]
+    fn buffer_should_only_accept_tagged_buffer_from_js() {
+        let options: BufferOptions =
+            from_js_json_value(json!({ "buffer": { "$buffer": "2aff" } })).unwrap();
+        assert_eq!(options.buffer, vec![0x2A, 0xFF]);
+
+        let err = from_js_json_value::<BufferOptions>(json!({ "buffer": "2aff" })).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid options at `buffer`: invalid type: string \"2aff\", expected a Buffer"
+        );
+        assert!(from_js_json_value::<BufferOptions>(json!({ "buffer": [0x2A] })).is_err());
+        assert!(from_js_json_value::<BufferOptions>(json!({ "buffer": { "hex": "2a" } })).is_err());
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct AddressOptions {
+        #[serde(deserialize_with = "super::staking_address")]
+        address: StakedStateAddress,
+    }
+
+    #[test]
+    fn string_should_not_accept_tagged_buffer() {
+        let err = from_js_json_value::<AddressOptions>(json!({
+            "address": { "$buffer": "b5698ee21f69a6184afbe59b3626ed9d4bd755b0" },
+        }))
+        .unwrap_err();
+
+        assert!(err.to_string().contains("expected a string"));
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct BuffersOptions {
+        #[serde(deserialize_with = "super::buffers")]
+        buffers: Vec<Vec<u8>>,
+    }
+
+    #[test]
+    fn buffers_should_accept_hex_strings() {
+        let options: BuffersOptions =
+            from_json_value(json!({ "buffers": ["2aff", [0x01]] })).unwrap();
+
+        assert_eq!(options.buffers, vec![vec![0x2A, 0xFF], vec![0x01]]);
+    }
+
+    #[test]
+    fn buffer_should_reject_invalid_hex_string_and_other_types() {
+        let err = from_json_value::<BufferOptions>(json!({ "buffer": "2g" })).unwrap_err();
+        assert!(err.to_string().contains("Invalid hex buffer"));
+
+        let err = from_json_value::<BufferOptions>(json!({ "buffer": 42 })).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected an array of bytes or a hex string"));
+    }
+}
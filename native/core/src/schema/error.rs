@@ -0,0 +1,37 @@
+use std::fmt;
+
+/// Result of a schema conversion
+pub type SchemaResult<T> = std::result::Result<T, SchemaError>;
+
+/// Conversion error annotated with the path of the offending field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    path: String,
+    message: String,
+}
+
+impl SchemaError {
+    /// Create a new error at the given path
+    pub fn new<P, M>(path: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        SchemaError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() || self.path == "." {
+            write!(f, "Invalid options: {}", self.message)
+        } else {
+            write!(f, "Invalid options at `{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
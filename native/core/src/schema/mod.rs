@@ -0,0 +1,57 @@
+//! Declarative conversion of option objects into Rust structs
+//!
+//! Options are deserialized from a `serde_json::Value` with
+//! `serde_path_to_error`, so that every error reports the exact location of
+//! the offending field, e.g. `inputs[2].addressParams.totalSigners`.
+//!
+//! JS bindings convert buffers to objects tagged with `de::BUFFER_TAG`, and
+//! deserialize with `from_js_json_value` so that strings are not accepted in
+//! place of buffers. JSON options of the command line and C library have no
+//! buffer type and give them as hex strings.
+pub mod de;
+mod error;
+pub mod types;
+
+use std::cell::Cell;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+pub use error::{SchemaError, SchemaResult};
+
+thread_local! {
+    /// Set while deserializing a value converted from JS, whose buffers are
+    /// all tagged
+    static TAGGED_BUFFERS: Cell<bool> = Cell::new(false);
+}
+
+/// Deserialize a JSON value into `T` and keep track of the path of the
+/// offending field on failure
+pub fn from_json_value<T>(value: Value) -> SchemaResult<T>
+where
+    T: DeserializeOwned,
+{
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let path = err.path().to_string();
+        SchemaError::new(path, err.into_inner().to_string())
+    })
+}
+
+/// Deserialize a JSON value converted from a JS value into `T`. Buffers
+/// should be tagged, hex strings and arrays of bytes are rejected in their
+/// place.
+pub fn from_js_json_value<T>(value: Value) -> SchemaResult<T>
+where
+    T: DeserializeOwned,
+{
+    TAGGED_BUFFERS.with(|tagged_buffers| tagged_buffers.set(true));
+    let result = from_json_value(value);
+    TAGGED_BUFFERS.with(|tagged_buffers| tagged_buffers.set(false));
+
+    result
+}
+
+/// Returns true when buffers should be tagged
+fn is_tagged_buffers() -> bool {
+    TAGGED_BUFFERS.with(Cell::get)
+}
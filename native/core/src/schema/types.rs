@@ -0,0 +1,205 @@
+//! Schemas shared by the transaction builder options
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::fee::{LinearFee, Milli};
+use client_common::{PrivateKey, PublicKey};
+use serde::de::{Deserializer, Error};
+use serde::Deserialize;
+
+use super::de;
+use super::{SchemaError, SchemaResult};
+use crate::fee::{FeeSchedule, FeeTier};
+use crate::network::{network_by_chain_hex_id, NetworkDefinition};
+use crate::transfer_transaction::transfer_address_leaves;
+
+/// Transaction output with a network dependent address
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputOptions {
+    pub address: String,
+    #[serde(deserialize_with = "de::coin")]
+    pub value: Coin,
+    #[serde(default, deserialize_with = "de::optional_timespec")]
+    pub valid_from: Option<Timespec>,
+}
+
+impl OutputOptions {
+    /// Convert to `TxOut` of the provided network. `path` is the location of
+    /// this output in the options object.
+    pub fn to_tx_out(&self, network: &NetworkDefinition, path: &str) -> SchemaResult<TxOut> {
+        let address = network
+            .decode_transfer_address(&self.address)
+            .map_err(|err| {
+                SchemaError::new(
+                    format!("{}.address", path),
+                    format!("Invalid transfer address: {}", err),
+                )
+            })?;
+
+        Ok(TxOut {
+            address,
+            value: self.value,
+            valid_from: self.valid_from,
+        })
+    }
+}
+
+/// Convert a list of outputs located at `path` to `TxOut`s
+pub fn to_tx_outs(
+    outputs: &[OutputOptions],
+    network: &NetworkDefinition,
+    path: &str,
+) -> SchemaResult<Vec<TxOut>> {
+    outputs
+        .iter()
+        .enumerate()
+        .map(|(i, output)| output.to_tx_out(network, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+/// Find the registered network of the chain hex id located at `path`
+pub fn network_of_chain_hex_id(chain_hex_id: u8, path: &str) -> SchemaResult<NetworkDefinition> {
+    network_by_chain_hex_id(chain_hex_id).map_err(|err| SchemaError::new(path, err.to_string()))
+}
+
+/// Maximum number of signers of a multi-signature transfer address. Fee
+/// estimation generates a key of each signer.
+pub const MAX_TOTAL_SIGNERS: u64 = 256;
+
+/// Signers of a multi-signature transfer address
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressParamsOptions {
+    pub required_signers: u64,
+    pub total_signers: u64,
+}
+
+impl Default for AddressParamsOptions {
+    fn default() -> Self {
+        AddressParamsOptions {
+            required_signers: 1,
+            total_signers: 1,
+        }
+    }
+}
+
+impl AddressParamsOptions {
+    /// Verify the signers located at `path` can form an address
+    pub fn verify(&self, path: &str) -> SchemaResult<()> {
+        if self.total_signers > MAX_TOTAL_SIGNERS {
+            return Err(SchemaError::new(
+                path,
+                format!("Total signers should be at most {}", MAX_TOTAL_SIGNERS),
+            ));
+        }
+        if self.required_signers == 0 || self.required_signers > self.total_signers {
+            return Err(SchemaError::new(
+                path,
+                "Required signers should be between 1 and total signers",
+            ));
+        }
+        transfer_address_leaves(self.total_signers, self.required_signers)
+            .map_err(|err| SchemaError::new(path, err.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Fee configuration, tagged by `algorithm`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "algorithm")]
+pub enum FeeConfigOptions {
+    #[serde(rename_all = "camelCase")]
+    LinearFee {
+        #[serde(deserialize_with = "de::milli")]
+        constant: Milli,
+        #[serde(deserialize_with = "de::milli")]
+        coefficient: Milli,
+        #[serde(default, deserialize_with = "de::optional_coin")]
+        minimum_fee: Option<Coin>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TieredFee {
+        #[serde(deserialize_with = "fee_tiers")]
+        tiers: Vec<FeeTierOptions>,
+        #[serde(default, deserialize_with = "de::optional_coin")]
+        minimum_fee: Option<Coin>,
+    },
+}
+
+/// Linear fee of transactions up to `maxSize` bytes
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTierOptions {
+    #[serde(default)]
+    pub max_size: Option<usize>,
+    #[serde(deserialize_with = "de::milli")]
+    pub constant: Milli,
+    #[serde(deserialize_with = "de::milli")]
+    pub coefficient: Milli,
+}
+
+/// Fee tiers sorted by strictly increasing size, the last of which has no
+/// size bound
+fn fee_tiers<'de, D>(deserializer: D) -> Result<Vec<FeeTierOptions>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tiers = Vec::<FeeTierOptions>::deserialize(deserializer)?;
+
+    let (last_tier, bounded_tiers) = tiers
+        .split_last()
+        .ok_or_else(|| D::Error::custom("Fee tiers should not be empty"))?;
+    if last_tier.max_size.is_some() {
+        return Err(D::Error::custom("Last fee tier should have no max size"));
+    }
+    let mut prev_max_size = None;
+    for (i, tier) in bounded_tiers.iter().enumerate() {
+        let max_size = tier
+            .max_size
+            .ok_or_else(|| D::Error::custom(format!("Fee tier {} should have a max size", i)))?;
+        if prev_max_size.map_or(false, |prev_max_size| max_size <= prev_max_size) {
+            return Err(D::Error::custom(
+                "Fee tiers should be sorted by increasing max size",
+            ));
+        }
+        prev_max_size = Some(max_size);
+    }
+
+    Ok(tiers)
+}
+
+impl FeeConfigOptions {
+    /// Returns the fee algorithm of this configuration
+    pub fn to_fee_algorithm(&self) -> FeeSchedule {
+        match self {
+            FeeConfigOptions::LinearFee {
+                constant,
+                coefficient,
+                minimum_fee,
+            } => FeeSchedule::linear(LinearFee::new(*constant, *coefficient), *minimum_fee),
+            FeeConfigOptions::TieredFee { tiers, minimum_fee } => FeeSchedule::new(
+                tiers
+                    .iter()
+                    .map(|tier| FeeTier {
+                        max_size: tier.max_size,
+                        linear_fee: LinearFee::new(tier.constant, tier.coefficient),
+                    })
+                    .collect(),
+                *minimum_fee,
+            ),
+        }
+    }
+}
+
+/// Key pair object from `KeyPair.toObject()`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPairOptions {
+    #[serde(deserialize_with = "de::private_key")]
+    pub private_key: PrivateKey,
+    #[serde(deserialize_with = "de::public_key")]
+    pub public_key: PublicKey,
+}
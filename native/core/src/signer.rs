@@ -12,14 +12,33 @@ use secp256k1::{Message, SecretKey};
 pub struct KeyPairSigner {
     proof: Proof<RawXOnlyPubkey>,
     private_key: PrivateKey,
+    public_key: PublicKey,
+    transfer_address: ExtendedAddr,
 }
 
 impl KeyPairSigner {
     /// Create a new signer using the provided key pair
     #[inline]
     pub fn new(private_key: PrivateKey, public_key: PublicKey) -> Result<Self> {
-        let (_, proof) = generate_extended_addr_and_proof(public_key)?;
-        Ok(KeyPairSigner { proof, private_key })
+        let (transfer_address, proof) = generate_extended_addr_and_proof(public_key.clone())?;
+        Ok(KeyPairSigner {
+            proof,
+            private_key,
+            public_key,
+            transfer_address,
+        })
+    }
+
+    /// Returns the public key of the key pair
+    #[inline]
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Returns the 1-of-1 transfer address unlocked by the Schnorr witness
+    #[inline]
+    pub fn transfer_address(&self) -> &ExtendedAddr {
+        &self.transfer_address
     }
 }
 
@@ -72,3 +91,56 @@ impl KeyPairSigner {
         Ok(signature)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::state::account::StakedStateOpWitness;
+    use chain_tx_validation::witness::verify_tx_recover_address;
+
+    use crate::address::{extended_address_from_public_key, staking_address_from_public_key};
+
+    fn key_pair() -> (PrivateKey, PublicKey) {
+        let private_key = PrivateKey::deserialize_from(&[1; 32]).unwrap();
+        let public_key = PublicKey::from(&private_key);
+
+        (private_key, public_key)
+    }
+
+    #[test]
+    fn sign_should_be_recoverable_to_staking_address() {
+        let (private_key, public_key) = key_pair();
+        let signer = KeyPairSigner::new(private_key, public_key.clone()).unwrap();
+        let tx_id = [3; 32];
+
+        let witness = StakedStateOpWitness::new(signer.sign(&tx_id).unwrap());
+
+        assert_eq!(
+            verify_tx_recover_address(&witness, &tx_id).unwrap(),
+            staking_address_from_public_key(&public_key)
+        );
+    }
+
+    #[test]
+    fn schnorr_sign_txid_should_verify_against_transfer_address() {
+        let (private_key, public_key) = key_pair();
+        let signer = KeyPairSigner::new(private_key, public_key.clone()).unwrap();
+        let tx_id = [3; 32];
+
+        let witness = signer.schnorr_sign_txid(&tx_id).unwrap();
+        let address = extended_address_from_public_key(&public_key).unwrap();
+
+        assert!(witness.verify_tx_address(&tx_id, &address).is_ok());
+        assert!(witness.verify_tx_address(&[4; 32], &address).is_err());
+    }
+
+    #[test]
+    fn sign_should_reject_message_which_is_not_32_bytes() {
+        let (private_key, public_key) = key_pair();
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+
+        assert!(signer.sign(&[3; 31]).is_err());
+        assert!(signer.schnorr_sign(&[3; 33]).is_err());
+    }
+}
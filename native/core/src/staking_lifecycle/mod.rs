@@ -0,0 +1,526 @@
+//! Staking lifecycle planner and the transactions of its steps
+mod plan;
+
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{
+    Nonce, StakedStateAddress, StakedStateOpAttributes, UnbondTx, UnjailTx, WithdrawUnbondedTx,
+};
+use chain_core::tx::data::access::{TxAccess, TxAccessPolicy};
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::data::attribute::TxAttributes;
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use chain_core::tx::fee::FeeAlgorithm;
+use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
+use client_common::{Error, ErrorKind, PublicKey, Result, ResultExt, SignedTransaction};
+use client_core::signer::DummySigner;
+use serde::Deserialize;
+
+use crate::address::staking_address_from_public_key;
+use crate::fee::FeeSchedule;
+use crate::schema::types::{network_of_chain_hex_id, AddressParamsOptions, FeeConfigOptions};
+use crate::schema::{de, SchemaError, SchemaResult};
+use crate::signer::KeyPairSigner;
+use crate::staking_transaction::deposit::{
+    deposit_amounts, deposit_bond_tx, deposit_spent_outputs, verify_key_pair_inputs,
+    DepositInputOptions,
+};
+use crate::staking_transaction::staked_state_op_witness;
+use crate::tx_aux::{signed_transaction_to_tx_aux, Features};
+
+pub use plan::{plan_next_step, StakedStateView, StakingGoal, StakingStep};
+
+/// Options of the next step planning
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanNextStepOptions {
+    pub goal: StakingGoal,
+    #[serde(default)]
+    pub staked_state: Option<StakedStateView>,
+    pub block_time: Timespec,
+    #[serde(default, deserialize_with = "de::optional_nonce")]
+    pub expected_nonce: Option<Nonce>,
+}
+
+impl PlanNextStepOptions {
+    /// Plan the next valid step towards the goal
+    pub fn plan(&self) -> StakingStep {
+        plan_next_step(
+            self.goal,
+            self.staked_state.as_ref(),
+            self.block_time,
+            self.expected_nonce,
+        )
+    }
+}
+
+/// Planned step with a transaction to build
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StepOptions {
+    Deposit {
+        #[serde(deserialize_with = "de::nonce")]
+        nonce: Nonce,
+        #[serde(default)]
+        inputs: Vec<DepositInputOptions>,
+    },
+    Unjail {
+        #[serde(deserialize_with = "de::nonce")]
+        nonce: Nonce,
+    },
+    Unbond {
+        #[serde(deserialize_with = "de::nonce")]
+        nonce: Nonce,
+        #[serde(deserialize_with = "de::coin")]
+        amount: Coin,
+    },
+    Withdraw {
+        #[serde(deserialize_with = "de::nonce")]
+        nonce: Nonce,
+        #[serde(deserialize_with = "de::coin")]
+        amount: Coin,
+    },
+}
+
+impl StepOptions {
+    pub fn to_staking_step(&self) -> StakingStep {
+        match *self {
+            StepOptions::Deposit { nonce, .. } => StakingStep::Deposit { nonce },
+            StepOptions::Unjail { nonce } => StakingStep::Unjail { nonce },
+            StepOptions::Unbond { nonce, amount } => StakingStep::Unbond { nonce, amount },
+            StepOptions::Withdraw { nonce, amount } => StakingStep::Withdraw { nonce, amount },
+        }
+    }
+}
+
+/// Options of the transaction of a planned step
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildStepTransactionOptions {
+    pub step: StepOptions,
+    #[serde(deserialize_with = "de::staking_address")]
+    pub staking_address: StakedStateAddress,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+    #[serde(default)]
+    pub to_address: Option<String>,
+    #[serde(default, deserialize_with = "de::view_keys")]
+    pub view_keys: Vec<PublicKey>,
+    #[serde(default)]
+    pub fee_config: Option<FeeConfigOptions>,
+}
+
+impl BuildStepTransactionOptions {
+    /// Verify the options required by the step are provided
+    pub fn verify(&self) -> SchemaResult<()> {
+        match self.step {
+            StepOptions::Deposit { ref inputs, .. } => {
+                self.deposit_inputs(inputs)?;
+            }
+            StepOptions::Withdraw { .. } => {
+                self.withdraw_destination()?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Outputs spent by the inputs of a deposit step and the fee algorithm.
+    /// Inputs are signed by the key pair and so should spend 1-of-1
+    /// addresses.
+    fn deposit_inputs(
+        &self,
+        inputs: &[DepositInputOptions],
+    ) -> SchemaResult<(Vec<(TxOut, AddressParamsOptions)>, FeeSchedule)> {
+        if inputs.is_empty() {
+            return Err(SchemaError::new(
+                "step.inputs",
+                "Inputs should be provided to deposit",
+            ));
+        }
+        let spent_outputs = deposit_spent_outputs(inputs, self.chain_hex_id, "step.inputs")?;
+        verify_key_pair_inputs(inputs, "step.inputs")?;
+        let fee_algorithm = self
+            .fee_config
+            .as_ref()
+            .map(FeeConfigOptions::to_fee_algorithm)
+            .ok_or_else(|| {
+                SchemaError::new(
+                    "feeConfig",
+                    "Fee configuration should be provided to deposit",
+                )
+            })?;
+
+        Ok((spent_outputs, fee_algorithm))
+    }
+
+    /// Destination address and fee algorithm of a withdraw step
+    fn withdraw_destination(&self) -> SchemaResult<(ExtendedAddr, FeeSchedule)> {
+        let network = network_of_chain_hex_id(self.chain_hex_id, "chainHexId")?;
+        let to_address = self.to_address.as_ref().ok_or_else(|| {
+            SchemaError::new(
+                "toAddress",
+                "Destination address should be provided to withdraw",
+            )
+        })?;
+        let to_address = network.decode_transfer_address(to_address).map_err(|err| {
+            SchemaError::new("toAddress", format!("Invalid transfer address: {}", err))
+        })?;
+        let fee_algorithm = self
+            .fee_config
+            .as_ref()
+            .map(FeeConfigOptions::to_fee_algorithm)
+            .ok_or_else(|| {
+                SchemaError::new(
+                    "feeConfig",
+                    "Fee configuration should be provided to withdraw",
+                )
+            })?;
+
+        Ok((to_address, fee_algorithm))
+    }
+
+    /// Build the withdraw unbonded transaction of `amount` to the destination
+    /// address, with the fee deducted from the output
+    pub fn withdraw_unbonded_tx(&self, nonce: Nonce, amount: Coin) -> Result<WithdrawUnbondedTx> {
+        let (to_address, fee_algorithm) = self
+            .withdraw_destination()
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))?;
+
+        let access_policies = self
+            .view_keys
+            .iter()
+            .map(|view_key| TxAccessPolicy {
+                view_key: view_key.into(),
+                access: TxAccess::AllData,
+            })
+            .collect();
+        let attributes = TxAttributes::new_with_access(self.chain_hex_id, access_policies);
+
+        let estimate_tx = WithdrawUnbondedTx::new(
+            nonce,
+            vec![TxOut::new(to_address.clone(), amount)],
+            attributes.clone(),
+        );
+        let fee = fee_algorithm
+            .calculate_for_txaux(&DummySigner().mock_txaux_for_withdraw(estimate_tx))
+            .chain(|| {
+                (
+                    ErrorKind::InvalidInput,
+                    "Unable to estimate transaction fee",
+                )
+            })?
+            .to_coin();
+        let value = (amount - fee).chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Unbonded amount is not enough to pay the transaction fee",
+            )
+        })?;
+
+        Ok(WithdrawUnbondedTx::new(
+            nonce,
+            vec![TxOut::new(to_address, value)],
+            attributes,
+        ))
+    }
+}
+
+/// Signed transaction of a planned step
+#[derive(Debug)]
+pub struct StepTransaction {
+    pub tx_id: TxId,
+    pub tx_aux: TxAux,
+    /// Deposited or withdrawn amount net of the fee, or unbonded amount
+    pub amount: Option<Coin>,
+    /// Staked state nonce expected after the transaction is committed
+    pub expected_nonce: Option<Nonce>,
+}
+
+/// Build the signed transaction of a planned deposit, unjail, unbond or
+/// withdraw step. The key pair should be the one of the staking address. The
+/// Tendermint address and features obfuscate deposit and withdraw
+/// transactions and are required by them only.
+pub fn build_step_transaction(
+    options: &BuildStepTransactionOptions,
+    signer: &KeyPairSigner,
+    obfuscation: Option<(&str, Features)>,
+) -> Result<StepTransaction> {
+    if staking_address_from_public_key(signer.public_key()) != options.staking_address {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Key pair does not belong to the staking address",
+        ));
+    }
+
+    let step = options.step.to_staking_step();
+    let attributes = StakedStateOpAttributes::new(options.chain_hex_id);
+
+    let (tx_id, tx_aux, amount) = match step {
+        StakingStep::Deposit { .. } => {
+            let (tendermint_address, features) = obfuscation.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "Tendermint address and features should be provided to deposit",
+                )
+            })?;
+            let inputs = match options.step {
+                StepOptions::Deposit { ref inputs, .. } => inputs,
+                _ => unreachable!("Deposit step is built from deposit options"),
+            };
+
+            let (spent_outputs, fee_algorithm) = options
+                .deposit_inputs(inputs)
+                .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))?;
+            if spent_outputs
+                .iter()
+                .any(|(output, _)| &output.address != signer.transfer_address())
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Input address is not signable by the key pair",
+                ));
+            }
+
+            let tx = deposit_bond_tx(inputs, options.staking_address, options.chain_hex_id);
+            let tx_id = tx.id();
+            let amounts = deposit_amounts(&tx, &spent_outputs, &fee_algorithm)?;
+            let witness = spent_outputs
+                .iter()
+                .map(|_| signer.schnorr_sign_txid(&tx_id))
+                .collect::<Result<Vec<_>>>()?;
+
+            let signed_transaction = SignedTransaction::DepositStakeTransaction(tx, witness.into());
+            let tx_aux =
+                signed_transaction_to_tx_aux(signed_transaction, tendermint_address, features)?;
+
+            (tx_id, tx_aux, Some(amounts.deposit_amount))
+        }
+        StakingStep::Unjail { nonce } => {
+            let tx = UnjailTx::new(nonce, options.staking_address, attributes);
+            let witness = staked_state_op_witness(signer, &tx.id())?;
+
+            (
+                tx.id(),
+                TxAux::PublicTx(TxPublicAux::UnjailTx(tx, witness)),
+                None,
+            )
+        }
+        StakingStep::Unbond { nonce, amount } => {
+            let tx = UnbondTx::new(options.staking_address, nonce, amount, attributes);
+            let witness = staked_state_op_witness(signer, &tx.id())?;
+
+            (
+                tx.id(),
+                TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, witness)),
+                Some(amount),
+            )
+        }
+        StakingStep::Withdraw { nonce, amount } => {
+            let (tendermint_address, features) = obfuscation.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "Tendermint address and features should be provided to withdraw",
+                )
+            })?;
+
+            let tx = options.withdraw_unbonded_tx(nonce, amount)?;
+            let output_value = tx.outputs[0].value;
+            let witness = staked_state_op_witness(signer, &tx.id())?;
+            let tx_id = tx.id();
+
+            let signed_transaction =
+                SignedTransaction::WithdrawUnbondedStakeTransaction(tx, witness);
+            let tx_aux =
+                signed_transaction_to_tx_aux(signed_transaction, tendermint_address, features)?;
+
+            (tx_id, tx_aux, Some(output_value))
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Step {} has no transaction to build", step.name()),
+            ))
+        }
+    };
+
+    Ok(StepTransaction {
+        tx_id,
+        tx_aux,
+        amount,
+        expected_nonce: step.expected_nonce(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    use crate::address::{staking_address_from_public_key, transfer_address_from_public_key};
+    use crate::network::{network_by_name, MAINNET};
+    use crate::schema::from_json_value;
+    use crate::transfer_transaction::tests::key_pair;
+
+    fn options(step: Value, extra: Value) -> BuildStepTransactionOptions {
+        let staking_address = staking_address_from_public_key(&key_pair(1).1);
+        let mut options = json!({
+            "step": step,
+            "stakingAddress": staking_address.to_string(),
+            "chainHexId": [0x2A],
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            options[key] = value.clone();
+        }
+
+        from_json_value(options).unwrap()
+    }
+
+    fn signer() -> KeyPairSigner {
+        let (private_key, public_key) = key_pair(1);
+
+        KeyPairSigner::new(private_key, public_key).unwrap()
+    }
+
+    fn withdraw_extra() -> Value {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let to_address = transfer_address_from_public_key(&mainnet, &key_pair(2).1).unwrap();
+
+        json!({
+            "toAddress": to_address,
+            "feeConfig": {
+                "algorithm": "LinearFee",
+                "constant": "1.1",
+                "coefficient": "1.25",
+            },
+        })
+    }
+
+    #[test]
+    fn build_step_transaction_should_sign_unbond_of_bonded_amount() {
+        let options = options(
+            json!({ "type": "unbond", "nonce": "4", "amount": "1000" }),
+            json!({}),
+        );
+
+        let transaction = build_step_transaction(&options, &signer(), None).unwrap();
+
+        assert_eq!(transaction.amount, Some(Coin::new(1000).unwrap()));
+        assert_eq!(transaction.expected_nonce, Some(5));
+        assert_eq!(transaction.tx_aux.tx_id(), transaction.tx_id);
+    }
+
+    fn deposit_step(key_pair_seed: u8) -> Value {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address =
+            transfer_address_from_public_key(&mainnet, &key_pair(key_pair_seed).1).unwrap();
+
+        json!({
+            "type": "deposit",
+            "nonce": "0",
+            "inputs": [{
+                "prevTxId": hex::encode([1; 32]),
+                "prevIndex": 0,
+                "prevOutput": { "address": address, "value": "100000000" },
+            }],
+        })
+    }
+
+    #[test]
+    fn build_step_transaction_should_reject_key_pair_of_other_staking_address() {
+        let options = options(
+            json!({ "type": "unbond", "nonce": "4", "amount": "1000" }),
+            json!({}),
+        );
+        let (private_key, public_key) = key_pair(2);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+
+        assert!(build_step_transaction(&options, &signer, None).is_err());
+    }
+
+    #[test]
+    fn build_step_transaction_should_deposit_inputs_of_key_pair() {
+        let options = options(deposit_step(1), withdraw_extra());
+        options.verify().unwrap();
+
+        assert!(build_step_transaction(&options, &signer(), None).is_err());
+
+        let transaction =
+            build_step_transaction(&options, &signer(), Some(("", Features::MockObfuscation)))
+                .unwrap();
+
+        assert_eq!(transaction.tx_aux.tx_id(), transaction.tx_id);
+        assert!(transaction.amount.unwrap() < Coin::new(100_000_000).unwrap());
+        assert_eq!(transaction.expected_nonce, Some(1));
+    }
+
+    #[test]
+    fn build_step_transaction_should_reject_deposit_input_of_other_key_pair() {
+        let options = options(deposit_step(2), withdraw_extra());
+
+        assert!(
+            build_step_transaction(&options, &signer(), Some(("", Features::MockObfuscation)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn verify_should_require_inputs_of_deposit() {
+        let options = options(json!({ "type": "deposit", "nonce": "0" }), withdraw_extra());
+
+        assert!(options
+            .verify()
+            .unwrap_err()
+            .to_string()
+            .contains("`step.inputs`"));
+    }
+
+    #[test]
+    fn verify_should_require_destination_of_withdraw() {
+        let options = options(
+            json!({ "type": "withdraw", "nonce": "4", "amount": "1000" }),
+            json!({}),
+        );
+
+        assert!(options
+            .verify()
+            .unwrap_err()
+            .to_string()
+            .contains("`toAddress`"));
+    }
+
+    #[test]
+    fn withdraw_unbonded_tx_should_deduct_fee_from_output() {
+        let options = options(
+            json!({ "type": "withdraw", "nonce": "4", "amount": "100000000" }),
+            withdraw_extra(),
+        );
+        options.verify().unwrap();
+
+        let tx = options
+            .withdraw_unbonded_tx(4, Coin::new(100_000_000).unwrap())
+            .unwrap();
+
+        assert!(tx.outputs[0].value < Coin::new(100_000_000).unwrap());
+    }
+
+    #[test]
+    fn build_step_transaction_should_obfuscate_withdraw() {
+        let options = options(
+            json!({ "type": "withdraw", "nonce": "4", "amount": "100000000" }),
+            withdraw_extra(),
+        );
+
+        assert!(build_step_transaction(&options, &signer(), None).is_err());
+
+        let transaction =
+            build_step_transaction(&options, &signer(), Some(("", Features::MockObfuscation)))
+                .unwrap();
+
+        assert_eq!(transaction.tx_aux.tx_id(), transaction.tx_id);
+        assert!(transaction.amount.unwrap() < Coin::new(100_000_000).unwrap());
+    }
+}
@@ -0,0 +1,297 @@
+//! Planner of the next valid step of a staking address lifecycle
+//!
+//! Deposit bonds the deposited amount to the staked state. Bonded amount is
+//! unbonded by an unbond transaction and becomes withdrawable after the
+//! unbonding period. Every transaction increments the staked state nonce.
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::state::account::Nonce;
+use serde::Deserialize;
+
+use crate::schema::de;
+
+/// Target of the staking lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StakingGoal {
+    /// Have some amount bonded
+    Bond,
+    /// Unbond and withdraw everything of the staked state
+    WithdrawAll,
+}
+
+/// Fields of the staked state relevant to the lifecycle
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakedStateView {
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    #[serde(deserialize_with = "de::coin")]
+    pub bonded: Coin,
+    #[serde(deserialize_with = "de::coin")]
+    pub unbonded: Coin,
+    pub unbonded_from: Timespec,
+    #[serde(default, deserialize_with = "de::optional_timespec")]
+    pub jailed_until: Option<Timespec>,
+}
+
+/// Next step of the staking lifecycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakingStep {
+    /// Deposit to bond funds to the staked state of the nonce
+    Deposit { nonce: Nonce },
+    /// Previous transaction is not yet reflected in the staked state
+    WaitForNonce { expected_nonce: Nonce },
+    /// Staked state is jailed until the time
+    WaitJailed { until: Timespec },
+    /// Unjail the staked state
+    Unjail { nonce: Nonce },
+    /// Unbond the amount from bonded
+    Unbond { nonce: Nonce, amount: Coin },
+    /// Unbonded amount becomes withdrawable at the time
+    WaitUnbonding { until: Timespec },
+    /// Withdraw the unbonded amount
+    Withdraw { nonce: Nonce, amount: Coin },
+    /// Nothing left to do for the goal
+    Done,
+}
+
+impl StakingStep {
+    /// Returns the name of the step
+    pub fn name(&self) -> &'static str {
+        match self {
+            StakingStep::Deposit { .. } => "deposit",
+            StakingStep::WaitForNonce { .. } => "waitForNonce",
+            StakingStep::WaitJailed { .. } => "waitJailed",
+            StakingStep::Unjail { .. } => "unjail",
+            StakingStep::Unbond { .. } => "unbond",
+            StakingStep::WaitUnbonding { .. } => "waitUnbonding",
+            StakingStep::Withdraw { .. } => "withdraw",
+            StakingStep::Done => "done",
+        }
+    }
+
+    /// Returns the staked state nonce the transaction of the step is built on
+    pub fn nonce(&self) -> Option<Nonce> {
+        match self {
+            StakingStep::Deposit { nonce }
+            | StakingStep::Unjail { nonce }
+            | StakingStep::Unbond { nonce, .. }
+            | StakingStep::Withdraw { nonce, .. } => Some(*nonce),
+            _ => None,
+        }
+    }
+
+    /// Returns the staked state nonce expected after the transaction of the
+    /// step is committed
+    pub fn expected_nonce(&self) -> Option<Nonce> {
+        self.nonce().map(|nonce| nonce.saturating_add(1))
+    }
+}
+
+/// Plan the next valid step towards the goal.
+///
+/// `expected_nonce` is the nonce expected after the last submitted
+/// transaction. The planner waits until the staked state reaches it so that
+/// no transaction is built on top of a stale staked state.
+pub fn plan_next_step(
+    goal: StakingGoal,
+    staked_state: Option<&StakedStateView>,
+    block_time: Timespec,
+    expected_nonce: Option<Nonce>,
+) -> StakingStep {
+    let current_nonce = staked_state.map(|state| state.nonce).unwrap_or(0);
+    if let Some(expected_nonce) = expected_nonce {
+        if current_nonce < expected_nonce {
+            return StakingStep::WaitForNonce { expected_nonce };
+        }
+    }
+
+    let staked_state = match staked_state {
+        None => {
+            return match goal {
+                StakingGoal::Bond => StakingStep::Deposit { nonce: 0 },
+                StakingGoal::WithdrawAll => StakingStep::Done,
+            }
+        }
+        Some(staked_state) => staked_state,
+    };
+
+    match goal {
+        StakingGoal::Bond => {
+            if staked_state.bonded == Coin::zero() {
+                StakingStep::Deposit {
+                    nonce: staked_state.nonce,
+                }
+            } else {
+                StakingStep::Done
+            }
+        }
+        StakingGoal::WithdrawAll => plan_withdraw_all(staked_state, block_time),
+    }
+}
+
+fn plan_withdraw_all(staked_state: &StakedStateView, block_time: Timespec) -> StakingStep {
+    if let Some(jailed_until) = staked_state.jailed_until {
+        return if block_time < jailed_until {
+            StakingStep::WaitJailed {
+                until: jailed_until,
+            }
+        } else {
+            StakingStep::Unjail {
+                nonce: staked_state.nonce,
+            }
+        };
+    }
+
+    // Unbonding resets the unbonded time, so the withdrawable amount is
+    // withdrawn before the bonded amount is unbonded
+    let withdrawable =
+        staked_state.unbonded > Coin::zero() && block_time >= staked_state.unbonded_from;
+    if withdrawable {
+        return StakingStep::Withdraw {
+            nonce: staked_state.nonce,
+            amount: staked_state.unbonded,
+        };
+    }
+
+    if staked_state.bonded > Coin::zero() {
+        return StakingStep::Unbond {
+            nonce: staked_state.nonce,
+            amount: staked_state.bonded,
+        };
+    }
+
+    if staked_state.unbonded > Coin::zero() {
+        return StakingStep::WaitUnbonding {
+            until: staked_state.unbonded_from,
+        };
+    }
+
+    StakingStep::Done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staked_state(nonce: Nonce, bonded: u64, unbonded: u64) -> StakedStateView {
+        StakedStateView {
+            nonce,
+            bonded: Coin::new(bonded).unwrap(),
+            unbonded: Coin::new(unbonded).unwrap(),
+            unbonded_from: 1000,
+            jailed_until: None,
+        }
+    }
+
+    #[test]
+    fn plan_next_step_should_deposit_without_staked_state() {
+        assert_eq!(
+            plan_next_step(StakingGoal::Bond, None, 0, None),
+            StakingStep::Deposit { nonce: 0 }
+        );
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, None, 0, None),
+            StakingStep::Done
+        );
+    }
+
+    #[test]
+    fn plan_next_step_should_wait_for_expected_nonce() {
+        let staked_state = staked_state(1, 100, 0);
+
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&staked_state), 0, Some(2)),
+            StakingStep::WaitForNonce { expected_nonce: 2 }
+        );
+    }
+
+    #[test]
+    fn plan_next_step_should_unjail_before_unbonding() {
+        let mut staked_state = staked_state(1, 100, 0);
+        staked_state.jailed_until = Some(500);
+
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&staked_state), 499, None),
+            StakingStep::WaitJailed { until: 500 }
+        );
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&staked_state), 500, None),
+            StakingStep::Unjail { nonce: 1 }
+        );
+    }
+
+    #[test]
+    fn plan_next_step_should_unbond_then_withdraw_after_unbonding_period() {
+        let bonded = staked_state(1, 100, 0);
+        let unbonded = staked_state(2, 0, 100);
+
+        let unbond = plan_next_step(StakingGoal::WithdrawAll, Some(&bonded), 0, None);
+        assert_eq!(
+            unbond,
+            StakingStep::Unbond {
+                nonce: 1,
+                amount: Coin::new(100).unwrap(),
+            }
+        );
+        assert_eq!(unbond.expected_nonce(), Some(2));
+
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&unbonded), 999, Some(2)),
+            StakingStep::WaitUnbonding { until: 1000 }
+        );
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&unbonded), 1000, Some(2)),
+            StakingStep::Withdraw {
+                nonce: 2,
+                amount: Coin::new(100).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn plan_next_step_should_withdraw_before_unbonding_when_withdrawable() {
+        let staked_state = staked_state(3, 100, 50);
+
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&staked_state), 1000, None),
+            StakingStep::Withdraw {
+                nonce: 3,
+                amount: Coin::new(50).unwrap(),
+            }
+        );
+        assert_eq!(
+            plan_next_step(StakingGoal::WithdrawAll, Some(&staked_state), 999, None),
+            StakingStep::Unbond {
+                nonce: 3,
+                amount: Coin::new(100).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn plan_next_step_should_deposit_on_top_of_staked_state_nonce() {
+        let deposit = plan_next_step(StakingGoal::Bond, Some(&staked_state(4, 0, 0)), 0, None);
+
+        assert_eq!(deposit, StakingStep::Deposit { nonce: 4 });
+        assert_eq!(deposit.expected_nonce(), Some(5));
+    }
+
+    #[test]
+    fn plan_next_step_should_be_done_when_goal_is_reached() {
+        assert_eq!(
+            plan_next_step(StakingGoal::Bond, Some(&staked_state(1, 100, 0)), 0, None),
+            StakingStep::Done
+        );
+        assert_eq!(
+            plan_next_step(
+                StakingGoal::WithdrawAll,
+                Some(&staked_state(3, 0, 0)),
+                0,
+                None
+            ),
+            StakingStep::Done
+        );
+    }
+}
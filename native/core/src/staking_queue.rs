@@ -0,0 +1,497 @@
+//! Nonce-aware queue of the staked state operations of a staking address
+//!
+//! A staked state operation is only valid with the current nonce of the
+//! staked state, so operations sent before the previous ones are committed
+//! need sequential nonces. The queue assigns them, and re-bases the pending
+//! operations when one is confirmed or fails, or when the committed nonce is
+//! read from the chain. Each operation is signed when queued and signed
+//! again only when re-based, since re-based operations have a new
+//! transaction id and should be broadcasted again.
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{
+    CouncilNode, Nonce, StakedStateAddress, StakedStateOpAttributes, UnbondTx, UnjailTx,
+};
+use chain_core::state::validator::NodeJoinRequestTx;
+use chain_core::tx::data::TxId;
+use chain_core::tx::{TransactionId, TxAux};
+use client_common::{Error, ErrorKind, Result};
+use serde::Deserialize;
+
+use crate::address::staking_address_from_public_key;
+use crate::council_node_transaction::node_join::sign_node_join_transaction;
+use crate::council_node_transaction::unjail::sign_unjail_transaction;
+use crate::council_node_transaction::CouncilNodeOptions;
+use crate::schema::{de, SchemaResult};
+use crate::signer::KeyPairSigner;
+use crate::staking_transaction::unbond::sign_unbond_transaction;
+
+/// Staked state operation waiting for its nonce
+#[derive(Debug, Clone)]
+pub enum QueuedOperation {
+    Unbond { amount: Coin },
+    Unjail,
+    NodeJoin { council_node: CouncilNode },
+}
+
+impl QueuedOperation {
+    /// Sign the transaction of the operation with the nonce
+    fn to_tx_aux(
+        &self,
+        staking_address: StakedStateAddress,
+        nonce: Nonce,
+        chain_hex_id: u8,
+        signer: &KeyPairSigner,
+    ) -> Result<TxAux> {
+        let attributes = StakedStateOpAttributes::new(chain_hex_id);
+
+        match self {
+            QueuedOperation::Unbond { amount } => sign_unbond_transaction(
+                UnbondTx::new(staking_address, nonce, *amount, attributes),
+                signer,
+            ),
+            QueuedOperation::Unjail => {
+                sign_unjail_transaction(UnjailTx::new(nonce, staking_address, attributes), signer)
+            }
+            QueuedOperation::NodeJoin { council_node } => sign_node_join_transaction(
+                NodeJoinRequestTx::new(nonce, staking_address, attributes, council_node.clone()),
+                signer,
+            ),
+        }
+    }
+}
+
+/// Operation queued after the pending ones, with the transaction signed
+/// with its nonce
+#[derive(Debug)]
+pub struct PendingOperation {
+    pub id: u64,
+    pub nonce: Nonce,
+    pub operation: QueuedOperation,
+    pub tx_id: TxId,
+    pub tx_aux: TxAux,
+}
+
+/// Queue of the pending staked state operations of a staking address
+pub struct StakingQueue {
+    staking_address: StakedStateAddress,
+    chain_hex_id: u8,
+    signer: KeyPairSigner,
+    committed_nonce: Nonce,
+    next_id: u64,
+    pending: Vec<PendingOperation>,
+}
+
+impl StakingQueue {
+    /// Create an empty queue of the staking address whose staked state has
+    /// the committed nonce. Operations are signed with the signer, which
+    /// should be the one of the staking address.
+    pub fn new(
+        staking_address: StakedStateAddress,
+        chain_hex_id: u8,
+        signer: KeyPairSigner,
+        committed_nonce: Nonce,
+    ) -> Result<StakingQueue> {
+        if staking_address_from_public_key(signer.public_key()) != staking_address {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Key pair does not belong to the staking address",
+            ));
+        }
+
+        Ok(StakingQueue {
+            staking_address,
+            chain_hex_id,
+            signer,
+            committed_nonce,
+            next_id: 0,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Nonce of the staked state once every committed operation is applied
+    pub fn committed_nonce(&self) -> Nonce {
+        self.committed_nonce
+    }
+
+    /// Nonce of the next queued operation
+    pub fn next_nonce(&self) -> Nonce {
+        self.committed_nonce + self.pending.len() as Nonce
+    }
+
+    /// Pending operations in nonce order, with their signed transaction
+    pub fn pending(&self) -> &[PendingOperation] {
+        &self.pending
+    }
+
+    /// Sign the operation with the next nonce and queue it. Returns the
+    /// queued operation.
+    pub fn push(&mut self, operation: QueuedOperation) -> Result<&PendingOperation> {
+        let nonce = self.next_nonce();
+        let tx_aux = self.sign(&operation, nonce)?;
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.push(PendingOperation {
+            id,
+            nonce,
+            operation,
+            tx_id: tx_aux.tx_id(),
+            tx_aux,
+        });
+
+        Ok(&self.pending[self.pending.len() - 1])
+    }
+
+    /// Remove the committed operation, which should be the first pending
+    /// one since the chain applies nonces in order
+    pub fn confirm(&mut self, id: u64) -> Result<QueuedOperation> {
+        match self.pending.first() {
+            Some(pending) if pending.id == id => {}
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Operation {} is not the next operation to commit", id),
+                ))
+            }
+        }
+
+        self.committed_nonce += 1;
+
+        Ok(self.pending.remove(0).operation)
+    }
+
+    /// Remove the failed operation and re-base the following ones on its
+    /// nonce. Returns the re-based operations, signed with their new nonce.
+    pub fn fail(&mut self, id: u64) -> Result<&[PendingOperation]> {
+        let index = self
+            .pending
+            .iter()
+            .position(|pending| pending.id == id)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Operation {} is not pending", id),
+                )
+            })?;
+
+        // Signed before removing so that the queue is unchanged on error
+        let rebased = self.resign(index + 1, self.committed_nonce + index as Nonce)?;
+        self.pending.remove(index);
+        self.apply(rebased);
+
+        Ok(&self.pending[index..])
+    }
+
+    /// Re-base the queue on the nonce committed by the chain. Operations
+    /// below the committed nonce are considered committed and removed, and
+    /// the remaining ones are renumbered from the committed nonce and signed
+    /// again when their nonce changes. Returns the ids of the committed
+    /// operations.
+    pub fn rebase(&mut self, committed_nonce: Nonce) -> Result<Vec<u64>> {
+        let committed = committed_nonce.saturating_sub(self.committed_nonce) as usize;
+        let committed = committed.min(self.pending.len());
+
+        let rebased = self.resign(committed, committed_nonce)?;
+        self.committed_nonce = committed_nonce;
+        let committed = self
+            .pending
+            .drain(..committed)
+            .map(|pending| pending.id)
+            .collect();
+        self.apply(rebased);
+
+        Ok(committed)
+    }
+
+    /// Re-base the queue on the nonce of the staked state queried from the
+    /// Tendermint node. Returns the ids of the committed operations.
+    #[cfg(feature = "websocket-rpc")]
+    pub fn sync(&mut self, tendermint_address: &str) -> Result<Vec<u64>> {
+        let committed_nonce = crate::staking_transaction::query_staked_state(
+            &self.staking_address,
+            tendermint_address,
+        )?
+        .map(|staked_state| staked_state.nonce)
+        .unwrap_or_default();
+
+        self.rebase(committed_nonce)
+    }
+
+    fn sign(&self, operation: &QueuedOperation, nonce: Nonce) -> Result<TxAux> {
+        operation.to_tx_aux(self.staking_address, nonce, self.chain_hex_id, &self.signer)
+    }
+
+    /// Sign the pending operations from `start` whose nonce changes when
+    /// renumbered from `first_nonce`. Returns the id, new nonce and
+    /// transaction of each of them.
+    fn resign(&self, start: usize, first_nonce: Nonce) -> Result<Vec<(u64, Nonce, TxAux)>> {
+        self.pending
+            .iter()
+            .skip(start)
+            .enumerate()
+            .map(|(i, pending)| (pending, first_nonce + i as Nonce))
+            .filter(|(pending, nonce)| pending.nonce != *nonce)
+            .map(|(pending, nonce)| Ok((pending.id, nonce, self.sign(&pending.operation, nonce)?)))
+            .collect()
+    }
+
+    fn apply(&mut self, rebased: Vec<(u64, Nonce, TxAux)>) {
+        for (id, nonce, tx_aux) in rebased {
+            if let Some(pending) = self.pending.iter_mut().find(|pending| pending.id == id) {
+                pending.nonce = nonce;
+                pending.tx_id = tx_aux.tx_id();
+                pending.tx_aux = tx_aux;
+            }
+        }
+    }
+}
+
+/// Queued operation of the options
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum QueuedOperationOptions {
+    #[serde(rename_all = "camelCase")]
+    Unbond {
+        #[serde(deserialize_with = "de::coin")]
+        amount: Coin,
+    },
+    Unjail,
+    #[serde(rename_all = "camelCase")]
+    NodeJoin {
+        council_node: CouncilNodeOptions,
+    },
+}
+
+impl QueuedOperationOptions {
+    /// Validate and convert to the operation
+    pub fn to_operation(&self) -> SchemaResult<QueuedOperation> {
+        match self {
+            QueuedOperationOptions::Unbond { amount } => {
+                Ok(QueuedOperation::Unbond { amount: *amount })
+            }
+            QueuedOperationOptions::Unjail => Ok(QueuedOperation::Unjail),
+            QueuedOperationOptions::NodeJoin { council_node } => Ok(QueuedOperation::NodeJoin {
+                council_node: council_node.to_council_node("councilNode")?,
+            }),
+        }
+    }
+}
+
+/// Staking address and committed nonce of a queue
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingQueueOptions {
+    #[serde(deserialize_with = "de::staking_address")]
+    pub staking_address: StakedStateAddress,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+    #[serde(deserialize_with = "de::nonce")]
+    pub committed_nonce: Nonce,
+}
+
+impl StakingQueueOptions {
+    /// Create the empty queue signing with the signer of the staking address
+    pub fn to_queue(&self, signer: KeyPairSigner) -> Result<StakingQueue> {
+        StakingQueue::new(
+            self.staking_address,
+            self.chain_hex_id,
+            signer,
+            self.committed_nonce,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::schema::from_json_value;
+    use crate::transfer_transaction::tests::{key_pair, CHAIN_HEX_ID};
+
+    fn queue(committed_nonce: Nonce) -> StakingQueue {
+        let staking_address = staking_address_from_public_key(&key_pair(1).1);
+
+        StakingQueue::new(staking_address, CHAIN_HEX_ID, signer(), committed_nonce).unwrap()
+    }
+
+    fn unbond(amount: u64) -> QueuedOperation {
+        QueuedOperation::Unbond {
+            amount: Coin::new(amount).unwrap(),
+        }
+    }
+
+    fn signer() -> KeyPairSigner {
+        let (private_key, public_key) = key_pair(1);
+
+        KeyPairSigner::new(private_key, public_key).unwrap()
+    }
+
+    fn nonces(queue: &StakingQueue) -> Vec<Nonce> {
+        queue
+            .pending()
+            .iter()
+            .map(|pending| pending.nonce)
+            .collect()
+    }
+
+    fn tx_ids(queue: &StakingQueue) -> Vec<TxId> {
+        queue
+            .pending()
+            .iter()
+            .map(|pending| pending.tx_id)
+            .collect()
+    }
+
+    fn push(queue: &mut StakingQueue, operation: QueuedOperation) -> u64 {
+        queue.push(operation).unwrap().id
+    }
+
+    #[test]
+    fn new_should_reject_signer_of_other_staking_address() {
+        let staking_address = staking_address_from_public_key(&key_pair(2).1);
+
+        let err = StakingQueue::new(staking_address, CHAIN_HEX_ID, signer(), 0)
+            .err()
+            .unwrap();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err
+            .to_string()
+            .contains("Key pair does not belong to the staking address"));
+    }
+
+    #[test]
+    fn push_should_assign_sequential_nonces() {
+        let mut queue = queue(3);
+
+        push(&mut queue, unbond(1));
+        push(&mut queue, QueuedOperation::Unjail);
+        push(&mut queue, unbond(2));
+
+        assert_eq!(nonces(&queue), vec![3, 4, 5]);
+        assert_eq!(queue.next_nonce(), 6);
+    }
+
+    #[test]
+    fn confirm_should_only_accept_first_pending_operation() {
+        let mut queue = queue(3);
+        let first = push(&mut queue, unbond(1));
+        let second = push(&mut queue, QueuedOperation::Unjail);
+
+        assert!(queue.confirm(second).is_err());
+        queue.confirm(first).unwrap();
+
+        assert_eq!(queue.committed_nonce(), 4);
+        assert_eq!(nonces(&queue), vec![4]);
+    }
+
+    #[test]
+    fn fail_should_rebase_following_operations() {
+        let mut queue = queue(3);
+        push(&mut queue, unbond(1));
+        let failed = push(&mut queue, QueuedOperation::Unjail);
+        let third = push(&mut queue, unbond(2));
+        let fourth = push(&mut queue, unbond(3));
+
+        let rebased: Vec<u64> = queue
+            .fail(failed)
+            .unwrap()
+            .iter()
+            .map(|pending| pending.id)
+            .collect();
+
+        assert_eq!(rebased, vec![third, fourth]);
+        assert_eq!(nonces(&queue), vec![3, 4, 5]);
+        assert!(queue.fail(failed).is_err());
+    }
+
+    #[test]
+    fn rebase_should_remove_committed_operations() {
+        let mut queue = queue(3);
+        let first = push(&mut queue, unbond(1));
+        let second = push(&mut queue, QueuedOperation::Unjail);
+        push(&mut queue, unbond(2));
+
+        assert_eq!(queue.rebase(5).unwrap(), vec![first, second]);
+        assert_eq!(nonces(&queue), vec![5]);
+
+        // Chain behind the queue, e.g. after a dropped transaction
+        assert!(queue.rebase(4).unwrap().is_empty());
+        assert_eq!(nonces(&queue), vec![4]);
+
+        assert_eq!(queue.rebase(10).unwrap().len(), 1);
+        assert_eq!(queue.next_nonce(), 10);
+    }
+
+    #[test]
+    fn rebased_operations_should_be_signed_with_new_nonce() {
+        let mut queue = queue(0);
+        let failed = push(&mut queue, unbond(1));
+        push(&mut queue, QueuedOperation::Unjail);
+        let before = tx_ids(&queue);
+
+        queue.fail(failed).unwrap();
+        let after = &queue.pending()[0];
+
+        assert_eq!(after.nonce, 0);
+        assert_eq!(after.tx_aux.tx_id(), after.tx_id);
+        assert_ne!(after.tx_id, before[1]);
+    }
+
+    #[test]
+    fn operations_with_unchanged_nonce_should_not_be_signed_again() {
+        let mut queue = queue(0);
+        let first = push(&mut queue, unbond(1));
+        push(&mut queue, QueuedOperation::Unjail);
+        let last = push(&mut queue, unbond(2));
+        let before = tx_ids(&queue);
+
+        queue.confirm(first).unwrap();
+        assert_eq!(tx_ids(&queue), before[1..].to_vec());
+
+        assert!(queue.fail(last).unwrap().is_empty());
+        assert!(queue.rebase(1).unwrap().is_empty());
+        assert_eq!(tx_ids(&queue), before[1..2].to_vec());
+    }
+
+    #[test]
+    fn to_operation_should_report_path_of_invalid_field() {
+        let options: QueuedOperationOptions = from_json_value(json!({
+            "type": "nodeJoin",
+            "councilNode": {
+                "name": "",
+                "consensusPublicKey": "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=",
+                "confidentialInit": { "cert": [1] },
+            },
+        }))
+        .unwrap();
+
+        let err = options.to_operation().unwrap_err();
+
+        assert!(err.to_string().contains("`councilNode.name`"));
+    }
+
+    #[cfg(feature = "mock-node")]
+    #[test]
+    fn sync_should_rebase_on_nonce_of_mock_node() {
+        use chain_core::state::account::StakedState;
+
+        use crate::mock_node::MockNode;
+
+        let node = MockNode::start().unwrap();
+        let mut queue = queue(0);
+        let first = push(&mut queue, unbond(1));
+        push(&mut queue, QueuedOperation::Unjail);
+
+        assert!(queue.sync(&node.websocket_address()).unwrap().is_empty());
+
+        let mut staked_state = StakedState::default(queue.staking_address);
+        staked_state.nonce = 1;
+        node.set_staked_state(staked_state);
+
+        assert_eq!(queue.sync(&node.websocket_address()).unwrap(), vec![first]);
+        assert_eq!(nonces(&queue), vec![1]);
+    }
+}
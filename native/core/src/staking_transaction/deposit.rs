@@ -0,0 +1,328 @@
+use chain_core::init::coin::{sum_coins, Coin};
+use chain_core::state::account::{DepositBondTx, StakedStateAddress, StakedStateOpAttributes};
+use chain_core::tx::data::input::{TxoPointer, TxoSize};
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use chain_core::tx::fee::FeeAlgorithm;
+use chain_core::tx::witness::TxInWitness;
+use chain_core::tx::TransactionId;
+use client_common::{Error, ErrorKind, PrivateKey, PublicKey, Result, ResultExt};
+use serde::Deserialize;
+
+use crate::address::extended_address_from_public_key;
+use crate::fee_estimation::mock_witness::{mock_deposit_tx_aux, mock_tx_in_witness};
+use crate::schema::types::{
+    network_of_chain_hex_id, AddressParamsOptions, FeeConfigOptions, OutputOptions,
+};
+use crate::schema::{de, SchemaError, SchemaResult};
+use crate::signer::KeyPairSigner;
+
+/// Options of a deposit transaction. Previous outputs of the inputs are
+/// optional, and the deposit amount is computed when they are provided.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDepositTransactionOptions {
+    pub inputs: Vec<DepositInputOptions>,
+    #[serde(deserialize_with = "de::staking_address")]
+    pub to_address: StakedStateAddress,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+    #[serde(default)]
+    pub fee_config: Option<FeeConfigOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInputOptions {
+    #[serde(deserialize_with = "de::tx_id")]
+    pub prev_tx_id: TxId,
+    pub prev_index: TxoSize,
+    #[serde(default)]
+    pub prev_output: Option<OutputOptions>,
+    #[serde(default)]
+    pub address_params: AddressParamsOptions,
+}
+
+impl BuildDepositTransactionOptions {
+    /// Returns the unsigned deposit transaction
+    pub fn to_tx(&self) -> DepositBondTx {
+        deposit_bond_tx(&self.inputs, self.to_address, self.chain_hex_id)
+    }
+
+    /// Returns the outputs spent by the inputs when they are all provided
+    pub fn spent_outputs(&self) -> SchemaResult<Option<Vec<(TxOut, AddressParamsOptions)>>> {
+        let has_prev_output = self.inputs.iter().any(|input| input.prev_output.is_some());
+        if !has_prev_output {
+            return Ok(None);
+        }
+        if self.fee_config.is_none() {
+            return Err(SchemaError::new(
+                "feeConfig",
+                "Fee config is required to compute the deposit amount",
+            ));
+        }
+
+        deposit_spent_outputs(&self.inputs, self.chain_hex_id, "inputs").map(Some)
+    }
+}
+
+/// Returns the unsigned transaction depositing the inputs to the staking
+/// address
+pub fn deposit_bond_tx(
+    inputs: &[DepositInputOptions],
+    to_address: StakedStateAddress,
+    chain_hex_id: u8,
+) -> DepositBondTx {
+    let inputs = inputs
+        .iter()
+        .map(|input| TxoPointer {
+            id: input.prev_tx_id,
+            index: input.prev_index,
+        })
+        .collect();
+    let attributes = StakedStateOpAttributes::new(chain_hex_id);
+
+    DepositBondTx::new(inputs, to_address, attributes)
+}
+
+/// Returns the outputs spent by the inputs located at `path`, all of which
+/// should have their previous output provided
+pub fn deposit_spent_outputs(
+    inputs: &[DepositInputOptions],
+    chain_hex_id: u8,
+    path: &str,
+) -> SchemaResult<Vec<(TxOut, AddressParamsOptions)>> {
+    let network = network_of_chain_hex_id(chain_hex_id, "chainHexId")?;
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let prev_output = input.prev_output.as_ref().ok_or_else(|| {
+                SchemaError::new(
+                    format!("{}[{}].prevOutput", path, i),
+                    "Previous output should be provided for all inputs or none",
+                )
+            })?;
+            input
+                .address_params
+                .verify(&format!("{}[{}].addressParams", path, i))?;
+            let tx_out = prev_output.to_tx_out(&network, &format!("{}[{}].prevOutput", path, i))?;
+
+            Ok((tx_out, input.address_params))
+        })
+        .collect()
+}
+
+/// Verify the inputs located at `path` spend 1-of-1 addresses. Inputs are
+/// signed with a single Schnorr signature of the key pair, so the witness
+/// of multi-signature addresses is to be added as is.
+pub fn verify_key_pair_inputs(inputs: &[DepositInputOptions], path: &str) -> SchemaResult<()> {
+    for (i, input) in inputs.iter().enumerate() {
+        if input.address_params.total_signers != 1 {
+            return Err(SchemaError::new(
+                format!("{}[{}].addressParams", path, i),
+                "Multi-signature input cannot be signed by a key pair",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Amounts of a deposit transaction whose spent outputs are known
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepositAmounts {
+    pub input_amount: Coin,
+    pub fee: Coin,
+    pub deposit_amount: Coin,
+}
+
+/// Compute the amount deposited to the staking address, which is the sum of
+/// the spent outputs net of the transaction fee
+pub fn deposit_amounts<F>(
+    tx: &DepositBondTx,
+    spent_outputs: &[(TxOut, AddressParamsOptions)],
+    fee_algorithm: &F,
+) -> Result<DepositAmounts>
+where
+    F: FeeAlgorithm,
+{
+    let input_amount = sum_coins(spent_outputs.iter().map(|(output, _)| output.value))
+        .chain(|| (ErrorKind::InvalidInput, "Input amount exceeds maximum coin"))?;
+
+    let tx_id = tx.id();
+    let witness = spent_outputs
+        .iter()
+        .map(|(_, address_params)| mock_tx_in_witness(&tx_id, address_params))
+        .collect::<Result<Vec<TxInWitness>>>()?;
+    let tx_aux = mock_deposit_tx_aux(tx, witness.into());
+
+    let fee = fee_algorithm
+        .calculate_for_txaux(&tx_aux)
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Unable to calculate transaction fee",
+            )
+        })?
+        .to_coin();
+    let deposit_amount = (input_amount - fee).chain(|| {
+        (
+            ErrorKind::InvalidInput,
+            "Input amount is insufficient to pay the transaction fee",
+        )
+    })?;
+
+    Ok(DepositAmounts {
+        input_amount,
+        fee,
+        deposit_amount,
+    })
+}
+
+/// Sign an input of the deposit transaction, which should spend the 1-of-1
+/// address of the key pair. The previous output is verified to belong to
+/// the key pair when provided.
+pub fn sign_deposit_input(
+    tx: &DepositBondTx,
+    prev_output: Option<&TxOut>,
+    private_key: PrivateKey,
+    public_key: PublicKey,
+) -> Result<TxInWitness> {
+    if let Some(prev_output) = prev_output {
+        let signing_address = extended_address_from_public_key(&public_key)?;
+        if prev_output.address != signing_address {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Input address is not signable by the key pair",
+            ));
+        }
+    }
+
+    let signer = KeyPairSigner::new(private_key, public_key)?;
+
+    signer.schnorr_sign_txid(&tx.id())
+}
+
+/// Verify the witness unlocks the output spent by an input of the deposit
+/// transaction
+pub fn verify_deposit_input_witness(
+    tx: &DepositBondTx,
+    prev_output: &TxOut,
+    witness: &TxInWitness,
+) -> Result<()> {
+    witness
+        .verify_tx_address(&tx.id(), &prev_output.address)
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Witness does not unlock the input address",
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::address::transfer_address_from_public_key;
+    use crate::network::{network_by_name, MAINNET};
+    use crate::schema::from_json_value;
+    use crate::transfer_transaction::tests::{fee_config, key_pair};
+
+    const STAKING_ADDRESS: &str = "0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0";
+
+    fn options(prev_outputs: bool) -> BuildDepositTransactionOptions {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address = transfer_address_from_public_key(&mainnet, &key_pair(1).1).unwrap();
+        let input = |i: u8| {
+            let mut input = json!({
+                "prevTxId": hex::encode([i; 32]),
+                "prevIndex": 0,
+            });
+            if prev_outputs {
+                input["prevOutput"] = json!({ "address": address, "value": "100000000" });
+            }
+            input
+        };
+
+        from_json_value(json!({
+            "inputs": [input(1), input(2)],
+            "toAddress": STAKING_ADDRESS,
+            "chainHexId": [0x2A],
+            "feeConfig": {
+                "algorithm": "LinearFee",
+                "constant": "1.1",
+                "coefficient": "1.25",
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn spent_outputs_should_be_none_without_previous_outputs() {
+        assert!(options(false).spent_outputs().unwrap().is_none());
+    }
+
+    #[test]
+    fn deposit_amounts_should_deduct_fee_from_input_amount() {
+        let options = options(true);
+        let tx = options.to_tx();
+        let spent_outputs = options.spent_outputs().unwrap().unwrap();
+
+        let amounts =
+            deposit_amounts(&tx, &spent_outputs, &fee_config().to_fee_algorithm()).unwrap();
+
+        assert_eq!(amounts.input_amount, Coin::new(200_000_000).unwrap());
+        assert!(amounts.fee > Coin::zero());
+        assert_eq!(
+            (amounts.deposit_amount + amounts.fee).unwrap(),
+            amounts.input_amount
+        );
+    }
+
+    #[test]
+    fn sign_deposit_input_should_unlock_previous_output() {
+        let options = options(true);
+        let tx = options.to_tx();
+        let spent_outputs = options.spent_outputs().unwrap().unwrap();
+        let prev_output = &spent_outputs[0].0;
+        let (private_key, public_key) = key_pair(1);
+
+        let witness = sign_deposit_input(&tx, Some(prev_output), private_key, public_key).unwrap();
+
+        verify_deposit_input_witness(&tx, prev_output, &witness).unwrap();
+    }
+
+    #[test]
+    fn verify_key_pair_inputs_should_reject_multi_signature_input() {
+        let mut options = options(true);
+        verify_key_pair_inputs(&options.inputs, "inputs").unwrap();
+
+        options.inputs[1].address_params = AddressParamsOptions {
+            required_signers: 1,
+            total_signers: 2,
+        };
+
+        assert_eq!(
+            verify_key_pair_inputs(&options.inputs, "inputs")
+                .unwrap_err()
+                .to_string(),
+            "Invalid options at `inputs[1].addressParams`: Multi-signature input cannot be signed by a key pair"
+        );
+    }
+
+    #[test]
+    fn sign_deposit_input_should_reject_previous_output_of_other_key_pair() {
+        let options = options(true);
+        let tx = options.to_tx();
+        let spent_outputs = options.spent_outputs().unwrap().unwrap();
+        let (private_key, public_key) = key_pair(2);
+
+        assert!(
+            sign_deposit_input(&tx, Some(&spent_outputs[0].0), private_key, public_key).is_err()
+        );
+    }
+}
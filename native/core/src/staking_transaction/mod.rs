@@ -0,0 +1,23 @@
+//! Deposit, unbond and withdraw unbonded transactions of a staked state
+pub mod deposit;
+#[cfg(feature = "websocket-rpc")]
+mod staked_state;
+pub mod unbond;
+pub mod withdraw_unbonded;
+
+use chain_core::state::account::StakedStateOpWitness;
+use chain_core::tx::data::TxId;
+use client_common::Result;
+
+use crate::signer::KeyPairSigner;
+
+#[cfg(feature = "websocket-rpc")]
+pub use staked_state::query_staked_state;
+
+/// Sign a staked state operation with the key pair of the staking address
+pub fn staked_state_op_witness(
+    signer: &KeyPairSigner,
+    tx_id: &TxId,
+) -> Result<StakedStateOpWitness> {
+    signer.sign(tx_id).map(StakedStateOpWitness::new)
+}
@@ -0,0 +1,36 @@
+use chain_core::state::account::{StakedState, StakedStateAddress};
+use client_common::tendermint::types::AbciQueryExt;
+use client_common::tendermint::{Client, WebsocketRpcClient};
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use parity_scale_codec::Decode;
+
+/// Query the staked state of a staking address from the Tendermint node.
+/// Returns `None` when the staking address has no staked state.
+pub fn query_staked_state(
+    staking_address: &StakedStateAddress,
+    tendermint_address: &str,
+) -> Result<Option<StakedState>> {
+    let StakedStateAddress::BasicRedeem(redeem_address) = staking_address;
+
+    if !tendermint_address.starts_with("ws") {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Unsupported Tendermint client protocol",
+        ));
+    }
+    let tendermint_client = WebsocketRpcClient::new(tendermint_address).chain(|| {
+        (
+            ErrorKind::InvalidInput,
+            "Unable to create Tendermint client from address",
+        )
+    })?;
+
+    let response = tendermint_client.query("staking", &redeem_address.0, None, false)?;
+
+    Option::<StakedState>::decode(&mut response.bytes().as_slice()).chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            "Unable to decode staked state",
+        )
+    })
+}
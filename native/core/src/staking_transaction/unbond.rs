@@ -0,0 +1,292 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{Nonce, StakedStateAddress, StakedStateOpAttributes, UnbondTx};
+use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
+use client_common::Result;
+use serde::Deserialize;
+
+use crate::schema::{de, SchemaError, SchemaResult};
+use crate::signer::KeyPairSigner;
+
+use super::staked_state_op_witness;
+
+/// Sign the unbond transaction into a broadcast-able `TxAux`
+pub fn sign_unbond_transaction(tx: UnbondTx, signer: &KeyPairSigner) -> Result<TxAux> {
+    let witness = staked_state_op_witness(signer, &tx.id())?;
+
+    Ok(TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, witness)))
+}
+
+/// Options of an unbond transaction
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildUnbondTransactionOptions {
+    #[serde(deserialize_with = "de::staking_address")]
+    pub staking_address: StakedStateAddress,
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    pub amount: Option<Coin>,
+    #[serde(default)]
+    pub unbond_all: bool,
+    #[serde(default)]
+    pub staked_state: Option<StakedStateOptions>,
+    pub unbonding_period: Timespec,
+    #[serde(default, deserialize_with = "de::optional_timespec")]
+    pub block_time: Option<Timespec>,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+}
+
+/// Staked state of the staking address known by the caller
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakedStateOptions {
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    #[serde(deserialize_with = "de::coin")]
+    pub bonded: Coin,
+    #[serde(deserialize_with = "de::staking_address")]
+    pub address: StakedStateAddress,
+    #[serde(default, deserialize_with = "de::optional_timespec")]
+    pub jailed_until: Option<Timespec>,
+}
+
+impl BuildUnbondTransactionOptions {
+    /// Returns the unsigned unbond transaction of `amount`
+    pub fn to_tx(&self, amount: Coin) -> UnbondTx {
+        let attributes = StakedStateOpAttributes::new(self.chain_hex_id);
+
+        UnbondTx::new(self.staking_address, self.nonce, amount, attributes)
+    }
+
+    /// Returns the amount to unbond, verified against the staked state when
+    /// provided
+    pub fn unbond_amount(&self) -> SchemaResult<Coin> {
+        let amount = match (self.amount, self.unbond_all, &self.staked_state) {
+            (Some(_), true, _) => {
+                return Err(SchemaError::new(
+                    "amount",
+                    "Amount should not be provided when unbonding all bonded",
+                ))
+            }
+            (Some(amount), false, _) => amount,
+            (None, true, Some(staked_state)) => {
+                if staked_state.bonded == Coin::zero() {
+                    return Err(SchemaError::new(
+                        "stakedState.bonded",
+                        "Staked state has no bonded amount to unbond",
+                    ));
+                }
+                staked_state.bonded
+            }
+            (None, true, None) => {
+                return Err(SchemaError::new(
+                    "stakedState",
+                    "Staked state should be provided when unbonding all bonded",
+                ))
+            }
+            (None, false, _) => {
+                return Err(SchemaError::new(
+                    "amount",
+                    "Amount should be provided unless unbonding all bonded",
+                ))
+            }
+        };
+
+        if let Some(staked_state) = &self.staked_state {
+            staked_state.verify(&self.staking_address, self.nonce, amount)?;
+        }
+
+        Ok(amount)
+    }
+
+    /// Returns the unbonded from time of the unbonded amount when the
+    /// transaction is included in a block at `blockTime`, or now when the
+    /// block time is not provided
+    pub fn unbonded_from(&self) -> SchemaResult<Timespec> {
+        let block_time = match self.block_time {
+            Some(block_time) => block_time,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|_| SchemaError::new("blockTime", "System time is before Unix epoch"))?
+                .as_secs(),
+        };
+
+        block_time
+            .checked_add(self.unbonding_period)
+            .ok_or_else(|| {
+                SchemaError::new("unbondingPeriod", "Unbonded from time exceeds maximum")
+            })
+    }
+}
+
+impl StakedStateOptions {
+    /// Verify the staked state can unbond `amount` with the transaction
+    pub fn verify(
+        &self,
+        staking_address: &StakedStateAddress,
+        nonce: Nonce,
+        amount: Coin,
+    ) -> SchemaResult<()> {
+        if &self.address != staking_address {
+            return Err(SchemaError::new(
+                "stakedState.address",
+                "Staked state does not belong to the staking address",
+            ));
+        }
+        if self.nonce != nonce {
+            return Err(SchemaError::new(
+                "stakedState.nonce",
+                format!(
+                    "Staked state nonce {} does not match transaction nonce {}",
+                    self.nonce, nonce
+                ),
+            ));
+        }
+        if let Some(jailed_until) = self.jailed_until {
+            return Err(SchemaError::new(
+                "stakedState.jailedUntil",
+                format!(
+                    "Staked state is jailed until {}, it should be unjailed before unbonding",
+                    jailed_until
+                ),
+            ));
+        }
+        if amount > self.bonded {
+            return Err(SchemaError::new(
+                "amount",
+                format!(
+                    "Unbond amount {} exceeds bonded amount {}",
+                    u64::from(amount),
+                    u64::from(self.bonded)
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_tx_validation::witness::verify_tx_recover_address;
+    use serde_json::{json, Value};
+
+    use crate::address::staking_address_from_public_key;
+    use crate::schema::from_json_value;
+    use crate::transfer_transaction::tests::key_pair;
+
+    const STAKING_ADDRESS: &str = "0xb5698ee21f69a6184afbe59b3626ed9d4bd755b0";
+
+    fn options(extra: Value) -> BuildUnbondTransactionOptions {
+        let mut options = json!({
+            "stakingAddress": STAKING_ADDRESS,
+            "nonce": "1",
+            "unbondingPeriod": 60,
+            "chainHexId": [0x2A],
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            options[key] = value.clone();
+        }
+
+        from_json_value(options).unwrap()
+    }
+
+    fn staked_state(bonded: &str) -> Value {
+        json!({ "nonce": "1", "bonded": bonded, "address": STAKING_ADDRESS })
+    }
+
+    #[test]
+    fn unbond_amount_should_return_provided_amount() {
+        let options = options(json!({ "amount": "1000" }));
+
+        assert_eq!(options.unbond_amount().unwrap(), Coin::new(1000).unwrap());
+    }
+
+    #[test]
+    fn unbond_amount_should_unbond_all_bonded_of_staked_state() {
+        let options = options(json!({
+            "unbondAll": true,
+            "stakedState": staked_state("5000"),
+        }));
+
+        assert_eq!(options.unbond_amount().unwrap(), Coin::new(5000).unwrap());
+    }
+
+    #[test]
+    fn unbond_amount_should_reject_amount_over_bonded() {
+        let options = options(json!({
+            "amount": "5001",
+            "stakedState": staked_state("5000"),
+        }));
+
+        assert!(options
+            .unbond_amount()
+            .unwrap_err()
+            .to_string()
+            .contains("exceeds bonded amount"));
+    }
+
+    #[test]
+    fn unbond_amount_should_reject_missing_amount() {
+        assert!(options(json!({})).unbond_amount().is_err());
+        assert!(options(json!({ "unbondAll": true }))
+            .unbond_amount()
+            .is_err());
+        assert!(options(json!({ "amount": "1", "unbondAll": true }))
+            .unbond_amount()
+            .is_err());
+    }
+
+    #[test]
+    fn unbonded_from_should_add_unbonding_period_to_block_time() {
+        let options = options(json!({ "blockTime": 1000 }));
+
+        assert_eq!(options.unbonded_from().unwrap(), 1060);
+    }
+
+    #[test]
+    fn unbonded_from_should_default_block_time_to_now() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        assert!(options(json!({})).unbonded_from().unwrap() >= now + 60);
+    }
+
+    #[test]
+    fn options_should_require_unbonding_period() {
+        let result: SchemaResult<BuildUnbondTransactionOptions> = from_json_value(json!({
+            "stakingAddress": STAKING_ADDRESS,
+            "nonce": "1",
+            "amount": "1000",
+            "chainHexId": [0x2A],
+        }));
+
+        assert!(result.unwrap_err().to_string().contains("unbondingPeriod"));
+    }
+
+    #[test]
+    fn sign_unbond_transaction_should_be_signed_by_staking_address() {
+        let (private_key, public_key) = key_pair(1);
+        let signer = KeyPairSigner::new(private_key, public_key.clone()).unwrap();
+        let tx = options(json!({})).to_tx(Coin::new(1000).unwrap());
+
+        match sign_unbond_transaction(tx.clone(), &signer).unwrap() {
+            TxAux::PublicTx(TxPublicAux::UnbondStakeTx(signed_tx, witness)) => {
+                assert_eq!(signed_tx, tx);
+                assert_eq!(
+                    verify_tx_recover_address(&witness, &tx.id()).unwrap(),
+                    staking_address_from_public_key(&public_key)
+                );
+            }
+            _ => panic!("Expected unbond transaction"),
+        }
+    }
+}
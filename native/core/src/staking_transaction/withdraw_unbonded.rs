@@ -0,0 +1,124 @@
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{Nonce, WithdrawUnbondedTx};
+use chain_core::tx::data::access::{TxAccess, TxAccessPolicy};
+use chain_core::tx::data::attribute::TxAttributes;
+use chain_core::tx::fee::FeeAlgorithm;
+use client_common::{ErrorKind, PublicKey, Result, ResultExt};
+use client_core::signer::DummySigner;
+use serde::Deserialize;
+
+use crate::schema::types::{network_of_chain_hex_id, to_tx_outs, OutputOptions};
+use crate::schema::{de, SchemaResult};
+
+/// Options of a withdraw unbonded transaction
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildWithdrawUnbondedTransactionOptions {
+    #[serde(deserialize_with = "de::nonce")]
+    pub nonce: Nonce,
+    pub outputs: Vec<OutputOptions>,
+    #[serde(deserialize_with = "de::view_keys")]
+    pub view_keys: Vec<PublicKey>,
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+}
+
+impl BuildWithdrawUnbondedTransactionOptions {
+    /// Returns the unsigned withdraw unbonded transaction
+    pub fn to_tx(&self) -> SchemaResult<WithdrawUnbondedTx> {
+        let network = network_of_chain_hex_id(self.chain_hex_id, "chainHexId")?;
+        let outputs = to_tx_outs(&self.outputs, &network, "outputs")?;
+
+        let access_policies = self
+            .view_keys
+            .iter()
+            .map(|view_key| TxAccessPolicy {
+                view_key: view_key.into(),
+                access: TxAccess::AllData,
+            })
+            .collect();
+        let attributes = TxAttributes::new_with_access(self.chain_hex_id, access_policies);
+
+        Ok(WithdrawUnbondedTx::new(self.nonce, outputs, attributes))
+    }
+}
+
+/// Estimate the fee of the withdraw unbonded transaction once signed
+pub fn estimate_withdraw_unbonded_fee<F>(tx: WithdrawUnbondedTx, fee_algorithm: &F) -> Result<Coin>
+where
+    F: FeeAlgorithm,
+{
+    let tx_aux = DummySigner().mock_txaux_for_withdraw(tx);
+
+    fee_algorithm
+        .calculate_for_txaux(&tx_aux)
+        .map(|fee| fee.to_coin())
+        .chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Unable to calculate transaction fee",
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::address::transfer_address_from_public_key;
+    use crate::network::{network_by_name, MAINNET, TESTNET};
+    use crate::schema::from_json_value;
+    use crate::transfer_transaction::tests::{fee_config, key_pair};
+
+    fn options(address: &str) -> BuildWithdrawUnbondedTransactionOptions {
+        from_json_value(json!({
+            "nonce": "2",
+            "outputs": [{ "address": address, "value": "1000" }],
+            "viewKeys": [],
+            "chainHexId": [0x2A],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn to_tx_should_decode_network_outputs() {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address = transfer_address_from_public_key(&mainnet, &key_pair(1).1).unwrap();
+
+        let tx = options(&address).to_tx().unwrap();
+
+        assert_eq!(tx.nonce, 2);
+        assert_eq!(tx.outputs.len(), 1);
+        assert_eq!(tx.attributes.chain_hex_id, 0x2A);
+    }
+
+    #[test]
+    fn to_tx_should_reject_address_of_other_network() {
+        let testnet = network_by_name(TESTNET).unwrap();
+        let address = transfer_address_from_public_key(&testnet, &key_pair(1).1).unwrap();
+
+        assert!(options(&address)
+            .to_tx()
+            .unwrap_err()
+            .to_string()
+            .contains("`outputs[0].address`"));
+    }
+
+    #[test]
+    fn estimate_withdraw_unbonded_fee_should_grow_with_outputs() {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address = transfer_address_from_public_key(&mainnet, &key_pair(1).1).unwrap();
+        let fee_algorithm = fee_config().to_fee_algorithm();
+        let tx = options(&address).to_tx().unwrap();
+        let mut larger_tx = tx.clone();
+        larger_tx.outputs.push(tx.outputs[0].clone());
+
+        let fee = estimate_withdraw_unbonded_fee(tx, &fee_algorithm).unwrap();
+        let larger_fee = estimate_withdraw_unbonded_fee(larger_tx, &fee_algorithm).unwrap();
+
+        assert!(fee > Coin::zero());
+        assert!(larger_fee > fee);
+    }
+}
@@ -0,0 +1,110 @@
+//! Decryption of the outputs of enclave transactions with a view key
+//!
+//! Transfer and withdraw unbonded transactions are obfuscated by the enclave.
+//! Their outputs are decrypted through the transaction query enclave of the
+//! node for the transactions the view key has access to. Mock obfuscations
+//! keep the signed transaction in plain as the payload, which is decoded as
+//! is.
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use chain_core::tx::{TxAux, TxEnclaveAux};
+use client_common::{Error, ErrorKind, PrivateKey, Result, SignedTransaction, Transaction};
+use client_core::cipher::TransactionObfuscation;
+
+use crate::codec::decode_transaction;
+use crate::tx_aux::{Features, Obfuscation};
+
+/// View key decrypting the outputs of the transactions matched against the
+/// transfer addresses of a filter
+pub struct ViewKeyDecryption {
+    pub view_key: PrivateKey,
+    /// Obfuscation of the node. `MockAbci` and `MockObfuscation` payloads
+    /// are decoded without the node.
+    pub features: Features,
+}
+
+/// Decryption of a subscription, connected to the transaction query enclave
+/// when first needed
+pub(super) struct Decryptor {
+    address: String,
+    decryption: ViewKeyDecryption,
+    obfuscation: Option<Obfuscation>,
+}
+
+impl Decryptor {
+    pub(super) fn new(address: String, decryption: ViewKeyDecryption) -> Decryptor {
+        Decryptor {
+            address,
+            decryption,
+            obfuscation: None,
+        }
+    }
+
+    /// Outputs of the transfer or withdraw unbonded transaction. Returns
+    /// `None` for other transactions and for the ones the view key has no
+    /// access to. Connection failures are returned so that the transaction
+    /// is decrypted again on reconnection.
+    pub(super) fn outputs(&mut self, tx_id: &TxId, tx_aux: &TxAux) -> Result<Option<Vec<TxOut>>> {
+        let payload = match tx_aux {
+            TxAux::EnclaveTx(TxEnclaveAux::TransferTx { payload, .. })
+            | TxAux::EnclaveTx(TxEnclaveAux::WithdrawUnbondedStakeTx { payload, .. }) => payload,
+            _ => return Ok(None),
+        };
+
+        if self.decryption.features != Features::AllDefault {
+            let outputs = decode_transaction::<SignedTransaction>(&payload.txpayload, "payload")
+                .ok()
+                .and_then(|transaction| match transaction {
+                    SignedTransaction::TransferTransaction(tx, _) => Some(tx.outputs),
+                    SignedTransaction::WithdrawUnbondedStakeTransaction(tx, _) => Some(tx.outputs),
+                    _ => None,
+                });
+            return Ok(outputs);
+        }
+
+        let decrypted = match self
+            .obfuscation()?
+            .decrypt(&[*tx_id], &self.decryption.view_key)
+        {
+            Ok(decrypted) => decrypted,
+            Err(err) if is_connection_error(&err) => {
+                self.obfuscation = None;
+                return Err(err);
+            }
+            Err(_) => return Ok(None),
+        };
+
+        Ok(decrypted
+            .into_iter()
+            .find_map(|transaction| match transaction {
+                Transaction::TransferTransaction(tx) => Some(tx.outputs),
+                Transaction::WithdrawUnbondedStakeTransaction(tx) => Some(tx.outputs),
+                _ => None,
+            }))
+    }
+
+    fn obfuscation(&mut self) -> Result<&Obfuscation> {
+        if self.obfuscation.is_none() {
+            let obfuscation =
+                Obfuscation::new(&self.address, self.decryption.features).map_err(|err| {
+                    Error::new(
+                        ErrorKind::ConnectionError,
+                        format!("Unable to connect to transaction query enclave: {}", err),
+                    )
+                })?;
+            self.obfuscation = Some(obfuscation);
+        }
+
+        Ok(self
+            .obfuscation
+            .as_ref()
+            .expect("Obfuscation is created above"))
+    }
+}
+
+fn is_connection_error(err: &Error) -> bool {
+    match err.kind() {
+        ErrorKind::ConnectionError | ErrorKind::TendermintRpcError => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,280 @@
+//! Parsing of the Tendermint events and blocks into subscription events
+//!
+//! Events pushed by the `subscribe` method and blocks returned by the
+//! `block` and `block_results` methods share the same JSON shapes. Heights
+//! are decimal strings and result codes are omitted when zero.
+use chain_core::common::Timespec;
+use chain_core::state::account::StakedStateAddress;
+use chain_core::tx::data::TxId;
+use chain_core::tx::{TransactionId, TxAux, TxEnclaveAux, TxPublicAux};
+use chain_tx_validation::witness::verify_tx_recover_address;
+use serde_json::Value;
+
+use crate::codec::decode_transaction;
+use crate::timespec::timespec_from_iso8601;
+
+pub(super) const NEW_BLOCK_QUERY: &str = "tm.event='NewBlock'";
+pub(super) const TX_QUERY: &str = "tm.event='Tx'";
+
+const NEW_BLOCK_EVENT_TYPE: &str = "tendermint/event/NewBlock";
+const TX_EVENT_TYPE: &str = "tendermint/event/Tx";
+
+/// New block of the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockEvent {
+    pub height: u64,
+    pub time: Timespec,
+    pub tx_count: usize,
+}
+
+/// Transaction delivered in a block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxEvent {
+    pub height: u64,
+    pub index: u32,
+    pub tx_id: TxId,
+    /// Transaction type, named as in the transaction assertions, e.g.
+    /// `Deposit`
+    pub tx_type: &'static str,
+    /// Staking address of staking and council node transactions. Transfer
+    /// transactions have none since their outputs are obfuscated.
+    pub staking_address: Option<StakedStateAddress>,
+    /// Result code of the delivery, 0 when the transaction is valid
+    pub code: u32,
+    pub log: String,
+}
+
+/// Event pushed by Tendermint for one of the subscription queries
+#[derive(Debug)]
+pub(super) enum TendermintEvent {
+    Block(BlockEvent),
+    /// Transaction event with its decoded transaction
+    Tx(TxEvent, TxAux),
+}
+
+/// Parse the result of an event pushed by the `subscribe` method. Returns
+/// `None` for events of other types.
+pub(super) fn parse_event(result: &Value) -> Option<TendermintEvent> {
+    let data = result.get("data")?;
+    let value = data.get("value")?;
+
+    match data.get("type")?.as_str()? {
+        NEW_BLOCK_EVENT_TYPE => parse_block(value.get("block")?).map(TendermintEvent::Block),
+        TX_EVENT_TYPE => {
+            let tx_result = value.get("TxResult")?;
+
+            parse_tx(
+                u64_field(tx_result.get("height")?)?,
+                u64_field(tx_result.get("index").unwrap_or(&Value::Null)).unwrap_or_default()
+                    as u32,
+                &base64::decode(tx_result.get("tx")?.as_str()?).ok()?,
+                tx_result.get("result").unwrap_or(&Value::Null),
+            )
+            .map(|(tx, tx_aux)| TendermintEvent::Tx(tx, tx_aux))
+        }
+        _ => None,
+    }
+}
+
+/// Parse the block of the `block` method result or of a `NewBlock` event
+pub(super) fn parse_block(block: &Value) -> Option<BlockEvent> {
+    let header = block.get("header")?;
+
+    Some(BlockEvent {
+        height: u64_field(header.get("height")?)?,
+        time: parse_block_time(header.get("time")?.as_str()?)?,
+        tx_count: block_txs(block).len(),
+    })
+}
+
+/// Raw transactions of the block
+pub(super) fn block_txs(block: &Value) -> Vec<Vec<u8>> {
+    block
+        .get("data")
+        .and_then(|data| data.get("txs"))
+        .and_then(Value::as_array)
+        .map(|txs| {
+            txs.iter()
+                .filter_map(Value::as_str)
+                .filter_map(|tx| base64::decode(tx).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the transaction of the block at the index with its delivery
+/// result. Returns `None` when the transaction is not a `TxAux`.
+pub(super) fn parse_tx(
+    height: u64,
+    index: u32,
+    tx: &[u8],
+    result: &Value,
+) -> Option<(TxEvent, TxAux)> {
+    let tx_aux = decode_transaction::<TxAux>(tx, "transaction").ok()?;
+    let (tx_type, staking_address) = describe_tx_aux(&tx_aux);
+
+    let tx_event = TxEvent {
+        height,
+        index,
+        tx_id: tx_aux.tx_id(),
+        tx_type,
+        staking_address,
+        code: u64_field(result.get("code").unwrap_or(&Value::Null)).unwrap_or_default() as u32,
+        log: result
+            .get("log")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_owned(),
+    };
+
+    Some((tx_event, tx_aux))
+}
+
+/// Type and staking address of the transaction. The staking address of
+/// withdraw unbonded transactions is recovered from their witness.
+fn describe_tx_aux(tx_aux: &TxAux) -> (&'static str, Option<StakedStateAddress>) {
+    match tx_aux {
+        TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, _)) => {
+            ("Unbond", Some(tx.from_staked_account))
+        }
+        TxAux::PublicTx(TxPublicAux::NodeJoinTx(tx, _)) => ("NodeJoin", Some(tx.address)),
+        TxAux::PublicTx(TxPublicAux::UnjailTx(tx, _)) => ("Unjail", Some(tx.address)),
+        TxAux::EnclaveTx(TxEnclaveAux::TransferTx { .. }) => ("Transfer", None),
+        TxAux::EnclaveTx(TxEnclaveAux::DepositStakeTx { tx, .. }) => {
+            ("Deposit", Some(tx.to_staked_account))
+        }
+        TxAux::EnclaveTx(TxEnclaveAux::WithdrawUnbondedStakeTx {
+            witness, payload, ..
+        }) => (
+            "WithdrawUnbonded",
+            verify_tx_recover_address(witness, &payload.txid).ok(),
+        ),
+    }
+}
+
+/// Parse the RFC 3339 block time, dropping the fraction of second which
+/// Timespec has no room for
+fn parse_block_time(time: &str) -> Option<Timespec> {
+    let time = match time.find('.') {
+        Some(dot) => {
+            let fraction_end = time[dot + 1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map(|end| dot + 1 + end)
+                .unwrap_or_else(|| time.len());
+
+            format!("{}{}", &time[..dot], &time[fraction_end..])
+        }
+        None => time.to_owned(),
+    };
+
+    timespec_from_iso8601(&time).ok()
+}
+
+/// Integer as a decimal string or a number
+pub(super) fn u64_field(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(value) => value.parse().ok(),
+        value => value.as_u64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::state::account::{StakedStateOpAttributes, UnjailTx};
+    use parity_scale_codec::Encode;
+    use serde_json::json;
+
+    use crate::address::staking_address_from_public_key;
+    use crate::council_node_transaction::unjail::sign_unjail_transaction;
+    use crate::signer::KeyPairSigner;
+    use crate::transfer_transaction::tests::{key_pair, CHAIN_HEX_ID};
+
+    fn unjail_tx_aux() -> TxAux {
+        let (private_key, public_key) = key_pair(1);
+        let staking_address = staking_address_from_public_key(&public_key);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+
+        sign_unjail_transaction(
+            UnjailTx::new(
+                0,
+                staking_address,
+                StakedStateOpAttributes::new(CHAIN_HEX_ID),
+            ),
+            &signer,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_event_should_parse_new_block() {
+        let event = parse_event(&json!({
+            "query": NEW_BLOCK_QUERY,
+            "data": {
+                "type": NEW_BLOCK_EVENT_TYPE,
+                "value": {
+                    "block": {
+                        "header": {
+                            "height": "12",
+                            "time": "2020-04-17T02:30:14.123456789Z",
+                        },
+                        "data": { "txs": [base64::encode(&[0u8])] },
+                    },
+                },
+            },
+        }));
+
+        match event {
+            Some(TendermintEvent::Block(block)) => assert_eq!(
+                block,
+                BlockEvent {
+                    height: 12,
+                    time: 1_587_090_614,
+                    tx_count: 1,
+                }
+            ),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+    }
+
+    #[test]
+    fn parse_event_should_parse_tx_with_staking_address() {
+        let tx_aux = unjail_tx_aux();
+
+        let event = parse_event(&json!({
+            "query": TX_QUERY,
+            "data": {
+                "type": TX_EVENT_TYPE,
+                "value": {
+                    "TxResult": {
+                        "height": "12",
+                        "index": 1,
+                        "tx": base64::encode(&tx_aux.encode()),
+                        "result": { "log": "" },
+                    },
+                },
+            },
+        }));
+
+        match event {
+            Some(TendermintEvent::Tx(tx, _)) => {
+                assert_eq!(tx.height, 12);
+                assert_eq!(tx.index, 1);
+                assert_eq!(tx.tx_id, tx_aux.tx_id());
+                assert_eq!(tx.tx_type, "Unjail");
+                assert_eq!(
+                    tx.staking_address,
+                    Some(staking_address_from_public_key(&key_pair(1).1))
+                );
+                assert_eq!(tx.code, 0);
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+    }
+
+    #[test]
+    fn parse_tx_should_skip_non_tx_aux() {
+        assert!(parse_tx(1, 0, &[0xFF, 0xFF], &Value::Null).is_none());
+    }
+}
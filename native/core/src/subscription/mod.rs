@@ -0,0 +1,619 @@
+//! Subscription to the new blocks and transactions of a Tendermint node
+//!
+//! The subscription keeps a websocket connection to the node on a thread of
+//! its own and queues the events matching its filter. When the connection is
+//! lost, it reconnects with exponential back-off and replays every block
+//! committed in the meantime, so that no transaction is missed.
+//!
+//! At most `EVENT_CAPACITY` events are queued. The connection thread waits
+//! for events to be received when the queue is full, during which the node
+//! may drop the connection. The blocks committed meanwhile are then replayed
+//! on reconnection.
+//!
+//! Transactions are matched by transaction id, staking address or transfer
+//! address. The outputs of transfer and withdraw transactions are obfuscated
+//! by the enclave, so transfer addresses are matched against the outputs
+//! decrypted with a view key.
+mod decryption;
+mod event;
+mod worker;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chain_core::state::account::StakedStateAddress;
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use client_common::{Error, ErrorKind, Result};
+use serde::Deserialize;
+
+use crate::network::decode_transfer_address;
+use crate::schema::{de, SchemaError, SchemaResult};
+
+pub use decryption::ViewKeyDecryption;
+pub use event::{BlockEvent, TxEvent};
+
+use worker::Worker;
+
+/// Maximum number of events returned at once by `Subscription::next_events`
+const MAX_EVENT_BATCH: usize = 256;
+/// Maximum number of events queued before the connection thread waits for
+/// them to be received
+const EVENT_CAPACITY: usize = 1024;
+
+/// Event of a subscription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionEvent {
+    Block(BlockEvent),
+    Tx(TxEvent),
+    /// Connection is lost and is about to be re-established
+    Reconnecting {
+        attempt: u32,
+        error: String,
+    },
+    /// Connection is re-established. Blocks from `from_height` to
+    /// `to_height` are replayed next, starting from the last received block
+    /// whose transactions may not all have been received. Events already
+    /// delivered are not delivered again.
+    Resumed {
+        from_height: u64,
+        to_height: u64,
+    },
+}
+
+/// Events to deliver. Transactions are delivered when `txs` is set or any
+/// transaction id, staking address or transfer address is given, in which
+/// case only the matching ones are.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+    #[serde(default)]
+    pub blocks: bool,
+    #[serde(default)]
+    pub txs: bool,
+    #[serde(default, deserialize_with = "de::tx_ids")]
+    pub tx_ids: Vec<TxId>,
+    #[serde(default, deserialize_with = "de::staking_addresses")]
+    pub staking_addresses: Vec<StakedStateAddress>,
+    /// Transfer addresses of the outputs of transfer and withdraw unbonded
+    /// transactions, which are decrypted with a view key
+    #[serde(default)]
+    pub transfer_addresses: Vec<String>,
+}
+
+impl EventFilter {
+    /// Verify the filter selects any event
+    pub fn verify(&self) -> SchemaResult<()> {
+        self.decoded_transfer_addresses()?;
+        if !self.blocks && !self.includes_txs() {
+            return Err(SchemaError::new(
+                ".",
+                "Filter should select blocks or transactions",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Decode the transfer addresses of any registered network
+    fn decoded_transfer_addresses(&self) -> SchemaResult<Vec<ExtendedAddr>> {
+        self.transfer_addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| {
+                decode_transfer_address(address)
+                    .map(|(_, address)| address)
+                    .map_err(|err| {
+                        SchemaError::new(
+                            format!("transferAddresses[{}]", i),
+                            format!("Invalid transfer address: {}", err),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns true when any transaction may be delivered
+    fn includes_txs(&self) -> bool {
+        self.txs
+            || !self.tx_ids.is_empty()
+            || !self.staking_addresses.is_empty()
+            || !self.transfer_addresses.is_empty()
+    }
+
+    /// Returns true when the filter has no transaction criteria
+    fn has_tx_criteria(&self) -> bool {
+        !self.tx_ids.is_empty()
+            || !self.staking_addresses.is_empty()
+            || !self.transfer_addresses.is_empty()
+    }
+
+    /// Match the transaction by id or staking address. Transfer addresses
+    /// are matched separately against the decrypted outputs.
+    fn matches_tx(&self, tx: &TxEvent) -> bool {
+        if !self.has_tx_criteria() {
+            return self.txs;
+        }
+
+        self.tx_ids.contains(&tx.tx_id)
+            || tx
+                .staking_address
+                .map(|staking_address| self.staking_addresses.contains(&staking_address))
+                .unwrap_or(false)
+    }
+}
+
+/// Returns true when any output is to one of the transfer addresses
+fn outputs_match(outputs: &[TxOut], transfer_addresses: &[ExtendedAddr]) -> bool {
+    outputs
+        .iter()
+        .any(|output| transfer_addresses.contains(&output.address))
+}
+
+/// Running subscription, closed when dropped. The connection thread exits
+/// on its own once it sees the subscription is closed.
+pub struct Subscription {
+    events: Mutex<Receiver<SubscriptionEvent>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Subscription {
+    /// Subscribe to the events of the filter from the Tendermint websocket
+    /// address. The view key decryption is required by transfer addresses.
+    /// The connection is established in the background, failures are
+    /// reported as `Reconnecting` events.
+    pub fn start(
+        tendermint_address: &str,
+        filter: EventFilter,
+        decryption: Option<ViewKeyDecryption>,
+    ) -> Result<Subscription> {
+        if !tendermint_address.starts_with("ws") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unsupported Tendermint client protocol",
+            ));
+        }
+        filter
+            .verify()
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))?;
+        if !filter.transfer_addresses.is_empty() && decryption.is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "View key should be provided to filter transfer addresses",
+            ));
+        }
+
+        let (sender, receiver) = sync_channel(EVENT_CAPACITY);
+        let stop = Arc::new(AtomicBool::new(false));
+        let address = tendermint_address.to_owned();
+        let worker_stop = stop.clone();
+
+        // The worker is created on its thread since the obfuscation of the
+        // decryption is only used there
+        thread::spawn(move || Worker::new(address, filter, decryption, sender, worker_stop).run());
+
+        Ok(Subscription {
+            events: Mutex::new(receiver),
+            stop,
+        })
+    }
+
+    /// Wait up to the timeout for the next events. Returns an empty list on
+    /// timeout, and `None` once the subscription is closed and every event
+    /// is received.
+    pub fn next_events(&self, timeout: Duration) -> Option<Vec<SubscriptionEvent>> {
+        let events = self
+            .events
+            .lock()
+            .expect("Subscription events lock is poisoned");
+
+        let first = match events.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => return Some(Vec::new()),
+            Err(RecvTimeoutError::Disconnected) => return None,
+        };
+
+        Some(batch_after(first, &events))
+    }
+
+    /// Wait for the next events. Returns `None` once the subscription is
+    /// closed and every event is received.
+    pub fn wait_events(&self) -> Option<Vec<SubscriptionEvent>> {
+        let events = self
+            .events
+            .lock()
+            .expect("Subscription events lock is poisoned");
+
+        let first = events.recv().ok()?;
+
+        Some(batch_after(first, &events))
+    }
+
+    /// Stop the subscription. Events already received are still returned by
+    /// `next_events`.
+    pub fn close(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true once the subscription is closed
+    pub fn is_closed(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// The event followed by the events already queued, up to the batch size
+fn batch_after(
+    first: SubscriptionEvent,
+    events: &Receiver<SubscriptionEvent>,
+) -> Vec<SubscriptionEvent> {
+    let mut batch = vec![first];
+    batch.extend(events.try_iter().take(MAX_EVENT_BATCH - 1));
+
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::init::coin::Coin;
+    use serde_json::json;
+
+    use crate::address::{extended_address_from_public_key, transfer_address_from_public_key};
+    use crate::network::{network_by_name, MAINNET};
+    use crate::schema::from_json_value;
+    use crate::transfer_transaction::tests::key_pair;
+
+    fn tx_event(tx_id: TxId, staking_address: Option<StakedStateAddress>) -> TxEvent {
+        TxEvent {
+            height: 1,
+            index: 0,
+            tx_id,
+            tx_type: "Deposit",
+            staking_address,
+            code: 0,
+            log: String::new(),
+        }
+    }
+
+    #[test]
+    fn filter_should_match_tx_id_or_staking_address() {
+        let staking_address = "0x83fe11feb0887183eb62c30994bdd9e303497e3d";
+        let filter: EventFilter = from_json_value(json!({
+            "txIds": [hex::encode([1; 32])],
+            "stakingAddresses": [staking_address],
+        }))
+        .unwrap();
+        let staking_address = staking_address.parse().unwrap();
+
+        assert!(filter.matches_tx(&tx_event([1; 32], None)));
+        assert!(filter.matches_tx(&tx_event([2; 32], Some(staking_address))));
+        assert!(!filter.matches_tx(&tx_event([2; 32], None)));
+    }
+
+    #[test]
+    fn filter_should_match_every_tx_without_criteria() {
+        let filter: EventFilter = from_json_value(json!({ "txs": true })).unwrap();
+
+        assert!(filter.matches_tx(&tx_event([2; 32], None)));
+    }
+
+    #[test]
+    fn filter_should_select_some_events() {
+        let filter: EventFilter = from_json_value(json!({})).unwrap();
+
+        assert!(filter
+            .verify()
+            .unwrap_err()
+            .to_string()
+            .contains("Filter should select blocks or transactions"));
+    }
+
+    #[test]
+    fn filter_should_reject_invalid_transfer_addresses() {
+        let filter: EventFilter = from_json_value(json!({
+            "transferAddresses": ["dcro1..."],
+        }))
+        .unwrap();
+
+        assert!(filter
+            .verify()
+            .unwrap_err()
+            .to_string()
+            .contains("`transferAddresses[0]`"));
+    }
+
+    #[test]
+    fn filter_should_match_outputs_to_transfer_addresses() {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address = transfer_address_from_public_key(&mainnet, &key_pair(1).1).unwrap();
+        let filter: EventFilter = from_json_value(json!({
+            "transferAddresses": [address],
+        }))
+        .unwrap();
+        filter.verify().unwrap();
+        let transfer_addresses = filter.decoded_transfer_addresses().unwrap();
+        let output = |seed| {
+            TxOut::new(
+                extended_address_from_public_key(&key_pair(seed).1).unwrap(),
+                Coin::new(1000).unwrap(),
+            )
+        };
+
+        assert!(!filter.matches_tx(&tx_event([1; 32], None)));
+        assert!(outputs_match(&[output(2), output(1)], &transfer_addresses));
+        assert!(!outputs_match(&[output(2)], &transfer_addresses));
+    }
+
+    #[test]
+    fn start_should_require_view_key_of_transfer_addresses() {
+        let mainnet = network_by_name(MAINNET).unwrap();
+        let address = transfer_address_from_public_key(&mainnet, &key_pair(1).1).unwrap();
+        let filter = EventFilter {
+            transfer_addresses: vec![address],
+            ..Default::default()
+        };
+
+        assert!(Subscription::start("ws://localhost:26657/websocket", filter, None).is_err());
+    }
+
+    #[test]
+    fn filter_should_report_invalid_tx_id() {
+        let err = from_json_value::<EventFilter>(json!({ "txIds": ["00"] })).unwrap_err();
+
+        assert!(err.to_string().contains("Invalid tx id at 0"));
+    }
+
+    #[test]
+    fn start_should_reject_non_websocket_address() {
+        let filter = EventFilter {
+            blocks: true,
+            ..Default::default()
+        };
+
+        assert!(Subscription::start("http://localhost:26657", filter, None).is_err());
+    }
+
+    #[cfg(feature = "mock-node")]
+    mod mock_node {
+        use super::*;
+
+        use std::time::Instant;
+
+        use chain_core::state::account::{StakedStateOpAttributes, UnjailTx};
+        use chain_core::tx::TransactionId;
+        use client_common::tendermint::{Client, WebsocketRpcClient};
+        use parity_scale_codec::Encode;
+
+        use crate::address::staking_address_from_public_key;
+        use crate::council_node_transaction::unjail::sign_unjail_transaction;
+        use crate::mock_node::MockNode;
+        use crate::signer::KeyPairSigner;
+        use crate::transfer_transaction::tests::{builder_of, key_pair, CHAIN_HEX_ID};
+        use crate::transfer_transaction::{sign_input, to_tx_aux};
+        use crate::tx_aux::Features;
+
+        const TIMEOUT: Duration = Duration::from_secs(10);
+
+        /// Broadcast an unjail transaction of the nonce. Returns its id.
+        fn broadcast_unjail(node: &MockNode, nonce: u64) -> TxId {
+            let (private_key, public_key) = key_pair(1);
+            let staking_address = staking_address_from_public_key(&public_key);
+            let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+            let tx_aux = sign_unjail_transaction(
+                UnjailTx::new(
+                    nonce,
+                    staking_address,
+                    StakedStateOpAttributes::new(CHAIN_HEX_ID),
+                ),
+                &signer,
+            )
+            .unwrap();
+
+            WebsocketRpcClient::new(&node.websocket_address())
+                .unwrap()
+                .broadcast_transaction(&tx_aux.encode())
+                .unwrap();
+
+            tx_aux.tx_id()
+        }
+
+        /// Wait for the event matching the predicate, collecting every
+        /// event received before it
+        fn wait_for<F>(subscription: &Subscription, predicate: F) -> Vec<SubscriptionEvent>
+        where
+            F: Fn(&SubscriptionEvent) -> bool,
+        {
+            let deadline = Instant::now() + TIMEOUT;
+            let mut received = Vec::new();
+
+            while Instant::now() < deadline {
+                let events = subscription
+                    .next_events(Duration::from_millis(100))
+                    .unwrap();
+                for event in events {
+                    let found = predicate(&event);
+                    received.push(event);
+                    if found {
+                        return received;
+                    }
+                }
+            }
+
+            panic!("Expected event not received: {:?}", received);
+        }
+
+        #[test]
+        fn subscription_should_deliver_matching_tx_and_blocks() {
+            let node = MockNode::start().unwrap();
+            let staking_address = staking_address_from_public_key(&key_pair(1).1);
+            let subscription = Subscription::start(
+                &node.websocket_address(),
+                EventFilter {
+                    blocks: true,
+                    staking_addresses: vec![staking_address],
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+            // Wait until subscribed to the node
+            while node.subscriber_count() == 0 {
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            let tx_id = broadcast_unjail(&node, 0);
+
+            let events = wait_for(&subscription, |event| match event {
+                SubscriptionEvent::Tx(tx) => tx.tx_id == tx_id,
+                _ => false,
+            });
+            assert!(events.iter().any(|event| match event {
+                SubscriptionEvent::Block(block) => block.height == 1 && block.tx_count == 1,
+                _ => false,
+            }));
+        }
+
+        #[test]
+        fn subscription_should_deliver_tx_of_transfer_address() {
+            let node = MockNode::start().unwrap();
+            let (view_key, public_key) = key_pair(9);
+            let mainnet = network_by_name(MAINNET).unwrap();
+            let transfer_address = transfer_address_from_public_key(&mainnet, &public_key).unwrap();
+            let subscription = Subscription::start(
+                &node.websocket_address(),
+                EventFilter {
+                    transfer_addresses: vec![transfer_address],
+                    ..Default::default()
+                },
+                Some(ViewKeyDecryption {
+                    view_key,
+                    features: Features::MockObfuscation,
+                }),
+            )
+            .unwrap();
+            while node.subscriber_count() == 0 {
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            let unjail_tx_id = broadcast_unjail(&node, 0);
+            // The output of the transfer is to the view key address
+            let (private_key, public_key) = key_pair(1);
+            let mut builder = builder_of(&[key_pair(1)]);
+            let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+            sign_input(&mut builder, 0, &signer).unwrap();
+            let tx_aux = to_tx_aux(&builder, "", Features::MockObfuscation).unwrap();
+            WebsocketRpcClient::new(&node.websocket_address())
+                .unwrap()
+                .broadcast_transaction(&tx_aux.encode())
+                .unwrap();
+
+            let events = wait_for(&subscription, |event| match event {
+                SubscriptionEvent::Tx(tx) => tx.tx_id == tx_aux.tx_id(),
+                _ => false,
+            });
+            assert!(!events.iter().any(|event| match event {
+                SubscriptionEvent::Tx(tx) => tx.tx_id == unjail_tx_id,
+                _ => false,
+            }));
+        }
+
+        #[test]
+        fn subscription_should_replay_blocks_after_reconnection() {
+            let node = MockNode::start().unwrap();
+            let subscription = Subscription::start(
+                &node.websocket_address(),
+                EventFilter {
+                    blocks: true,
+                    txs: true,
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+            while node.subscriber_count() == 0 {
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            broadcast_unjail(&node, 0);
+            wait_for(&subscription, |event| match event {
+                SubscriptionEvent::Block(block) => block.height == 1,
+                _ => false,
+            });
+
+            // Commit a block while the subscription is disconnected
+            node.disconnect_clients();
+            let missed_tx_id = broadcast_unjail(&node, 1);
+
+            let events = wait_for(&subscription, |event| match event {
+                SubscriptionEvent::Tx(tx) => tx.tx_id == missed_tx_id,
+                _ => false,
+            });
+            assert!(events.iter().any(|event| match event {
+                SubscriptionEvent::Reconnecting { attempt, .. } => *attempt == 1,
+                _ => false,
+            }));
+            assert!(events.iter().any(|event| event
+                == &SubscriptionEvent::Resumed {
+                    from_height: 1,
+                    to_height: 2,
+                }));
+        }
+
+        #[test]
+        fn subscription_should_replay_every_block_of_long_outage() {
+            let node = MockNode::start().unwrap();
+            let subscription = Subscription::start(
+                &node.websocket_address(),
+                EventFilter {
+                    txs: true,
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+            while node.subscriber_count() == 0 {
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            broadcast_unjail(&node, 0);
+            wait_for(&subscription, |event| match event {
+                SubscriptionEvent::Tx(tx) => tx.height == 1,
+                _ => false,
+            });
+
+            // Commit many blocks while the subscription is disconnected
+            node.disconnect_clients();
+            for nonce in 1..=150 {
+                broadcast_unjail(&node, nonce);
+            }
+
+            let events = wait_for(&subscription, |event| match event {
+                SubscriptionEvent::Tx(tx) => tx.height == 151,
+                _ => false,
+            });
+            assert!(events.iter().any(|event| match event {
+                SubscriptionEvent::Resumed { from_height, .. } => *from_height == 1,
+                _ => false,
+            }));
+            let heights: Vec<u64> = events
+                .iter()
+                .filter_map(|event| match event {
+                    SubscriptionEvent::Tx(tx) => Some(tx.height),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(heights, (2..=151).collect::<Vec<_>>());
+        }
+    }
+}
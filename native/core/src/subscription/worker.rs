@@ -0,0 +1,422 @@
+//! Websocket connection of a subscription and its reconnection loop
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::SyncSender;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::TxAux;
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use serde_json::{json, Value};
+use tungstenite::client::AutoStream;
+use tungstenite::stream::Stream;
+use tungstenite::{Message, WebSocket};
+
+use super::decryption::{Decryptor, ViewKeyDecryption};
+use super::event::{
+    block_txs, parse_block, parse_event, parse_tx, u64_field, TendermintEvent, NEW_BLOCK_QUERY,
+    TX_QUERY,
+};
+use super::{outputs_match, BlockEvent, EventFilter, SubscriptionEvent, TxEvent};
+
+/// Interval at which a waiting connection checks whether the subscription
+/// is closed
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Maximum time to wait for the response of a request
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Websocket JSON-RPC connection to the Tendermint node
+struct Connection {
+    websocket: WebSocket<AutoStream>,
+    next_id: u64,
+    /// Messages received while waiting for a response
+    pending_messages: VecDeque<Value>,
+}
+
+impl Connection {
+    fn open(address: &str) -> Result<Connection> {
+        let (websocket, _) = tungstenite::connect(address).chain(|| {
+            (
+                ErrorKind::ConnectionError,
+                format!("Unable to connect to Tendermint node at {}", address),
+            )
+        })?;
+
+        // Reads time out so that a closed subscription is noticed
+        let stream = match websocket.get_ref() {
+            Stream::Plain(stream) => stream,
+            Stream::Tls(stream) => stream.get_ref(),
+        };
+        stream.set_read_timeout(Some(POLL_INTERVAL)).chain(|| {
+            (
+                ErrorKind::ConnectionError,
+                "Unable to set read timeout of Tendermint connection",
+            )
+        })?;
+
+        Ok(Connection {
+            websocket,
+            next_id: 0,
+            pending_messages: VecDeque::new(),
+        })
+    }
+
+    /// Call the method and wait for its result. Other messages received in
+    /// the meantime are kept for `next_message`.
+    fn call(&mut self, method: &str, params: Value, stop: &AtomicBool) -> Result<Value> {
+        let id = format!("cro-nodelib-{}", self.next_id);
+        self.next_id += 1;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        self.websocket
+            .write_message(Message::Text(request.to_string()))
+            .chain(|| {
+                (
+                    ErrorKind::ConnectionError,
+                    format!("Unable to send {} request to Tendermint node", method),
+                )
+            })?;
+
+        let deadline = Instant::now() + CALL_TIMEOUT;
+        loop {
+            if stop.load(Ordering::SeqCst) {
+                return Err(closed_error());
+            }
+            if Instant::now() > deadline {
+                return Err(Error::new(
+                    ErrorKind::ConnectionError,
+                    format!("Timeout waiting for {} response", method),
+                ));
+            }
+
+            let message = match self.read()? {
+                Some(message) => message,
+                None => continue,
+            };
+            if message.get("id").and_then(Value::as_str) != Some(id.as_str()) {
+                self.pending_messages.push_back(message);
+                continue;
+            }
+
+            return match message.get("error") {
+                Some(error) => Err(Error::new(
+                    ErrorKind::TendermintRpcError,
+                    format!("Tendermint {} request failed: {}", method, error),
+                )),
+                None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+            };
+        }
+    }
+
+    /// Next message from the node, or `None` when none is received within
+    /// the poll interval
+    fn next_message(&mut self) -> Result<Option<Value>> {
+        match self.pending_messages.pop_front() {
+            Some(message) => Ok(Some(message)),
+            None => self.read(),
+        }
+    }
+
+    fn read(&mut self) -> Result<Option<Value>> {
+        match self.websocket.read_message() {
+            Ok(Message::Text(text)) => serde_json::from_str(&text).map(Some).chain(|| {
+                (
+                    ErrorKind::DeserializationError,
+                    "Unable to parse message of Tendermint node",
+                )
+            }),
+            Ok(Message::Close(_)) => Err(Error::new(
+                ErrorKind::ConnectionError,
+                "Connection closed by Tendermint node",
+            )),
+            Ok(_) => Ok(None),
+            Err(tungstenite::Error::Io(ref err))
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(Error::new(
+                ErrorKind::ConnectionError,
+                format!("Connection to Tendermint node failed: {}", err),
+            )),
+        }
+    }
+}
+
+/// Subscription worker delivering the events to the subscription channel
+pub(super) struct Worker {
+    address: String,
+    filter: EventFilter,
+    /// Decoded transfer addresses of the filter
+    transfer_addresses: Vec<ExtendedAddr>,
+    decryptor: Option<Decryptor>,
+    events: SyncSender<SubscriptionEvent>,
+    stop: Arc<AtomicBool>,
+    /// Height of the last block received, from which to resume. Its
+    /// transactions may not all be received yet.
+    last_height: Option<u64>,
+    /// Height and index of the transactions handled at the last height, so
+    /// that the ones replayed on reconnection are not delivered twice
+    handled_txs: HashSet<(u64, u32)>,
+}
+
+impl Worker {
+    pub(super) fn new(
+        address: String,
+        filter: EventFilter,
+        decryption: Option<ViewKeyDecryption>,
+        events: SyncSender<SubscriptionEvent>,
+        stop: Arc<AtomicBool>,
+    ) -> Worker {
+        // The filter is verified when the subscription starts
+        let transfer_addresses = filter.decoded_transfer_addresses().unwrap_or_default();
+        let decryptor = decryption.map(|decryption| Decryptor::new(address.clone(), decryption));
+
+        Worker {
+            address,
+            filter,
+            transfer_addresses,
+            decryptor,
+            events,
+            stop,
+            last_height: None,
+            handled_txs: HashSet::new(),
+        }
+    }
+
+    /// Serve the subscription until it is closed, reconnecting with
+    /// exponential back-off whenever the connection is lost
+    pub(super) fn run(mut self) {
+        let mut attempt = 0;
+        let mut delay = INITIAL_RECONNECT_DELAY;
+
+        while !self.is_stopped() {
+            let error = match self.serve(&mut attempt) {
+                Ok(()) => return,
+                Err(_) if self.is_stopped() => return,
+                Err(err) => err,
+            };
+
+            if attempt == 0 {
+                delay = INITIAL_RECONNECT_DELAY;
+            }
+            attempt += 1;
+            if self
+                .emit(SubscriptionEvent::Reconnecting {
+                    attempt,
+                    error: error.to_string(),
+                })
+                .is_err()
+            {
+                return;
+            }
+
+            self.sleep(delay);
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    /// Connect and deliver the events until the connection is lost. Resets
+    /// the reconnection attempts once subscribed.
+    fn serve(&mut self, attempt: &mut u32) -> Result<()> {
+        let mut connection = Connection::open(&self.address)?;
+
+        // New blocks are always subscribed to keep track of the height to
+        // resume from
+        connection.call("subscribe", json!({ "query": NEW_BLOCK_QUERY }), &self.stop)?;
+        if self.filter.includes_txs() {
+            connection.call("subscribe", json!({ "query": TX_QUERY }), &self.stop)?;
+        }
+        *attempt = 0;
+
+        if let Some(last_height) = self.last_height {
+            self.resume(&mut connection, last_height)?;
+        }
+
+        while !self.is_stopped() {
+            let message = match connection.next_message()? {
+                Some(message) => message,
+                None => continue,
+            };
+            if let Some(error) = message.get("error") {
+                return Err(Error::new(
+                    ErrorKind::TendermintRpcError,
+                    format!("Tendermint subscription failed: {}", error),
+                ));
+            }
+
+            match message.get("result").and_then(parse_event) {
+                Some(TendermintEvent::Block(block)) => self.deliver_block(block)?,
+                Some(TendermintEvent::Tx(tx, tx_aux)) => self.deliver_tx(tx, &tx_aux)?,
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replay every block committed since the last height, including the
+    /// last one whose transactions may have been missed. Blocks committed
+    /// during the replay are received as live events once it is done.
+    fn resume(&mut self, connection: &mut Connection, last_height: u64) -> Result<()> {
+        let status = connection.call("status", json!({}), &self.stop)?;
+        let latest_height = status
+            .pointer("/sync_info/latest_block_height")
+            .and_then(u64_field)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::DeserializationError,
+                    "Unable to parse latest block height of Tendermint node",
+                )
+            })?;
+
+        self.emit(SubscriptionEvent::Resumed {
+            from_height: last_height,
+            to_height: latest_height,
+        })?;
+
+        for height in last_height..=latest_height {
+            self.replay_block(connection, height)?;
+        }
+
+        // The node may also be behind the last height after a chain reset
+        if latest_height < last_height {
+            self.last_height = Some(latest_height);
+            self.handled_txs.clear();
+        }
+
+        Ok(())
+    }
+
+    fn replay_block(&mut self, connection: &mut Connection, height: u64) -> Result<()> {
+        let params = json!({ "height": height.to_string() });
+        let result = connection.call("block", params.clone(), &self.stop)?;
+        let block = result.get("block").unwrap_or(&Value::Null);
+        let block_event = parse_block(block).ok_or_else(|| {
+            Error::new(
+                ErrorKind::DeserializationError,
+                format!("Unable to parse block {} of Tendermint node", height),
+            )
+        })?;
+
+        let txs = block_txs(block);
+        let results = if self.filter.includes_txs() && !txs.is_empty() {
+            connection.call("block_results", params, &self.stop)?
+        } else {
+            Value::Null
+        };
+
+        self.deliver_block(block_event)?;
+        for (index, tx) in txs.iter().enumerate() {
+            let result = results
+                .get("txs_results")
+                .and_then(|results| results.get(index))
+                .unwrap_or(&Value::Null);
+
+            if let Some((tx, tx_aux)) = parse_tx(height, index as u32, tx, result) {
+                self.deliver_tx(tx, &tx_aux)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deliver the block unless it is already delivered
+    fn deliver_block(&mut self, block: BlockEvent) -> Result<()> {
+        if self
+            .last_height
+            .map(|last_height| block.height <= last_height)
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+        self.last_height = Some(block.height);
+        self.handled_txs
+            .retain(|(height, _)| *height >= block.height);
+
+        if self.filter.blocks {
+            self.emit(SubscriptionEvent::Block(block))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Deliver the transaction when it matches and is not already handled.
+    /// The transactions of the blocks before the last one are all handled.
+    fn deliver_tx(&mut self, tx: TxEvent, tx_aux: &TxAux) -> Result<()> {
+        let key = (tx.height, tx.index);
+        let is_handled = self
+            .last_height
+            .map(|last_height| tx.height < last_height)
+            .unwrap_or(false)
+            || self.handled_txs.contains(&key);
+        if is_handled {
+            return Ok(());
+        }
+
+        if self.matches_tx(&tx, tx_aux)? {
+            self.emit(SubscriptionEvent::Tx(tx))?;
+        }
+        self.handled_txs.insert(key);
+
+        Ok(())
+    }
+
+    /// Match the transaction against the filter, decrypting its outputs
+    /// when transfer addresses are filtered
+    fn matches_tx(&mut self, tx: &TxEvent, tx_aux: &TxAux) -> Result<bool> {
+        if self.filter.matches_tx(tx) {
+            return Ok(true);
+        }
+        let decryptor = match self.decryptor.as_mut() {
+            Some(decryptor) if !self.transfer_addresses.is_empty() => decryptor,
+            _ => return Ok(false),
+        };
+
+        let outputs = decryptor.outputs(&tx.tx_id, tx_aux)?;
+
+        Ok(outputs
+            .map(|outputs| outputs_match(&outputs, &self.transfer_addresses))
+            .unwrap_or(false))
+    }
+
+    /// Queue the event, waiting for queued events to be received when the
+    /// queue is full. The subscription is closed when the receiver is
+    /// dropped.
+    fn emit(&self, event: SubscriptionEvent) -> Result<()> {
+        self.events.send(event).map_err(|_| {
+            self.stop.store(true, Ordering::SeqCst);
+
+            closed_error()
+        })
+    }
+
+    /// Sleep for the duration unless the subscription is closed meanwhile
+    fn sleep(&self, duration: Duration) {
+        let deadline = Instant::now() + duration;
+
+        while !self.is_stopped() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+}
+
+fn closed_error() -> Error {
+    Error::new(ErrorKind::InvalidInput, "Subscription is closed")
+}
@@ -0,0 +1,252 @@
+//! Conversion between Timespec and ISO-8601 date time strings
+//!
+//! Timespec is the number of seconds since UNIX epoch. Date times are
+//! formatted in UTC as `YYYY-MM-DDTHH:MM:SSZ` and parsed from the same format
+//! with an optional UTC offset in place of `Z`.
+use chain_core::common::Timespec;
+use client_common::{Error, ErrorKind, Result};
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+
+/// Timespec of 9999-12-31T23:59:59Z, the last date time of four digits year
+pub const MAX_ISO8601_TIMESPEC: Timespec = 253_402_300_799;
+
+const EXPECTED_FORMAT: &str = "expected YYYY-MM-DDTHH:MM:SS followed by Z or offset";
+
+/// Number of days since UNIX epoch of the proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian (year, month, day) of the number of days since UNIX
+/// epoch
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = if days >= 0 { days } else { days - 146_096 } / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Format Timespec as ISO-8601 UTC date time. Timespecs beyond year 9999
+/// have no four digits year and are rejected.
+pub fn timespec_to_iso8601(timespec: Timespec) -> Result<String> {
+    if timespec > MAX_ISO8601_TIMESPEC {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Timespec {} exceeds maximum ISO-8601 date time {}",
+                timespec, MAX_ISO8601_TIMESPEC
+            ),
+        ));
+    }
+
+    let timespec = timespec as i64;
+    let (year, month, day) = civil_from_days(timespec / SECONDS_PER_DAY);
+    let seconds_of_day = timespec % SECONDS_PER_DAY;
+
+    Ok(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / SECONDS_PER_HOUR,
+        seconds_of_day % SECONDS_PER_HOUR / SECONDS_PER_MINUTE,
+        seconds_of_day % SECONDS_PER_MINUTE,
+    ))
+}
+
+/// Parse ISO-8601 date time of second precision into Timespec. The date
+/// time must be in UTC (`Z`) or have a `+HH:MM` or `-HH:MM` offset.
+pub fn timespec_from_iso8601(date_time: &str) -> Result<Timespec> {
+    let invalid = |reason: &str| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid ISO-8601 date time {}: {}", date_time, reason),
+        )
+    };
+    let number = |part: &str| -> Result<i64> {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid(EXPECTED_FORMAT));
+        }
+        part.parse().map_err(|_| invalid(EXPECTED_FORMAT))
+    };
+
+    if date_time.len() < 20 || !date_time.is_ascii() {
+        return Err(invalid(EXPECTED_FORMAT));
+    }
+    let (date_time_part, zone) = date_time.split_at(19);
+    let bytes = date_time_part.as_bytes();
+    if bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || (bytes[10] != b'T' && bytes[10] != b't')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return Err(invalid(EXPECTED_FORMAT));
+    }
+
+    let year = number(&date_time_part[0..4])?;
+    let month = number(&date_time_part[5..7])?;
+    let day = number(&date_time_part[8..10])?;
+    let hour = number(&date_time_part[11..13])?;
+    let minute = number(&date_time_part[14..16])?;
+    let second = number(&date_time_part[17..19])?;
+    if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) {
+        return Err(invalid("date does not exist"));
+    }
+    if hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid("time does not exist"));
+    }
+
+    // Fractional seconds are accepted only when they are all zero since
+    // Timespec has second precision
+    let zone = if zone.starts_with('.') {
+        let digits_len = zone[1..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits_len == 0 {
+            return Err(invalid("expected digits of fractional seconds"));
+        }
+        if zone[1..=digits_len].bytes().any(|b| b != b'0') {
+            return Err(invalid("Timespec does not support sub-second precision"));
+        }
+        &zone[1 + digits_len..]
+    } else {
+        zone
+    };
+    let offset = match zone {
+        "Z" | "z" => 0,
+        _ if zone.len() == 6 && zone.as_bytes()[3] == b':' => {
+            let sign = match zone.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(invalid("expected Z or +HH:MM or -HH:MM offset")),
+            };
+            let offset_hour = number(&zone[1..3])?;
+            let offset_minute = number(&zone[4..6])?;
+            if offset_hour > 23 || offset_minute > 59 {
+                return Err(invalid("offset does not exist"));
+            }
+            sign * (offset_hour * SECONDS_PER_HOUR + offset_minute * SECONDS_PER_MINUTE)
+        }
+        _ => return Err(invalid("expected Z or +HH:MM or -HH:MM offset")),
+    };
+
+    let timespec = days_from_civil(year, month, day) * SECONDS_PER_DAY
+        + hour * SECONDS_PER_HOUR
+        + minute * SECONDS_PER_MINUTE
+        + second
+        - offset;
+    if timespec < 0 {
+        return Err(invalid("date time is before UNIX epoch"));
+    }
+
+    Ok(timespec as Timespec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timespec_to_iso8601_should_format_utc_date_time() {
+        assert_eq!(timespec_to_iso8601(0).unwrap(), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            timespec_to_iso8601(951_782_400).unwrap(),
+            "2000-02-29T00:00:00Z"
+        );
+        assert_eq!(
+            timespec_to_iso8601(1_596_240_000).unwrap(),
+            "2020-08-01T00:00:00Z"
+        );
+        assert_eq!(
+            timespec_to_iso8601(MAX_ISO8601_TIMESPEC).unwrap(),
+            "9999-12-31T23:59:59Z"
+        );
+    }
+
+    #[test]
+    fn timespec_to_iso8601_should_reject_timespec_beyond_year_9999() {
+        assert!(timespec_to_iso8601(MAX_ISO8601_TIMESPEC + 1).is_err());
+        assert!(timespec_to_iso8601(Timespec::max_value()).is_err());
+    }
+
+    #[test]
+    fn timespec_from_iso8601_should_round_trip() {
+        for timespec in &[0, 951_782_400, 1_596_240_001, MAX_ISO8601_TIMESPEC] {
+            let date_time = timespec_to_iso8601(*timespec).unwrap();
+            assert_eq!(timespec_from_iso8601(&date_time).unwrap(), *timespec);
+        }
+    }
+
+    #[test]
+    fn timespec_from_iso8601_should_apply_offset() {
+        assert_eq!(
+            timespec_from_iso8601("2020-08-01T08:00:00+08:00").unwrap(),
+            1_596_240_000
+        );
+        assert_eq!(
+            timespec_from_iso8601("2020-07-31T19:30:00-04:30").unwrap(),
+            1_596_240_000
+        );
+    }
+
+    #[test]
+    fn timespec_from_iso8601_should_accept_zero_fractional_seconds() {
+        assert_eq!(
+            timespec_from_iso8601("2020-08-01T00:00:00.000Z").unwrap(),
+            1_596_240_000
+        );
+        assert!(timespec_from_iso8601("2020-08-01T00:00:00.5Z").is_err());
+        assert!(timespec_from_iso8601("2020-08-01T00:00:00.Z").is_err());
+    }
+
+    #[test]
+    fn timespec_from_iso8601_should_reject_invalid_date_time() {
+        for date_time in &[
+            "2020-08-01",
+            "2020-08-01 00:00:00Z",
+            "2020-02-30T00:00:00Z",
+            "2019-02-29T00:00:00Z",
+            "2020-08-01T24:00:00Z",
+            "2020-08-01T00:00:00+24:00",
+            "2020-08-01T00:00:00",
+            "1969-12-31T23:59:59Z",
+            "1970-01-01T00:00:00+00:01",
+        ] {
+            assert!(timespec_from_iso8601(date_time).is_err(), "{}", date_time);
+        }
+    }
+}
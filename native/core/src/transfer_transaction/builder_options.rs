@@ -0,0 +1,161 @@
+use chain_core::tx::data::input::{TxoPointer, TxoSize};
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use client_common::PublicKey;
+use serde::Deserialize;
+
+use crate::schema::de;
+use crate::schema::types::{
+    network_of_chain_hex_id, to_tx_outs, AddressParamsOptions, FeeConfigOptions, OutputOptions,
+};
+use crate::schema::SchemaResult;
+
+#[derive(Debug)]
+pub struct RawTransactionOptions {
+    pub inputs: Vec<RawTransactionOptionsInput>,
+    pub outputs: Vec<TxOut>,
+    pub view_keys: Vec<PublicKey>,
+    pub chain_hex_id: u8,
+}
+
+#[derive(Debug)]
+pub struct RawTransactionOptionsInput {
+    pub prev_output: (TxoPointer, TxOut),
+    pub address_params: RawTransactionOptionsInputAddressParams,
+}
+
+#[derive(Debug)]
+pub struct RawTransactionOptionsInputAddressParams {
+    pub required_signers: u64,
+    pub total_signers: u64,
+}
+
+/// Options of a basic transfer transaction builder
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuilderOptions {
+    #[serde(deserialize_with = "de::chain_hex_id")]
+    pub chain_hex_id: u8,
+    pub inputs: Vec<InputOptions>,
+    pub outputs: Vec<OutputOptions>,
+    #[serde(deserialize_with = "de::view_keys")]
+    pub view_keys: Vec<PublicKey>,
+    pub fee_config: FeeConfigOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputOptions {
+    #[serde(deserialize_with = "de::tx_id")]
+    pub prev_tx_id: TxId,
+    pub prev_index: TxoSize,
+    pub prev_output: OutputOptions,
+    pub address_params: AddressParamsOptions,
+}
+
+impl BuilderOptions {
+    pub fn to_raw_tx_options(&self) -> SchemaResult<RawTransactionOptions> {
+        let network = network_of_chain_hex_id(self.chain_hex_id, "chainHexId")?;
+
+        let inputs = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let address_params = &input.address_params;
+                address_params.verify(&format!("inputs[{}].addressParams", i))?;
+
+                let tx_out = input
+                    .prev_output
+                    .to_tx_out(&network, &format!("inputs[{}].prevOutput", i))?;
+
+                Ok(RawTransactionOptionsInput {
+                    prev_output: (
+                        TxoPointer {
+                            id: input.prev_tx_id,
+                            index: input.prev_index,
+                        },
+                        tx_out,
+                    ),
+                    address_params: RawTransactionOptionsInputAddressParams {
+                        required_signers: address_params.required_signers,
+                        total_signers: address_params.total_signers,
+                    },
+                })
+            })
+            .collect::<SchemaResult<Vec<RawTransactionOptionsInput>>>()?;
+
+        let outputs = to_tx_outs(&self.outputs, &network, "outputs")?;
+
+        Ok(RawTransactionOptions {
+            inputs,
+            outputs,
+            view_keys: self.view_keys.clone(),
+            chain_hex_id: self.chain_hex_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    use crate::schema::from_json_value;
+
+    const ADDRESS: &str = "cro12mpa9cpru8gn6nhl2edf5rn75yp7mjr7x4wcp4szz6zrs88yquhq95hkw4";
+
+    fn input(address_params: Value) -> Value {
+        json!({
+            "prevTxId": "0000000000000000000000000000000000000000000000000000000000000000",
+            "prevIndex": 0,
+            "prevOutput": { "address": ADDRESS, "value": "1000" },
+            "addressParams": address_params,
+        })
+    }
+
+    fn options(inputs: Vec<Value>) -> Value {
+        json!({
+            "chainHexId": [0x2A],
+            "inputs": inputs,
+            "outputs": [{ "address": ADDRESS, "value": "500" }],
+            "viewKeys": [],
+            "feeConfig": {
+                "algorithm": "LinearFee",
+                "constant": "1.1",
+                "coefficient": "1.25",
+            },
+        })
+    }
+
+    #[test]
+    fn builder_options_should_report_path_of_nested_input_field() {
+        let single_signer = json!({ "requiredSigners": 1, "totalSigners": 1 });
+        let options = options(vec![
+            input(single_signer.clone()),
+            input(single_signer),
+            input(json!({ "requiredSigners": 1, "totalSigners": "2" })),
+        ]);
+
+        let err = from_json_value::<BuilderOptions>(options).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Invalid options at `inputs[2].addressParams.totalSigners`: "));
+    }
+
+    #[test]
+    fn to_raw_tx_options_should_read_total_signers_from_its_own_key() {
+        let options: BuilderOptions = from_json_value(options(vec![input(
+            json!({ "requiredSigners": 2, "totalSigners": 3 }),
+        )]))
+        .unwrap();
+
+        let raw_tx_options = options.to_raw_tx_options().unwrap();
+        let address_params = &raw_tx_options.inputs[0].address_params;
+
+        assert_eq!(address_params.required_signers, 2);
+        assert_eq!(address_params.total_signers, 3);
+    }
+}
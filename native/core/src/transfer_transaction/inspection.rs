@@ -0,0 +1,309 @@
+//! Per-input report of an incomplete transfer transaction
+//!
+//! `isCompleted` only tells whether every input has a witness. When a
+//! multi-party transfer stalls, the report tells which input is missing a
+//! witness and whether the collected witnesses still verify against the
+//! current transaction id.
+use chain_core::tx::data::attribute::TxAttributes;
+use chain_core::tx::data::input::{TxoPointer, TxoSize};
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use chain_core::tx::witness::TxInWitness;
+use client_common::{Error, ErrorKind, Result};
+use parity_scale_codec::{Decode, Encode};
+use serde::Deserialize;
+
+use crate::codec::decode_transaction;
+use crate::schema::types::{AddressParamsOptions, FeeConfigOptions};
+use crate::schema::{de, SchemaResult};
+
+use super::{from_incomplete, transfer_address_leaves};
+
+/// Encoding of `RawTransferTransactionBuilder::to_incomplete`, whose fields
+/// are private to client-core. client-core has no accessor of the inputs, the
+/// layout is pinned by `incomplete_layout_should_match_client_core_builder`.
+#[derive(Debug, Encode, Decode)]
+pub(super) struct IncompleteTransferTransaction {
+    inputs: Vec<IncompleteInput>,
+    // Outputs are decoded only to reach the attributes
+    #[allow(dead_code)]
+    outputs: Vec<TxOut>,
+    attributes: TxAttributes,
+}
+
+#[derive(Debug, Encode, Decode)]
+struct IncompleteInput {
+    input: TxoPointer,
+    prev_output: TxOut,
+    /// Number of leaves of the merkle tree of the input address
+    address_leaves: u16,
+    witness: Option<TxInWitness>,
+}
+
+/// Witness status and signer requirements of an input
+#[derive(Debug)]
+pub struct InputInspection {
+    pub prev_tx_id: TxId,
+    pub prev_index: TxoSize,
+    pub prev_output: TxOut,
+    /// Number of signer combinations of the input address, as stored by the
+    /// builder
+    pub address_leaves: u16,
+    /// Signers of the input address, verified against the address leaves.
+    /// Only known when provided by the caller since the builder stores the
+    /// number of address leaves only.
+    pub address_params: Option<AddressParamsOptions>,
+    pub has_witness: bool,
+    pub is_witness_valid: bool,
+}
+
+/// Report of an incomplete transfer transaction
+#[derive(Debug)]
+pub struct IncompleteInspection {
+    pub tx_id: TxId,
+    pub chain_hex_id: u8,
+    pub inputs: Vec<InputInspection>,
+}
+
+/// Inspect each input of the incomplete transaction. Witnesses are verified
+/// against `tx_id`, which should be the current transaction id of the
+/// builder.
+pub fn inspect_incomplete(
+    incomplete_hex: &[u8],
+    tx_id: TxId,
+    address_params: Option<&[AddressParamsOptions]>,
+) -> Result<IncompleteInspection> {
+    let incomplete: IncompleteTransferTransaction =
+        decode_transaction(incomplete_hex, "incomplete transfer transaction")?;
+
+    if let Some(address_params) = address_params {
+        if address_params.len() != incomplete.inputs.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Address params has {} inputs but transaction has {} inputs",
+                    address_params.len(),
+                    incomplete.inputs.len()
+                ),
+            ));
+        }
+    }
+
+    let inputs = incomplete
+        .inputs
+        .into_iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let address_params = match address_params {
+                Some(address_params) => {
+                    let address_params = &address_params[i];
+                    let address_leaves = transfer_address_leaves(
+                        address_params.total_signers,
+                        address_params.required_signers,
+                    )?;
+                    if address_leaves != input.address_leaves {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!(
+                                "Address params of input {} does not match the input address",
+                                i
+                            ),
+                        ));
+                    }
+                    Some(*address_params)
+                }
+                None => None,
+            };
+
+            let is_witness_valid = match input.witness {
+                Some(ref witness) => witness
+                    .verify_tx_address(&tx_id, &input.prev_output.address)
+                    .is_ok(),
+                None => false,
+            };
+
+            Ok(InputInspection {
+                prev_tx_id: input.input.id,
+                prev_index: input.input.index,
+                prev_output: input.prev_output,
+                address_leaves: input.address_leaves,
+                address_params,
+                has_witness: input.witness.is_some(),
+                is_witness_valid,
+            })
+        })
+        .collect::<Result<Vec<InputInspection>>>()?;
+
+    Ok(IncompleteInspection {
+        tx_id,
+        chain_hex_id: incomplete.attributes.chain_hex_id,
+        inputs,
+    })
+}
+
+/// Options of an incomplete transfer transaction inspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectIncompleteOptions {
+    #[serde(deserialize_with = "de::buffer")]
+    pub incomplete_hex: Vec<u8>,
+    pub fee_config: FeeConfigOptions,
+    #[serde(default)]
+    pub address_params: Option<Vec<AddressParamsOptions>>,
+}
+
+impl InspectIncompleteOptions {
+    pub fn verify(&self) -> SchemaResult<()> {
+        if let Some(ref address_params) = self.address_params {
+            for (i, address_params) in address_params.iter().enumerate() {
+                address_params.verify(&format!("addressParams[{}]", i))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inspect the incomplete transaction against the current transaction id
+    /// of its builder
+    pub fn inspect(&self) -> Result<IncompleteInspection> {
+        let builder = from_incomplete(
+            self.incomplete_hex.clone(),
+            self.fee_config.to_fee_algorithm(),
+        )?;
+
+        inspect_incomplete(
+            &self.incomplete_hex,
+            builder.tx_id(),
+            self.address_params.as_deref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chain_core::init::coin::Coin;
+
+    use crate::address::extended_address_from_public_key;
+    use crate::signer::KeyPairSigner;
+    use crate::transfer_transaction::tests::{builder_of, fee_config, key_pair, CHAIN_HEX_ID};
+    use crate::transfer_transaction::{
+        build_incomplete, sign_input, RawTransactionOptions, RawTransactionOptionsInput,
+        RawTransactionOptionsInputAddressParams,
+    };
+
+    fn inspect(
+        incomplete_hex: Vec<u8>,
+        address_params: Option<Vec<AddressParamsOptions>>,
+    ) -> Result<IncompleteInspection> {
+        InspectIncompleteOptions {
+            incomplete_hex,
+            fee_config: fee_config(),
+            address_params,
+        }
+        .inspect()
+    }
+
+    #[test]
+    fn incomplete_layout_should_match_client_core_builder() {
+        let (private_key, public_key) = key_pair(1);
+        let input_address = extended_address_from_public_key(&public_key).unwrap();
+        let output_address = extended_address_from_public_key(&key_pair(9).1).unwrap();
+        let input = |index, required_signers, total_signers| RawTransactionOptionsInput {
+            prev_output: (
+                TxoPointer::new([7; 32], index),
+                TxOut::new(input_address.clone(), Coin::new(1_000).unwrap()),
+            ),
+            address_params: RawTransactionOptionsInputAddressParams {
+                required_signers,
+                total_signers,
+            },
+        };
+        let options = RawTransactionOptions {
+            inputs: vec![input(0, 1, 1), input(1, 2, 3)],
+            outputs: vec![TxOut::new_with_timelock(
+                output_address,
+                Coin::new(500).unwrap(),
+                1_586_094_976,
+            )],
+            view_keys: Vec::new(),
+            chain_hex_id: CHAIN_HEX_ID,
+        };
+        let mut builder = build_incomplete(&options, fee_config().to_fee_algorithm()).unwrap();
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+        let incomplete_hex = builder.to_incomplete();
+
+        let incomplete: IncompleteTransferTransaction =
+            decode_transaction(&incomplete_hex, "incomplete transfer transaction").unwrap();
+
+        assert_eq!(incomplete.encode(), incomplete_hex);
+        assert_eq!(incomplete.inputs.len(), 2);
+        for (input, expected) in incomplete.inputs.iter().zip(options.inputs.iter()) {
+            assert_eq!(input.input, expected.prev_output.0);
+            assert_eq!(input.prev_output, expected.prev_output.1);
+        }
+        assert_eq!(incomplete.inputs[0].address_leaves, 1);
+        assert_eq!(incomplete.inputs[1].address_leaves, 3);
+        assert!(incomplete.inputs[0].witness.is_some());
+        assert!(incomplete.inputs[1].witness.is_none());
+        assert_eq!(incomplete.outputs, options.outputs);
+        assert_eq!(incomplete.attributes.chain_hex_id, CHAIN_HEX_ID);
+    }
+
+    #[test]
+    fn inspect_should_report_missing_witness() {
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1), key_pair(2)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+
+        let inspection = inspect(builder.to_incomplete(), None).unwrap();
+
+        assert_eq!(inspection.tx_id, builder.tx_id());
+        assert_eq!(inspection.chain_hex_id, CHAIN_HEX_ID);
+        assert!(inspection.inputs[0].has_witness);
+        assert!(inspection.inputs[0].is_witness_valid);
+        assert!(!inspection.inputs[1].has_witness);
+        assert!(!inspection.inputs[1].is_witness_valid);
+        assert_eq!(inspection.inputs[1].address_leaves, 1);
+        assert!(inspection.inputs[1].address_params.is_none());
+    }
+
+    #[test]
+    fn inspect_should_report_witness_signed_by_other_key() {
+        let (private_key, public_key) = key_pair(2);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        let witness = signer.schnorr_sign_txid(&builder.tx_id()).unwrap();
+        builder.add_witness(0, witness).unwrap();
+
+        let inspection = inspect(builder.to_incomplete(), None).unwrap();
+
+        assert!(inspection.inputs[0].has_witness);
+        assert!(!inspection.inputs[0].is_witness_valid);
+    }
+
+    #[test]
+    fn inspect_should_reject_mismatched_address_params() {
+        let builder = builder_of(&[key_pair(1)]);
+
+        assert!(inspect(builder.to_incomplete(), Some(Vec::new())).is_err());
+        assert!(inspect(
+            builder.to_incomplete(),
+            Some(vec![AddressParamsOptions {
+                required_signers: 2,
+                total_signers: 3,
+            }])
+        )
+        .is_err());
+
+        let inspection = inspect(
+            builder.to_incomplete(),
+            Some(vec![AddressParamsOptions::default()]),
+        )
+        .unwrap();
+        assert!(inspection.inputs[0].address_params.is_some());
+    }
+}
@@ -0,0 +1,372 @@
+//! Basic transfer transaction builder and its incomplete encoding
+//!
+//! A transfer is built without witnesses, passed around as the incomplete
+//! hex between the signers of each input, and finished into a `TxAux` once
+//! every input has a witness.
+mod builder_options;
+mod inspection;
+
+use std::convert::TryFrom;
+
+use chain_core::tx::data::access::{TxAccess, TxAccessPolicy};
+use chain_core::tx::data::attribute::TxAttributes;
+use chain_core::tx::data::Tx;
+use chain_core::tx::fee::FeeAlgorithm;
+use chain_core::tx::witness::TxWitness;
+use chain_core::tx::{TxAux, TxEnclaveAux};
+use client_common::{Error, ErrorKind, Result, ResultExt, SignedTransaction};
+use client_core::transaction_builder::RawTransferTransactionBuilder;
+use gcd::Gcd;
+use parity_scale_codec::Decode;
+use serde::Deserialize;
+
+use crate::codec::decode_transaction;
+use crate::fee::FeeSchedule;
+use crate::schema::de;
+use crate::schema::types::FeeConfigOptions;
+use crate::signer::KeyPairSigner;
+use crate::tx_aux::{Features, MockTransactionCipher, Obfuscation};
+
+pub use builder_options::{
+    BuilderOptions, InputOptions, RawTransactionOptions, RawTransactionOptionsInput,
+    RawTransactionOptionsInputAddressParams,
+};
+pub use inspection::{
+    inspect_incomplete, IncompleteInspection, InputInspection, InspectIncompleteOptions,
+};
+
+use inspection::IncompleteTransferTransaction;
+
+pub type FeeScheduleRawTransferTransactionBuilder = RawTransferTransactionBuilder<FeeSchedule>;
+
+/// Create a basic transfer transaction builder without witnesses
+pub fn build_incomplete<F>(
+    options: &RawTransactionOptions,
+    fee_algorithm: F,
+) -> Result<RawTransferTransactionBuilder<F>>
+where
+    F: FeeAlgorithm,
+{
+    let access_policies = options
+        .view_keys
+        .iter()
+        .map(|view_key| TxAccessPolicy {
+            view_key: view_key.into(),
+            access: TxAccess::AllData,
+        })
+        .collect();
+
+    let attributes = TxAttributes::new_with_access(options.chain_hex_id, access_policies);
+
+    let mut builder = RawTransferTransactionBuilder::new(attributes, fee_algorithm);
+
+    for input in options.inputs.iter() {
+        let address_params = &input.address_params;
+        builder.add_input(
+            input.prev_output.clone(),
+            transfer_address_leaves(
+                address_params.total_signers,
+                address_params.required_signers,
+            )?,
+        );
+    }
+    for output in options.outputs.iter() {
+        builder.add_output(output.to_owned());
+    }
+
+    Ok(builder)
+}
+
+/// Number of leaves of the merkle tree of a transfer address, i.e. the
+/// number of combinations of required signers out of the total signers.
+/// Combinations which do not fit in the `u16` leaf count are rejected.
+pub fn transfer_address_leaves(total_signers: u64, required_signers: u64) -> Result<u16> {
+    let too_many_combinations = || {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Combinations of {} signers out of {} exceed {}",
+                required_signers,
+                total_signers,
+                u16::MAX
+            ),
+        )
+    };
+
+    let mut n = total_signers;
+    let mut d = 1;
+    let mut result: u64 = 1;
+    while d <= required_signers {
+        let gcd = result.gcd(d);
+        result /= gcd;
+        let t = n / (d / gcd);
+        result = result.checked_mul(t).ok_or_else(too_many_combinations)?;
+        d += 1;
+        n -= 1;
+    }
+
+    u16::try_from(result).map_err(|_| too_many_combinations())
+}
+
+/// Incomplete transfer transaction hex and the fee config it is built with
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncompleteBuilderOptions {
+    #[serde(deserialize_with = "de::buffer")]
+    pub incomplete_hex: Vec<u8>,
+    pub fee_config: FeeConfigOptions,
+}
+
+impl IncompleteBuilderOptions {
+    /// Restore the builder from the incomplete hex
+    pub fn to_builder(&self) -> Result<FeeScheduleRawTransferTransactionBuilder> {
+        from_incomplete(
+            self.incomplete_hex.clone(),
+            self.fee_config.to_fee_algorithm(),
+        )
+    }
+}
+
+/// Restore the builder from the incomplete hex, which often comes from
+/// another signer. The hex is decoded strictly before it is handed to the
+/// builder.
+pub fn from_incomplete<F>(
+    incomplete_hex: Vec<u8>,
+    fee_algorithm: F,
+) -> Result<RawTransferTransactionBuilder<F>>
+where
+    F: FeeAlgorithm,
+{
+    decode_transaction::<IncompleteTransferTransaction>(
+        &incomplete_hex,
+        "incomplete transfer transaction",
+    )?;
+
+    RawTransferTransactionBuilder::from_incomplete(incomplete_hex, fee_algorithm)
+}
+
+/// Sign a particular input with the signer
+pub fn sign_input<F>(
+    builder: &mut RawTransferTransactionBuilder<F>,
+    input_index: usize,
+    signer: &KeyPairSigner,
+) -> Result<()>
+where
+    F: FeeAlgorithm,
+{
+    let witness = signer.schnorr_sign_txid(&builder.tx_id())?;
+
+    builder.add_witness(input_index, witness)
+}
+
+/// Finish the completed builder into a broadcast-able `TxAux` with the
+/// obfuscation of the features
+pub fn to_tx_aux<F>(
+    builder: &RawTransferTransactionBuilder<F>,
+    tendermint_address: &str,
+    features: Features,
+) -> Result<TxAux>
+where
+    F: FeeAlgorithm,
+{
+    let obfuscation = Obfuscation::new(tendermint_address, features)?;
+
+    builder.to_tx_aux(obfuscation)
+}
+
+/// Signed plain transaction and witness of a completed builder, before it
+/// is obfuscated
+pub fn signed_transfer_transaction<F>(
+    builder: &RawTransferTransactionBuilder<F>,
+) -> Result<(Tx, TxWitness)>
+where
+    F: FeeAlgorithm,
+{
+    let tx_aux = builder.to_tx_aux(MockTransactionCipher)?;
+    let payload = match tx_aux {
+        TxAux::EnclaveTx(TxEnclaveAux::TransferTx { payload, .. }) => payload,
+        _ => unreachable!("Mock transaction cipher only creates transfer transactions"),
+    };
+
+    let signed_transaction =
+        SignedTransaction::decode(&mut payload.txpayload.as_slice()).chain(|| {
+            (
+                ErrorKind::DeserializationError,
+                "Unable to decode signed transfer transaction",
+            )
+        })?;
+    match signed_transaction {
+        SignedTransaction::TransferTransaction(tx, witness) => Ok((tx, witness)),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Transaction is not a transfer transaction",
+        )),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use chain_core::init::coin::Coin;
+    use chain_core::tx::data::input::TxoPointer;
+    use chain_core::tx::data::output::TxOut;
+    use chain_core::tx::TransactionId;
+    use client_common::{PrivateKey, PublicKey};
+    use serde_json::json;
+
+    use crate::address::extended_address_from_public_key;
+    use crate::schema::from_json_value;
+
+    pub const CHAIN_HEX_ID: u8 = 0x2A;
+
+    pub fn key_pair(secret: u8) -> (PrivateKey, PublicKey) {
+        let private_key = PrivateKey::deserialize_from(&[secret; 32]).unwrap();
+        let public_key = PublicKey::from(&private_key);
+
+        (private_key, public_key)
+    }
+
+    pub fn fee_config() -> FeeConfigOptions {
+        from_json_value(json!({
+            "algorithm": "LinearFee",
+            "constant": "1.1",
+            "coefficient": "1.25",
+        }))
+        .unwrap()
+    }
+
+    /// Builder spending one output of each key pair to a single output
+    pub fn builder_of(
+        key_pairs: &[(PrivateKey, PublicKey)],
+    ) -> FeeScheduleRawTransferTransactionBuilder {
+        let inputs = key_pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (_, public_key))| RawTransactionOptionsInput {
+                prev_output: (
+                    TxoPointer {
+                        id: [i as u8; 32],
+                        index: 0,
+                    },
+                    TxOut::new(
+                        extended_address_from_public_key(public_key).unwrap(),
+                        Coin::new(100_000_000).unwrap(),
+                    ),
+                ),
+                address_params: RawTransactionOptionsInputAddressParams {
+                    required_signers: 1,
+                    total_signers: 1,
+                },
+            })
+            .collect();
+        let output_address = extended_address_from_public_key(&key_pair(9).1).unwrap();
+        let options = RawTransactionOptions {
+            inputs,
+            outputs: vec![TxOut::new(output_address, Coin::new(50_000_000).unwrap())],
+            view_keys: Vec::new(),
+            chain_hex_id: CHAIN_HEX_ID,
+        };
+
+        build_incomplete(&options, fee_config().to_fee_algorithm()).unwrap()
+    }
+
+    #[test]
+    fn transfer_address_leaves_should_count_signer_combinations() {
+        assert_eq!(transfer_address_leaves(1, 1).unwrap(), 1);
+        assert_eq!(transfer_address_leaves(3, 2).unwrap(), 3);
+        assert_eq!(transfer_address_leaves(5, 3).unwrap(), 10);
+        assert_eq!(transfer_address_leaves(4, 4).unwrap(), 1);
+    }
+
+    #[test]
+    fn transfer_address_leaves_should_reject_combinations_above_u16() {
+        assert_eq!(transfer_address_leaves(18, 9).unwrap(), 48620);
+        assert!(transfer_address_leaves(20, 10).is_err());
+        assert!(transfer_address_leaves(u64::MAX, u64::MAX / 2).is_err());
+    }
+
+    #[test]
+    fn builder_should_round_trip_through_incomplete_hex() {
+        let builder = builder_of(&[key_pair(1)]);
+
+        let restored = IncompleteBuilderOptions {
+            incomplete_hex: builder.to_incomplete(),
+            fee_config: fee_config(),
+        }
+        .to_builder()
+        .unwrap();
+
+        assert_eq!(restored.tx_id(), builder.tx_id());
+        assert!(!restored.is_completed());
+    }
+
+    #[test]
+    fn from_incomplete_should_reject_trailing_bytes() {
+        let mut incomplete_hex = builder_of(&[key_pair(1)]).to_incomplete();
+        incomplete_hex.push(0);
+
+        let err = from_incomplete(incomplete_hex, fee_config().to_fee_algorithm()).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Unexpected trailing bytes after incomplete transfer transaction"));
+    }
+
+    #[test]
+    fn sign_input_should_complete_builder() {
+        let key_pairs = vec![key_pair(1), key_pair(2)];
+        let mut builder = builder_of(&key_pairs);
+
+        for (i, (private_key, public_key)) in key_pairs.into_iter().enumerate() {
+            assert!(!builder.is_completed());
+            let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+            sign_input(&mut builder, i, &signer).unwrap();
+        }
+
+        assert!(builder.is_completed());
+        builder.verify().unwrap();
+    }
+
+    #[test]
+    fn sign_input_should_reject_out_of_range_input() {
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+
+        assert!(sign_input(&mut builder, 1, &signer).is_err());
+    }
+
+    #[test]
+    fn signed_transfer_transaction_should_return_signed_plain_transaction() {
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+
+        let (tx, witness) = signed_transfer_transaction(&builder).unwrap();
+
+        assert_eq!(tx.id(), builder.tx_id());
+        assert_eq!(tx.attributes.chain_hex_id, CHAIN_HEX_ID);
+        assert_eq!(witness.len(), 1);
+    }
+
+    #[test]
+    fn to_tx_aux_should_reject_unsupported_protocol() {
+        let builder = builder_of(&[key_pair(1)]);
+
+        assert!(to_tx_aux(&builder, "http://localhost:26657", Features::AllDefault).is_err());
+    }
+
+    #[test]
+    fn to_tx_aux_should_finish_with_mock_obfuscation() {
+        let (private_key, public_key) = key_pair(1);
+        let mut builder = builder_of(&[key_pair(1)]);
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+        sign_input(&mut builder, 0, &signer).unwrap();
+
+        let tx_aux = to_tx_aux(&builder, "", Features::MockObfuscation).unwrap();
+
+        assert_eq!(tx_aux.tx_id(), builder.tx_id());
+    }
+}
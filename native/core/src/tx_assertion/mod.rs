@@ -0,0 +1,472 @@
+//! Assertion based verification of transactions prepared by other parties
+//!
+//! Callers provide the expected fields of a transaction and get back every
+//! field which does not match, instead of an error at the first mismatch.
+//! Provided fields which cannot be checked, e.g. the obfuscated outputs of
+//! enclave transactions, are reported as unverifiable and fail the
+//! verification.
+mod options;
+mod report;
+
+use chain_core::state::account::{StakedStateAddress, StakedStateOpWitness, WithdrawUnbondedTx};
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::TxId;
+use chain_core::tx::{TransactionId, TxAux, TxEnclaveAux, TxPublicAux};
+use chain_tx_validation::witness::verify_tx_recover_address;
+use client_common::{Error, ErrorKind, Result};
+use client_core::signer::DummySigner;
+
+use crate::network::{network_by_chain_hex_id, NetworkDefinition};
+
+pub use options::{FeeAssertion, InputAssertion, TxAssertions};
+pub use report::{AssertionReport, Mismatch};
+
+/// Result of asserting a transaction
+#[derive(Debug)]
+pub struct TxVerification {
+    /// Type of the transaction, e.g. `Unbond`
+    pub tx_type: &'static str,
+    pub tx_id: TxId,
+    pub report: AssertionReport,
+}
+
+/// Assert the fields of a broadcast-able transaction
+pub fn assert_tx_aux(tx_aux: &TxAux, assertions: &TxAssertions) -> Result<TxVerification> {
+    let mut report = AssertionReport::default();
+
+    let (tx_type, tx_id) = match tx_aux {
+        TxAux::PublicTx(TxPublicAux::UnbondStakeTx(tx, witness)) => {
+            let tx_id = tx.id();
+            assert_chain_hex_id(&mut report, assertions, tx.attributes.chain_hex_id);
+            report.assert_field(
+                "stakingAddress",
+                assertions.staking_address.as_ref(),
+                &tx.from_staked_account,
+                ToString::to_string,
+            );
+            report.assert_field(
+                "nonce",
+                assertions.nonce.as_ref(),
+                &tx.nonce,
+                ToString::to_string,
+            );
+            report.assert_field("amount", assertions.amount.as_ref(), &tx.value, |amount| {
+                u64::from(*amount)
+            });
+            assert_signer(&mut report, witness, &tx_id, &tx.from_staked_account);
+            assertions.assert_verifiable(
+                &mut report,
+                &["chainHexId", "stakingAddress", "nonce", "amount"],
+            );
+
+            ("Unbond", tx_id)
+        }
+        TxAux::PublicTx(TxPublicAux::NodeJoinTx(tx, witness)) => {
+            let tx_id = tx.id();
+            assert_chain_hex_id(&mut report, assertions, tx.attributes.chain_hex_id);
+            report.assert_field(
+                "stakingAddress",
+                assertions.staking_address.as_ref(),
+                &tx.address,
+                ToString::to_string,
+            );
+            report.assert_field(
+                "nonce",
+                assertions.nonce.as_ref(),
+                &tx.nonce,
+                ToString::to_string,
+            );
+            report.assert_field(
+                "validatorName",
+                assertions.validator_name.as_ref(),
+                &tx.node_meta.name,
+                ToString::to_string,
+            );
+            let expected_security_contact = assertions.security_contact.clone().map(Some);
+            report.assert_field(
+                "securityContact",
+                expected_security_contact.as_ref(),
+                &tx.node_meta.security_contact,
+                |security_contact| security_contact.clone().unwrap_or_default(),
+            );
+            report.assert_field(
+                "consensusPublicKey",
+                assertions.consensus_public_key.as_ref(),
+                &tx.node_meta.consensus_pubkey,
+                |consensus_pubkey| serde_json::to_string(consensus_pubkey).unwrap_or_default(),
+            );
+            assert_signer(&mut report, witness, &tx_id, &tx.address);
+            assertions.assert_verifiable(
+                &mut report,
+                &[
+                    "chainHexId",
+                    "stakingAddress",
+                    "nonce",
+                    "validatorName",
+                    "securityContact",
+                    "consensusPublicKey",
+                ],
+            );
+
+            ("NodeJoin", tx_id)
+        }
+        TxAux::PublicTx(TxPublicAux::UnjailTx(tx, witness)) => {
+            let tx_id = tx.id();
+            assert_chain_hex_id(&mut report, assertions, tx.attributes.chain_hex_id);
+            report.assert_field(
+                "stakingAddress",
+                assertions.staking_address.as_ref(),
+                &tx.address,
+                ToString::to_string,
+            );
+            report.assert_field(
+                "nonce",
+                assertions.nonce.as_ref(),
+                &tx.nonce,
+                ToString::to_string,
+            );
+            assert_signer(&mut report, witness, &tx_id, &tx.address);
+            assertions.assert_verifiable(&mut report, &["chainHexId", "stakingAddress", "nonce"]);
+
+            ("Unjail", tx_id)
+        }
+        TxAux::EnclaveTx(TxEnclaveAux::TransferTx {
+            inputs,
+            no_of_outputs,
+            payload,
+        }) => {
+            assertions.assert_inputs(&mut report, inputs);
+            report.assert_field(
+                "outputsLength",
+                assertions.outputs_length.as_ref(),
+                no_of_outputs,
+                ToString::to_string,
+            );
+            assertions.assert_verifiable(&mut report, &["inputs", "outputsLength"]);
+
+            ("Transfer", payload.txid)
+        }
+        TxAux::EnclaveTx(TxEnclaveAux::DepositStakeTx { tx, .. }) => {
+            assert_chain_hex_id(&mut report, assertions, tx.attributes.chain_hex_id);
+            assertions.assert_inputs(&mut report, &tx.inputs);
+            report.assert_field(
+                "stakingAddress",
+                assertions.staking_address.as_ref(),
+                &tx.to_staked_account,
+                ToString::to_string,
+            );
+            assertions.assert_verifiable(&mut report, &["chainHexId", "inputs", "stakingAddress"]);
+
+            ("Deposit", tx.id())
+        }
+        TxAux::EnclaveTx(TxEnclaveAux::WithdrawUnbondedStakeTx {
+            no_of_outputs,
+            witness,
+            payload,
+        }) => {
+            report.assert_field(
+                "outputsLength",
+                assertions.outputs_length.as_ref(),
+                no_of_outputs,
+                ToString::to_string,
+            );
+            assert_recovered_signer(&mut report, assertions, witness, &payload.txid);
+            assertions.assert_verifiable(&mut report, &["outputsLength", "stakingAddress"]);
+
+            ("WithdrawUnbonded", payload.txid)
+        }
+    };
+
+    report.assert_field("txId", assertions.tx_id.as_ref(), &tx_id, hex::encode);
+    assertions.assert_fee(&mut report, tx_aux)?;
+
+    Ok(TxVerification {
+        tx_type,
+        tx_id,
+        report,
+    })
+}
+
+/// Assert the fields of a signed withdraw unbonded transaction in plain form,
+/// which is the form shared with the signer before obfuscation
+pub fn assert_signed_withdraw_unbonded_tx(
+    tx: &WithdrawUnbondedTx,
+    witness: &StakedStateOpWitness,
+    assertions: &TxAssertions,
+) -> Result<TxVerification> {
+    let mut report = AssertionReport::default();
+    let tx_id = tx.id();
+
+    assert_chain_hex_id(&mut report, assertions, tx.attributes.chain_hex_id);
+    report.assert_field(
+        "nonce",
+        assertions.nonce.as_ref(),
+        &tx.nonce,
+        ToString::to_string,
+    );
+    report.assert_field(
+        "outputsLength",
+        assertions.outputs_length.as_ref(),
+        &(tx.outputs.len() as u16),
+        ToString::to_string,
+    );
+    assert_outputs(
+        &mut report,
+        assertions,
+        &tx.outputs,
+        tx.attributes.chain_hex_id,
+    )?;
+    assert_recovered_signer(&mut report, assertions, witness, &tx_id);
+    assertions.assert_verifiable(
+        &mut report,
+        &[
+            "chainHexId",
+            "nonce",
+            "outputsLength",
+            "outputs",
+            "stakingAddress",
+        ],
+    );
+    report.assert_field("txId", assertions.tx_id.as_ref(), &tx_id, hex::encode);
+
+    let tx_aux = DummySigner().mock_txaux_for_withdraw(tx.clone());
+    assertions.assert_fee(&mut report, &tx_aux)?;
+
+    Ok(TxVerification {
+        tx_type: "WithdrawUnbonded",
+        tx_id,
+        report,
+    })
+}
+
+fn assert_chain_hex_id(report: &mut AssertionReport, assertions: &TxAssertions, chain_hex_id: u8) {
+    report.assert_field(
+        "chainHexId",
+        assertions.chain_hex_id.as_ref(),
+        &chain_hex_id,
+        |chain_hex_id| format!("{:02X}", chain_hex_id),
+    );
+}
+
+/// Assert the witness is signed by the staking address of the transaction
+fn assert_signer(
+    report: &mut AssertionReport,
+    witness: &StakedStateOpWitness,
+    tx_id: &TxId,
+    staking_address: &StakedStateAddress,
+) {
+    match verify_tx_recover_address(witness, tx_id) {
+        Ok(ref signer) if signer == staking_address => {}
+        Ok(signer) => report.add_mismatch("signature", staking_address, signer),
+        Err(err) => report.add_mismatch(
+            "signature",
+            staking_address,
+            format!("Invalid signature: {}", err),
+        ),
+    }
+}
+
+/// Assert the witness is signed by the expected staking address, used by
+/// transactions without a staking address field
+fn assert_recovered_signer(
+    report: &mut AssertionReport,
+    assertions: &TxAssertions,
+    witness: &StakedStateOpWitness,
+    tx_id: &TxId,
+) {
+    match verify_tx_recover_address(witness, tx_id) {
+        Ok(signer) => report.assert_field(
+            "stakingAddress",
+            assertions.staking_address.as_ref(),
+            &signer,
+            ToString::to_string,
+        ),
+        Err(err) => report.add_mismatch(
+            "signature",
+            "Valid signature",
+            format!("Invalid signature: {}", err),
+        ),
+    }
+}
+
+fn assert_outputs(
+    report: &mut AssertionReport,
+    assertions: &TxAssertions,
+    outputs: &[TxOut],
+    chain_hex_id: u8,
+) -> Result<()> {
+    let expected_outputs = match assertions.outputs {
+        Some(ref expected_outputs) => expected_outputs,
+        None => return Ok(()),
+    };
+
+    if expected_outputs.len() != outputs.len() {
+        report.add_mismatch("outputs.length", expected_outputs.len(), outputs.len());
+        return Ok(());
+    }
+
+    let network = network_by_chain_hex_id(chain_hex_id)?;
+    for (i, (expected_output, output)) in expected_outputs.iter().zip(outputs).enumerate() {
+        let path = format!("outputs[{}]", i);
+        let expected_output = expected_output
+            .to_tx_out(&network, &path)
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))?;
+
+        report.assert_field(
+            &format!("{}.address", path),
+            Some(&expected_output.address),
+            &output.address,
+            |address| format_transfer_address(&network, address),
+        );
+        report.assert_field(
+            &format!("{}.value", path),
+            Some(&expected_output.value),
+            &output.value,
+            |value| u64::from(*value),
+        );
+        report.assert_field(
+            &format!("{}.validFrom", path),
+            Some(&expected_output.valid_from),
+            &output.valid_from,
+            |valid_from| {
+                valid_from
+                    .map(|valid_from| valid_from.to_string())
+                    .unwrap_or_default()
+            },
+        );
+    }
+
+    Ok(())
+}
+
+fn format_transfer_address(network: &NetworkDefinition, address: &ExtendedAddr) -> String {
+    network
+        .encode_transfer_address(address)
+        .unwrap_or_else(|_| address.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    use crate::address::staking_address_from_public_key;
+    use crate::council_node_transaction::unjail::{sign_unjail_transaction, UnjailTxOptions};
+    use crate::schema::from_json_value;
+    use crate::signer::KeyPairSigner;
+    use crate::transfer_transaction::tests::{key_pair, CHAIN_HEX_ID};
+
+    fn unjail_tx_aux(secret: u8) -> TxAux {
+        let (private_key, public_key) = key_pair(secret);
+        let tx = UnjailTxOptions {
+            staking_address: staking_address_from_public_key(&key_pair(1).1),
+            nonce: 3,
+            chain_hex_id: CHAIN_HEX_ID,
+        }
+        .to_tx();
+        let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+
+        sign_unjail_transaction(tx, &signer).unwrap()
+    }
+
+    fn assertions(value: Value) -> TxAssertions {
+        from_json_value(value).unwrap()
+    }
+
+    fn mismatched_fields(verification: &TxVerification) -> Vec<&str> {
+        verification
+            .report
+            .mismatches()
+            .iter()
+            .map(|mismatch| mismatch.field.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn assert_tx_aux_should_accept_matching_fields() {
+        let staking_address = staking_address_from_public_key(&key_pair(1).1);
+
+        let verification = assert_tx_aux(
+            &unjail_tx_aux(1),
+            &assertions(json!({
+                "chainHexId": [CHAIN_HEX_ID],
+                "stakingAddress": staking_address.to_string(),
+                "nonce": "3",
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(verification.tx_type, "Unjail");
+        assert!(verification.report.is_valid());
+    }
+
+    #[test]
+    fn assert_tx_aux_should_report_every_mismatched_field() {
+        let verification = assert_tx_aux(
+            &unjail_tx_aux(1),
+            &assertions(json!({
+                "chainHexId": [0x42],
+                "nonce": "4",
+            })),
+        )
+        .unwrap();
+
+        assert!(!verification.report.is_valid());
+        assert_eq!(
+            mismatched_fields(&verification),
+            vec!["chainHexId", "nonce"]
+        );
+        assert_eq!(verification.report.mismatches()[1].expected, "4");
+        assert_eq!(verification.report.mismatches()[1].actual, "3");
+    }
+
+    #[test]
+    fn assert_tx_aux_should_report_signature_of_other_key() {
+        let verification = assert_tx_aux(&unjail_tx_aux(2), &assertions(json!({}))).unwrap();
+
+        assert_eq!(mismatched_fields(&verification), vec!["signature"]);
+    }
+
+    #[test]
+    fn assert_tx_aux_should_report_assertions_not_checkable_for_the_type() {
+        let verification = assert_tx_aux(
+            &unjail_tx_aux(1),
+            &assertions(json!({
+                "nonce": "3",
+                "amount": "1000",
+                "outputsLength": 1,
+            })),
+        )
+        .unwrap();
+
+        assert!(verification.report.mismatches().is_empty());
+        assert_eq!(
+            verification.report.unverifiable(),
+            &["amount".to_owned(), "outputsLength".to_owned()]
+        );
+        assert!(!verification.report.is_valid());
+    }
+
+    #[test]
+    fn assert_tx_aux_should_report_fee_out_of_bounds() {
+        let verification = assert_tx_aux(
+            &unjail_tx_aux(1),
+            &assertions(json!({
+                "fee": {
+                    "feeConfig": {
+                        "algorithm": "LinearFee",
+                        "constant": "1.1",
+                        "coefficient": "1.25",
+                    },
+                    "max": "1",
+                },
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(mismatched_fields(&verification), vec!["fee"]);
+        assert_eq!(verification.report.mismatches()[0].expected, "<= 1");
+    }
+}
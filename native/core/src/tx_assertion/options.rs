@@ -0,0 +1,157 @@
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{Nonce, StakedStateAddress};
+use chain_core::state::tendermint::TendermintValidatorPubKey;
+use chain_core::tx::data::input::{TxoPointer, TxoSize};
+use chain_core::tx::data::TxId;
+use chain_core::tx::fee::FeeAlgorithm;
+use chain_core::tx::TxAux;
+use client_common::{ErrorKind, Result, ResultExt};
+use serde::Deserialize;
+
+use super::report::AssertionReport;
+use crate::schema::de;
+use crate::schema::types::{FeeConfigOptions, OutputOptions};
+
+/// Expected fields of a transaction. Fields which are not provided are not
+/// asserted, and provided fields which cannot be checked for the transaction
+/// type are reported as unverifiable.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxAssertions {
+    #[serde(default, deserialize_with = "de::optional_chain_hex_id")]
+    pub chain_hex_id: Option<u8>,
+    #[serde(default, deserialize_with = "de::optional_tx_id")]
+    pub tx_id: Option<TxId>,
+    #[serde(default, deserialize_with = "de::optional_staking_address")]
+    pub staking_address: Option<StakedStateAddress>,
+    #[serde(default, deserialize_with = "de::optional_nonce")]
+    pub nonce: Option<Nonce>,
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    pub amount: Option<Coin>,
+    #[serde(default)]
+    pub inputs: Option<Vec<InputAssertion>>,
+    #[serde(default)]
+    pub outputs: Option<Vec<OutputOptions>>,
+    #[serde(default)]
+    pub outputs_length: Option<TxoSize>,
+    #[serde(default)]
+    pub validator_name: Option<String>,
+    #[serde(default)]
+    pub security_contact: Option<String>,
+    #[serde(default)]
+    pub consensus_public_key: Option<TendermintValidatorPubKey>,
+    #[serde(default)]
+    pub fee: Option<FeeAssertion>,
+}
+
+/// Expected previous output pointer of an input
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputAssertion {
+    #[serde(deserialize_with = "de::tx_id")]
+    prev_tx_id: TxId,
+    prev_index: TxoSize,
+}
+
+impl From<&InputAssertion> for TxoPointer {
+    fn from(input: &InputAssertion) -> Self {
+        TxoPointer {
+            id: input.prev_tx_id,
+            index: input.prev_index,
+        }
+    }
+}
+
+/// Bounds of the fee charged for the transaction size
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeAssertion {
+    fee_config: FeeConfigOptions,
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    min: Option<Coin>,
+    #[serde(default, deserialize_with = "de::optional_coin")]
+    max: Option<Coin>,
+}
+
+impl TxAssertions {
+    /// Record the provided assertions which are not among the fields
+    /// checked for the transaction type. Transaction id and fee are checked
+    /// for every type.
+    pub fn assert_verifiable(&self, report: &mut AssertionReport, checked_fields: &[&str]) {
+        let provided_fields = [
+            ("chainHexId", self.chain_hex_id.is_some()),
+            ("stakingAddress", self.staking_address.is_some()),
+            ("nonce", self.nonce.is_some()),
+            ("amount", self.amount.is_some()),
+            ("inputs", self.inputs.is_some()),
+            ("outputs", self.outputs.is_some()),
+            ("outputsLength", self.outputs_length.is_some()),
+            ("validatorName", self.validator_name.is_some()),
+            ("securityContact", self.security_contact.is_some()),
+            ("consensusPublicKey", self.consensus_public_key.is_some()),
+        ];
+        for (field, provided) in provided_fields.iter() {
+            if *provided && !checked_fields.contains(field) {
+                report.add_unverifiable(field);
+            }
+        }
+    }
+
+    /// Assert the previous output pointers of the inputs
+    pub fn assert_inputs(&self, report: &mut AssertionReport, inputs: &[TxoPointer]) {
+        let expected_inputs = match self.inputs {
+            Some(ref expected_inputs) => expected_inputs,
+            None => return,
+        };
+
+        if expected_inputs.len() != inputs.len() {
+            report.add_mismatch("inputs.length", expected_inputs.len(), inputs.len());
+            return;
+        }
+        for (i, (expected_input, input)) in expected_inputs.iter().zip(inputs).enumerate() {
+            report.assert_field(
+                &format!("inputs[{}]", i),
+                Some(&TxoPointer::from(expected_input)),
+                input,
+                format_txo_pointer,
+            );
+        }
+    }
+
+    /// Assert the fee charged for the size of the transaction
+    pub fn assert_fee(&self, report: &mut AssertionReport, tx_aux: &TxAux) -> Result<()> {
+        let fee_assertion = match self.fee {
+            Some(ref fee_assertion) => fee_assertion,
+            None => return Ok(()),
+        };
+
+        let fee = fee_assertion
+            .fee_config
+            .to_fee_algorithm()
+            .calculate_for_txaux(tx_aux)
+            .chain(|| {
+                (
+                    ErrorKind::InvalidInput,
+                    "Unable to calculate transaction fee",
+                )
+            })?
+            .to_coin();
+        let fee = u64::from(fee);
+        if let Some(min) = fee_assertion.min.map(u64::from) {
+            if fee < min {
+                report.add_mismatch("fee", format!(">= {}", min), fee);
+            }
+        }
+        if let Some(max) = fee_assertion.max.map(u64::from) {
+            if fee > max {
+                report.add_mismatch("fee", format!("<= {}", max), fee);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_txo_pointer(pointer: &TxoPointer) -> String {
+    format!("{}:{}", hex::encode(pointer.id), pointer.index)
+}
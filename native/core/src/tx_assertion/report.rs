@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+/// A field of the transaction which does not match the expected value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Mismatches collected while asserting a transaction, and the provided
+/// assertions which cannot be checked for the transaction type
+#[derive(Debug, Default)]
+pub struct AssertionReport {
+    mismatches: Vec<Mismatch>,
+    unverifiable: Vec<String>,
+}
+
+impl AssertionReport {
+    /// Compare the actual value of the field with the expected one, if any
+    pub fn assert_field<T, F, D>(
+        &mut self,
+        field: &str,
+        expected: Option<&T>,
+        actual: &T,
+        format: F,
+    ) where
+        T: PartialEq + ?Sized,
+        F: Fn(&T) -> D,
+        D: Display,
+    {
+        if let Some(expected) = expected {
+            if expected != actual {
+                self.add_mismatch(field, format(expected), format(actual));
+            }
+        }
+    }
+
+    /// Record a mismatch of the field
+    pub fn add_mismatch<E, A>(&mut self, field: &str, expected: E, actual: A)
+    where
+        E: Display,
+        A: Display,
+    {
+        self.mismatches.push(Mismatch {
+            field: field.to_owned(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+
+    /// Record a provided assertion which cannot be checked
+    pub fn add_unverifiable(&mut self, field: &str) {
+        self.unverifiable.push(field.to_owned());
+    }
+
+    /// Returns true when no mismatch is recorded and every provided
+    /// assertion is checked
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty() && self.unverifiable.is_empty()
+    }
+
+    /// Returns the recorded mismatches
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+
+    /// Returns the provided assertions which cannot be checked
+    pub fn unverifiable(&self) -> &[String] {
+        &self.unverifiable
+    }
+}
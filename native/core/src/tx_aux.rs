@@ -0,0 +1,308 @@
+//! Obfuscation of signed transactions into broadcast-able `TxAux`
+use std::str::FromStr;
+
+use chain_core::state::tendermint::BlockHeight;
+use chain_core::tx::data::input::TxoSize;
+use chain_core::tx::data::TxId;
+use chain_core::tx::{TransactionId, TxAux, TxEnclaveAux, TxObfuscated};
+#[cfg(feature = "websocket-rpc")]
+use client_common::tendermint::WebsocketRpcClient;
+use client_common::{
+    Error, ErrorKind, PrivateKey, Result, ResultExt, SignedTransaction, Transaction,
+};
+#[cfg(feature = "websocket-rpc")]
+use client_core::cipher::mock::MockAbciTransactionObfuscation;
+#[cfg(feature = "websocket-rpc")]
+use client_core::cipher::DefaultTransactionObfuscation;
+use client_core::cipher::TransactionObfuscation;
+use parity_scale_codec::Encode;
+
+use crate::codec::decode_transaction;
+
+// TODO: Use feature conditional compilation when ready
+// https://github.com/neon-bindings/neon/issues/471
+/// Obfuscation used to finish transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Features {
+    /// Obfuscate with the transaction query enclave of the node
+    AllDefault,
+    /// Obfuscate with the mock ABCI of a node without enclave
+    MockAbci,
+    /// Wrap the signed transaction as is without reaching the node
+    MockObfuscation,
+}
+
+impl FromStr for Features {
+    type Err = Error;
+
+    fn from_str(features: &str) -> Result<Features> {
+        match features {
+            "AllDefault" => Ok(Features::AllDefault),
+            "MockAbci" => Ok(Features::MockAbci),
+            "MockObfuscation" => Ok(Features::MockObfuscation),
+            _ => Err(Error::new(ErrorKind::InvalidInput, "Unrecognized features")),
+        }
+    }
+}
+
+/// Transaction obfuscation selected by `Features`
+#[derive(Clone)]
+pub enum Obfuscation {
+    #[cfg(feature = "websocket-rpc")]
+    Default(DefaultTransactionObfuscation),
+    #[cfg(feature = "websocket-rpc")]
+    MockAbci(MockAbciTransactionObfuscation<WebsocketRpcClient>),
+    Mock(MockTransactionCipher),
+}
+
+impl Obfuscation {
+    /// Create the obfuscation of the features. Obfuscations other than the
+    /// mock one connect to the Tendermint websocket address.
+    #[cfg(feature = "websocket-rpc")]
+    pub fn new(tendermint_address: &str, features: Features) -> Result<Obfuscation> {
+        if features == Features::MockObfuscation {
+            return Ok(Obfuscation::Mock(MockTransactionCipher));
+        }
+
+        if !tendermint_address.starts_with("ws") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unsupported Tendermint client protocol",
+            ));
+        }
+        let tendermint_client = WebsocketRpcClient::new(tendermint_address).chain(|| {
+            (
+                ErrorKind::InvalidInput,
+                "Unable to create Tendermint client from address",
+            )
+        })?;
+
+        match features {
+            Features::MockAbci => Ok(Obfuscation::MockAbci(MockAbciTransactionObfuscation::new(
+                tendermint_client,
+            ))),
+            _ => DefaultTransactionObfuscation::from_tx_query(&tendermint_client)
+                .map(Obfuscation::Default)
+                .chain(|| {
+                    (
+                        ErrorKind::InvalidInput,
+                        "Unable to create transaction obfuscation from tx query address",
+                    )
+                }),
+        }
+    }
+
+    /// Create the obfuscation of the features. Only the mock obfuscation is
+    /// available without the `websocket-rpc` feature, e.g. on WebAssembly
+    /// where the host obfuscates the transactions instead.
+    #[cfg(not(feature = "websocket-rpc"))]
+    pub fn new(_tendermint_address: &str, features: Features) -> Result<Obfuscation> {
+        match features {
+            Features::MockObfuscation => Ok(Obfuscation::Mock(MockTransactionCipher)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tendermint obfuscation is not available without the websocket-rpc feature",
+            )),
+        }
+    }
+}
+
+impl TransactionObfuscation for Obfuscation {
+    fn decrypt(
+        &self,
+        transaction_ids: &[TxId],
+        private_key: &PrivateKey,
+    ) -> Result<Vec<Transaction>> {
+        match self {
+            #[cfg(feature = "websocket-rpc")]
+            Obfuscation::Default(obfuscation) => obfuscation.decrypt(transaction_ids, private_key),
+            #[cfg(feature = "websocket-rpc")]
+            Obfuscation::MockAbci(obfuscation) => obfuscation.decrypt(transaction_ids, private_key),
+            Obfuscation::Mock(obfuscation) => obfuscation.decrypt(transaction_ids, private_key),
+        }
+    }
+
+    fn encrypt(&self, transaction: SignedTransaction) -> Result<TxAux> {
+        match self {
+            #[cfg(feature = "websocket-rpc")]
+            Obfuscation::Default(obfuscation) => obfuscation.encrypt(transaction),
+            #[cfg(feature = "websocket-rpc")]
+            Obfuscation::MockAbci(obfuscation) => obfuscation.encrypt(transaction),
+            Obfuscation::Mock(obfuscation) => obfuscation.encrypt(transaction),
+        }
+    }
+}
+
+/// Obfuscate the signed transaction with the obfuscation of the features
+pub fn signed_transaction_to_tx_aux(
+    signed_transaction: SignedTransaction,
+    tendermint_address: &str,
+    features: Features,
+) -> Result<TxAux> {
+    Obfuscation::new(tendermint_address, features)?.encrypt(signed_transaction)
+}
+
+/// Check the `TxAux` obfuscated by a host outside of the library, e.g. a
+/// browser wallet reaching the node with its own transport, is the
+/// obfuscation of the signed transaction handed to it
+pub fn host_obfuscated_tx_aux(
+    signed_transaction: &SignedTransaction,
+    tx_aux: &[u8],
+) -> Result<TxAux> {
+    let tx_aux: TxAux = decode_transaction(tx_aux, "obfuscated transaction")?;
+
+    let is_obfuscation = match (signed_transaction, &tx_aux) {
+        (
+            SignedTransaction::TransferTransaction(tx, _),
+            TxAux::EnclaveTx(TxEnclaveAux::TransferTx {
+                inputs,
+                no_of_outputs,
+                payload,
+            }),
+        ) => {
+            tx.inputs.encode() == inputs.encode()
+                && tx.outputs.len() == *no_of_outputs as usize
+                && payload.txid == tx.id()
+        }
+        (
+            SignedTransaction::DepositStakeTransaction(tx, _),
+            TxAux::EnclaveTx(TxEnclaveAux::DepositStakeTx {
+                tx: obfuscated_tx,
+                payload,
+            }),
+        ) => tx.encode() == obfuscated_tx.encode() && payload.txid == tx.id(),
+        (
+            SignedTransaction::WithdrawUnbondedStakeTransaction(tx, witness),
+            TxAux::EnclaveTx(TxEnclaveAux::WithdrawUnbondedStakeTx {
+                no_of_outputs,
+                witness: obfuscated_witness,
+                payload,
+            }),
+        ) => {
+            tx.outputs.len() == *no_of_outputs as usize
+                && witness.encode() == obfuscated_witness.encode()
+                && payload.txid == tx.id()
+        }
+        _ => false,
+    };
+    if !is_obfuscation {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Obfuscated transaction does not match the signed transaction",
+        ));
+    }
+
+    Ok(tx_aux)
+}
+
+/// Obfuscation which keeps the signed transaction in plain as the payload
+// #[cfg(feature = "mock")]
+#[derive(Debug, Clone)]
+pub struct MockTransactionCipher;
+
+// #[cfg(feature = "mock")]
+impl TransactionObfuscation for MockTransactionCipher {
+    fn decrypt(
+        &self,
+        _transaction_ids: &[TxId],
+        _private_key: &PrivateKey,
+    ) -> Result<Vec<Transaction>> {
+        Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Mock transaction cipher cannot decrypt transactions",
+        ))
+    }
+
+    fn encrypt(&self, transaction: SignedTransaction) -> Result<TxAux> {
+        let txpayload = transaction.encode();
+
+        match transaction {
+            SignedTransaction::TransferTransaction(tx, _) => {
+                Ok(TxAux::EnclaveTx(TxEnclaveAux::TransferTx {
+                    inputs: tx.inputs.clone(),
+                    no_of_outputs: tx.outputs.len() as TxoSize,
+                    payload: mock_payload(tx.id(), txpayload),
+                }))
+            }
+            SignedTransaction::DepositStakeTransaction(tx, _) => {
+                Ok(TxAux::EnclaveTx(TxEnclaveAux::DepositStakeTx {
+                    tx: tx.clone(),
+                    payload: mock_payload(tx.id(), txpayload),
+                }))
+            }
+            SignedTransaction::WithdrawUnbondedStakeTransaction(tx, witness) => {
+                Ok(TxAux::EnclaveTx(TxEnclaveAux::WithdrawUnbondedStakeTx {
+                    no_of_outputs: tx.outputs.len() as TxoSize,
+                    witness,
+                    payload: mock_payload(tx.id(), txpayload),
+                }))
+            }
+        }
+    }
+}
+
+fn mock_payload(txid: TxId, txpayload: Vec<u8>) -> TxObfuscated {
+    TxObfuscated {
+        txid,
+        key_from: BlockHeight::new(0),
+        init_vector: [0u8; 12],
+        txpayload,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signer::KeyPairSigner;
+    use crate::transfer_transaction::tests::{builder_of, key_pair};
+    use crate::transfer_transaction::{sign_input, signed_transfer_transaction};
+
+    fn signed_transfer(secrets: &[u8]) -> SignedTransaction {
+        let key_pairs: Vec<_> = secrets.iter().map(|secret| key_pair(*secret)).collect();
+        let mut builder = builder_of(&key_pairs);
+        for (i, (private_key, public_key)) in key_pairs.into_iter().enumerate() {
+            let signer = KeyPairSigner::new(private_key, public_key).unwrap();
+            sign_input(&mut builder, i, &signer).unwrap();
+        }
+
+        let (tx, witness) = signed_transfer_transaction(&builder).unwrap();
+
+        SignedTransaction::TransferTransaction(tx, witness)
+    }
+
+    #[test]
+    fn host_obfuscated_tx_aux_should_accept_obfuscation_of_signed_transaction() {
+        let signed_transaction = signed_transfer(&[1]);
+        let tx_aux = MockTransactionCipher
+            .encrypt(signed_transaction.clone())
+            .unwrap();
+
+        let host_tx_aux = host_obfuscated_tx_aux(&signed_transaction, &tx_aux.encode()).unwrap();
+
+        assert_eq!(host_tx_aux.tx_id(), tx_aux.tx_id());
+    }
+
+    #[test]
+    fn host_obfuscated_tx_aux_should_reject_other_transaction() {
+        let signed_transaction = signed_transfer(&[1]);
+        let other_tx_aux = MockTransactionCipher
+            .encrypt(signed_transfer(&[1, 2]))
+            .unwrap();
+
+        let err = host_obfuscated_tx_aux(&signed_transaction, &other_tx_aux.encode()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Obfuscated transaction does not match the signed transaction"
+        );
+        assert!(host_obfuscated_tx_aux(&signed_transaction, &[0, 1]).is_err());
+    }
+
+    #[test]
+    fn obfuscation_should_reject_unrecognized_features() {
+        assert!("MockAbci".parse::<Features>().is_ok());
+        assert!("Unknown".parse::<Features>().is_err());
+        assert!(Obfuscation::new("http://localhost", Features::AllDefault).is_err());
+    }
+}
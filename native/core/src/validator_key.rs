@@ -0,0 +1,329 @@
+//! Tendermint validator consensus keys
+use chain_core::state::tendermint::TendermintValidatorPubKey;
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use ed25519_dalek::{PublicKey, SecretKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Amino type of Tendermint ed25519 public key
+pub const ED25519_PUB_KEY_TYPE: &str = "tendermint/PubKeyEd25519";
+/// Amino type of Tendermint ed25519 private key
+pub const ED25519_PRIV_KEY_TYPE: &str = "tendermint/PrivKeyEd25519";
+
+const ED25519_SEED_SIZE: usize = 32;
+const ED25519_PUB_KEY_SIZE: usize = 32;
+/// Tendermint ed25519 private key is the 32 bytes seed followed by the
+/// 32 bytes public key
+const ED25519_PRIV_KEY_SIZE: usize = ED25519_SEED_SIZE + ED25519_PUB_KEY_SIZE;
+/// Tendermint address is the first 20 bytes of SHA256 of the public key
+const CONSENSUS_ADDRESS_SIZE: usize = 20;
+
+/// Amino JSON encoded key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AminoKey {
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub value: String,
+}
+
+/// Content of Tendermint `priv_validator_key.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivValidatorKey {
+    #[serde(default)]
+    pub address: String,
+    pub pub_key: AminoKey,
+    pub priv_key: AminoKey,
+}
+
+/// Tendermint ed25519 validator key
+pub struct ValidatorKey {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl ValidatorKey {
+    /// Generate a random validator key
+    pub fn generate() -> Result<Self> {
+        let mut seed = [0u8; ED25519_SEED_SIZE];
+        OsRng.fill_bytes(&mut seed);
+
+        ValidatorKey::from_seed(&seed)
+    }
+
+    /// Create validator key from the 32 bytes ed25519 seed
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        if seed.len() != ED25519_SEED_SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Expected ed25519 seed to be {} bytes but got {} bytes",
+                    ED25519_SEED_SIZE,
+                    seed.len()
+                ),
+            ));
+        }
+
+        let secret_key = SecretKey::from_bytes(seed).map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid ed25519 seed: {}", err),
+            )
+        })?;
+        let public_key = PublicKey::from(&secret_key);
+
+        Ok(ValidatorKey {
+            secret_key,
+            public_key,
+        })
+    }
+
+    /// Import validator key from the content of `priv_validator_key.json`.
+    /// The public key and address in the file are verified against the
+    /// private key.
+    pub fn from_priv_validator_key_json(json: &str) -> Result<Self> {
+        let priv_validator_key: PrivValidatorKey = serde_json::from_str(json).chain(|| {
+            (
+                ErrorKind::DeserializationError,
+                "Invalid priv_validator_key.json",
+            )
+        })?;
+
+        let private_key = decode_amino_key(
+            &priv_validator_key.priv_key,
+            ED25519_PRIV_KEY_TYPE,
+            ED25519_PRIV_KEY_SIZE,
+        )?;
+        let public_key = decode_amino_key(
+            &priv_validator_key.pub_key,
+            ED25519_PUB_KEY_TYPE,
+            ED25519_PUB_KEY_SIZE,
+        )?;
+
+        let validator_key = ValidatorKey::from_seed(&private_key[..ED25519_SEED_SIZE])?;
+        if private_key[ED25519_SEED_SIZE..] != public_key[..]
+            || validator_key.public_key.as_bytes()[..] != public_key[..]
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Public key does not belong to the private key",
+            ));
+        }
+        if !priv_validator_key.address.is_empty()
+            && !priv_validator_key
+                .address
+                .eq_ignore_ascii_case(&validator_key.consensus_address())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Address does not belong to the public key",
+            ));
+        }
+
+        Ok(validator_key)
+    }
+
+    /// Returns the content of `priv_validator_key.json` of the key
+    pub fn to_priv_validator_key_json(&self) -> Result<String> {
+        let mut private_key = Vec::with_capacity(ED25519_PRIV_KEY_SIZE);
+        private_key.extend_from_slice(self.secret_key.as_bytes());
+        private_key.extend_from_slice(self.public_key.as_bytes());
+
+        let priv_validator_key = PrivValidatorKey {
+            address: self.consensus_address(),
+            pub_key: AminoKey {
+                key_type: ED25519_PUB_KEY_TYPE.to_owned(),
+                value: self.consensus_public_key_base64(),
+            },
+            priv_key: AminoKey {
+                key_type: ED25519_PRIV_KEY_TYPE.to_owned(),
+                value: base64::encode(&private_key),
+            },
+        };
+
+        serde_json::to_string_pretty(&priv_validator_key).chain(|| {
+            (
+                ErrorKind::SerializationError,
+                "Unable to serialize priv_validator_key.json",
+            )
+        })
+    }
+
+    /// Returns the 32 bytes ed25519 seed
+    pub fn seed(&self) -> &[u8] {
+        self.secret_key.as_bytes()
+    }
+
+    /// Returns the consensus public key in the encoding of `CouncilNode`
+    pub fn consensus_public_key(&self) -> TendermintValidatorPubKey {
+        TendermintValidatorPubKey::Ed25519(self.public_key.to_bytes())
+    }
+
+    /// Returns the base64 encoded consensus public key
+    pub fn consensus_public_key_base64(&self) -> String {
+        base64::encode(self.public_key.as_bytes())
+    }
+
+    /// Returns the Tendermint validator address in uppercase hex
+    pub fn consensus_address(&self) -> String {
+        consensus_address(&self.consensus_public_key())
+    }
+}
+
+/// Parse a base64 encoded Tendermint ed25519 consensus public key
+pub fn consensus_public_key_from_base64(value: &str) -> Result<TendermintValidatorPubKey> {
+    let decoded = decode_amino_key(
+        &AminoKey {
+            key_type: ED25519_PUB_KEY_TYPE.to_owned(),
+            value: value.to_owned(),
+        },
+        ED25519_PUB_KEY_TYPE,
+        ED25519_PUB_KEY_SIZE,
+    )?;
+    let public_key = PublicKey::from_bytes(&decoded).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid ed25519 public key: {}", err),
+        )
+    })?;
+
+    Ok(TendermintValidatorPubKey::Ed25519(public_key.to_bytes()))
+}
+
+/// Returns the Tendermint validator address of the consensus public key in
+/// uppercase hex
+pub fn consensus_address(consensus_public_key: &TendermintValidatorPubKey) -> String {
+    let TendermintValidatorPubKey::Ed25519(public_key) = consensus_public_key;
+    let hash = Sha256::digest(public_key);
+
+    hex::encode_upper(&hash[..CONSENSUS_ADDRESS_SIZE])
+}
+
+fn decode_amino_key(key: &AminoKey, key_type: &str, size: usize) -> Result<Vec<u8>> {
+    if key.key_type != key_type {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Unsupported key type {}, expected {}",
+                key.key_type, key_type
+            ),
+        ));
+    }
+
+    let decoded = base64::decode(&key.value).chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            "Key is not a valid base64 string",
+        )
+    })?;
+    if decoded.len() != size {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Expected {} to be {} bytes but got {} bytes",
+                key_type,
+                size,
+                decoded.len()
+            ),
+        ));
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const CONSENSUS_PUBLIC_KEY: &str = "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=";
+    const CONSENSUS_ADDRESS: &str = "21FE31DFA154A261626BF854046FD2271B7BED4B";
+    const PRIVATE_KEY: &str =
+        "nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2DXWpgBgrEKt9VL/tPJZAc6DuFy89qmIyWvAhpo9wdRGg==";
+
+    fn priv_validator_key_json(address: &str, public_key: &str) -> String {
+        serde_json::to_string(&PrivValidatorKey {
+            address: address.to_owned(),
+            pub_key: AminoKey {
+                key_type: ED25519_PUB_KEY_TYPE.to_owned(),
+                value: public_key.to_owned(),
+            },
+            priv_key: AminoKey {
+                key_type: ED25519_PRIV_KEY_TYPE.to_owned(),
+                value: PRIVATE_KEY.to_owned(),
+            },
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn from_seed_should_derive_consensus_key_and_address() {
+        let validator_key = ValidatorKey::from_seed(&hex::decode(SEED).unwrap()).unwrap();
+
+        assert_eq!(
+            validator_key.consensus_public_key_base64(),
+            CONSENSUS_PUBLIC_KEY
+        );
+        assert_eq!(validator_key.consensus_address(), CONSENSUS_ADDRESS);
+        assert_eq!(hex::encode(validator_key.seed()), SEED);
+    }
+
+    #[test]
+    fn from_seed_should_reject_seed_of_wrong_size() {
+        assert!(ValidatorKey::from_seed(&[0; 31]).is_err());
+    }
+
+    #[test]
+    fn priv_validator_key_json_should_round_trip() {
+        let validator_key = ValidatorKey::generate().unwrap();
+        let json = validator_key.to_priv_validator_key_json().unwrap();
+
+        let imported = ValidatorKey::from_priv_validator_key_json(&json).unwrap();
+
+        assert_eq!(imported.seed(), validator_key.seed());
+        assert_eq!(
+            imported.consensus_public_key(),
+            validator_key.consensus_public_key()
+        );
+    }
+
+    #[test]
+    fn from_priv_validator_key_json_should_verify_public_key_and_address() {
+        let json = priv_validator_key_json(CONSENSUS_ADDRESS, CONSENSUS_PUBLIC_KEY);
+        let validator_key = ValidatorKey::from_priv_validator_key_json(&json).unwrap();
+        assert_eq!(validator_key.consensus_address(), CONSENSUS_ADDRESS);
+
+        let json = priv_validator_key_json(CONSENSUS_ADDRESS, &base64::encode(&[1; 32]));
+        assert!(ValidatorKey::from_priv_validator_key_json(&json).is_err());
+
+        let json = priv_validator_key_json(
+            "0000000000000000000000000000000000000000",
+            CONSENSUS_PUBLIC_KEY,
+        );
+        assert!(ValidatorKey::from_priv_validator_key_json(&json).is_err());
+    }
+
+    #[test]
+    fn consensus_public_key_from_base64_should_decode_ed25519_key() {
+        let consensus_public_key = consensus_public_key_from_base64(CONSENSUS_PUBLIC_KEY).unwrap();
+
+        assert_eq!(consensus_address(&consensus_public_key), CONSENSUS_ADDRESS);
+        assert!(consensus_public_key_from_base64("invalid").is_err());
+        assert!(consensus_public_key_from_base64(&base64::encode(&[1; 31])).is_err());
+    }
+
+    #[test]
+    fn consensus_public_key_from_base64_should_reject_key_off_the_curve() {
+        // y = 2 does not decompress to a point of the curve
+        let mut public_key = [0u8; ED25519_PUB_KEY_SIZE];
+        public_key[0] = 2;
+
+        assert!(
+            consensus_public_key_from_base64(&base64::encode(&public_key))
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid ed25519 public key")
+        );
+    }
+}
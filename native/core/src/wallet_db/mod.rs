@@ -0,0 +1,768 @@
+//! Local wallet database tracking the UTXOs of owned transfer addresses
+//!
+//! The database records the owned transfer addresses with their signers,
+//! and the outputs paid to them by the ingested transactions. Transactions
+//! are ingested in their decrypted raw form, since the outputs of enclave
+//! transactions are obfuscated on chain. Unspent outputs then feed the
+//! inputs of transfer transactions, and stay pending until the transaction
+//! spending them is ingested or released.
+mod options;
+mod storage;
+
+use chain_core::common::Timespec;
+use chain_core::init::coin::Coin;
+use chain_core::state::account::{DepositBondTx, WithdrawUnbondedTx};
+use chain_core::tx::data::address::ExtendedAddr;
+use chain_core::tx::data::input::TxoPointer;
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::data::{Tx, TxId};
+use chain_core::tx::fee::FeeAlgorithm;
+use chain_core::tx::TransactionId;
+use client_common::{Error, ErrorKind, PublicKey, Result};
+use client_core::transaction_builder::RawTransferTransactionBuilder;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::codec::decode_transaction;
+use crate::coin::{add_coins, sub_coins};
+use crate::fee_estimation::TxKind;
+use crate::schema::types::AddressParamsOptions;
+use crate::transfer_transaction::{
+    build_incomplete, RawTransactionOptions, RawTransactionOptionsInput,
+    RawTransactionOptionsInputAddressParams,
+};
+
+pub use options::{
+    AddAddressOptions, IngestBlockOptions, IngestTransactionOptions, RawTransactionItem,
+    WalletStoreOptions, WalletTransferOptions,
+};
+pub use storage::{decode_wallet_db, encode_wallet_db, read_wallet_db, write_wallet_db};
+
+/// Transfer address owned by the wallet
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct OwnedAddress {
+    pub address: ExtendedAddr,
+    pub required_signers: u64,
+    pub total_signers: u64,
+}
+
+/// Spending status of a tracked output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum OutputStatus {
+    Unspent,
+    /// Input of a transaction built by the wallet which is not ingested yet
+    Pending(TxId),
+    /// Spent by the ingested transaction
+    Spent(TxId),
+}
+
+/// Output paid to an owned address
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TrackedOutput {
+    pub pointer: TxoPointer,
+    pub output: TxOut,
+    /// Height of the block of the transaction, `None` when the transaction
+    /// is ingested on its own
+    pub height: Option<u64>,
+    pub status: OutputStatus,
+}
+
+/// Decrypted raw transaction of a kind moving UTXOs
+#[derive(Debug, Clone)]
+pub enum RawTransaction {
+    Transfer(Tx),
+    Deposit(DepositBondTx),
+    WithdrawUnbonded(WithdrawUnbondedTx),
+}
+
+impl RawTransaction {
+    /// Decode the raw transaction of `kind`
+    pub fn decode(kind: TxKind, raw_tx: &[u8]) -> Result<RawTransaction> {
+        match kind {
+            TxKind::Transfer => {
+                decode_transaction(raw_tx, "raw transaction").map(RawTransaction::Transfer)
+            }
+            TxKind::Deposit => {
+                decode_transaction(raw_tx, "raw transaction").map(RawTransaction::Deposit)
+            }
+            TxKind::WithdrawUnbonded => {
+                decode_transaction(raw_tx, "raw transaction").map(RawTransaction::WithdrawUnbonded)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Only transfer, deposit and withdraw unbonded transactions move UTXOs",
+            )),
+        }
+    }
+
+    pub fn tx_id(&self) -> TxId {
+        match self {
+            RawTransaction::Transfer(tx) => tx.id(),
+            RawTransaction::Deposit(tx) => tx.id(),
+            RawTransaction::WithdrawUnbonded(tx) => tx.id(),
+        }
+    }
+
+    fn chain_hex_id(&self) -> u8 {
+        match self {
+            RawTransaction::Transfer(tx) => tx.attributes.chain_hex_id,
+            RawTransaction::Deposit(tx) => tx.attributes.chain_hex_id,
+            RawTransaction::WithdrawUnbonded(tx) => tx.attributes.chain_hex_id,
+        }
+    }
+
+    fn inputs(&self) -> &[TxoPointer] {
+        match self {
+            RawTransaction::Transfer(tx) => &tx.inputs,
+            RawTransaction::Deposit(tx) => &tx.inputs,
+            RawTransaction::WithdrawUnbonded(_) => &[],
+        }
+    }
+
+    fn outputs(&self) -> &[TxOut] {
+        match self {
+            RawTransaction::Transfer(tx) => &tx.outputs,
+            RawTransaction::Deposit(_) => &[],
+            RawTransaction::WithdrawUnbonded(tx) => &tx.outputs,
+        }
+    }
+}
+
+/// Number of outputs affected by an ingestion
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IngestionSummary {
+    /// Outputs paid to owned addresses
+    pub received: usize,
+    /// Tracked outputs spent
+    pub spent: usize,
+}
+
+/// Values of the tracked outputs which are not spent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Balance {
+    /// Unspent outputs which can be spent at the last block time
+    pub available: Coin,
+    /// Unspent outputs valid from after the last block time
+    pub locked: Coin,
+    /// Inputs of the transactions built by the wallet
+    pub pending: Coin,
+}
+
+/// Recipients of a transfer funded by the wallet
+#[derive(Debug)]
+pub struct TransferOptions {
+    pub outputs: Vec<TxOut>,
+    pub view_keys: Vec<PublicKey>,
+    /// Owned address receiving the change, the first owned address when
+    /// omitted
+    pub change_address: Option<ExtendedAddr>,
+}
+
+/// Transfer transaction built from the unspent outputs of the wallet
+#[derive(Debug)]
+pub struct WalletTransfer<F>
+where
+    F: FeeAlgorithm,
+{
+    pub builder: RawTransferTransactionBuilder<F>,
+    /// Spent outputs, in the order of the inputs
+    pub inputs: Vec<TrackedOutput>,
+    /// Change output, the last output of the transaction
+    pub change: Option<TxOut>,
+    pub fee: Coin,
+}
+
+/// Owned addresses and their tracked outputs
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct WalletDb {
+    chain_hex_id: u8,
+    last_height: u64,
+    last_block_time: Timespec,
+    addresses: Vec<OwnedAddress>,
+    outputs: Vec<TrackedOutput>,
+}
+
+impl WalletDb {
+    /// Create an empty database of the chain
+    pub fn new(chain_hex_id: u8) -> Self {
+        WalletDb {
+            chain_hex_id,
+            last_height: 0,
+            last_block_time: 0,
+            addresses: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn chain_hex_id(&self) -> u8 {
+        self.chain_hex_id
+    }
+
+    /// Height of the last ingested block, 0 when none is ingested
+    pub fn last_height(&self) -> u64 {
+        self.last_height
+    }
+
+    pub fn addresses(&self) -> &[OwnedAddress] {
+        &self.addresses
+    }
+
+    pub fn outputs(&self) -> &[TrackedOutput] {
+        &self.outputs
+    }
+
+    /// Record the address as owned, or update its signers. Outputs of
+    /// transactions ingested before are not tracked retroactively. Returns
+    /// true when the address is new.
+    pub fn add_address(&mut self, address: ExtendedAddr, params: AddressParamsOptions) -> bool {
+        let owned_address = OwnedAddress {
+            address,
+            required_signers: params.required_signers,
+            total_signers: params.total_signers,
+        };
+
+        match self
+            .addresses
+            .iter_mut()
+            .find(|owned| owned.address == owned_address.address)
+        {
+            Some(owned) => {
+                *owned = owned_address;
+                false
+            }
+            None => {
+                self.addresses.push(owned_address);
+                true
+            }
+        }
+    }
+
+    fn owned_address(&self, address: &ExtendedAddr) -> Option<&OwnedAddress> {
+        self.addresses
+            .iter()
+            .find(|owned| &owned.address == address)
+    }
+
+    /// Ingest the transaction: track its outputs paid to owned addresses and
+    /// mark the tracked outputs it spends
+    pub fn ingest_transaction(
+        &mut self,
+        tx: &RawTransaction,
+        height: Option<u64>,
+    ) -> Result<IngestionSummary> {
+        if tx.chain_hex_id() != self.chain_hex_id {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Transaction {} is of chain hex id {:02X} but wallet is of {:02X}",
+                    hex::encode(tx.tx_id()),
+                    tx.chain_hex_id(),
+                    self.chain_hex_id
+                ),
+            ));
+        }
+
+        let tx_id = tx.tx_id();
+        let mut summary = IngestionSummary::default();
+
+        for pointer in tx.inputs() {
+            if let Some(tracked) = self.tracked_output_mut(pointer) {
+                if tracked.status != OutputStatus::Spent(tx_id) {
+                    tracked.status = OutputStatus::Spent(tx_id);
+                    summary.spent += 1;
+                }
+            }
+        }
+
+        for (index, output) in tx.outputs().iter().enumerate() {
+            if self.owned_address(&output.address).is_none() {
+                continue;
+            }
+
+            let pointer = TxoPointer::new(tx_id, index);
+            match self.tracked_output_mut(&pointer) {
+                // Ingested again, e.g. on its own then in its block
+                Some(tracked) => {
+                    tracked.height = tracked.height.or(height);
+                }
+                None => {
+                    self.outputs.push(TrackedOutput {
+                        pointer,
+                        output: output.clone(),
+                        height,
+                        status: OutputStatus::Unspent,
+                    });
+                    summary.received += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Ingest the decrypted transactions of the block. The block should be
+    /// after the last ingested one. Nothing is ingested when any transaction
+    /// is invalid.
+    pub fn ingest_block(
+        &mut self,
+        height: u64,
+        time: Timespec,
+        txs: &[RawTransaction],
+    ) -> Result<IngestionSummary> {
+        if height <= self.last_height {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Block {} is not after the last ingested block {}",
+                    height, self.last_height
+                ),
+            ));
+        }
+
+        let mut db = self.clone();
+        let mut summary = IngestionSummary::default();
+        for tx in txs {
+            let tx_summary = db.ingest_transaction(tx, Some(height))?;
+            summary.received += tx_summary.received;
+            summary.spent += tx_summary.spent;
+        }
+        db.last_height = height;
+        db.last_block_time = time;
+
+        *self = db;
+
+        Ok(summary)
+    }
+
+    fn tracked_output_mut(&mut self, pointer: &TxoPointer) -> Option<&mut TrackedOutput> {
+        self.outputs
+            .iter_mut()
+            .find(|tracked| &tracked.pointer == pointer)
+    }
+
+    /// Whether the unspent output can be spent at the last block time
+    fn is_spendable(&self, tracked: &TrackedOutput) -> bool {
+        tracked.status == OutputStatus::Unspent
+            && tracked
+                .output
+                .valid_from
+                .map_or(true, |valid_from| valid_from <= self.last_block_time)
+    }
+
+    /// Values of the outputs which are not spent
+    pub fn balance(&self) -> Result<Balance> {
+        let mut available = Coin::zero();
+        let mut locked = Coin::zero();
+        let mut pending = Coin::zero();
+
+        for tracked in self.outputs.iter() {
+            let total = match tracked.status {
+                OutputStatus::Unspent if self.is_spendable(tracked) => &mut available,
+                OutputStatus::Unspent => &mut locked,
+                OutputStatus::Pending(_) => &mut pending,
+                OutputStatus::Spent(_) => continue,
+            };
+            *total = add_coins(*total, tracked.output.value)?;
+        }
+
+        Ok(Balance {
+            available,
+            locked,
+            pending,
+        })
+    }
+
+    /// Build a transfer to the outputs from the spendable outputs, largest
+    /// first, with the change to the change address. The spent outputs are
+    /// pending until the transaction is ingested or released. A change below
+    /// the fee of its own output is left to the fee instead.
+    pub fn build_transfer<F>(
+        &mut self,
+        options: &TransferOptions,
+        fee_algorithm: F,
+    ) -> Result<WalletTransfer<F>>
+    where
+        F: FeeAlgorithm + Clone,
+    {
+        if options.outputs.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Transfer should have at least one output",
+            ));
+        }
+        let change_address = self.change_address(options)?;
+        let output_total = options
+            .outputs
+            .iter()
+            .try_fold(Coin::zero(), |total, output| add_coins(total, output.value))?;
+
+        let mut candidates: Vec<TrackedOutput> = self
+            .outputs
+            .iter()
+            .filter(|tracked| self.is_spendable(tracked))
+            .cloned()
+            .collect();
+        candidates.sort_by(|a, b| b.output.value.cmp(&a.output.value));
+
+        let mut selected = Vec::new();
+        let mut input_total = Coin::zero();
+        let mut required = output_total;
+        for candidate in candidates {
+            input_total = add_coins(input_total, candidate.output.value)?;
+            selected.push(candidate);
+            if input_total < output_total {
+                continue;
+            }
+
+            let fee_without_change = self
+                .build_incomplete(
+                    &selected,
+                    options.outputs.clone(),
+                    options,
+                    fee_algorithm.clone(),
+                )?
+                .estimate_fee()?
+                .to_coin();
+            required = add_coins(output_total, fee_without_change)?;
+            if input_total < required {
+                continue;
+            }
+
+            // Fee does not depend on the change value, which is encoded in
+            // a fixed size
+            let mut outputs = options.outputs.clone();
+            outputs.push(TxOut::new(change_address.clone(), Coin::zero()));
+            let fee_with_change = self
+                .build_incomplete(&selected, outputs, options, fee_algorithm.clone())?
+                .estimate_fee()?
+                .to_coin();
+            let change_output_fee = sub_coins(fee_with_change, fee_without_change)?;
+            let remaining = sub_coins(input_total, output_total)?;
+
+            let (change, fee) = match sub_coins(remaining, fee_with_change) {
+                Ok(change_value)
+                    if change_value > Coin::zero() && change_value >= change_output_fee =>
+                {
+                    (
+                        Some(TxOut::new(change_address, change_value)),
+                        fee_with_change,
+                    )
+                }
+                _ => (None, remaining),
+            };
+            let mut outputs = options.outputs.clone();
+            outputs.extend(change.clone());
+            let builder = self.build_incomplete(&selected, outputs, options, fee_algorithm)?;
+
+            let tx_id = builder.tx_id();
+            for input in selected.iter() {
+                if let Some(tracked) = self.tracked_output_mut(&input.pointer) {
+                    tracked.status = OutputStatus::Pending(tx_id);
+                }
+            }
+
+            return Ok(WalletTransfer {
+                builder,
+                inputs: selected,
+                change,
+                fee,
+            });
+        }
+
+        Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Insufficient balance: {} available, at least {} required",
+                u64::from(input_total),
+                u64::from(required)
+            ),
+        ))
+    }
+
+    fn change_address(&self, options: &TransferOptions) -> Result<ExtendedAddr> {
+        match options.change_address {
+            Some(ref change_address) if self.owned_address(change_address).is_some() => {
+                Ok(change_address.clone())
+            }
+            Some(_) => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Change address is not owned by the wallet",
+            )),
+            None => self
+                .addresses
+                .first()
+                .map(|owned| owned.address.clone())
+                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Wallet has no address")),
+        }
+    }
+
+    fn build_incomplete<F>(
+        &self,
+        inputs: &[TrackedOutput],
+        outputs: Vec<TxOut>,
+        options: &TransferOptions,
+        fee_algorithm: F,
+    ) -> Result<RawTransferTransactionBuilder<F>>
+    where
+        F: FeeAlgorithm,
+    {
+        let inputs = inputs
+            .iter()
+            .map(|tracked| {
+                let owned = self.owned_address(&tracked.output.address).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "Tracked output is not paid to an owned address",
+                    )
+                })?;
+
+                Ok(RawTransactionOptionsInput {
+                    prev_output: (tracked.pointer.clone(), tracked.output.clone()),
+                    address_params: RawTransactionOptionsInputAddressParams {
+                        required_signers: owned.required_signers,
+                        total_signers: owned.total_signers,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        build_incomplete(
+            &RawTransactionOptions {
+                inputs,
+                outputs,
+                view_keys: options.view_keys.clone(),
+                chain_hex_id: self.chain_hex_id,
+            },
+            fee_algorithm,
+        )
+    }
+
+    /// Mark the outputs pending for the transaction as unspent again, e.g.
+    /// when it is rejected. Returns the number of released outputs.
+    pub fn release_pending(&mut self, tx_id: &TxId) -> usize {
+        let mut released = 0;
+        for tracked in self.outputs.iter_mut() {
+            if tracked.status == OutputStatus::Pending(*tx_id) {
+                tracked.status = OutputStatus::Unspent;
+                released += 1;
+            }
+        }
+
+        released
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use chain_core::tx::data::attribute::TxAttributes;
+
+    use crate::address::extended_address_from_public_key;
+    use crate::transfer_transaction::tests::{fee_config, key_pair, CHAIN_HEX_ID};
+
+    pub fn owned_address() -> ExtendedAddr {
+        extended_address_from_public_key(&key_pair(1).1).unwrap()
+    }
+
+    fn other_address() -> ExtendedAddr {
+        extended_address_from_public_key(&key_pair(9).1).unwrap()
+    }
+
+    fn coin(value: u64) -> Coin {
+        Coin::new(value).unwrap()
+    }
+
+    /// Transfer of the previous transaction id paying the values to the
+    /// owned address
+    pub fn transfer_to_owned(inputs: Vec<TxoPointer>, values: &[u64]) -> RawTransaction {
+        let outputs = values
+            .iter()
+            .map(|value| TxOut::new(owned_address(), coin(*value)))
+            .collect();
+
+        RawTransaction::Transfer(Tx {
+            inputs,
+            outputs,
+            attributes: TxAttributes::new(CHAIN_HEX_ID),
+        })
+    }
+
+    pub fn wallet_db() -> WalletDb {
+        let mut db = WalletDb::new(CHAIN_HEX_ID);
+        db.add_address(owned_address(), AddressParamsOptions::default());
+
+        db
+    }
+
+    fn transfer_options(value: u64) -> TransferOptions {
+        TransferOptions {
+            outputs: vec![TxOut::new(other_address(), coin(value))],
+            view_keys: Vec::new(),
+            change_address: None,
+        }
+    }
+
+    #[test]
+    fn ingest_block_should_track_owned_outputs_and_spend_them() {
+        let mut db = wallet_db();
+        let received = transfer_to_owned(Vec::new(), &[1_000, 2_000]);
+        let received_id = received.tx_id();
+
+        let summary = db.ingest_block(1, 1000, &[received]).unwrap();
+        assert_eq!(
+            summary,
+            IngestionSummary {
+                received: 2,
+                spent: 0
+            }
+        );
+        assert_eq!(db.last_height(), 1);
+
+        let spending = RawTransaction::Transfer(Tx {
+            inputs: vec![TxoPointer::new(received_id, 0)],
+            outputs: vec![TxOut::new(other_address(), coin(900))],
+            attributes: TxAttributes::new(CHAIN_HEX_ID),
+        });
+        let spending_id = spending.tx_id();
+        let summary = db.ingest_block(2, 1010, &[spending]).unwrap();
+
+        assert_eq!(
+            summary,
+            IngestionSummary {
+                received: 0,
+                spent: 1
+            }
+        );
+        assert_eq!(db.outputs()[0].status, OutputStatus::Spent(spending_id));
+        assert_eq!(db.balance().unwrap().available, coin(2_000));
+    }
+
+    #[test]
+    fn ingest_block_should_reject_block_not_after_last_one() {
+        let mut db = wallet_db();
+        db.ingest_block(2, 1000, &[]).unwrap();
+
+        let err = db.ingest_block(2, 1000, &[]).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Block 2 is not after the last ingested block 2"));
+    }
+
+    #[test]
+    fn ingest_block_should_ingest_nothing_when_tx_is_of_other_chain() {
+        let mut db = wallet_db();
+        let other_chain_tx = RawTransaction::Transfer(Tx {
+            inputs: Vec::new(),
+            outputs: vec![TxOut::new(owned_address(), coin(1_000))],
+            attributes: TxAttributes::new(CHAIN_HEX_ID + 1),
+        });
+
+        let txs = vec![transfer_to_owned(Vec::new(), &[1_000]), other_chain_tx];
+        assert!(db.ingest_block(1, 1000, &txs).is_err());
+        assert!(db.outputs().is_empty());
+        assert_eq!(db.last_height(), 0);
+    }
+
+    #[test]
+    fn balance_should_report_locked_outputs() {
+        let mut db = wallet_db();
+        let mut locked_output = TxOut::new(owned_address(), coin(500));
+        locked_output.valid_from = Some(2000);
+        let tx = RawTransaction::WithdrawUnbonded(WithdrawUnbondedTx::new(
+            0,
+            vec![locked_output],
+            TxAttributes::new(CHAIN_HEX_ID),
+        ));
+
+        db.ingest_block(1, 1000, &[tx]).unwrap();
+
+        let balance = db.balance().unwrap();
+        assert_eq!(balance.available, Coin::zero());
+        assert_eq!(balance.locked, coin(500));
+    }
+
+    #[test]
+    fn build_transfer_should_spend_largest_outputs_with_change() {
+        let mut db = wallet_db();
+        db.ingest_block(
+            1,
+            1000,
+            &[transfer_to_owned(Vec::new(), &[1_000, 50_000, 30_000])],
+        )
+        .unwrap();
+
+        let transfer = db
+            .build_transfer(&transfer_options(60_000), fee_config().to_fee_algorithm())
+            .unwrap();
+
+        let input_values: Vec<Coin> = transfer
+            .inputs
+            .iter()
+            .map(|input| input.output.value)
+            .collect();
+        assert_eq!(input_values, vec![coin(50_000), coin(30_000)]);
+        let change = transfer.change.unwrap();
+        assert_eq!(change.address, owned_address());
+        assert_eq!(
+            (coin(80_000) - coin(60_000)).unwrap(),
+            (change.value + transfer.fee).unwrap()
+        );
+
+        let balance = db.balance().unwrap();
+        assert_eq!(balance.available, coin(1_000));
+        assert_eq!(balance.pending, coin(80_000));
+    }
+
+    #[test]
+    fn build_transfer_should_leave_change_below_its_output_fee_to_fee() {
+        let transfer_from = |value: u64| {
+            let mut db = wallet_db();
+            db.ingest_block(1, 1000, &[transfer_to_owned(Vec::new(), &[value])])
+                .unwrap();
+
+            db.build_transfer(&transfer_options(10_000), fee_config().to_fee_algorithm())
+                .unwrap()
+        };
+        let with_change = transfer_from(1_000_000);
+        let fee_with_change = u64::from(with_change.fee);
+        assert!(with_change.change.is_some());
+
+        // Change of 1 costs more than its value
+        let transfer = transfer_from(10_000 + fee_with_change + 1);
+        assert!(transfer.change.is_none());
+        assert_eq!(u64::from(transfer.fee), fee_with_change + 1);
+
+        // Enough for the fee without change output only
+        let transfer = transfer_from(10_000 + fee_with_change - 1);
+        assert!(transfer.change.is_none());
+        assert_eq!(u64::from(transfer.fee), fee_with_change - 1);
+    }
+
+    #[test]
+    fn build_transfer_should_reject_insufficient_balance() {
+        let mut db = wallet_db();
+        db.ingest_block(1, 1000, &[transfer_to_owned(Vec::new(), &[1_000])])
+            .unwrap();
+
+        let err = db
+            .build_transfer(&transfer_options(1_000), fee_config().to_fee_algorithm())
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Insufficient balance"));
+        assert_eq!(db.balance().unwrap().pending, Coin::zero());
+    }
+
+    #[test]
+    fn release_pending_should_make_outputs_spendable_again() {
+        let mut db = wallet_db();
+        db.ingest_block(1, 1000, &[transfer_to_owned(Vec::new(), &[50_000])])
+            .unwrap();
+        let transfer = db
+            .build_transfer(&transfer_options(10_000), fee_config().to_fee_algorithm())
+            .unwrap();
+
+        assert_eq!(db.release_pending(&transfer.builder.tx_id()), 1);
+        assert_eq!(db.balance().unwrap().available, coin(50_000));
+    }
+}
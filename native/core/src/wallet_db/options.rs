@@ -0,0 +1,174 @@
+use std::path::Path;
+
+use chain_core::common::Timespec;
+use chain_core::tx::data::address::ExtendedAddr;
+use client_common::{Error, ErrorKind, PublicKey, Result};
+use secstr::SecUtf8;
+use serde::Deserialize;
+
+use super::storage::{read_wallet_db, write_wallet_db};
+use super::{RawTransaction, TransferOptions, WalletDb};
+use crate::fee_estimation::TxKind;
+use crate::network::NetworkDefinition;
+use crate::schema::types::{to_tx_outs, AddressParamsOptions, FeeConfigOptions, OutputOptions};
+use crate::schema::{de, SchemaError, SchemaResult};
+
+/// Location of the wallet database file and the passphrase it is encrypted
+/// with, if any
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletStoreOptions {
+    pub path: String,
+    #[serde(default)]
+    pub passphrase: Option<SecUtf8>,
+}
+
+impl WalletStoreOptions {
+    /// Create the database file of the chain, which should not exist yet
+    pub fn create(&self, chain_hex_id: u8) -> Result<WalletDb> {
+        if Path::new(&self.path).exists() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Wallet database already exists at {}", self.path),
+            ));
+        }
+
+        let db = WalletDb::new(chain_hex_id);
+        self.save(&db)?;
+
+        Ok(db)
+    }
+
+    pub fn open(&self) -> Result<WalletDb> {
+        read_wallet_db(Path::new(&self.path), self.passphrase.as_ref())
+    }
+
+    pub fn save(&self, db: &WalletDb) -> Result<()> {
+        write_wallet_db(Path::new(&self.path), db, self.passphrase.as_ref())
+    }
+}
+
+/// Owned transfer address and its signers
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddAddressOptions {
+    pub address: String,
+    #[serde(default)]
+    pub address_params: AddressParamsOptions,
+}
+
+impl AddAddressOptions {
+    pub fn to_address(
+        &self,
+        network: &NetworkDefinition,
+    ) -> SchemaResult<(ExtendedAddr, AddressParamsOptions)> {
+        self.address_params.verify("addressParams")?;
+        let address = network
+            .decode_transfer_address(&self.address)
+            .map_err(|err| {
+                SchemaError::new("address", format!("Invalid transfer address: {}", err))
+            })?;
+
+        Ok((address, self.address_params))
+    }
+}
+
+/// Decrypted raw transaction and its kind
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawTransactionItem {
+    pub kind: TxKind,
+    #[serde(deserialize_with = "de::buffer")]
+    pub raw_tx: Vec<u8>,
+}
+
+impl RawTransactionItem {
+    pub fn to_raw_transaction(&self) -> Result<RawTransaction> {
+        RawTransaction::decode(self.kind, &self.raw_tx)
+    }
+}
+
+/// Decrypted raw transaction ingested on its own, e.g. before its block
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestTransactionOptions {
+    pub kind: TxKind,
+    #[serde(deserialize_with = "de::buffer")]
+    pub raw_tx: Vec<u8>,
+    #[serde(default, deserialize_with = "de::optional_height")]
+    pub height: Option<u64>,
+}
+
+impl IngestTransactionOptions {
+    pub fn to_raw_transaction(&self) -> Result<RawTransaction> {
+        RawTransaction::decode(self.kind, &self.raw_tx)
+    }
+}
+
+/// Block with its decrypted raw transactions
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestBlockOptions {
+    #[serde(deserialize_with = "de::height")]
+    pub height: u64,
+    pub time: Timespec,
+    pub txs: Vec<RawTransactionItem>,
+}
+
+impl IngestBlockOptions {
+    pub fn to_raw_transactions(&self) -> Result<Vec<RawTransaction>> {
+        self.txs
+            .iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                tx.to_raw_transaction().map_err(|err| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Unable to decode transaction {} of block: {}", i, err),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Options of a transfer funded by the wallet
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletTransferOptions {
+    pub outputs: Vec<OutputOptions>,
+    #[serde(default, deserialize_with = "de::view_keys")]
+    pub view_keys: Vec<PublicKey>,
+    #[serde(default)]
+    pub change_address: Option<String>,
+    pub fee_config: FeeConfigOptions,
+}
+
+impl WalletTransferOptions {
+    pub fn to_transfer_options(
+        &self,
+        network: &NetworkDefinition,
+    ) -> SchemaResult<TransferOptions> {
+        let outputs = to_tx_outs(&self.outputs, network, "outputs")?;
+        let change_address = self
+            .change_address
+            .as_ref()
+            .map(|change_address| {
+                network
+                    .decode_transfer_address(change_address)
+                    .map_err(|err| {
+                        SchemaError::new(
+                            "changeAddress",
+                            format!("Invalid transfer address: {}", err),
+                        )
+                    })
+            })
+            .transpose()?;
+
+        Ok(TransferOptions {
+            outputs,
+            view_keys: self.view_keys.clone(),
+            change_address,
+        })
+    }
+}
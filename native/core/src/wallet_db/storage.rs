@@ -0,0 +1,250 @@
+//! Wallet database file, optionally encrypted with a passphrase
+//!
+//! The file starts with a magic and a format byte, followed by the SCALE
+//! encoded database. Encrypted files hold the PBKDF2 salt and the nonce,
+//! followed by the ChaCha20-Poly1305 ciphertext of the encoded database
+//! authenticated with the magic and format byte.
+use std::fs;
+use std::path::Path;
+
+use chacha20poly1305::aead::generic_array::GenericArray;
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use hmac::Hmac;
+use parity_scale_codec::Encode;
+use rand::RngCore;
+use secstr::SecUtf8;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use super::WalletDb;
+use crate::codec::decode_strict;
+
+const MAGIC: &[u8] = b"CROWDB";
+const PLAIN_FORMAT: u8 = 0;
+const ENCRYPTED_FORMAT: u8 = 1;
+
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+const KEY_SIZE: usize = 32;
+const PBKDF2_ROUNDS: usize = 100_000;
+
+/// Maximum size of the encoded database
+pub const MAX_WALLET_DB_SIZE: usize = 64 * 1024 * 1024;
+
+/// Encode the database, encrypted when the passphrase is provided
+pub fn encode_wallet_db(db: &WalletDb, passphrase: Option<&SecUtf8>) -> Result<Vec<u8>> {
+    let encoded = db.encode();
+    let mut bytes = MAGIC.to_vec();
+
+    match passphrase {
+        None => {
+            bytes.push(PLAIN_FORMAT);
+            bytes.extend(encoded);
+        }
+        Some(passphrase) => {
+            let mut salt = [0u8; SALT_SIZE];
+            let mut nonce = [0u8; NONCE_SIZE];
+            let mut rng = rand::thread_rng();
+            rng.fill_bytes(&mut salt);
+            rng.fill_bytes(&mut nonce);
+
+            let payload = Payload {
+                msg: &encoded,
+                aad: &encrypted_header(),
+            };
+            let ciphertext = cipher(passphrase, &salt)
+                .encrypt(GenericArray::from_slice(&nonce), payload)
+                .map_err(|_| {
+                    Error::new(
+                        ErrorKind::EncryptionError,
+                        "Unable to encrypt wallet database",
+                    )
+                })?;
+
+            bytes.push(ENCRYPTED_FORMAT);
+            bytes.extend_from_slice(&salt);
+            bytes.extend_from_slice(&nonce);
+            bytes.extend(ciphertext);
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Decode the database, which should be encrypted if and only if the
+/// passphrase is provided
+pub fn decode_wallet_db(bytes: &[u8], passphrase: Option<&SecUtf8>) -> Result<WalletDb> {
+    if bytes.len() <= MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(Error::new(
+            ErrorKind::DeserializationError,
+            "Data is not a wallet database",
+        ));
+    }
+    let format = bytes[MAGIC.len()];
+    let body = &bytes[MAGIC.len() + 1..];
+
+    let encoded = match (format, passphrase) {
+        (PLAIN_FORMAT, None) => body.to_vec(),
+        (PLAIN_FORMAT, Some(_)) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Wallet database is not encrypted",
+            ))
+        }
+        (ENCRYPTED_FORMAT, None) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Wallet database is encrypted, passphrase is required",
+            ))
+        }
+        (ENCRYPTED_FORMAT, Some(passphrase)) => decrypt(body, passphrase)?,
+        (format, _) => {
+            return Err(Error::new(
+                ErrorKind::DeserializationError,
+                format!("Unsupported wallet database format {}", format),
+            ))
+        }
+    };
+
+    decode_strict(&encoded, MAX_WALLET_DB_SIZE, "wallet database")
+}
+
+fn decrypt(body: &[u8], passphrase: &SecUtf8) -> Result<Vec<u8>> {
+    if body.len() < SALT_SIZE + NONCE_SIZE {
+        return Err(Error::new(
+            ErrorKind::DeserializationError,
+            "Encrypted wallet database is truncated",
+        ));
+    }
+    let (salt, body) = body.split_at(SALT_SIZE);
+    let (nonce, ciphertext) = body.split_at(NONCE_SIZE);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: &encrypted_header(),
+    };
+
+    cipher(passphrase, salt)
+        .decrypt(GenericArray::from_slice(nonce), payload)
+        .map_err(|_| {
+            Error::new(
+                ErrorKind::DecryptionError,
+                "Unable to decrypt wallet database, passphrase may be incorrect",
+            )
+        })
+}
+
+/// Magic and format byte of encrypted files, authenticated along with the
+/// ciphertext
+fn encrypted_header() -> Vec<u8> {
+    let mut header = MAGIC.to_vec();
+    header.push(ENCRYPTED_FORMAT);
+
+    header
+}
+
+/// Cipher of the key derived from the passphrase and the salt. The derived
+/// key is zeroed once the cipher is created.
+fn cipher(passphrase: &SecUtf8, salt: &[u8]) -> ChaCha20Poly1305 {
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(
+        passphrase.unsecure().as_bytes(),
+        salt,
+        PBKDF2_ROUNDS,
+        &mut *key,
+    );
+
+    ChaCha20Poly1305::new(GenericArray::from_slice(&*key))
+}
+
+/// Read the database file
+pub fn read_wallet_db(path: &Path, passphrase: Option<&SecUtf8>) -> Result<WalletDb> {
+    let bytes = fs::read(path).chain(|| {
+        (
+            ErrorKind::IoError,
+            format!("Unable to read wallet database at {}", path.display()),
+        )
+    })?;
+
+    decode_wallet_db(&bytes, passphrase)
+}
+
+/// Write the database file. The file is replaced at once so that it is not
+/// left half written.
+pub fn write_wallet_db(path: &Path, db: &WalletDb, passphrase: Option<&SecUtf8>) -> Result<()> {
+    let bytes = encode_wallet_db(db, passphrase)?;
+    let write_error = || {
+        (
+            ErrorKind::IoError,
+            format!("Unable to write wallet database at {}", path.display()),
+        )
+    };
+
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, &bytes).chain(write_error)?;
+    fs::rename(&temp_path, path).chain(write_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::wallet_db::tests::{transfer_to_owned, wallet_db};
+
+    fn ingested_wallet_db() -> WalletDb {
+        let mut db = wallet_db();
+        db.ingest_block(1, 1000, &[transfer_to_owned(Vec::new(), &[1_000])])
+            .unwrap();
+
+        db
+    }
+
+    #[test]
+    fn plain_wallet_db_should_round_trip() {
+        let db = ingested_wallet_db();
+
+        let bytes = encode_wallet_db(&db, None).unwrap();
+
+        assert_eq!(decode_wallet_db(&bytes, None).unwrap(), db);
+    }
+
+    #[test]
+    fn encrypted_wallet_db_should_round_trip_with_passphrase() {
+        let db = ingested_wallet_db();
+        let passphrase = SecUtf8::from("passphrase");
+
+        let bytes = encode_wallet_db(&db, Some(&passphrase)).unwrap();
+
+        assert!(decode_wallet_db(&bytes, None).is_err());
+        assert_eq!(
+            decode_wallet_db(&bytes, Some(&SecUtf8::from("incorrect")))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DecryptionError
+        );
+        assert_eq!(decode_wallet_db(&bytes, Some(&passphrase)).unwrap(), db);
+    }
+
+    #[test]
+    fn encrypted_wallet_db_should_authenticate_header() {
+        let passphrase = SecUtf8::from("passphrase");
+        let bytes = encode_wallet_db(&ingested_wallet_db(), Some(&passphrase)).unwrap();
+        let body = &bytes[MAGIC.len() + 1..];
+        let (salt, rest) = body.split_at(SALT_SIZE);
+        let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+
+        // Ciphertext does not authenticate without the header
+        assert!(cipher(&passphrase, salt)
+            .decrypt(GenericArray::from_slice(nonce), ciphertext)
+            .is_err());
+        assert!(decrypt(body, &passphrase).is_ok());
+    }
+
+    #[test]
+    fn decode_wallet_db_should_reject_other_data() {
+        let err = decode_wallet_db(b"not a wallet", None).unwrap_err();
+
+        assert!(err.to_string().contains("Data is not a wallet database"));
+    }
+}
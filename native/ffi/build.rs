@@ -0,0 +1,21 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "generate-header")]
+    generate_header();
+}
+
+/// Regenerate the shipped header, only on `--features generate-header` so
+/// that regular builds leave the source tree untouched
+#[cfg(feature = "generate-header")]
+fn generate_header() {
+    let crate_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    cbindgen::generate(&crate_dir)
+        .expect("Unable to generate C header")
+        .write_to_file("include/cro_nodelib.h");
+}
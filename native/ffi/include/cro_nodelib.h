@@ -0,0 +1,318 @@
+#ifndef CRO_NODELIB_H
+#define CRO_NODELIB_H
+
+/* Generated by cbindgen from native/ffi, do not edit manually */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ * Status returned by every function of the library
+ */
+typedef enum {
+  CRO_STATUS_OK = 0,
+  /*
+   * Invalid argument, e.g. null pointer, invalid UTF-8 or invalid options
+   */
+  CRO_STATUS_INVALID_INPUT = 1,
+  /*
+   * Unable to decode bytes, e.g. a key or a raw transaction
+   */
+  CRO_STATUS_DESERIALIZATION_ERROR = 2,
+  /*
+   * Unable to encode a value
+   */
+  CRO_STATUS_SERIALIZATION_ERROR = 3,
+  /*
+   * Any other error, e.g. the Tendermint node is unreachable
+   */
+  CRO_STATUS_ERROR = 4,
+  /*
+   * Unexpected panic inside the library
+   */
+  CRO_STATUS_PANIC = 5,
+} CroStatus;
+
+/*
+ * Opaque secp256k1 key pair
+ */
+typedef struct CroKeyPair CroKeyPair;
+
+/*
+ * Opaque transfer transaction builder
+ */
+typedef struct CroTransferBuilder CroTransferBuilder;
+
+/*
+ * Bytes owned by the caller, released with `cro_buffer_free()`. Also used
+ * to pass lists of byte buffers to the library.
+ */
+typedef struct {
+  uint8_t *data;
+  uintptr_t len;
+} CroBuffer;
+
+/*
+ * Release a buffer returned by the library
+ */
+CroStatus cro_buffer_free(CroBuffer buffer);
+
+/*
+ * SCALE encoded unsigned deposit transaction of the JSON options
+ */
+CroStatus cro_deposit_build_raw(const char *options, CroBuffer *out);
+
+/*
+ * SCALE encoded `TxInWitness` of an input of the deposit transaction. The
+ * JSON previous output spent by the input may be null, and is verified to
+ * belong to the key pair when provided.
+ */
+CroStatus cro_deposit_sign_input(const uint8_t *raw_tx,
+                                 uintptr_t raw_tx_len,
+                                 const char *prev_output,
+                                 const CroKeyPair *key_pair,
+                                 CroBuffer *out);
+
+/*
+ * Finish the deposit transaction with the SCALE encoded `TxInWitness` of
+ * each of its inputs into the SCALE encoded broadcast-able `TxAux`
+ */
+CroStatus cro_deposit_to_tx_aux(const uint8_t *raw_tx,
+                                uintptr_t raw_tx_len,
+                                const CroBuffer *witnesses,
+                                uintptr_t witnesses_len,
+                                const char *tendermint_address,
+                                const char *features,
+                                CroBuffer *out);
+
+/*
+ * Whether the address is a staking address
+ */
+CroStatus cro_is_staking_address_valid(const char *address, bool *out);
+
+/*
+ * Whether the address is a transfer address of the named network
+ */
+CroStatus cro_is_transfer_address_valid(const char *address, const char *network, bool *out);
+
+/*
+ * Release the key pair
+ */
+CroStatus cro_key_pair_free(CroKeyPair *key_pair);
+
+/*
+ * Create a key pair from the 32 bytes of a private key
+ */
+CroStatus cro_key_pair_from_private_key(const uint8_t *private_key,
+                                        uintptr_t private_key_len,
+                                        CroKeyPair **out);
+
+/*
+ * Create a key pair from a new random private key
+ */
+CroStatus cro_key_pair_new(CroKeyPair **out);
+
+/*
+ * Bytes of the private key
+ */
+CroStatus cro_key_pair_private_key(const CroKeyPair *key_pair, CroBuffer *out);
+
+/*
+ * Bytes of the public key, in 33 bytes compressed or 65 bytes uncompressed
+ * form
+ */
+CroStatus cro_key_pair_public_key(const CroKeyPair *key_pair, bool compressed, CroBuffer *out);
+
+/*
+ * Schnorr signature of the 32 bytes message
+ */
+CroStatus cro_key_pair_schnorr_sign(const CroKeyPair *key_pair,
+                                    const uint8_t *message,
+                                    uintptr_t message_len,
+                                    CroBuffer *out);
+
+/*
+ * SCALE encoded `StakedStateOpWitness` of the 32 bytes transaction id, for
+ * transactions signed by a staking address
+ */
+CroStatus cro_key_pair_sign_staked_state_op(const CroKeyPair *key_pair,
+                                            const uint8_t *tx_id,
+                                            uintptr_t tx_id_len,
+                                            CroBuffer *out);
+
+/*
+ * Message of the error of the last call on the calling thread, or null when
+ * it succeeded. The message is owned by the library and valid until the
+ * next call on the same thread.
+ */
+const char *cro_last_error_message(void);
+
+/*
+ * SCALE encoded unsigned node join transaction of the JSON options
+ */
+CroStatus cro_node_join_build_raw(const char *options, CroBuffer *out);
+
+/*
+ * Sign the node join transaction with the key pair of the staking address
+ * into the SCALE encoded broadcast-able `TxAux`
+ */
+CroStatus cro_node_join_to_tx_aux(const uint8_t *raw_tx,
+                                  uintptr_t raw_tx_len,
+                                  const CroKeyPair *key_pair,
+                                  CroBuffer *out);
+
+/*
+ * Register a network from the JSON network definition, e.g.
+ * `{ "name": "Devnet", "chainHexId": "AB", "addressPrefix": "dcro", "bip44CoinType": 1 }`
+ */
+CroStatus cro_register_network(const char *network);
+
+/*
+ * Staking address of the public key
+ */
+CroStatus cro_staking_address_from_public_key(const uint8_t *public_key,
+                                              uintptr_t public_key_len,
+                                              char **out);
+
+/*
+ * Release a string returned by the library
+ */
+CroStatus cro_string_free(char *string);
+
+/*
+ * Transfer address of the public key on the named network
+ */
+CroStatus cro_transfer_address_from_public_key(const uint8_t *public_key,
+                                               uintptr_t public_key_len,
+                                               const char *network,
+                                               char **out);
+
+/*
+ * Add the SCALE encoded `TxInWitness` to a particular input
+ */
+CroStatus cro_transfer_builder_add_witness(CroTransferBuilder *builder,
+                                           uintptr_t input_index,
+                                           const uint8_t *witness,
+                                           uintptr_t witness_len);
+
+/*
+ * Estimated fee of the builder in base unit
+ */
+CroStatus cro_transfer_builder_estimate_fee(const CroTransferBuilder *builder, uint64_t *out);
+
+/*
+ * Release the builder
+ */
+CroStatus cro_transfer_builder_free(CroTransferBuilder *builder);
+
+/*
+ * Restore a transfer transaction builder from its incomplete bytes and the
+ * JSON fee config it is built with
+ */
+CroStatus cro_transfer_builder_from_incomplete(const uint8_t *incomplete,
+                                               uintptr_t incomplete_len,
+                                               const char *fee_config,
+                                               CroTransferBuilder **out);
+
+/*
+ * Whether all the inputs of the builder have a witness
+ */
+CroStatus cro_transfer_builder_is_completed(const CroTransferBuilder *builder, bool *out);
+
+/*
+ * Create a transfer transaction builder without witnesses from the JSON
+ * builder options
+ */
+CroStatus cro_transfer_builder_new(const char *options, CroTransferBuilder **out);
+
+/*
+ * Sign a particular input with the key pair
+ */
+CroStatus cro_transfer_builder_sign_input(CroTransferBuilder *builder,
+                                          uintptr_t input_index,
+                                          const CroKeyPair *key_pair);
+
+/*
+ * Incomplete bytes of the builder, to be passed to the other signers
+ */
+CroStatus cro_transfer_builder_to_incomplete(const CroTransferBuilder *builder, CroBuffer *out);
+
+/*
+ * Finish the completed builder into the SCALE encoded broadcast-able
+ * `TxAux`, obfuscated with the features, e.g. `"MockObfuscation"`
+ */
+CroStatus cro_transfer_builder_to_tx_aux(const CroTransferBuilder *builder,
+                                         const char *tendermint_address,
+                                         const char *features,
+                                         CroBuffer *out);
+
+/*
+ * 32 bytes transaction id of the builder
+ */
+CroStatus cro_transfer_builder_tx_id(const CroTransferBuilder *builder, CroBuffer *out);
+
+/*
+ * Verify the builder is a valid transaction to be broadcasted
+ */
+CroStatus cro_transfer_builder_verify(const CroTransferBuilder *builder);
+
+/*
+ * 32 bytes transaction id of the SCALE encoded `TxAux`
+ */
+CroStatus cro_tx_aux_tx_id(const uint8_t *tx_aux, uintptr_t tx_aux_len, CroBuffer *out);
+
+/*
+ * SCALE encoded unsigned unbond transaction of the JSON options
+ */
+CroStatus cro_unbond_build_raw(const char *options, CroBuffer *out);
+
+/*
+ * Sign the unbond transaction with the key pair of the staking address into
+ * the SCALE encoded broadcast-able `TxAux`
+ */
+CroStatus cro_unbond_to_tx_aux(const uint8_t *raw_tx,
+                               uintptr_t raw_tx_len,
+                               const CroKeyPair *key_pair,
+                               CroBuffer *out);
+
+/*
+ * SCALE encoded unsigned unjail transaction of the JSON options
+ */
+CroStatus cro_unjail_build_raw(const char *options, CroBuffer *out);
+
+/*
+ * Sign the unjail transaction with the key pair of the staking address into
+ * the SCALE encoded broadcast-able `TxAux`
+ */
+CroStatus cro_unjail_to_tx_aux(const uint8_t *raw_tx,
+                               uintptr_t raw_tx_len,
+                               const CroKeyPair *key_pair,
+                               CroBuffer *out);
+
+/*
+ * SCALE encoded unsigned withdraw unbonded transaction of the JSON options
+ */
+CroStatus cro_withdraw_unbonded_build_raw(const char *options, CroBuffer *out);
+
+/*
+ * Estimated fee in base unit of the withdraw unbonded transaction with the
+ * JSON fee config
+ */
+CroStatus cro_withdraw_unbonded_estimate_fee(const uint8_t *raw_tx,
+                                             uintptr_t raw_tx_len,
+                                             const char *fee_config,
+                                             uint64_t *out);
+
+/*
+ * Sign the withdraw unbonded transaction with the key pair of the staking
+ * address into the SCALE encoded broadcast-able `TxAux`
+ */
+CroStatus cro_withdraw_unbonded_to_tx_aux(const uint8_t *raw_tx,
+                                          uintptr_t raw_tx_len,
+                                          const CroKeyPair *key_pair,
+                                          const char *tendermint_address,
+                                          const char *features,
+                                          CroBuffer *out);
+#endif /* CRO_NODELIB_H */
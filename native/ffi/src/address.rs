@@ -0,0 +1,148 @@
+//! Transfer and staking addresses and the network registry
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use chain_core::state::account::StakedStateAddress;
+use client_common::{ErrorKind, PublicKey, Result, ResultExt};
+use cro_nodelib_core::address::{
+    staking_address_from_public_key, transfer_address_from_public_key,
+};
+use cro_nodelib_core::network::{insert_network, network_by_name, NetworkDefinition};
+
+use crate::buffer::{bytes_argument, options_argument, str_argument, write_output, write_string};
+use crate::error::{ffi_call, CroStatus};
+
+/// Transfer address of the public key on the named network
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_address_from_public_key(
+    public_key: *const u8,
+    public_key_len: usize,
+    network: *const c_char,
+    out: *mut *mut c_char,
+) -> CroStatus {
+    ffi_call(|| {
+        let public_key = public_key_argument(public_key, public_key_len)?;
+        let network = network_by_name(str_argument(network, "network")?)?;
+
+        let address = transfer_address_from_public_key(&network, &public_key)?;
+
+        write_string(out, address)
+    })
+}
+
+/// Staking address of the public key
+#[no_mangle]
+pub unsafe extern "C" fn cro_staking_address_from_public_key(
+    public_key: *const u8,
+    public_key_len: usize,
+    out: *mut *mut c_char,
+) -> CroStatus {
+    ffi_call(|| {
+        let public_key = public_key_argument(public_key, public_key_len)?;
+
+        write_string(
+            out,
+            staking_address_from_public_key(&public_key).to_string(),
+        )
+    })
+}
+
+/// Whether the address is a transfer address of the named network
+#[no_mangle]
+pub unsafe extern "C" fn cro_is_transfer_address_valid(
+    address: *const c_char,
+    network: *const c_char,
+    out: *mut bool,
+) -> CroStatus {
+    ffi_call(|| {
+        let address = str_argument(address, "address")?;
+        let network = network_by_name(str_argument(network, "network")?)?;
+
+        write_output(out, network.decode_transfer_address(address).is_ok())
+    })
+}
+
+/// Whether the address is a staking address
+#[no_mangle]
+pub unsafe extern "C" fn cro_is_staking_address_valid(
+    address: *const c_char,
+    out: *mut bool,
+) -> CroStatus {
+    ffi_call(|| {
+        let address = str_argument(address, "address")?;
+
+        write_output(out, StakedStateAddress::from_str(address).is_ok())
+    })
+}
+
+/// Register a network from the JSON network definition, e.g.
+/// `{ "name": "Devnet", "chainHexId": "AB", "addressPrefix": "dcro", "bip44CoinType": 1 }`
+#[no_mangle]
+pub unsafe extern "C" fn cro_register_network(network: *const c_char) -> CroStatus {
+    ffi_call(|| {
+        let network: NetworkDefinition = options_argument(network, "network")?;
+
+        insert_network(network)
+    })
+}
+
+unsafe fn public_key_argument(public_key: *const u8, len: usize) -> Result<PublicKey> {
+    let public_key = bytes_argument(public_key, len, "public_key")?;
+
+    PublicKey::deserialize_from(public_key).chain(|| {
+        (
+            ErrorKind::DeserializationError,
+            "Unable to deserialize public key",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    use crate::buffer::tests::{c_string, last_error_message, take_string};
+
+    #[test]
+    fn staking_address_should_be_derived_from_public_key() {
+        let public_key =
+            hex::decode("031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f")
+                .unwrap();
+        let mut address = ptr::null_mut();
+
+        let status = unsafe {
+            cro_staking_address_from_public_key(public_key.as_ptr(), public_key.len(), &mut address)
+        };
+        assert_eq!(status, CroStatus::Ok);
+
+        let address = c_string(&take_string(address));
+        let mut is_valid = false;
+        let status = unsafe { cro_is_staking_address_valid(address.as_ptr(), &mut is_valid) };
+        assert_eq!(status, CroStatus::Ok);
+        assert!(is_valid);
+    }
+
+    #[test]
+    fn transfer_address_should_reject_unknown_network() {
+        let public_key =
+            hex::decode("031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f")
+                .unwrap();
+        let network = c_string("Unknown");
+        let mut address = ptr::null_mut();
+
+        let status = unsafe {
+            cro_transfer_address_from_public_key(
+                public_key.as_ptr(),
+                public_key.len(),
+                network.as_ptr(),
+                &mut address,
+            )
+        };
+
+        assert_eq!(status, CroStatus::InvalidInput);
+        assert_eq!(last_error_message(), "Unrecognized network Unknown");
+        assert!(address.is_null());
+    }
+}
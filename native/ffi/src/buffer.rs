@@ -0,0 +1,308 @@
+//! Byte buffers, strings and options passed across the C boundary
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::{ptr, slice};
+
+use chain_core::common::HASH_SIZE_256;
+use chain_core::tx::data::TxId;
+use client_common::{Error, ErrorKind, Result, ResultExt};
+use cro_nodelib_core::codec::{decode_transaction, decode_witness};
+use cro_nodelib_core::schema::{from_json_value, SchemaError};
+use parity_scale_codec::{Decode, Encode};
+use serde::de::DeserializeOwned;
+
+use crate::error::{ffi_call, CroStatus};
+
+/// Bytes owned by the caller, released with `cro_buffer_free()`. Also used
+/// to pass lists of byte buffers to the library.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CroBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl CroBuffer {
+    pub(crate) fn from_vec(bytes: Vec<u8>) -> CroBuffer {
+        let bytes = Box::leak(bytes.into_boxed_slice());
+
+        CroBuffer {
+            data: bytes.as_mut_ptr(),
+            len: bytes.len(),
+        }
+    }
+}
+
+/// Release a buffer returned by the library
+#[no_mangle]
+pub unsafe extern "C" fn cro_buffer_free(buffer: CroBuffer) -> CroStatus {
+    ffi_call(|| {
+        if !buffer.data.is_null() {
+            drop(Box::from_raw(slice::from_raw_parts_mut(
+                buffer.data,
+                buffer.len,
+            )));
+        }
+
+        Ok(())
+    })
+}
+
+/// Release a string returned by the library
+#[no_mangle]
+pub unsafe extern "C" fn cro_string_free(string: *mut c_char) -> CroStatus {
+    ffi_call(|| {
+        if !string.is_null() {
+            drop(CString::from_raw(string));
+        }
+
+        Ok(())
+    })
+}
+
+/// Bytes of a pointer and length argument
+pub(crate) unsafe fn bytes_argument<'a>(
+    data: *const u8,
+    len: usize,
+    name: &str,
+) -> Result<&'a [u8]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+
+    non_null(data, name)?;
+
+    Ok(slice::from_raw_parts(data, len))
+}
+
+/// Strictly decode the SCALE encoded transaction bytes of a pointer and
+/// length argument
+pub(crate) unsafe fn decode_argument<T>(data: *const u8, len: usize, name: &str) -> Result<T>
+where
+    T: Decode + Encode,
+{
+    decode_transaction(bytes_argument(data, len, name)?, name)
+}
+
+/// Strictly decode the SCALE encoded witness bytes of a pointer and length
+/// argument
+pub(crate) unsafe fn witness_argument<T>(data: *const u8, len: usize, name: &str) -> Result<T>
+where
+    T: Decode + Encode,
+{
+    decode_witness(bytes_argument(data, len, name)?, name)
+}
+
+/// Transaction id of a 32 bytes pointer and length argument
+pub(crate) unsafe fn tx_id_argument(tx_id: *const u8, len: usize) -> Result<TxId> {
+    let tx_id = bytes_argument(tx_id, len, "tx_id")?;
+    if tx_id.len() != HASH_SIZE_256 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Transaction id should be {} bytes, got {} bytes",
+                HASH_SIZE_256,
+                tx_id.len()
+            ),
+        ));
+    }
+
+    let mut id: TxId = [0; HASH_SIZE_256];
+    id.copy_from_slice(tx_id);
+
+    Ok(id)
+}
+
+/// UTF-8 string of a NUL-terminated string argument
+pub(crate) unsafe fn str_argument<'a>(string: *const c_char, name: &str) -> Result<&'a str> {
+    non_null(string, name)?;
+
+    CStr::from_ptr(string).to_str().chain(|| {
+        (
+            ErrorKind::InvalidInput,
+            format!("Invalid UTF-8 of {}", name),
+        )
+    })
+}
+
+/// Deserialize the options JSON string argument into `T`
+pub(crate) unsafe fn options_argument<T>(options: *const c_char, name: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let options = str_argument(options, name)?;
+    let options = serde_json::from_str(options).chain(|| {
+        (
+            ErrorKind::InvalidInput,
+            format!("Unable to parse {} JSON", name),
+        )
+    })?;
+
+    from_json_value(options).map_err(schema_error)
+}
+
+/// Deserialize the optional options JSON string argument into `T`, which is
+/// `None` when the pointer is null
+pub(crate) unsafe fn optional_options_argument<T>(
+    options: *const c_char,
+    name: &str,
+) -> Result<Option<T>>
+where
+    T: DeserializeOwned,
+{
+    if options.is_null() {
+        Ok(None)
+    } else {
+        options_argument(options, name).map(Some)
+    }
+}
+
+#[inline]
+pub(crate) fn schema_error(err: SchemaError) -> Error {
+    Error::new(ErrorKind::InvalidInput, err.to_string())
+}
+
+/// Handle of a pointer argument
+pub(crate) unsafe fn handle_argument<'a, T>(handle: *const T, name: &str) -> Result<&'a T> {
+    non_null(handle, name)?;
+
+    Ok(&*handle)
+}
+
+/// Mutable handle of a pointer argument
+pub(crate) unsafe fn handle_argument_mut<'a, T>(handle: *mut T, name: &str) -> Result<&'a mut T> {
+    non_null(handle, name)?;
+
+    Ok(&mut *handle)
+}
+
+/// Write the result to the `out` pointer argument
+pub(crate) unsafe fn write_output<T>(out: *mut T, value: T) -> Result<()> {
+    non_null(out, "out")?;
+    ptr::write(out, value);
+
+    Ok(())
+}
+
+/// Write the new handle of the value to the `out` pointer argument
+pub(crate) unsafe fn write_handle<T>(out: *mut *mut T, value: T) -> Result<()> {
+    non_null(out, "out")?;
+
+    write_output(out, Box::into_raw(Box::new(value)))
+}
+
+/// Write the bytes to the `out` buffer argument
+pub(crate) unsafe fn write_bytes(out: *mut CroBuffer, bytes: Vec<u8>) -> Result<()> {
+    non_null(out, "out")?;
+
+    write_output(out, CroBuffer::from_vec(bytes))
+}
+
+/// Write the string to the `out` string argument
+pub(crate) unsafe fn write_string(out: *mut *mut c_char, string: String) -> Result<()> {
+    non_null(out, "out")?;
+    let string = CString::new(string)
+        .chain(|| (ErrorKind::SerializationError, "Unable to encode string"))?;
+
+    write_output(out, string.into_raw())
+}
+
+fn non_null<T>(pointer: *const T, name: &str) -> Result<()> {
+    if pointer.is_null() {
+        Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Null pointer of {}", name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use crate::error::cro_last_error_message;
+
+    pub fn last_error_message() -> String {
+        unsafe { CStr::from_ptr(cro_last_error_message()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn empty_buffer() -> CroBuffer {
+        CroBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    pub fn c_string(value: &str) -> CString {
+        CString::new(value).unwrap()
+    }
+
+    /// Take the bytes of a buffer returned by the library and release it
+    pub fn take_bytes(buffer: CroBuffer) -> Vec<u8> {
+        let bytes = unsafe { slice::from_raw_parts(buffer.data, buffer.len) }.to_vec();
+        assert_eq!(unsafe { cro_buffer_free(buffer) }, CroStatus::Ok);
+
+        bytes
+    }
+
+    /// Take the string returned by the library and release it
+    pub fn take_string(string: *mut c_char) -> String {
+        let value = unsafe { CStr::from_ptr(string) }
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(unsafe { cro_string_free(string) }, CroStatus::Ok);
+
+        value
+    }
+
+    #[test]
+    fn buffer_should_round_trip_bytes() {
+        let buffer = CroBuffer::from_vec(vec![1, 2, 3]);
+
+        assert_eq!(take_bytes(buffer), vec![1, 2, 3]);
+        assert_eq!(
+            take_bytes(CroBuffer::from_vec(Vec::new())),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[test]
+    fn bytes_argument_should_reject_null_pointer_with_length() {
+        let empty = unsafe { bytes_argument(ptr::null(), 0, "bytes") }.unwrap();
+        assert!(empty.is_empty());
+
+        let err = unsafe { bytes_argument(ptr::null(), 1, "bytes") }.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "Null pointer of bytes");
+    }
+
+    #[test]
+    fn options_argument_should_report_path_of_invalid_field() {
+        let options = c_string(r#"{ "name": 1 }"#);
+
+        let err = unsafe {
+            options_argument::<cro_nodelib_core::network::NetworkDefinition>(
+                options.as_ptr(),
+                "network",
+            )
+        }
+        .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("`name`"), "{}", err);
+
+        let options = c_string("{");
+        let err = unsafe {
+            options_argument::<cro_nodelib_core::network::NetworkDefinition>(
+                options.as_ptr(),
+                "network",
+            )
+        }
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Unable to parse network JSON"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Node join and unjail transactions
+use std::os::raw::c_char;
+
+use chain_core::state::account::UnjailTx;
+use chain_core::state::validator::NodeJoinRequestTx;
+use cro_nodelib_core::council_node_transaction::node_join::{
+    sign_node_join_transaction, BuildNodeJoinTransactionOptions,
+};
+use cro_nodelib_core::council_node_transaction::unjail::{
+    sign_unjail_transaction, UnjailTxOptions,
+};
+use parity_scale_codec::Encode;
+
+use crate::buffer::{
+    decode_argument, handle_argument, options_argument, schema_error, write_bytes, CroBuffer,
+};
+use crate::error::{ffi_call, CroStatus};
+use crate::key_pair::CroKeyPair;
+
+/// SCALE encoded unsigned node join transaction of the JSON options
+#[no_mangle]
+pub unsafe extern "C" fn cro_node_join_build_raw(
+    options: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let options: BuildNodeJoinTransactionOptions = options_argument(options, "options")?;
+        let council_node = options.council_node().map_err(schema_error)?;
+
+        write_bytes(out, options.to_tx(council_node).encode())
+    })
+}
+
+/// Sign the node join transaction with the key pair of the staking address
+/// into the SCALE encoded broadcast-able `TxAux`
+#[no_mangle]
+pub unsafe extern "C" fn cro_node_join_to_tx_aux(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    key_pair: *const CroKeyPair,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let node_join_request_tx: NodeJoinRequestTx =
+            decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let signer = handle_argument(key_pair, "key_pair")?.signer()?;
+
+        let tx_aux = sign_node_join_transaction(node_join_request_tx, &signer)?;
+
+        write_bytes(out, tx_aux.encode())
+    })
+}
+
+/// SCALE encoded unsigned unjail transaction of the JSON options
+#[no_mangle]
+pub unsafe extern "C" fn cro_unjail_build_raw(
+    options: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let options: UnjailTxOptions = options_argument(options, "options")?;
+
+        write_bytes(out, options.to_tx().encode())
+    })
+}
+
+/// Sign the unjail transaction with the key pair of the staking address into
+/// the SCALE encoded broadcast-able `TxAux`
+#[no_mangle]
+pub unsafe extern "C" fn cro_unjail_to_tx_aux(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    key_pair: *const CroKeyPair,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let unjail_tx: UnjailTx = decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let signer = handle_argument(key_pair, "key_pair")?.signer()?;
+
+        let tx_aux = sign_unjail_transaction(unjail_tx, &signer)?;
+
+        write_bytes(out, tx_aux.encode())
+    })
+}
@@ -0,0 +1,115 @@
+//! Status codes and last error message
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use client_common::{ErrorKind, Result};
+
+/// Status returned by every function of the library
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CroStatus {
+    Ok = 0,
+    /// Invalid argument, e.g. null pointer, invalid UTF-8 or invalid options
+    InvalidInput = 1,
+    /// Unable to decode bytes, e.g. a key or a raw transaction
+    DeserializationError = 2,
+    /// Unable to encode a value
+    SerializationError = 3,
+    /// Any other error, e.g. the Tendermint node is unreachable
+    Error = 4,
+    /// Unexpected panic inside the library
+    Panic = 5,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Run the body of an exported function and convert its error to a status.
+/// Panics are caught so that they never unwind across the C boundary.
+pub(crate) fn ffi_call<F>(body: F) -> CroStatus
+where
+    F: FnOnce() -> Result<()>,
+{
+    let (status, message) = match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => (CroStatus::Ok, None),
+        Ok(Err(err)) => (status_of(err.kind()), Some(err.to_string())),
+        Err(_) => (CroStatus::Panic, Some("Unexpected panic".to_owned())),
+    };
+
+    LAST_ERROR.with(|last_error| {
+        *last_error.borrow_mut() =
+            message.map(|message| CString::new(message.replace('\0', " ")).unwrap_or_default())
+    });
+
+    status
+}
+
+fn status_of(kind: ErrorKind) -> CroStatus {
+    match kind {
+        ErrorKind::InvalidInput => CroStatus::InvalidInput,
+        ErrorKind::DeserializationError => CroStatus::DeserializationError,
+        ErrorKind::SerializationError => CroStatus::SerializationError,
+        _ => CroStatus::Error,
+    }
+}
+
+/// Message of the error of the last call on the calling thread, or null when
+/// it succeeded. The message is owned by the library and valid until the
+/// next call on the same thread.
+#[no_mangle]
+pub extern "C" fn cro_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last_error| match *last_error.borrow() {
+        Some(ref message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+
+    use client_common::Error;
+
+    fn last_error_message() -> Option<String> {
+        let message = cro_last_error_message();
+        if message.is_null() {
+            None
+        } else {
+            Some(
+                unsafe { CStr::from_ptr(message) }
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        }
+    }
+
+    #[test]
+    fn ffi_call_should_record_error_message_of_failed_call() {
+        let status = ffi_call(|| {
+            Err(Error::new(
+                ErrorKind::DeserializationError,
+                "Unable to decode",
+            ))
+        });
+
+        assert_eq!(status, CroStatus::DeserializationError);
+        assert_eq!(last_error_message().unwrap(), "Unable to decode");
+
+        assert_eq!(ffi_call(|| Ok(())), CroStatus::Ok);
+        assert!(last_error_message().is_none());
+    }
+
+    #[test]
+    fn ffi_call_should_catch_panic() {
+        let status = ffi_call(|| panic!("boom"));
+
+        assert_eq!(status, CroStatus::Panic);
+        assert_eq!(last_error_message().unwrap(), "Unexpected panic");
+    }
+}
@@ -0,0 +1,228 @@
+//! Key pair handle used to sign transactions
+use client_common::{ErrorKind, PrivateKey, PublicKey, Result, ResultExt};
+use cro_nodelib_core::signer::KeyPairSigner;
+use cro_nodelib_core::staking_transaction::staked_state_op_witness;
+use parity_scale_codec::Encode;
+
+use crate::buffer::{
+    bytes_argument, handle_argument, tx_id_argument, write_bytes, write_handle, CroBuffer,
+};
+use crate::error::{ffi_call, CroStatus};
+
+/// Opaque secp256k1 key pair
+pub struct CroKeyPair {
+    private_key: PrivateKey,
+    public_key: PublicKey,
+}
+
+impl CroKeyPair {
+    fn new(private_key: PrivateKey) -> CroKeyPair {
+        let public_key = PublicKey::from(&private_key);
+
+        CroKeyPair {
+            private_key,
+            public_key,
+        }
+    }
+
+    pub(crate) fn signer(&self) -> Result<KeyPairSigner> {
+        let private_key = PrivateKey::deserialize_from(&self.private_key.serialize())?;
+
+        KeyPairSigner::new(private_key, self.public_key.clone())
+    }
+
+    /// Owned copy of the key pair, for core functions consuming the keys
+    pub(crate) fn keys(&self) -> Result<(PrivateKey, PublicKey)> {
+        let private_key = PrivateKey::deserialize_from(&self.private_key.serialize())?;
+
+        Ok((private_key, self.public_key.clone()))
+    }
+}
+
+/// Create a key pair from a new random private key
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_new(out: *mut *mut CroKeyPair) -> CroStatus {
+    ffi_call(|| {
+        let private_key = PrivateKey::new()
+            .chain(|| (ErrorKind::InvalidInput, "Unable to create new private key"))?;
+
+        write_handle(out, CroKeyPair::new(private_key))
+    })
+}
+
+/// Create a key pair from the 32 bytes of a private key
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_from_private_key(
+    private_key: *const u8,
+    private_key_len: usize,
+    out: *mut *mut CroKeyPair,
+) -> CroStatus {
+    ffi_call(|| {
+        let private_key = bytes_argument(private_key, private_key_len, "private_key")?;
+        let private_key = PrivateKey::deserialize_from(private_key).chain(|| {
+            (
+                ErrorKind::DeserializationError,
+                "Unable to deserialize private key",
+            )
+        })?;
+
+        write_handle(out, CroKeyPair::new(private_key))
+    })
+}
+
+/// Bytes of the private key
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_private_key(
+    key_pair: *const CroKeyPair,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let key_pair = handle_argument(key_pair, "key_pair")?;
+
+        write_bytes(out, key_pair.private_key.serialize())
+    })
+}
+
+/// Bytes of the public key, in 33 bytes compressed or 65 bytes uncompressed
+/// form
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_public_key(
+    key_pair: *const CroKeyPair,
+    compressed: bool,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let key_pair = handle_argument(key_pair, "key_pair")?;
+
+        let public_key = if compressed {
+            key_pair.public_key.serialize_compressed()
+        } else {
+            key_pair.public_key.serialize()
+        };
+
+        write_bytes(out, public_key)
+    })
+}
+
+/// Schnorr signature of the 32 bytes message
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_schnorr_sign(
+    key_pair: *const CroKeyPair,
+    message: *const u8,
+    message_len: usize,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let key_pair = handle_argument(key_pair, "key_pair")?;
+        let message = bytes_argument(message, message_len, "message")?;
+
+        let signature = key_pair.signer()?.schnorr_sign(message)?;
+
+        write_bytes(out, signature.serialize_default().to_vec())
+    })
+}
+
+/// SCALE encoded `StakedStateOpWitness` of the 32 bytes transaction id, for
+/// transactions signed by a staking address
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_sign_staked_state_op(
+    key_pair: *const CroKeyPair,
+    tx_id: *const u8,
+    tx_id_len: usize,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let key_pair = handle_argument(key_pair, "key_pair")?;
+        let tx_id = tx_id_argument(tx_id, tx_id_len)?;
+
+        let witness = staked_state_op_witness(&key_pair.signer()?, &tx_id)?;
+
+        write_bytes(out, witness.encode())
+    })
+}
+
+/// Release the key pair
+#[no_mangle]
+pub unsafe extern "C" fn cro_key_pair_free(key_pair: *mut CroKeyPair) -> CroStatus {
+    ffi_call(|| {
+        if !key_pair.is_null() {
+            drop(Box::from_raw(key_pair));
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    use crate::buffer::tests::{empty_buffer, last_error_message, take_bytes};
+
+    pub fn key_pair_of(secret: u8) -> *mut CroKeyPair {
+        let mut key_pair = ptr::null_mut();
+        let status =
+            unsafe { cro_key_pair_from_private_key([secret; 32].as_ptr(), 32, &mut key_pair) };
+        assert_eq!(status, CroStatus::Ok);
+
+        key_pair
+    }
+
+    pub fn public_key_of(key_pair: *const CroKeyPair) -> PublicKey {
+        let mut public_key = empty_buffer();
+        let status = unsafe { cro_key_pair_public_key(key_pair, true, &mut public_key) };
+        assert_eq!(status, CroStatus::Ok);
+
+        PublicKey::deserialize_from(&take_bytes(public_key)).unwrap()
+    }
+
+    #[test]
+    fn key_pair_should_derive_public_key_from_private_key() {
+        let key_pair = key_pair_of(1);
+        let mut public_key = empty_buffer();
+
+        let status = unsafe { cro_key_pair_public_key(key_pair, true, &mut public_key) };
+        assert_eq!(status, CroStatus::Ok);
+        assert_eq!(
+            hex::encode(take_bytes(public_key)),
+            "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+        );
+
+        assert_eq!(unsafe { cro_key_pair_free(key_pair) }, CroStatus::Ok);
+    }
+
+    #[test]
+    fn key_pair_should_reject_invalid_private_key() {
+        let mut key_pair = ptr::null_mut();
+
+        let status = unsafe { cro_key_pair_from_private_key([0; 32].as_ptr(), 32, &mut key_pair) };
+        assert_eq!(status, CroStatus::DeserializationError);
+        assert!(last_error_message().starts_with("Unable to deserialize private key"));
+        assert!(key_pair.is_null());
+
+        let status = unsafe { cro_key_pair_from_private_key(ptr::null(), 32, &mut key_pair) };
+        assert_eq!(status, CroStatus::InvalidInput);
+        assert_eq!(last_error_message(), "Null pointer of private_key");
+    }
+
+    #[test]
+    fn sign_staked_state_op_should_reject_invalid_tx_id() {
+        let key_pair = key_pair_of(1);
+        let mut witness = empty_buffer();
+
+        let status = unsafe {
+            cro_key_pair_sign_staked_state_op(key_pair, [0; 31].as_ptr(), 31, &mut witness)
+        };
+        assert_eq!(status, CroStatus::InvalidInput);
+
+        let status = unsafe {
+            cro_key_pair_sign_staked_state_op(key_pair, [0; 32].as_ptr(), 32, &mut witness)
+        };
+        assert_eq!(status, CroStatus::Ok);
+        assert!(!take_bytes(witness).is_empty());
+
+        assert_eq!(unsafe { cro_key_pair_free(key_pair) }, CroStatus::Ok);
+    }
+}
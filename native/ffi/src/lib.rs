@@ -0,0 +1,31 @@
+//! Stable C ABI of the node library core for non-Node consumers, e.g.
+//! mobile apps and Python bindings. The header `include/cro_nodelib.h` is
+//! regenerated with `cargo build --features generate-header`.
+//!
+//! Conventions:
+//! - Every function returns a `CroStatus`. On failure the message of the
+//!   error is available from `cro_last_error_message()` on the same thread.
+//! - Results are written to the `out` pointer of the function, and only on
+//!   success.
+//! - Objects are opaque handles released with their `*_free` function.
+//! - Byte buffers are passed in as pointer and length. Buffers and strings
+//!   returned by the library are owned by the caller and released with
+//!   `cro_buffer_free()` and `cro_string_free()`.
+//! - Options are NUL-terminated JSON strings in the same shape as the
+//!   options objects of the JS library, with buffers as hex strings.
+//!
+//! # Safety
+//!
+//! Pointers must be null or valid for the given length, handles must come
+//! from this library and not be used after they are freed. Handles are not
+//! synchronized and should not be used from several threads at once.
+#![allow(clippy::missing_safety_doc)]
+
+pub mod address;
+pub mod buffer;
+pub mod council_node_transaction;
+pub mod error;
+pub mod key_pair;
+pub mod staking_transaction;
+pub mod transfer_transaction;
+pub mod tx_aux;
@@ -0,0 +1,364 @@
+//! Deposit, unbond and withdraw unbonded transactions
+use std::os::raw::c_char;
+use std::slice;
+
+use chain_core::state::account::{DepositBondTx, UnbondTx, WithdrawUnbondedTx};
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::witness::TxInWitness;
+use chain_core::tx::TransactionId;
+use client_common::{Result, SignedTransaction};
+use cro_nodelib_core::schema::types::{network_of_chain_hex_id, FeeConfigOptions, OutputOptions};
+use cro_nodelib_core::staking_transaction::deposit::{
+    sign_deposit_input, BuildDepositTransactionOptions,
+};
+use cro_nodelib_core::staking_transaction::staked_state_op_witness;
+use cro_nodelib_core::staking_transaction::unbond::{
+    sign_unbond_transaction, BuildUnbondTransactionOptions,
+};
+use cro_nodelib_core::staking_transaction::withdraw_unbonded::{
+    estimate_withdraw_unbonded_fee, BuildWithdrawUnbondedTransactionOptions,
+};
+use cro_nodelib_core::tx_aux::signed_transaction_to_tx_aux;
+use parity_scale_codec::Encode;
+
+use crate::buffer::{
+    decode_argument, handle_argument, optional_options_argument, options_argument, schema_error,
+    witness_argument, write_bytes, write_output, CroBuffer,
+};
+use crate::error::{ffi_call, CroStatus};
+use crate::key_pair::CroKeyPair;
+use crate::tx_aux::features_argument;
+
+/// SCALE encoded unsigned deposit transaction of the JSON options
+#[no_mangle]
+pub unsafe extern "C" fn cro_deposit_build_raw(
+    options: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let options: BuildDepositTransactionOptions = options_argument(options, "options")?;
+        options.spent_outputs().map_err(schema_error)?;
+
+        write_bytes(out, options.to_tx().encode())
+    })
+}
+
+/// SCALE encoded `TxInWitness` of an input of the deposit transaction. The
+/// JSON previous output spent by the input may be null, and is verified to
+/// belong to the key pair when provided.
+#[no_mangle]
+pub unsafe extern "C" fn cro_deposit_sign_input(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    prev_output: *const c_char,
+    key_pair: *const CroKeyPair,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let deposit_bond_tx: DepositBondTx = decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let prev_output = prev_output_argument(prev_output, &deposit_bond_tx)?;
+        let (private_key, public_key) = handle_argument(key_pair, "key_pair")?.keys()?;
+
+        let witness = sign_deposit_input(
+            &deposit_bond_tx,
+            prev_output.as_ref(),
+            private_key,
+            public_key,
+        )?;
+
+        write_bytes(out, witness.encode())
+    })
+}
+
+/// Finish the deposit transaction with the SCALE encoded `TxInWitness` of
+/// each of its inputs into the SCALE encoded broadcast-able `TxAux`
+#[no_mangle]
+pub unsafe extern "C" fn cro_deposit_to_tx_aux(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    witnesses: *const CroBuffer,
+    witnesses_len: usize,
+    tendermint_address: *const c_char,
+    features: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let deposit_bond_tx: DepositBondTx = decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let witnesses = witnesses_argument(witnesses, witnesses_len)?;
+        let (tendermint_address, features) = features_argument(tendermint_address, features)?;
+
+        let signed_transaction =
+            SignedTransaction::DepositStakeTransaction(deposit_bond_tx, witnesses.into());
+        let tx_aux =
+            signed_transaction_to_tx_aux(signed_transaction, tendermint_address, features)?;
+
+        write_bytes(out, tx_aux.encode())
+    })
+}
+
+/// SCALE encoded unsigned unbond transaction of the JSON options
+#[no_mangle]
+pub unsafe extern "C" fn cro_unbond_build_raw(
+    options: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let options: BuildUnbondTransactionOptions = options_argument(options, "options")?;
+        let amount = options.unbond_amount().map_err(schema_error)?;
+        options.unbonded_from().map_err(schema_error)?;
+
+        write_bytes(out, options.to_tx(amount).encode())
+    })
+}
+
+/// Sign the unbond transaction with the key pair of the staking address into
+/// the SCALE encoded broadcast-able `TxAux`
+#[no_mangle]
+pub unsafe extern "C" fn cro_unbond_to_tx_aux(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    key_pair: *const CroKeyPair,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let unbond_tx: UnbondTx = decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let signer = handle_argument(key_pair, "key_pair")?.signer()?;
+
+        let tx_aux = sign_unbond_transaction(unbond_tx, &signer)?;
+
+        write_bytes(out, tx_aux.encode())
+    })
+}
+
+/// SCALE encoded unsigned withdraw unbonded transaction of the JSON options
+#[no_mangle]
+pub unsafe extern "C" fn cro_withdraw_unbonded_build_raw(
+    options: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let options: BuildWithdrawUnbondedTransactionOptions =
+            options_argument(options, "options")?;
+        let tx = options.to_tx().map_err(schema_error)?;
+
+        write_bytes(out, tx.encode())
+    })
+}
+
+/// Estimated fee in base unit of the withdraw unbonded transaction with the
+/// JSON fee config
+#[no_mangle]
+pub unsafe extern "C" fn cro_withdraw_unbonded_estimate_fee(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    fee_config: *const c_char,
+    out: *mut u64,
+) -> CroStatus {
+    ffi_call(|| {
+        let withdraw_unbonded_tx: WithdrawUnbondedTx =
+            decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let fee_config: FeeConfigOptions = options_argument(fee_config, "fee_config")?;
+
+        let estimated_fee =
+            estimate_withdraw_unbonded_fee(withdraw_unbonded_tx, &fee_config.to_fee_algorithm())?;
+
+        write_output(out, u64::from(estimated_fee))
+    })
+}
+
+/// Sign the withdraw unbonded transaction with the key pair of the staking
+/// address into the SCALE encoded broadcast-able `TxAux`
+#[no_mangle]
+pub unsafe extern "C" fn cro_withdraw_unbonded_to_tx_aux(
+    raw_tx: *const u8,
+    raw_tx_len: usize,
+    key_pair: *const CroKeyPair,
+    tendermint_address: *const c_char,
+    features: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let withdraw_unbonded_tx: WithdrawUnbondedTx =
+            decode_argument(raw_tx, raw_tx_len, "raw_tx")?;
+        let signer = handle_argument(key_pair, "key_pair")?.signer()?;
+        let (tendermint_address, features) = features_argument(tendermint_address, features)?;
+
+        let witness = staked_state_op_witness(&signer, &withdraw_unbonded_tx.id())?;
+        let signed_transaction =
+            SignedTransaction::WithdrawUnbondedStakeTransaction(withdraw_unbonded_tx, witness);
+        let tx_aux =
+            signed_transaction_to_tx_aux(signed_transaction, tendermint_address, features)?;
+
+        write_bytes(out, tx_aux.encode())
+    })
+}
+
+unsafe fn witnesses_argument(
+    witnesses: *const CroBuffer,
+    witnesses_len: usize,
+) -> Result<Vec<TxInWitness>> {
+    if witnesses_len == 0 {
+        return Ok(Vec::new());
+    }
+    handle_argument(witnesses, "witnesses")?;
+
+    slice::from_raw_parts(witnesses, witnesses_len)
+        .iter()
+        .map(|witness| witness_argument(witness.data, witness.len, "witness"))
+        .collect()
+}
+
+/// Parse the optional JSON previous output argument with the network of the
+/// deposit transaction
+unsafe fn prev_output_argument(
+    prev_output: *const c_char,
+    deposit_bond_tx: &DepositBondTx,
+) -> Result<Option<TxOut>> {
+    let prev_output: Option<OutputOptions> = optional_options_argument(prev_output, "prev_output")?;
+
+    prev_output
+        .map(|prev_output| {
+            let network =
+                network_of_chain_hex_id(deposit_bond_tx.attributes.chain_hex_id, "prevOutput")?;
+
+            prev_output.to_tx_out(&network, "prevOutput")
+        })
+        .transpose()
+        .map_err(schema_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    use chain_core::tx::TxAux;
+    use parity_scale_codec::Decode;
+    use serde_json::json;
+
+    use crate::address::cro_staking_address_from_public_key;
+    use crate::buffer::tests::{
+        c_string, empty_buffer, last_error_message, take_bytes, take_string,
+    };
+    use crate::key_pair::cro_key_pair_free;
+    use crate::key_pair::tests::{key_pair_of, public_key_of};
+
+    fn staking_address_of(key_pair: *const CroKeyPair) -> String {
+        let public_key = public_key_of(key_pair).serialize();
+        let mut address = ptr::null_mut();
+        let status = unsafe {
+            cro_staking_address_from_public_key(public_key.as_ptr(), public_key.len(), &mut address)
+        };
+        assert_eq!(status, CroStatus::Ok);
+
+        take_string(address)
+    }
+
+    #[test]
+    fn deposit_transaction_should_be_signed_and_finished() {
+        let key_pair = key_pair_of(1);
+        let options = c_string(
+            &json!({
+                "inputs": [{ "prevTxId": hex::encode([0; 32]), "prevIndex": 0 }],
+                "toAddress": staking_address_of(key_pair),
+                "chainHexId": "2a",
+            })
+            .to_string(),
+        );
+
+        let mut raw_tx = empty_buffer();
+        let status = unsafe { cro_deposit_build_raw(options.as_ptr(), &mut raw_tx) };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+        let raw_tx = take_bytes(raw_tx);
+
+        let mut witness = empty_buffer();
+        let status = unsafe {
+            cro_deposit_sign_input(
+                raw_tx.as_ptr(),
+                raw_tx.len(),
+                ptr::null(),
+                key_pair,
+                &mut witness,
+            )
+        };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+
+        let features = c_string("MockObfuscation");
+        let mut tx_aux = empty_buffer();
+        let status = unsafe {
+            cro_deposit_to_tx_aux(
+                raw_tx.as_ptr(),
+                raw_tx.len(),
+                &witness,
+                1,
+                ptr::null(),
+                features.as_ptr(),
+                &mut tx_aux,
+            )
+        };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+        take_bytes(witness);
+
+        let deposit_bond_tx = DepositBondTx::decode(&mut raw_tx.as_slice()).unwrap();
+        let tx_aux = TxAux::decode(&mut take_bytes(tx_aux).as_slice()).unwrap();
+        assert_eq!(tx_aux.tx_id(), deposit_bond_tx.id());
+
+        assert_eq!(unsafe { cro_key_pair_free(key_pair) }, CroStatus::Ok);
+    }
+
+    #[test]
+    fn unbond_transaction_should_be_signed() {
+        let key_pair = key_pair_of(1);
+        let options = c_string(
+            &json!({
+                "stakingAddress": staking_address_of(key_pair),
+                "nonce": "1",
+                "amount": "100000000",
+                "chainHexId": "2a",
+            })
+            .to_string(),
+        );
+
+        let mut raw_tx = empty_buffer();
+        let status = unsafe { cro_unbond_build_raw(options.as_ptr(), &mut raw_tx) };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+        let raw_tx = take_bytes(raw_tx);
+
+        let mut tx_aux = empty_buffer();
+        let status =
+            unsafe { cro_unbond_to_tx_aux(raw_tx.as_ptr(), raw_tx.len(), key_pair, &mut tx_aux) };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+
+        let unbond_tx = UnbondTx::decode(&mut raw_tx.as_slice()).unwrap();
+        let tx_aux = TxAux::decode(&mut take_bytes(tx_aux).as_slice()).unwrap();
+        assert_eq!(tx_aux.tx_id(), unbond_tx.id());
+
+        assert_eq!(unsafe { cro_key_pair_free(key_pair) }, CroStatus::Ok);
+    }
+
+    #[test]
+    fn deposit_to_tx_aux_should_reject_invalid_witness() {
+        let witness = CroBuffer {
+            data: [0xff].as_ptr() as *mut u8,
+            len: 1,
+        };
+        let features = c_string("MockObfuscation");
+        let mut tx_aux = empty_buffer();
+
+        let status = unsafe {
+            cro_deposit_to_tx_aux(
+                ptr::null(),
+                0,
+                &witness,
+                1,
+                ptr::null(),
+                features.as_ptr(),
+                &mut tx_aux,
+            )
+        };
+
+        assert_eq!(status, CroStatus::DeserializationError);
+        assert!(last_error_message().starts_with("Unable to decode raw_tx"));
+    }
+}
@@ -0,0 +1,382 @@
+//! Transfer transaction builder handle
+use std::os::raw::c_char;
+
+use chain_core::tx::witness::TxInWitness;
+use client_common::{ErrorKind, Result, ResultExt};
+use cro_nodelib_core::schema::types::FeeConfigOptions;
+use cro_nodelib_core::transfer_transaction::{
+    self, build_incomplete, BuilderOptions, FeeScheduleRawTransferTransactionBuilder,
+};
+use parity_scale_codec::Encode;
+
+use crate::buffer::{
+    bytes_argument, handle_argument, handle_argument_mut, options_argument, schema_error,
+    witness_argument, write_bytes, write_handle, write_output, CroBuffer,
+};
+use crate::error::{ffi_call, CroStatus};
+use crate::key_pair::CroKeyPair;
+use crate::tx_aux::features_argument;
+
+/// Opaque transfer transaction builder
+pub struct CroTransferBuilder(FeeScheduleRawTransferTransactionBuilder);
+
+/// Create a transfer transaction builder without witnesses from the JSON
+/// builder options
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_new(
+    options: *const c_char,
+    out: *mut *mut CroTransferBuilder,
+) -> CroStatus {
+    ffi_call(|| {
+        let options: BuilderOptions = options_argument(options, "options")?;
+        let raw_tx_options = options.to_raw_tx_options().map_err(schema_error)?;
+
+        let builder = build_incomplete(&raw_tx_options, options.fee_config.to_fee_algorithm())?;
+
+        write_builder(out, builder)
+    })
+}
+
+/// Restore a transfer transaction builder from its incomplete bytes and the
+/// JSON fee config it is built with
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_from_incomplete(
+    incomplete: *const u8,
+    incomplete_len: usize,
+    fee_config: *const c_char,
+    out: *mut *mut CroTransferBuilder,
+) -> CroStatus {
+    ffi_call(|| {
+        let incomplete = bytes_argument(incomplete, incomplete_len, "incomplete")?;
+        let fee_config: FeeConfigOptions = options_argument(fee_config, "fee_config")?;
+
+        let builder = transfer_transaction::from_incomplete(
+            incomplete.to_vec(),
+            fee_config.to_fee_algorithm(),
+        )
+        .chain(|| {
+            (
+                ErrorKind::DeserializationError,
+                "Unable to deserialize raw transfer transaction bytes",
+            )
+        })?;
+
+        write_builder(out, builder)
+    })
+}
+
+/// Incomplete bytes of the builder, to be passed to the other signers
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_to_incomplete(
+    builder: *const CroTransferBuilder,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = builder_argument(builder)?;
+
+        write_bytes(out, builder.to_incomplete())
+    })
+}
+
+/// 32 bytes transaction id of the builder
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_tx_id(
+    builder: *const CroTransferBuilder,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = builder_argument(builder)?;
+
+        write_bytes(out, builder.tx_id().to_vec())
+    })
+}
+
+/// Sign a particular input with the key pair
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_sign_input(
+    builder: *mut CroTransferBuilder,
+    input_index: usize,
+    key_pair: *const CroKeyPair,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = handle_argument_mut(builder, "builder")?;
+        let key_pair = handle_argument(key_pair, "key_pair")?;
+
+        transfer_transaction::sign_input(&mut builder.0, input_index, &key_pair.signer()?)
+    })
+}
+
+/// Add the SCALE encoded `TxInWitness` to a particular input
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_add_witness(
+    builder: *mut CroTransferBuilder,
+    input_index: usize,
+    witness: *const u8,
+    witness_len: usize,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = handle_argument_mut(builder, "builder")?;
+        let witness: TxInWitness = witness_argument(witness, witness_len, "witness")?;
+
+        builder.0.add_witness(input_index, witness)
+    })
+}
+
+/// Whether all the inputs of the builder have a witness
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_is_completed(
+    builder: *const CroTransferBuilder,
+    out: *mut bool,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = builder_argument(builder)?;
+
+        write_output(out, builder.is_completed())
+    })
+}
+
+/// Estimated fee of the builder in base unit
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_estimate_fee(
+    builder: *const CroTransferBuilder,
+    out: *mut u64,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = builder_argument(builder)?;
+
+        let estimated_fee = builder.estimate_fee()?;
+
+        write_output(out, u64::from(estimated_fee.to_coin()))
+    })
+}
+
+/// Verify the builder is a valid transaction to be broadcasted
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_verify(
+    builder: *const CroTransferBuilder,
+) -> CroStatus {
+    ffi_call(|| builder_argument(builder)?.verify())
+}
+
+/// Finish the completed builder into the SCALE encoded broadcast-able
+/// `TxAux`, obfuscated with the features, e.g. `"MockObfuscation"`
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_to_tx_aux(
+    builder: *const CroTransferBuilder,
+    tendermint_address: *const c_char,
+    features: *const c_char,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let builder = builder_argument(builder)?;
+        let (tendermint_address, features) = features_argument(tendermint_address, features)?;
+
+        let tx_aux = transfer_transaction::to_tx_aux(builder, tendermint_address, features)?;
+
+        write_bytes(out, tx_aux.encode())
+    })
+}
+
+/// Release the builder
+#[no_mangle]
+pub unsafe extern "C" fn cro_transfer_builder_free(builder: *mut CroTransferBuilder) -> CroStatus {
+    ffi_call(|| {
+        if !builder.is_null() {
+            drop(Box::from_raw(builder));
+        }
+
+        Ok(())
+    })
+}
+
+unsafe fn builder_argument<'a>(
+    builder: *const CroTransferBuilder,
+) -> Result<&'a FeeScheduleRawTransferTransactionBuilder> {
+    handle_argument(builder, "builder").map(|builder| &builder.0)
+}
+
+unsafe fn write_builder(
+    out: *mut *mut CroTransferBuilder,
+    builder: FeeScheduleRawTransferTransactionBuilder,
+) -> Result<()> {
+    write_handle(out, CroTransferBuilder(builder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    use chain_core::tx::TxAux;
+    use cro_nodelib_core::address::transfer_address_from_public_key;
+    use cro_nodelib_core::network::{network_by_name, MAINNET};
+    use parity_scale_codec::Decode;
+    use serde_json::json;
+
+    use crate::buffer::tests::{c_string, empty_buffer, last_error_message, take_bytes};
+    use crate::key_pair::cro_key_pair_free;
+    use crate::key_pair::tests::{key_pair_of, public_key_of};
+
+    fn fee_config() -> serde_json::Value {
+        json!({
+            "algorithm": "LinearFee",
+            "constant": "1.1",
+            "coefficient": "1.25",
+        })
+    }
+
+    fn builder_options(key_pair: *const CroKeyPair) -> String {
+        let network = network_by_name(MAINNET).unwrap();
+        let address = transfer_address_from_public_key(&network, &public_key_of(key_pair)).unwrap();
+
+        json!({
+            "chainHexId": "2a",
+            "inputs": [{
+                "prevTxId": hex::encode([0; 32]),
+                "prevIndex": 0,
+                "prevOutput": { "address": address, "value": "100000000" },
+                "addressParams": { "requiredSigners": 1, "totalSigners": 1 },
+            }],
+            "outputs": [{ "address": address, "value": "50000000" }],
+            "viewKeys": [],
+            "feeConfig": fee_config(),
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn transfer_builder_should_be_signed_and_finished() {
+        let key_pair = key_pair_of(1);
+        let options = c_string(&builder_options(key_pair));
+        let mut builder = ptr::null_mut();
+
+        let status = unsafe { cro_transfer_builder_new(options.as_ptr(), &mut builder) };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+
+        let mut incomplete = empty_buffer();
+        let status = unsafe { cro_transfer_builder_to_incomplete(builder, &mut incomplete) };
+        assert_eq!(status, CroStatus::Ok);
+        let incomplete = take_bytes(incomplete);
+        assert_eq!(unsafe { cro_transfer_builder_free(builder) }, CroStatus::Ok);
+
+        let fee_config = c_string(&fee_config().to_string());
+        let mut builder = ptr::null_mut();
+        let status = unsafe {
+            cro_transfer_builder_from_incomplete(
+                incomplete.as_ptr(),
+                incomplete.len(),
+                fee_config.as_ptr(),
+                &mut builder,
+            )
+        };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+
+        let mut is_completed = true;
+        let status = unsafe { cro_transfer_builder_is_completed(builder, &mut is_completed) };
+        assert_eq!(status, CroStatus::Ok);
+        assert!(!is_completed);
+
+        let status = unsafe { cro_transfer_builder_sign_input(builder, 0, key_pair) };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+        let status = unsafe { cro_transfer_builder_is_completed(builder, &mut is_completed) };
+        assert_eq!(status, CroStatus::Ok);
+        assert!(is_completed);
+        assert_eq!(
+            unsafe { cro_transfer_builder_verify(builder) },
+            CroStatus::Ok
+        );
+
+        let mut fee = 0;
+        let status = unsafe { cro_transfer_builder_estimate_fee(builder, &mut fee) };
+        assert_eq!(status, CroStatus::Ok);
+        assert!(fee > 0);
+
+        let mut tx_id = empty_buffer();
+        let status = unsafe { cro_transfer_builder_tx_id(builder, &mut tx_id) };
+        assert_eq!(status, CroStatus::Ok);
+        let tx_id = take_bytes(tx_id);
+
+        let tendermint_address = c_string("");
+        let features = c_string("MockObfuscation");
+        let mut tx_aux = empty_buffer();
+        let status = unsafe {
+            cro_transfer_builder_to_tx_aux(
+                builder,
+                tendermint_address.as_ptr(),
+                features.as_ptr(),
+                &mut tx_aux,
+            )
+        };
+        assert_eq!(status, CroStatus::Ok, "{}", last_error_message());
+
+        let tx_aux = TxAux::decode(&mut take_bytes(tx_aux).as_slice()).unwrap();
+        assert_eq!(tx_aux.tx_id().to_vec(), tx_id);
+
+        assert_eq!(unsafe { cro_transfer_builder_free(builder) }, CroStatus::Ok);
+        assert_eq!(unsafe { cro_key_pair_free(key_pair) }, CroStatus::Ok);
+    }
+
+    #[test]
+    fn transfer_builder_should_reject_invalid_options() {
+        let mut builder = ptr::null_mut();
+
+        let status = unsafe { cro_transfer_builder_new(ptr::null(), &mut builder) };
+        assert_eq!(status, CroStatus::InvalidInput);
+        assert_eq!(last_error_message(), "Null pointer of options");
+
+        let options = c_string(r#"{ "chainHexId": "2a" }"#);
+        let status = unsafe { cro_transfer_builder_new(options.as_ptr(), &mut builder) };
+        assert_eq!(status, CroStatus::InvalidInput);
+        assert!(last_error_message().starts_with("Invalid options"));
+        assert!(builder.is_null());
+    }
+
+    #[test]
+    fn transfer_builder_should_reject_trailing_incomplete_bytes() {
+        let key_pair = key_pair_of(1);
+        let options = c_string(&builder_options(key_pair));
+        let mut builder = ptr::null_mut();
+        let mut incomplete = empty_buffer();
+        unsafe {
+            cro_transfer_builder_new(options.as_ptr(), &mut builder);
+            cro_transfer_builder_to_incomplete(builder, &mut incomplete);
+            cro_transfer_builder_free(builder);
+            cro_key_pair_free(key_pair);
+        }
+        let mut incomplete = take_bytes(incomplete);
+        incomplete.push(0);
+        let fee_config = c_string(&fee_config().to_string());
+        let mut builder = ptr::null_mut();
+
+        let status = unsafe {
+            cro_transfer_builder_from_incomplete(
+                incomplete.as_ptr(),
+                incomplete.len(),
+                fee_config.as_ptr(),
+                &mut builder,
+            )
+        };
+
+        assert_eq!(status, CroStatus::DeserializationError);
+        assert!(builder.is_null());
+    }
+
+    #[test]
+    fn transfer_builder_should_reject_invalid_incomplete_bytes() {
+        let fee_config = c_string(&fee_config().to_string());
+        let mut builder = ptr::null_mut();
+
+        let status = unsafe {
+            cro_transfer_builder_from_incomplete(
+                [1, 2, 3].as_ptr(),
+                3,
+                fee_config.as_ptr(),
+                &mut builder,
+            )
+        };
+
+        assert_eq!(status, CroStatus::DeserializationError);
+        assert!(builder.is_null());
+    }
+}
@@ -0,0 +1,76 @@
+//! Broadcast-able `TxAux` of finished transactions
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use chain_core::tx::TxAux;
+use client_common::Result;
+use cro_nodelib_core::tx_aux::Features;
+
+use crate::buffer::{decode_argument, str_argument, write_bytes, CroBuffer};
+use crate::error::{ffi_call, CroStatus};
+
+/// 32 bytes transaction id of the SCALE encoded `TxAux`
+#[no_mangle]
+pub unsafe extern "C" fn cro_tx_aux_tx_id(
+    tx_aux: *const u8,
+    tx_aux_len: usize,
+    out: *mut CroBuffer,
+) -> CroStatus {
+    ffi_call(|| {
+        let tx_aux: TxAux = decode_argument(tx_aux, tx_aux_len, "tx_aux")?;
+
+        write_bytes(out, tx_aux.tx_id().to_vec())
+    })
+}
+
+/// Tendermint websocket address and obfuscation features arguments. The
+/// address is unused and may be empty with `"MockObfuscation"`.
+pub(crate) unsafe fn features_argument<'a>(
+    tendermint_address: *const c_char,
+    features: *const c_char,
+) -> Result<(&'a str, Features)> {
+    let features = Features::from_str(str_argument(features, "features")?)?;
+    let tendermint_address =
+        if tendermint_address.is_null() && features == Features::MockObfuscation {
+            ""
+        } else {
+            str_argument(tendermint_address, "tendermint_address")?
+        };
+
+    Ok((tendermint_address, features))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ptr;
+
+    use crate::buffer::tests::{c_string, empty_buffer, last_error_message};
+
+    #[test]
+    fn features_argument_should_allow_null_address_of_mock_obfuscation() {
+        let features = c_string("MockObfuscation");
+        let (tendermint_address, features) =
+            unsafe { features_argument(ptr::null(), features.as_ptr()) }.unwrap();
+
+        assert_eq!(tendermint_address, "");
+        assert_eq!(features, Features::MockObfuscation);
+
+        let features = c_string("MockAbci");
+        assert!(unsafe { features_argument(ptr::null(), features.as_ptr()) }.is_err());
+
+        let features = c_string("Unknown");
+        assert!(unsafe { features_argument(ptr::null(), features.as_ptr()) }.is_err());
+    }
+
+    #[test]
+    fn tx_aux_tx_id_should_reject_invalid_bytes() {
+        let mut tx_id = empty_buffer();
+
+        let status = unsafe { cro_tx_aux_tx_id([0xff].as_ptr(), 1, &mut tx_id) };
+
+        assert_eq!(status, CroStatus::DeserializationError);
+        assert!(last_error_message().starts_with("Unable to decode tx_aux"));
+    }
+}
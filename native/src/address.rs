@@ -1,27 +1,24 @@
 use std::str::FromStr;
 
-use chain_core::init::address::{CroAddress, RedeemAddress};
 use chain_core::state::account::StakedStateAddress;
 use chain_core::tx::data::address::ExtendedAddr;
-use client_common::MultiSigAddress;
+use cro_nodelib_core::address::{
+    staking_address_from_public_key, transfer_address_from_public_key, validate_addresses,
+    AddressKind, AddressValidation, MAX_VALIDATION_BATCH_SIZE,
+};
+use cro_nodelib_core::network::{decode_transfer_address, NetworkDefinition};
 use neon::prelude::*;
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
+use crate::schema::object_argument;
 
 pub fn get_transfer_address_from_public_key(mut ctx: FunctionContext) -> JsResult<JsString> {
     let public_key = public_key_argument(&mut ctx, 0)?;
     let network = network_argument(&mut ctx, 1)?;
 
-    let required_signers = 1;
-    let multi_sig_address =
-        MultiSigAddress::new(vec![public_key.clone()], public_key, required_signers)
-            .chain_neon(&mut ctx, "Unable to create MultiSig address")?;
-
-    let extended_address = ExtendedAddr::from(multi_sig_address);
-    let cro_address = extended_address
-        .to_cro(network)
-        .chain_neon(&mut ctx, "Unable to convert to CRO address")?;
+    let cro_address = transfer_address_from_public_key(&network, &public_key)
+        .chain_neon(&mut ctx, "Unable to create transfer address")?;
 
     Ok(ctx.string(cro_address))
 }
@@ -29,7 +26,7 @@ pub fn get_transfer_address_from_public_key(mut ctx: FunctionContext) -> JsResul
 pub fn get_staking_address_from_public_key(mut ctx: FunctionContext) -> JsResult<JsString> {
     let public_key = public_key_argument(&mut ctx, 0)?;
 
-    let staked_state_address = StakedStateAddress::BasicRedeem(RedeemAddress::from(&public_key));
+    let staked_state_address = staking_address_from_public_key(&public_key);
 
     Ok(ctx.string(staked_state_address.to_string()))
 }
@@ -38,7 +35,7 @@ pub fn is_transfer_address_valid(mut ctx: FunctionContext) -> JsResult<JsBoolean
     let address = ctx.argument::<JsString>(0)?.value();
     let network = network_argument(&mut ctx, 1)?;
 
-    let is_valid = ExtendedAddr::from_cro(&address, network).is_ok();
+    let is_valid = network.decode_transfer_address(&address).is_ok();
 
     Ok(ctx.boolean(is_valid))
 }
@@ -51,6 +48,208 @@ pub fn is_staking_address_valid(mut ctx: FunctionContext) -> JsResult<JsBoolean>
     Ok(ctx.boolean(is_valid))
 }
 
+/// Decode a transfer address of any registered network
+/// @arguments
+/// - address: transfer address
+/// @return {
+///     network: string,
+///     addressPrefix: string,
+///     rootHash: Buffer
+/// }
+pub fn parse_transfer_address(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let address = ctx.argument::<JsString>(0)?.value();
+
+    let (network, extended_addr) =
+        decode_transfer_address(&address).chain_neon(&mut ctx, "Invalid transfer address")?;
+    let ExtendedAddr::OrTree(root_hash) = extended_addr;
+
+    let mut root_hash_buffer = ctx.buffer(root_hash.len() as u32)?;
+    ctx.borrow_mut(&mut root_hash_buffer, |data| {
+        let slice = data.as_mut_slice();
+        slice.copy_from_slice(&root_hash);
+    });
+
+    let js_object = JsObject::new(&mut ctx);
+    let network_name = ctx.string(network.name);
+    js_object.set(&mut ctx, "network", network_name)?;
+    let address_prefix = ctx.string(network.address_prefix);
+    js_object.set(&mut ctx, "addressPrefix", address_prefix)?;
+    js_object.set(&mut ctx, "rootHash", root_hash_buffer)?;
+
+    Ok(js_object)
+}
+
+/// Decode a staking address into its 20 bytes redeem address
+pub fn parse_staking_address(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
+    let staking_address = staking_address_argument(&mut ctx, 0)?;
+    let StakedStateAddress::BasicRedeem(redeem_address) = staking_address;
+
+    let mut buffer = ctx.buffer(redeem_address.0.len() as u32)?;
+    ctx.borrow_mut(&mut buffer, |data| {
+        let slice = data.as_mut_slice();
+        slice.copy_from_slice(&redeem_address.0);
+    });
+    Ok(buffer)
+}
+
+/// Returns the name of the network the transfer address belongs to
+pub fn get_transfer_address_network(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let address = ctx.argument::<JsString>(0)?.value();
+
+    let (network, _) =
+        decode_transfer_address(&address).chain_neon(&mut ctx, "Invalid transfer address")?;
+
+    Ok(ctx.string(network.name))
+}
+
+/// Re-encode a transfer address with the address prefix of another network
+pub fn convert_transfer_address(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let address = ctx.argument::<JsString>(0)?.value();
+    let network = network_argument(&mut ctx, 1)?;
+
+    let (_, extended_addr) =
+        decode_transfer_address(&address).chain_neon(&mut ctx, "Invalid transfer address")?;
+    let cro_address = network
+        .encode_transfer_address(&extended_addr)
+        .chain_neon(&mut ctx, "Unable to convert to CRO address")?;
+
+    Ok(ctx.string(cro_address))
+}
+
+struct ValidateAddressesTask {
+    kind: AddressKind,
+    addresses: Vec<String>,
+    network: Option<NetworkDefinition>,
+}
+
+impl ValidateAddressesTask {
+    fn argument(ctx: &mut FunctionContext, kind: AddressKind) -> NeonResult<ValidateAddressesTask> {
+        let addresses: Vec<String> = object_argument(ctx, 0)?;
+        if addresses.len() > MAX_VALIDATION_BATCH_SIZE {
+            return ctx.throw_error(format!(
+                "Batch size should not exceed {}",
+                MAX_VALIDATION_BATCH_SIZE
+            ));
+        }
+
+        let network = match (kind, ctx.argument_opt(1)) {
+            (AddressKind::Transfer, Some(network)) if network.is_a::<JsString>() => {
+                Some(network_argument(ctx, 1)?)
+            }
+            _ => None,
+        };
+
+        Ok(ValidateAddressesTask {
+            kind,
+            addresses,
+            network,
+        })
+    }
+
+    fn validate(&self) -> Vec<AddressValidation> {
+        validate_addresses(self.kind, &self.addresses, self.network.as_ref())
+    }
+}
+
+impl Task for ValidateAddressesTask {
+    type Output = Vec<AddressValidation>;
+    type Error = String;
+    type JsEvent = JsArray;
+
+    fn perform(&self) -> std::result::Result<Self::Output, Self::Error> {
+        Ok(self.validate())
+    }
+
+    fn complete(
+        self,
+        mut ctx: TaskContext,
+        result: std::result::Result<Self::Output, Self::Error>,
+    ) -> JsResult<Self::JsEvent> {
+        match result {
+            Ok(validations) => address_validations_to_js_array(&mut ctx, &validations),
+            Err(err) => ctx.throw_error(err),
+        }
+    }
+}
+
+fn address_validations_to_js_array<'a, C>(
+    ctx: &mut C,
+    validations: &[AddressValidation],
+) -> JsResult<'a, JsArray>
+where
+    C: Context<'a>,
+{
+    let js_array = JsArray::new(ctx, validations.len() as u32);
+    for (i, validation) in validations.iter().enumerate() {
+        let js_object = JsObject::new(ctx);
+
+        let address = ctx.string(&validation.address);
+        js_object.set(ctx, "address", address)?;
+        let valid = ctx.boolean(validation.is_valid());
+        js_object.set(ctx, "valid", valid)?;
+        if let Some(ref network) = validation.network {
+            let network = ctx.string(network);
+            js_object.set(ctx, "network", network)?;
+        }
+        if let Some(ref error) = validation.error {
+            let error = ctx.string(error);
+            js_object.set(ctx, "error", error)?;
+        }
+
+        js_array.set(ctx, i as u32, js_object)?;
+    }
+
+    Ok(js_array)
+}
+
+/// Validate a batch of transfer addresses
+/// @arguments
+/// - addresses: transfer addresses to validate
+/// - network: (optional) name of the network the addresses should belong to,
+///   detect from address prefix when omitted
+/// @return Array<{ address: string, valid: boolean, network?: string, error?: string }>
+pub fn validate_transfer_addresses(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let task = ValidateAddressesTask::argument(&mut ctx, AddressKind::Transfer)?;
+
+    let validations = task.validate();
+
+    address_validations_to_js_array(&mut ctx, &validations)
+}
+
+/// Validate a batch of transfer addresses off the main thread. Result is
+/// passed to the Node-style callback at the last argument
+pub fn validate_transfer_addresses_async(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let task = ValidateAddressesTask::argument(&mut ctx, AddressKind::Transfer)?;
+    let callback = ctx.argument::<JsFunction>(2)?;
+
+    task.schedule(callback);
+
+    Ok(ctx.undefined())
+}
+
+/// Validate a batch of staking addresses
+/// @arguments
+/// - addresses: staking addresses to validate
+/// @return Array<{ address: string, valid: boolean, error?: string }>
+pub fn validate_staking_addresses(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let task = ValidateAddressesTask::argument(&mut ctx, AddressKind::Staking)?;
+
+    let validations = task.validate();
+
+    address_validations_to_js_array(&mut ctx, &validations)
+}
+
+/// Validate a batch of staking addresses off the main thread. Result is
+/// passed to the Node-style callback at the last argument
+pub fn validate_staking_addresses_async(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let task = ValidateAddressesTask::argument(&mut ctx, AddressKind::Staking)?;
+    let callback = ctx.argument::<JsFunction>(1)?;
+
+    task.schedule(callback);
+
+    Ok(ctx.undefined())
+}
+
 pub fn register_address_module(ctx: &mut ModuleContext) -> NeonResult<()> {
     let js_object = JsObject::new(ctx);
 
@@ -76,5 +275,51 @@ pub fn register_address_module(ctx: &mut ModuleContext) -> NeonResult<()> {
     let is_staking_address_valid_fn = JsFunction::new(ctx, is_staking_address_valid)?;
     js_object.set(ctx, "isStakingAddressValid", is_staking_address_valid_fn)?;
 
+    let parse_transfer_address_fn = JsFunction::new(ctx, parse_transfer_address)?;
+    js_object.set(ctx, "parseTransferAddress", parse_transfer_address_fn)?;
+
+    let parse_staking_address_fn = JsFunction::new(ctx, parse_staking_address)?;
+    js_object.set(ctx, "parseStakingAddress", parse_staking_address_fn)?;
+
+    let get_transfer_address_network_fn = JsFunction::new(ctx, get_transfer_address_network)?;
+    js_object.set(
+        ctx,
+        "getTransferAddressNetwork",
+        get_transfer_address_network_fn,
+    )?;
+
+    let convert_transfer_address_fn = JsFunction::new(ctx, convert_transfer_address)?;
+    js_object.set(ctx, "convertTransferAddress", convert_transfer_address_fn)?;
+
+    let validate_transfer_addresses_fn = JsFunction::new(ctx, validate_transfer_addresses)?;
+    js_object.set(
+        ctx,
+        "validateTransferAddresses",
+        validate_transfer_addresses_fn,
+    )?;
+
+    let validate_transfer_addresses_async_fn =
+        JsFunction::new(ctx, validate_transfer_addresses_async)?;
+    js_object.set(
+        ctx,
+        "validateTransferAddressesAsync",
+        validate_transfer_addresses_async_fn,
+    )?;
+
+    let validate_staking_addresses_fn = JsFunction::new(ctx, validate_staking_addresses)?;
+    js_object.set(
+        ctx,
+        "validateStakingAddresses",
+        validate_staking_addresses_fn,
+    )?;
+
+    let validate_staking_addresses_async_fn =
+        JsFunction::new(ctx, validate_staking_addresses_async)?;
+    js_object.set(
+        ctx,
+        "validateStakingAddressesAsync",
+        validate_staking_addresses_async_fn,
+    )?;
+
     ctx.export_value("address", js_object)
 }
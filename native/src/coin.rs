@@ -0,0 +1,84 @@
+//! Exact conversion and arithmetic of Coin amounts
+//!
+//! Amounts cross the JS boundary as base-unit decimal strings.
+use std::str::FromStr;
+
+use chain_core::init::coin::Coin;
+use cro_nodelib_core::coin::{add_coins, coin_from_cro, coin_to_cro, sub_coins};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+
+fn coin_argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<Coin> {
+    let coin = ctx.argument::<JsString>(i)?.value();
+
+    Coin::from_str(&coin).chain_neon(ctx, format!("Invalid Coin {}", coin))
+}
+
+/// Convert base unit decimal string to CRO decimal string
+/// @arguments
+/// - coin: string
+/// @return string
+pub fn base_unit_to_cro(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let coin = coin_argument(&mut ctx, 0)?;
+
+    Ok(ctx.string(coin_to_cro(coin)))
+}
+
+/// Convert CRO decimal string to base unit decimal string
+/// @arguments
+/// - cro: string
+/// @return string
+pub fn cro_to_base_unit(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let cro = ctx.argument::<JsString>(0)?.value();
+    let coin = coin_from_cro(&cro).chain_neon(&mut ctx, "Unable to convert CRO to base unit")?;
+
+    Ok(ctx.string(u64::from(coin).to_string()))
+}
+
+/// Add two base unit decimal strings
+/// @arguments
+/// - augend: string
+/// - addend: string
+/// @return string
+pub fn add(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let augend = coin_argument(&mut ctx, 0)?;
+    let addend = coin_argument(&mut ctx, 1)?;
+
+    let sum = add_coins(augend, addend).chain_neon(&mut ctx, "Unable to add coins")?;
+
+    Ok(ctx.string(u64::from(sum).to_string()))
+}
+
+/// Subtract two base unit decimal strings
+/// @arguments
+/// - minuend: string
+/// - subtrahend: string
+/// @return string
+pub fn sub(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let minuend = coin_argument(&mut ctx, 0)?;
+    let subtrahend = coin_argument(&mut ctx, 1)?;
+
+    let difference =
+        sub_coins(minuend, subtrahend).chain_neon(&mut ctx, "Unable to subtract coins")?;
+
+    Ok(ctx.string(u64::from(difference).to_string()))
+}
+
+pub fn register_coin_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let base_unit_to_cro_fn = JsFunction::new(ctx, base_unit_to_cro)?;
+    js_object.set(ctx, "baseUnitToCRO", base_unit_to_cro_fn)?;
+
+    let cro_to_base_unit_fn = JsFunction::new(ctx, cro_to_base_unit)?;
+    js_object.set(ctx, "croToBaseUnit", cro_to_base_unit_fn)?;
+
+    let add_fn = JsFunction::new(ctx, add)?;
+    js_object.set(ctx, "add", add_fn)?;
+
+    let sub_fn = JsFunction::new(ctx, sub)?;
+    js_object.set(ctx, "sub", sub_fn)?;
+
+    ctx.export_value("coin", js_object)
+}
@@ -1,29 +1,28 @@
-use std::str::FromStr;
-
 use neon::prelude::*;
 
-use chain_core::state::account::{
-    CouncilNode, Nonce, StakedStateAddress, StakedStateOpAttributes, StakedStateOpWitness,
-};
+use chain_core::state::tendermint::TendermintValidatorPubKey;
 use chain_core::state::validator::NodeJoinRequestTx;
-use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
-use parity_scale_codec::{Decode, Encode};
+use chain_core::tx::TransactionId;
+use cro_nodelib_core::codec::decode_transaction;
+use cro_nodelib_core::council_node_transaction::node_join::{
+    sign_node_join_transaction, BuildNodeJoinTransactionOptions,
+};
+use parity_scale_codec::Encode;
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
 use crate::signer::KeyPairSigner;
 use crate::tx_aux::tx_aux_to_hex;
 
 pub fn build_raw_node_join_transaction(mut ctx: FunctionContext) -> JsResult<JsObject> {
-    let options = BuildNodeJoinTransactionOptions::parse(&mut ctx)?;
-    let attributes = StakedStateOpAttributes::new(options.chain_hex_id);
+    let options: BuildNodeJoinTransactionOptions = object_argument(&mut ctx, 0)?;
+    let council_node = options.council_node().or_throw(&mut ctx)?;
+
+    let TendermintValidatorPubKey::Ed25519(consensus_public_key) = &council_node.consensus_pubkey;
+    let consensus_public_key = base64::encode(consensus_public_key);
 
-    let tx = NodeJoinRequestTx::new(
-        options.nonce,
-        options.staking_address,
-        attributes,
-        options.council_node,
-    );
+    let tx = options.to_tx(council_node);
 
     let raw_tx = tx.encode();
     let mut raw_tx_buffer = ctx.buffer(raw_tx.len() as u32)?;
@@ -43,6 +42,14 @@ pub fn build_raw_node_join_transaction(mut ctx: FunctionContext) -> JsResult<JsO
         .set(&mut ctx, "txId", tx_id)
         .chain_neon(&mut ctx, "Unable to set txId of return object")?;
 
+    let consensus_public_key = ctx.string(consensus_public_key);
+    return_object
+        .set(&mut ctx, "consensusPublicKey", consensus_public_key)
+        .chain_neon(
+            &mut ctx,
+            "Unable to set consensusPublicKey of return object",
+        )?;
+
     Ok(return_object)
 }
 
@@ -52,13 +59,9 @@ pub fn node_join_transaction_to_hex(mut ctx: FunctionContext) -> JsResult<JsBuff
     let signer = KeyPairSigner::new(key_pair.0, key_pair.1)
         .chain_neon(&mut ctx, "Unable to create KeyPair signer")?;
 
-    let signature = signer
-        .sign(&node_join_request_tx.id())
-        .map(StakedStateOpWitness::new)
+    let tx_aux = sign_node_join_transaction(node_join_request_tx, &signer)
         .chain_neon(&mut ctx, "Error when signing transaction")?;
 
-    let tx_aux = TxAux::PublicTx(TxPublicAux::NodeJoinTx(node_join_request_tx, signature));
-
     tx_aux_to_hex(&mut ctx, tx_aux)
 }
 
@@ -67,60 +70,8 @@ fn node_join_request_tx_argument(
     i: i32,
 ) -> NeonResult<NodeJoinRequestTx> {
     let node_join_request_tx = ctx.argument::<JsBuffer>(i)?;
-    let mut node_join_request_tx = node_join_request_tx.borrow(&ctx.lock()).as_slice();
+    let node_join_request_tx = node_join_request_tx.borrow(&ctx.lock()).as_slice();
 
-    NodeJoinRequestTx::decode(&mut node_join_request_tx)
+    decode_transaction(node_join_request_tx, "raw transaction")
         .chain_neon(ctx, "Unable to decode raw transaction bytes")
 }
-
-struct BuildNodeJoinTransactionOptions {
-    staking_address: StakedStateAddress,
-    nonce: Nonce,
-    council_node: CouncilNode,
-    chain_hex_id: u8,
-}
-
-impl BuildNodeJoinTransactionOptions {
-    fn parse(ctx: &mut FunctionContext) -> NeonResult<BuildNodeJoinTransactionOptions> {
-        let options = ctx
-            .argument::<JsObject>(0)
-            .chain_neon(ctx, "Unable to deserialize options object")?;
-
-        let staking_address = options
-            .get(ctx, "stakingAddress")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast stakingAddress")?
-            .value();
-        let staking_address = StakedStateAddress::from_str(&staking_address)
-            .chain_neon(ctx, "Unable to deserialize stakingAddress")?;
-
-        let nonce = options
-            .get(ctx, "nonce")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast stakingAddress")?
-            .value();
-        let nonce = parse_account_nonce(ctx, nonce)?;
-
-        let council_node = options
-            .get(ctx, "nodeMetaData")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast nodeMetaData")?
-            .value();
-        let council_node = serde_json::from_str::<CouncilNode>(&council_node)
-            .chain_neon(ctx, "Unable to deserialize nodeMetaData")?;
-
-        let chain_hex_id = options
-            .get(ctx, "chainHexId")?
-            .downcast_or_throw::<JsBuffer, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast chainHexId")?;
-        let chain_hex_id = chain_hex_id.borrow(&ctx.lock()).as_slice().to_vec();
-        let chain_hex_id = chain_hex_id_from_vec(ctx, chain_hex_id)?;
-
-        Ok(BuildNodeJoinTransactionOptions {
-            staking_address,
-            nonce,
-            council_node,
-            chain_hex_id,
-        })
-    }
-}
@@ -1,24 +1,23 @@
-use std::str::FromStr;
-
 use neon::prelude::*;
 
-use chain_core::state::account::{
-    Nonce, StakedStateAddress, StakedStateOpAttributes, StakedStateOpWitness, UnjailTx,
+use chain_core::state::account::{StakedStateOpWitness, UnjailTx};
+use chain_core::tx::{TransactionId, TxAux};
+use cro_nodelib_core::codec::decode_transaction;
+use cro_nodelib_core::council_node_transaction::unjail::{
+    sign_unjail_transaction, unjail_tx_of_tx_aux, verify_unjail_transaction, UnjailTxOptions,
 };
-use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
-use chain_tx_validation::witness::verify_tx_recover_address;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::Encode;
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
+use crate::schema::object_argument;
 use crate::signer::KeyPairSigner;
 use crate::tx_aux::tx_aux_to_hex;
 
 pub fn build_raw_unjail_transaction(mut ctx: FunctionContext) -> JsResult<JsObject> {
-    let options = BuildNodeJoinTransactionOptions::argument(&mut ctx, 0)?;
-    let attributes = StakedStateOpAttributes::new(options.0.chain_hex_id);
+    let options: UnjailTxOptions = object_argument(&mut ctx, 0)?;
 
-    let tx = UnjailTx::new(options.0.nonce, options.0.staking_address, attributes);
+    let tx = options.to_tx();
 
     let raw_tx = tx.encode();
     let mut raw_tx_buffer = ctx.buffer(raw_tx.len() as u32)?;
@@ -47,42 +46,26 @@ pub fn unjail_transaction_to_hex(mut ctx: FunctionContext) -> JsResult<JsBuffer>
     let signer = KeyPairSigner::new(key_pair.0, key_pair.1)
         .chain_neon(&mut ctx, "Unable to create KeyPair signer")?;
 
-    let signature = signer
-        .sign(&unjail_tx.id())
-        .map(StakedStateOpWitness::new)
+    let tx_aux = sign_unjail_transaction(unjail_tx, &signer)
         .chain_neon(&mut ctx, "Error when signing transaction")?;
 
-    let tx_aux = TxAux::PublicTx(TxPublicAux::UnjailTx(unjail_tx, signature));
-
     tx_aux_to_hex(&mut ctx, tx_aux)
 }
 
 fn unjail_tx_argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<UnjailTx> {
     let unjail_tx = ctx.argument::<JsBuffer>(i)?;
-    let mut unjail_tx = unjail_tx.borrow(&ctx.lock()).as_slice();
+    let unjail_tx = unjail_tx.borrow(&ctx.lock()).as_slice();
 
-    UnjailTx::decode(&mut unjail_tx).chain_neon(ctx, "Unable to decode raw transaction bytes")
+    decode_transaction(unjail_tx, "raw transaction")
+        .chain_neon(ctx, "Unable to decode raw transaction bytes")
 }
 
 pub fn verify_unjail_tx_aux(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
     let (unjail_tx, witness) = unjail_tx_aux_argument(&mut ctx, 0)?;
-    let assertions = UnjailTxAssertion::argument(&mut ctx, 1)?;
-
-    if unjail_tx.address != assertions.0.staking_address {
-        return ctx.throw_error("Mismatch staking address");
-    }
-    if unjail_tx.nonce != assertions.0.nonce {
-        return ctx.throw_error("Mismatch staking account nonce");
-    }
-    if unjail_tx.attributes.chain_hex_id != assertions.0.chain_hex_id {
-        return ctx.throw_error("Mismatch chain hex id");
-    }
-
-    let address = verify_tx_recover_address(&witness, &unjail_tx.id())
-        .chain_neon(&mut ctx, "Invalid signature")?;
-    if address != assertions.0.staking_address {
-        return ctx.throw_error("Incorrect signature");
-    }
+    let assertions: UnjailTxOptions = object_argument(&mut ctx, 1)?;
+
+    verify_unjail_transaction(&unjail_tx, &witness, &assertions)
+        .chain_neon(&mut ctx, "Unable to verify unjail transaction")?;
 
     Ok(ctx.undefined())
 }
@@ -92,83 +75,10 @@ fn unjail_tx_aux_argument(
     i: i32,
 ) -> NeonResult<(UnjailTx, StakedStateOpWitness)> {
     let unjail_tx_aux = ctx.argument::<JsBuffer>(i)?;
-    let mut unjail_tx_aux = unjail_tx_aux.borrow(&ctx.lock()).as_slice();
-
-    let tx_aux =
-        TxAux::decode(&mut unjail_tx_aux).chain_neon(ctx, "Unable to decode transaction bytes")?;
-    match tx_aux {
-        TxAux::PublicTx(TxPublicAux::UnjailTx(unjail_tx, staked_state_op_witness)) => {
-            Ok((unjail_tx, staked_state_op_witness))
-        }
-        _ => ctx.throw_error("Transaction is not an Unjail transaction"),
-    }
-}
+    let unjail_tx_aux = unjail_tx_aux.borrow(&ctx.lock()).as_slice();
 
-struct UnjailTxAssertion(UnjailTxProps);
-
-impl UnjailTxAssertion {
-    fn argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<UnjailTxAssertion> {
-        let assertion = ctx
-            .argument::<JsObject>(i)
-            .chain_neon(ctx, "Unable to deserialize assertion object")?;
-
-        let unjail_tx_props = UnjailTxProps::parse(ctx, assertion)?;
-
-        Ok(UnjailTxAssertion(unjail_tx_props))
-    }
-}
-
-struct BuildNodeJoinTransactionOptions(UnjailTxProps);
-
-impl BuildNodeJoinTransactionOptions {
-    fn argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<BuildNodeJoinTransactionOptions> {
-        let options = ctx
-            .argument::<JsObject>(i)
-            .chain_neon(ctx, "Unable to deserialize options object")?;
-
-        let unjail_tx_props = UnjailTxProps::parse(ctx, options)?;
-
-        Ok(BuildNodeJoinTransactionOptions(unjail_tx_props))
-    }
-}
-
-struct UnjailTxProps {
-    staking_address: StakedStateAddress,
-    nonce: Nonce,
-    chain_hex_id: u8,
-}
+    let tx_aux: TxAux = decode_transaction(unjail_tx_aux, "transaction")
+        .chain_neon(ctx, "Unable to decode transaction bytes")?;
 
-impl UnjailTxProps {
-    fn parse(
-        ctx: &mut FunctionContext,
-        prop_object: Handle<JsObject>,
-    ) -> NeonResult<UnjailTxProps> {
-        let staking_address = prop_object
-            .get(ctx, "stakingAddress")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast stakingAddress")?
-            .value();
-        let staking_address = StakedStateAddress::from_str(&staking_address)
-            .chain_neon(ctx, "Unable to deserialize stakingAddress")?;
-
-        let nonce = prop_object
-            .get(ctx, "nonce")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast nonce")?
-            .value();
-        let nonce = parse_account_nonce(ctx, nonce)?;
-
-        let chain_hex_id = prop_object
-            .get(ctx, "chainHexId")?
-            .downcast_or_throw::<JsBuffer, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast chainHexId")?;
-        let chain_hex_id = chain_hex_id.borrow(&ctx.lock()).as_slice().to_vec();
-        let chain_hex_id = chain_hex_id_from_vec(ctx, chain_hex_id)?;
-
-        Ok(UnjailTxProps {
-            staking_address,
-            nonce,
-            chain_hex_id,
-        })
-    }
+    unjail_tx_of_tx_aux(tx_aux).chain_neon(ctx, "Unable to decode unjail transaction")
 }
@@ -0,0 +1,61 @@
+//! JS bindings of the fee estimation of unsigned transactions
+use cro_nodelib_core::fee_estimation::EstimateFeeOptions;
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+
+/// Estimate the exact fee of an unsigned transaction of any kind
+/// @arguments
+/// - options: {
+///     unsignedRawTx: Buffer,
+///     kind: 'transfer' | 'deposit' | 'withdrawUnbonded' | 'unbond' |
+///         'unjail' | 'nodeJoin',
+///     feeConfig: FeeConfig,
+///     witnessShape?: AddressParams[]
+///   }
+/// @return {
+///     fee: string,
+///     size: { transaction, witness, overhead, total }
+/// }
+pub fn estimate_fee(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let options: EstimateFeeOptions = object_argument(&mut ctx, 0)?;
+    options.verify().or_throw(&mut ctx)?;
+
+    let estimation = options
+        .estimate()
+        .chain_neon(&mut ctx, "Unable to estimate transaction fee")?;
+
+    let size = ctx.empty_object();
+    let breakdown = [
+        ("transaction", estimation.size.transaction),
+        ("witness", estimation.size.witness),
+        ("overhead", estimation.size.overhead()),
+        ("total", estimation.size.total),
+    ];
+    for (key, value) in breakdown.iter() {
+        let value = ctx.number(*value as f64);
+        size.set(&mut ctx, *key, value)
+            .chain_neon(&mut ctx, "Unable to set size breakdown of return object")?;
+    }
+
+    let return_object = ctx.empty_object();
+    let fee = ctx.string(u64::from(estimation.fee).to_string());
+    return_object
+        .set(&mut ctx, "fee", fee)
+        .chain_neon(&mut ctx, "Unable to set fee of return object")?;
+    return_object
+        .set(&mut ctx, "size", size)
+        .chain_neon(&mut ctx, "Unable to set size of return object")?;
+
+    Ok(return_object)
+}
+
+pub fn register_fee_estimation_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let estimate_fee_fn = JsFunction::new(ctx, estimate_fee)?;
+    js_object.set(ctx, "estimateFee", estimate_fee_fn)?;
+
+    ctx.export_value("feeEstimation", js_object)
+}
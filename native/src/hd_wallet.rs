@@ -1,18 +1,17 @@
-use client_core::{HDSeed, Mnemonic};
+use cro_nodelib_core::hd_wallet::{
+    seed_from_mnemonic, AddressDerivation, DeriveAddressesOptions, DerivedAddress,
+};
 use neon::prelude::*;
-use secstr::SecUtf8;
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
 
 fn get_seed_from_mnemonic(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
     let mnemonic = ctx.argument::<JsString>(0)?.value();
-    let mnemonic = SecUtf8::from(mnemonic.as_str());
 
-    let mnemonic =
-        Mnemonic::from_secstr(&mnemonic).chain_neon(&mut ctx, "Unable to deserialize mnemonic")?;
-
-    let value = mnemonic.seed();
+    let value =
+        seed_from_mnemonic(&mnemonic).chain_neon(&mut ctx, "Unable to deserialize mnemonic")?;
 
     let mut buffer = ctx.buffer(value.len() as u32)?;
     ctx.borrow_mut(&mut buffer, |data| {
@@ -35,10 +34,8 @@ fn derive_key_pair_from_seed(mut ctx: FunctionContext) -> JsResult<JsObject> {
         .parse::<u32>()
         .chain_neon(&mut ctx, "Unable to deserialize index")?;
 
-    let hd_seed = HDSeed::new(seed);
-
-    let (public_key, private_key) = hd_seed
-        .derive_key_pair(network, account, index)
+    let (public_key, private_key) = network
+        .derive_key_pair(&seed, account, index)
         .chain_neon(&mut ctx, "Unable to derive key pair")?;
 
     let serialized_public_key = public_key.serialize();
@@ -74,6 +71,116 @@ fn derive_key_pair_from_seed(mut ctx: FunctionContext) -> JsResult<JsObject> {
     Ok(js_object)
 }
 
+struct DeriveAddressesTask(AddressDerivation);
+
+impl DeriveAddressesTask {
+    fn argument(ctx: &mut FunctionContext) -> NeonResult<DeriveAddressesTask> {
+        let options: DeriveAddressesOptions = object_argument(ctx, 0)?;
+
+        AddressDerivation::new(options)
+            .map(DeriveAddressesTask)
+            .or_throw(ctx)
+    }
+
+    fn derive(&self) -> Result<Vec<DerivedAddress>, String> {
+        self.0.derive()
+    }
+}
+
+impl Task for DeriveAddressesTask {
+    type Output = Vec<DerivedAddress>;
+    type Error = String;
+    type JsEvent = JsArray;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        self.derive()
+    }
+
+    fn complete(
+        self,
+        mut ctx: TaskContext,
+        result: Result<Self::Output, Self::Error>,
+    ) -> JsResult<Self::JsEvent> {
+        match result {
+            Ok(derived_addresses) => derived_addresses_to_js_array(&mut ctx, &derived_addresses),
+            Err(err) => ctx.throw_error(err),
+        }
+    }
+}
+
+fn bytes_to_js_buffer<'a, C>(ctx: &mut C, bytes: &[u8]) -> JsResult<'a, JsBuffer>
+where
+    C: Context<'a>,
+{
+    let mut buffer = ctx.buffer(bytes.len() as u32)?;
+    ctx.borrow_mut(&mut buffer, |data| {
+        let slice = data.as_mut_slice();
+        slice.copy_from_slice(bytes);
+    });
+
+    Ok(buffer)
+}
+
+fn derived_addresses_to_js_array<'a, C>(
+    ctx: &mut C,
+    derived_addresses: &[DerivedAddress],
+) -> JsResult<'a, JsArray>
+where
+    C: Context<'a>,
+{
+    let js_array = JsArray::new(ctx, derived_addresses.len() as u32);
+    for (i, derived_address) in derived_addresses.iter().enumerate() {
+        let js_object = JsObject::new(ctx);
+
+        let index = ctx.number(derived_address.index);
+        js_object.set(ctx, "index", index)?;
+        let public_key = bytes_to_js_buffer(ctx, &derived_address.public_key.serialize())?;
+        js_object.set(ctx, "publicKey", public_key)?;
+        let compressed_public_key =
+            bytes_to_js_buffer(ctx, &derived_address.public_key.serialize_compressed())?;
+        js_object.set(ctx, "compressedPublicKey", compressed_public_key)?;
+        if let Some(ref private_key) = derived_address.private_key {
+            let private_key = bytes_to_js_buffer(ctx, &private_key.serialize())?;
+            js_object.set(ctx, "privateKey", private_key)?;
+        }
+        let transfer_address = ctx.string(&derived_address.transfer_address);
+        js_object.set(ctx, "transferAddress", transfer_address)?;
+        let staking_address = ctx.string(&derived_address.staking_address);
+        js_object.set(ctx, "stakingAddress", staking_address)?;
+
+        js_array.set(ctx, i as u32, js_object)?;
+    }
+
+    Ok(js_array)
+}
+
+/// Derive the key pairs and addresses of a range of indexes of an account
+/// @arguments
+/// - options: { seed, network, account, startIndex, count, includePrivateKey? }
+/// @return Array<{ index, publicKey, compressedPublicKey, privateKey?, transferAddress, stakingAddress }>
+fn derive_addresses_from_seed(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let task = DeriveAddressesTask::argument(&mut ctx)?;
+
+    let derived_addresses = task.derive();
+    let derived_addresses = match derived_addresses {
+        Ok(derived_addresses) => derived_addresses,
+        Err(err) => return ctx.throw_error(err),
+    };
+
+    derived_addresses_to_js_array(&mut ctx, &derived_addresses)
+}
+
+/// Derive the key pairs and addresses of a range of indexes off the main
+/// thread. Result is passed to the Node-style callback at the last argument
+fn derive_addresses_from_seed_async(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let task = DeriveAddressesTask::argument(&mut ctx)?;
+    let callback = ctx.argument::<JsFunction>(1)?;
+
+    task.schedule(callback);
+
+    Ok(ctx.undefined())
+}
+
 pub fn register_hd_wallet_module(ctx: &mut ModuleContext) -> NeonResult<()> {
     let js_object = JsObject::new(ctx);
 
@@ -83,5 +190,20 @@ pub fn register_hd_wallet_module(ctx: &mut ModuleContext) -> NeonResult<()> {
     let derive_key_pair_from_seed_fn = JsFunction::new(ctx, derive_key_pair_from_seed)?;
     js_object.set(ctx, "deriveKeyPairFromSeed", derive_key_pair_from_seed_fn)?;
 
+    let derive_addresses_from_seed_fn = JsFunction::new(ctx, derive_addresses_from_seed)?;
+    js_object.set(
+        ctx,
+        "deriveAddressesFromSeed",
+        derive_addresses_from_seed_fn,
+    )?;
+
+    let derive_addresses_from_seed_async_fn =
+        JsFunction::new(ctx, derive_addresses_from_seed_async)?;
+    js_object.set(
+        ctx,
+        "deriveAddressesFromSeedAsync",
+        derive_addresses_from_seed_async_fn,
+    )?;
+
     ctx.export_value("hdWallet", js_object)
 }
@@ -1,6 +1,5 @@
-use std::str::FromStr;
-
 use client_common::{PrivateKey, PublicKey};
+use cro_nodelib_core::key_pair;
 use neon::prelude::*;
 
 use crate::error::ClientErrorNeonExt;
@@ -85,12 +84,7 @@ pub fn new_private_key(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
 pub fn is_valid_view_key(mut ctx: FunctionContext) -> JsResult<JsBoolean> {
     let view_key = u8_buffer_argument(&mut ctx, 0)?;
 
-    let view_key = hex::encode_upper(view_key);
-
-    let is_valid = match PublicKey::from_str(&view_key) {
-        Ok(_) => true,
-        _ => false,
-    };
+    let is_valid = key_pair::is_valid_view_key(&view_key);
 
     Ok(ctx.boolean(is_valid))
 }
@@ -1,36 +1,73 @@
 use neon::register_module;
 
 mod address;
+mod coin;
 mod common;
 mod council_node_transaction;
 mod error;
+mod fee_estimation;
 mod function_types;
 mod hd_wallet;
 mod key_pair;
+mod local_validation;
 mod multi_sig;
+mod network;
+mod output_policy;
+mod schema;
 mod signer;
+mod staking_lifecycle;
+mod staking_queue;
 mod staking_transaction;
+mod subscription;
+mod timespec;
 mod transfer_transaction;
+mod tx_assertion;
 mod tx_aux;
+mod validator_key;
+mod wallet_db;
 
 use address::register_address_module;
+use coin::register_coin_module;
 use council_node_transaction::register_council_node_transaction_module;
+use fee_estimation::register_fee_estimation_module;
 use hd_wallet::register_hd_wallet_module;
 use key_pair::register_key_pair_module;
+use local_validation::register_local_validation_module;
 use multi_sig::register_multi_sig_module;
+use network::register_network_module;
+use output_policy::register_output_policy_module;
 use signer::register_signer_module;
+use staking_lifecycle::register_staking_lifecycle_module;
+use staking_queue::register_staking_queue_module;
 use staking_transaction::register_staking_transaction_module;
+use subscription::register_subscription_module;
+use timespec::register_timespec_module;
 use transfer_transaction::register_transfer_transaction_module;
+use tx_assertion::register_tx_assertion_module;
+use validator_key::register_validator_key_module;
+use wallet_db::register_wallet_db_module;
 
 register_module!(mut ctx, {
     register_address_module(&mut ctx)?;
+    register_coin_module(&mut ctx)?;
     register_council_node_transaction_module(&mut ctx)?;
+    register_fee_estimation_module(&mut ctx)?;
     register_hd_wallet_module(&mut ctx)?;
     register_key_pair_module(&mut ctx)?;
+    register_local_validation_module(&mut ctx)?;
     register_signer_module(&mut ctx)?;
+    register_staking_lifecycle_module(&mut ctx)?;
+    register_staking_queue_module(&mut ctx)?;
     register_staking_transaction_module(&mut ctx)?;
+    register_subscription_module(&mut ctx)?;
     register_multi_sig_module(&mut ctx)?;
+    register_network_module(&mut ctx)?;
+    register_output_policy_module(&mut ctx)?;
+    register_timespec_module(&mut ctx)?;
     register_transfer_transaction_module(&mut ctx)?;
+    register_tx_assertion_module(&mut ctx)?;
+    register_validator_key_module(&mut ctx)?;
+    register_wallet_db_module(&mut ctx)?;
 
     Ok(())
 });
@@ -0,0 +1,64 @@
+//! Local validation of signed transactions before broadcast
+use cro_nodelib_core::local_validation::{validate_locally, ValidateLocallyOptions};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+
+/// Validate a signed transaction with the validation rules of the chain
+/// @arguments
+/// - options: {
+///     transaction:
+///         | { kind: 'transfer', incompleteHex: Buffer }
+///         | { kind: 'deposit', unsignedRawTx: Buffer, witnesses: Buffer[] }
+///         | { kind: 'withdrawUnbonded', unsignedRawTx: Buffer, witness: Buffer }
+///         | { kind: 'unbond' | 'unjail', txAux: Buffer },
+///     chainHexId: Buffer,
+///     feeConfig: FeeConfig,
+///     context: {
+///         blockTime: number,
+///         minFee?: string,
+///         unbondingPeriod?: number, // required for unbond transactions
+///         spentOutputs?: Output[],
+///         stakedState?: StakedState
+///     }
+///   }
+/// @return { valid: boolean, reason?: string }
+pub fn validate(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let options: ValidateLocallyOptions = object_argument(&mut ctx, 0)?;
+    let context = options.to_validation_context().or_throw(&mut ctx)?;
+
+    let transaction = options
+        .transaction
+        .to_local_transaction(&options.fee_config)
+        .chain_neon(&mut ctx, "Unable to decode transaction to validate")?;
+    let reason = validate_locally(
+        &transaction,
+        &context,
+        &options.fee_config.to_fee_algorithm(),
+    )
+    .chain_neon(&mut ctx, "Unable to validate transaction locally")?;
+
+    let return_object = ctx.empty_object();
+    let valid = ctx.boolean(reason.is_none());
+    return_object
+        .set(&mut ctx, "valid", valid)
+        .chain_neon(&mut ctx, "Unable to set valid of return object")?;
+    if let Some(reason) = reason {
+        let reason = ctx.string(reason);
+        return_object
+            .set(&mut ctx, "reason", reason)
+            .chain_neon(&mut ctx, "Unable to set reason of return object")?;
+    }
+
+    Ok(return_object)
+}
+
+pub fn register_local_validation_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let validate_fn = JsFunction::new(ctx, validate)?;
+    js_object.set(ctx, "validate", validate_fn)?;
+
+    ctx.export_value("localValidation", js_object)
+}
@@ -1,12 +1,7 @@
-use chain_core::init::address::CroAddress;
-use chain_core::tx::data::address::ExtendedAddr;
-use client_common::{MultiSigAddress, PublicKey, SECP};
 use neon::prelude::*;
 
 use chain_core::common::H256;
-use client_core::multi_sig::MultiSigBuilder;
-use secp256k1::Message;
-use secp256k1::{schnorrsig::schnorr_verify, schnorrsig::SchnorrSignature};
+use cro_nodelib_core::multi_sig::{multi_sig_transfer_address, verify_signature, MultiSigBuilder};
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
@@ -28,13 +23,9 @@ pub fn create_address(mut ctx: FunctionContext) -> JsResult<JsString> {
         .value() as usize;
     let network = network_argument(&mut ctx, 3)?;
 
-    let multi_sig_address = MultiSigAddress::new(public_keys, self_public_key, required_signers)
-        .chain_neon(&mut ctx, "Unable to create MultiSig address")?;
-
-    let extended_address = ExtendedAddr::from(multi_sig_address);
-    let cro_address = extended_address
-        .to_cro(network)
-        .chain_neon(&mut ctx, "Unable to convert to CRO address")?;
+    let cro_address =
+        multi_sig_transfer_address(&network, public_keys, self_public_key, required_signers)
+            .chain_neon(&mut ctx, "Unable to create MultiSig address")?;
 
     Ok(ctx.string(cro_address))
 }
@@ -323,23 +314,12 @@ pub fn sign(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
 /// - public_keys: all co-signers' public keys
 /// @return boolean
 pub fn verify(mut ctx: FunctionContext) -> JsResult<JsBoolean> {
-    let raw = u8_buffer_argument(&mut ctx, 0)?;
-    let signature =
-        SchnorrSignature::from_default(&raw).chain_neon(&mut ctx, "Unable to restore signature")?;
-
+    let signature = u8_buffer_argument(&mut ctx, 0)?;
     let message = h256_str_argument(&mut ctx, 1)?;
-    let message = Message::from_slice(&message).chain_neon(&mut ctx, "Unable to form message")?;
-
-    let mut public_keys = public_key_vector_argument(&mut ctx, 2)?;
-    public_keys.sort(); // sort the public keys to keep the order consistency
+    let public_keys = public_key_vector_argument(&mut ctx, 2)?;
 
-    let combined_public_key = PublicKey::combine(&public_keys)
-        .chain_neon(&mut ctx, "Unable combine public keys")?
-        .0;
-
-    let verify_passed = SECP.with(|secp| {
-        schnorr_verify(&secp, &message, &signature, &combined_public_key.into()).is_ok()
-    });
+    let verify_passed = verify_signature(&signature, &message, public_keys)
+        .chain_neon(&mut ctx, "Unable to verify signature")?;
 
     Ok(ctx.boolean(verify_passed))
 }
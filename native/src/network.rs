@@ -0,0 +1,93 @@
+//! JS bindings of the network registry
+use neon::prelude::*;
+
+use cro_nodelib_core::network::{
+    insert_network, network_by_chain_hex_id, registered_networks, NetworkDefinition,
+};
+
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+use crate::schema::object_argument;
+
+fn network_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    network: &NetworkDefinition,
+) -> JsResult<'a, JsObject> {
+    let js_object = ctx.empty_object();
+
+    let name = ctx.string(&network.name);
+    js_object.set(ctx, "name", name)?;
+
+    if let Some(chain_hex_id) = network.chain_hex_id {
+        let mut chain_hex_id_buffer = ctx.buffer(1)?;
+        ctx.borrow_mut(&mut chain_hex_id_buffer, |data| {
+            data.as_mut_slice::<u8>()[0] = chain_hex_id;
+        });
+        js_object.set(ctx, "chainHexId", chain_hex_id_buffer)?;
+    }
+
+    let address_prefix = ctx.string(&network.address_prefix);
+    js_object.set(ctx, "addressPrefix", address_prefix)?;
+
+    let bip44_coin_type = ctx.number(network.bip44_coin_type);
+    js_object.set(ctx, "bip44CoinType", bip44_coin_type)?;
+
+    Ok(js_object)
+}
+
+pub fn register_network(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let network: NetworkDefinition = object_argument(&mut ctx, 0)?;
+
+    insert_network(network).chain_neon(&mut ctx, "Unable to register network")?;
+
+    Ok(ctx.undefined())
+}
+
+pub fn get_network_by_chain_hex_id(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let chain_hex_id = chain_hex_id_argument(&mut ctx, 0)?;
+
+    let network =
+        network_by_chain_hex_id(chain_hex_id).chain_neon(&mut ctx, "Unable to find network")?;
+
+    network_to_js_object(&mut ctx, &network)
+}
+
+pub fn get_network_by_name(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let network = network_argument(&mut ctx, 0)?;
+
+    network_to_js_object(&mut ctx, &network)
+}
+
+pub fn list_networks(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let networks = registered_networks();
+
+    let js_array = JsArray::new(&mut ctx, networks.len() as u32);
+    for (i, network) in networks.iter().enumerate() {
+        let js_object = network_to_js_object(&mut ctx, network)?;
+        js_array.set(&mut ctx, i as u32, js_object)?;
+    }
+
+    Ok(js_array)
+}
+
+pub fn register_network_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let register_network_fn = JsFunction::new(ctx, register_network)?;
+    js_object.set(ctx, "registerNetwork", register_network_fn)?;
+
+    let get_network_by_chain_hex_id_fn = JsFunction::new(ctx, get_network_by_chain_hex_id)?;
+    js_object.set(
+        ctx,
+        "getNetworkByChainHexId",
+        get_network_by_chain_hex_id_fn,
+    )?;
+
+    let get_network_by_name_fn = JsFunction::new(ctx, get_network_by_name)?;
+    js_object.set(ctx, "getNetworkByName", get_network_by_name_fn)?;
+
+    let list_networks_fn = JsFunction::new(ctx, list_networks)?;
+    js_object.set(ctx, "listNetworks", list_networks_fn)?;
+
+    ctx.export_value("network", js_object)
+}
@@ -0,0 +1,54 @@
+//! Policy checks of transaction outputs before signing
+use cro_nodelib_core::output_policy::{CheckPolicyOptions, PolicyViolation};
+use neon::prelude::*;
+
+use crate::schema::{object_argument, SchemaResultNeonExt};
+
+/// Check the outputs of a transaction against the policy
+/// @arguments
+/// - options: {
+///     chainHexId: Buffer,
+///     inputs: Output[], outputs spent by the inputs
+///     inputAmount?: string, amount available when there is no input
+///     outputs: Output[],
+///     fee?: string,
+///     blockTime: number,
+///     policy: OutputPolicy
+///   }
+/// @return Array<{ code, path, message }>
+pub fn check_policy(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let options: CheckPolicyOptions = object_argument(&mut ctx, 0)?;
+    let violations = options.check().or_throw(&mut ctx)?;
+
+    violations_to_js_array(&mut ctx, &violations)
+}
+
+fn violations_to_js_array<'a>(
+    ctx: &mut FunctionContext<'a>,
+    violations: &[PolicyViolation],
+) -> JsResult<'a, JsArray> {
+    let js_violations = JsArray::new(ctx, violations.len() as u32);
+    for (i, violation) in violations.iter().enumerate() {
+        let js_violation = JsObject::new(ctx);
+
+        let code = ctx.string(violation.code.name());
+        js_violation.set(ctx, "code", code)?;
+        let path = ctx.string(&violation.path);
+        js_violation.set(ctx, "path", path)?;
+        let message = ctx.string(&violation.message);
+        js_violation.set(ctx, "message", message)?;
+
+        js_violations.set(ctx, i as u32, js_violation)?;
+    }
+
+    Ok(js_violations)
+}
+
+pub fn register_output_policy_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let check_policy_fn = JsFunction::new(ctx, check_policy)?;
+    js_object.set(ctx, "checkPolicy", check_policy_fn)?;
+
+    ctx.export_value("outputPolicy", js_object)
+}
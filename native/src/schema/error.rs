@@ -0,0 +1,23 @@
+use neon::prelude::*;
+
+use cro_nodelib_core::schema::SchemaResult;
+
+pub trait SchemaResultNeonExt<T> {
+    /// Throws the schema error as a JS error
+    fn or_throw<'a, C>(self, ctx: &mut C) -> NeonResult<T>
+    where
+        C: Context<'a>;
+}
+
+impl<T> SchemaResultNeonExt<T> for SchemaResult<T> {
+    #[inline]
+    fn or_throw<'a, C>(self, ctx: &mut C) -> NeonResult<T>
+    where
+        C: Context<'a>,
+    {
+        match self {
+            Err(err) => ctx.throw_error(err.to_string()),
+            Ok(v) => Ok(v),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Declarative conversion of JavaScript option objects into Rust structs
+//!
+//! JS values are first converted to a `serde_json::Value` and then
+//! deserialized with `serde_path_to_error`, so that every error reports the
+//! exact location of the offending field, e.g.
+//! `inputs[2].addressParams.totalSigners`.
+//!
+//! Buffers are converted to an object tagged with `de::BUFFER_TAG` holding
+//! their hex encoding rather than an array of numbers, so that they are not
+//! mistaken for strings. `de::buffer` decodes them back into bytes.
+mod error;
+
+use neon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+pub use cro_nodelib_core::schema::{de, from_js_json_value, types, SchemaError, SchemaResult};
+pub use error::SchemaResultNeonExt;
+
+/// Maximum nesting level of a JS value accepted as options
+const MAX_DEPTH: usize = 32;
+
+/// Deserialize the `i`-th argument into `T`
+#[inline]
+pub fn object_argument<T>(ctx: &mut FunctionContext, i: i32) -> NeonResult<T>
+where
+    T: DeserializeOwned,
+{
+    let value = ctx.argument::<JsValue>(i)?;
+
+    from_js_value(ctx, value)
+}
+
+/// Deserialize a JS value into `T` and throw an error with the path of the
+/// offending field on failure
+#[inline]
+pub fn from_js_value<'a, T>(
+    ctx: &mut FunctionContext<'a>,
+    value: Handle<'a, JsValue>,
+) -> NeonResult<T>
+where
+    T: DeserializeOwned,
+{
+    let value = to_json_value(ctx, value, "", 0)?;
+
+    from_js_json_value(value).or_throw(ctx)
+}
+
+fn to_json_value<'a>(
+    ctx: &mut FunctionContext<'a>,
+    value: Handle<'a, JsValue>,
+    path: &str,
+    depth: usize,
+) -> NeonResult<Value> {
+    if depth > MAX_DEPTH {
+        return ctx.throw_error(SchemaError::new(path, "value is nested too deeply").to_string());
+    }
+
+    if value.is_a::<JsNull>() || value.is_a::<JsUndefined>() {
+        Ok(Value::Null)
+    } else if let Ok(boolean) = value.downcast::<JsBoolean>() {
+        Ok(Value::Bool(boolean.value()))
+    } else if let Ok(number) = value.downcast::<JsNumber>() {
+        Ok(number_to_json_value(number.value()))
+    } else if let Ok(string) = value.downcast::<JsString>() {
+        Ok(Value::String(string.value()))
+    } else if let Ok(buffer) = value.downcast::<JsBuffer>() {
+        let hex = ctx.borrow(&buffer, |data| hex::encode(data.as_slice::<u8>()));
+
+        let mut tagged = Map::with_capacity(1);
+        tagged.insert(de::BUFFER_TAG.to_owned(), Value::String(hex));
+
+        Ok(Value::Object(tagged))
+    } else if let Ok(array) = value.downcast::<JsArray>() {
+        let items = array.to_vec(ctx)?;
+
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| to_json_value(ctx, item, &format!("{}[{}]", path, i), depth + 1))
+            .collect::<NeonResult<Vec<Value>>>()
+            .map(Value::Array)
+    } else if value.is_a::<JsFunction>() {
+        ctx.throw_error(SchemaError::new(path, "functions are not supported").to_string())
+    } else if let Ok(object) = value.downcast::<JsObject>() {
+        let keys = object.get_own_property_names(ctx)?.to_vec(ctx)?;
+
+        let mut map = Map::with_capacity(keys.len());
+        for key in keys.into_iter() {
+            let key = match key.downcast::<JsString>() {
+                Ok(key) => key.value(),
+                Err(_) => continue,
+            };
+            let item = object.get(ctx, key.as_str())?;
+            let item_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+
+            map.insert(key, to_json_value(ctx, item, &item_path, depth + 1)?);
+        }
+
+        Ok(Value::Object(map))
+    } else {
+        ctx.throw_error(SchemaError::new(path, "unsupported value type").to_string())
+    }
+}
+
+fn number_to_json_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number >= 0.0 && number <= u64::max_value() as f64 {
+        Value::Number(Number::from(number as u64))
+    } else if number.fract() == 0.0 && number < 0.0 && number >= i64::min_value() as f64 {
+        Value::Number(Number::from(number as i64))
+    } else {
+        Number::from_f64(number)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
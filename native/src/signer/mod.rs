@@ -1,12 +1,10 @@
-mod key_pair_signer;
-
 use neon::prelude::*;
 use parity_scale_codec::Encode;
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
 
-pub use key_pair_signer::KeyPairSigner;
+pub use cro_nodelib_core::signer::KeyPairSigner;
 
 fn schnorr_sign_txid(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
     let message = ctx.argument::<JsBuffer>(0)?;
@@ -0,0 +1,151 @@
+use cro_nodelib_core::staking_lifecycle::{
+    build_step_transaction, BuildStepTransactionOptions, PlanNextStepOptions, StakingStep,
+    StepOptions,
+};
+use neon::prelude::*;
+use parity_scale_codec::Encode;
+
+use crate::common::features_argument;
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+use crate::signer::KeyPairSigner;
+
+/// Plan the next valid step of the staking lifecycle towards the goal
+pub fn plan_next_staking_step(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let options: PlanNextStepOptions = object_argument(&mut ctx, 0)?;
+
+    let step = options.plan();
+
+    staking_step_to_js_object(&mut ctx, &step)
+}
+
+/// Build the signed transaction of a planned deposit, unjail, unbond or
+/// withdraw step
+pub fn build_staking_step_transaction(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let options: BuildStepTransactionOptions = object_argument(&mut ctx, 0)?;
+    options.verify().or_throw(&mut ctx)?;
+    let key_pair = key_pair_argument(&mut ctx, 1)?;
+    let signer = KeyPairSigner::new(key_pair.0, key_pair.1)
+        .chain_neon(&mut ctx, "Unable to create KeyPair signer")?;
+
+    let obfuscation = match options.step {
+        StepOptions::Deposit { .. } | StepOptions::Withdraw { .. } => {
+            let tendermint_address = ctx.argument::<JsString>(2)?.value();
+            let features = features_argument(&mut ctx, 3)?;
+
+            Some((tendermint_address, features))
+        }
+        _ => None,
+    };
+
+    let transaction = build_step_transaction(
+        &options,
+        &signer,
+        obfuscation
+            .as_ref()
+            .map(|(tendermint_address, features)| (tendermint_address.as_str(), *features)),
+    )
+    .chain_neon(&mut ctx, "Unable to build staking step transaction")?;
+
+    let return_object = ctx.empty_object();
+
+    let tx_id = ctx.string(hex::encode(transaction.tx_id));
+    return_object
+        .set(&mut ctx, "txId", tx_id)
+        .chain_neon(&mut ctx, "Unable to set txId of return object")?;
+
+    let tx_aux = transaction.tx_aux.encode();
+    let mut tx_hex = ctx.buffer(tx_aux.len() as u32)?;
+    ctx.borrow_mut(&mut tx_hex, |data| {
+        data.as_mut_slice().copy_from_slice(&tx_aux);
+    });
+    return_object
+        .set(&mut ctx, "txHex", tx_hex)
+        .chain_neon(&mut ctx, "Unable to set txHex of return object")?;
+
+    if let Some(amount) = transaction.amount {
+        let amount = ctx.string(u64::from(amount).to_string());
+        return_object
+            .set(&mut ctx, "amount", amount)
+            .chain_neon(&mut ctx, "Unable to set amount of return object")?;
+    }
+
+    if let Some(expected_nonce) = transaction.expected_nonce {
+        let expected_nonce = ctx.string(expected_nonce.to_string());
+        return_object
+            .set(&mut ctx, "expectedNonce", expected_nonce)
+            .chain_neon(&mut ctx, "Unable to set expectedNonce of return object")?;
+    }
+
+    Ok(return_object)
+}
+
+fn staking_step_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    step: &StakingStep,
+) -> JsResult<'a, JsObject> {
+    let object = ctx.empty_object();
+
+    let step_type = ctx.string(step.name());
+    object
+        .set(ctx, "type", step_type)
+        .chain_neon(ctx, "Unable to set type of staking step")?;
+
+    let (nonce, amount, until, expected_nonce) = match step {
+        StakingStep::WaitForNonce { expected_nonce } => (None, None, None, Some(*expected_nonce)),
+        StakingStep::WaitJailed { until } | StakingStep::WaitUnbonding { until } => {
+            (None, None, Some(*until), None)
+        }
+        StakingStep::Deposit { nonce } | StakingStep::Unjail { nonce } => {
+            (Some(*nonce), None, None, None)
+        }
+        StakingStep::Unbond { nonce, amount } | StakingStep::Withdraw { nonce, amount } => {
+            (Some(*nonce), Some(*amount), None, None)
+        }
+        StakingStep::Done => (None, None, None, None),
+    };
+
+    if let Some(nonce) = nonce {
+        let nonce = ctx.string(nonce.to_string());
+        object
+            .set(ctx, "nonce", nonce)
+            .chain_neon(ctx, "Unable to set nonce of staking step")?;
+    }
+    if let Some(amount) = amount {
+        let amount = ctx.string(u64::from(amount).to_string());
+        object
+            .set(ctx, "amount", amount)
+            .chain_neon(ctx, "Unable to set amount of staking step")?;
+    }
+    if let Some(until) = until {
+        let until = ctx.number(until as f64);
+        object
+            .set(ctx, "until", until)
+            .chain_neon(ctx, "Unable to set until of staking step")?;
+    }
+    if let Some(expected_nonce) = expected_nonce {
+        let expected_nonce = ctx.string(expected_nonce.to_string());
+        object
+            .set(ctx, "expectedNonce", expected_nonce)
+            .chain_neon(ctx, "Unable to set expectedNonce of staking step")?;
+    }
+
+    Ok(object)
+}
+
+pub fn register_staking_lifecycle_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let plan_next_staking_step_fn = JsFunction::new(ctx, plan_next_staking_step)?;
+    js_object.set(ctx, "planNextStakingStep", plan_next_staking_step_fn)?;
+
+    let build_staking_step_transaction_fn = JsFunction::new(ctx, build_staking_step_transaction)?;
+    js_object.set(
+        ctx,
+        "buildStakingStepTransaction",
+        build_staking_step_transaction_fn,
+    )?;
+
+    ctx.export_value("stakingLifecycle", js_object)
+}
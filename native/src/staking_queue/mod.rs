@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use cro_nodelib_core::staking_queue::{
+    PendingOperation, QueuedOperationOptions, StakingQueue, StakingQueueOptions,
+};
+use lazy_static::lazy_static;
+use neon::prelude::*;
+use parity_scale_codec::Encode;
+
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+use crate::signer::KeyPairSigner;
+
+lazy_static! {
+    /// Staking queues by id. JS only holds the id, the queue is dropped when
+    /// removed.
+    static ref STAKING_QUEUES: Mutex<HashMap<u32, Arc<Mutex<StakingQueue>>>> =
+        Mutex::new(HashMap::new());
+}
+
+static NEXT_STAKING_QUEUE_ID: AtomicU32 = AtomicU32::new(1);
+
+fn staking_queue_argument(
+    ctx: &mut FunctionContext,
+    i: i32,
+) -> NeonResult<Arc<Mutex<StakingQueue>>> {
+    let id = ctx.argument::<JsNumber>(i)?.value() as u32;
+
+    let staking_queue = STAKING_QUEUES
+        .lock()
+        .expect("Staking queues lock is poisoned")
+        .get(&id)
+        .cloned();
+    match staking_queue {
+        Some(staking_queue) => Ok(staking_queue),
+        None => ctx.throw_error(format!("Staking queue {} is freed", id)),
+    }
+}
+
+/// Create an empty queue of the staked state operations of the staking
+/// address, signed with its key pair
+/// @arguments
+/// - options: {
+///     stakingAddress: string,
+///     chainHexId: Buffer,
+///     committedNonce: string,
+///   }
+/// - keyPair: KeyPair
+/// @return staking queue id
+pub fn create(mut ctx: FunctionContext) -> JsResult<JsNumber> {
+    let options: StakingQueueOptions = object_argument(&mut ctx, 0)?;
+    let key_pair = key_pair_argument(&mut ctx, 1)?;
+    let signer = KeyPairSigner::new(key_pair.0, key_pair.1)
+        .chain_neon(&mut ctx, "Unable to create KeyPair signer")?;
+    let staking_queue = options
+        .to_queue(signer)
+        .chain_neon(&mut ctx, "Unable to create staking queue")?;
+
+    let id = NEXT_STAKING_QUEUE_ID.fetch_add(1, Ordering::SeqCst);
+    STAKING_QUEUES
+        .lock()
+        .expect("Staking queues lock is poisoned")
+        .insert(id, Arc::new(Mutex::new(staking_queue)));
+
+    Ok(ctx.number(id))
+}
+
+/// Drop the staking queue
+/// @arguments
+/// - id: staking queue id
+pub fn free(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let id = ctx.argument::<JsNumber>(0)?.value() as u32;
+
+    STAKING_QUEUES
+        .lock()
+        .expect("Staking queues lock is poisoned")
+        .remove(&id);
+
+    Ok(ctx.undefined())
+}
+
+/// Sign the operation with the next nonce and queue it
+/// @arguments
+/// - id: staking queue id
+/// - operation: { type: 'unbond', amount: string }
+///     | { type: 'unjail' }
+///     | { type: 'nodeJoin', councilNode: CouncilNode }
+/// @return QueuedTransaction
+pub fn push(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let staking_queue = staking_queue_argument(&mut ctx, 0)?;
+    let operation: QueuedOperationOptions = object_argument(&mut ctx, 1)?;
+    let operation = operation.to_operation().or_throw(&mut ctx)?;
+
+    let mut staking_queue = staking_queue
+        .lock()
+        .expect("Staking queue lock is poisoned");
+    let pending = staking_queue
+        .push(operation)
+        .chain_neon(&mut ctx, "Unable to sign queued transaction")?;
+
+    pending_operation_to_js_object(&mut ctx, pending)
+}
+
+/// Remove the committed operation, which should be the first pending one
+/// @arguments
+/// - id: staking queue id
+/// - operationId: id of the committed operation
+pub fn confirm(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let staking_queue = staking_queue_argument(&mut ctx, 0)?;
+    let operation_id = ctx.argument::<JsNumber>(1)?.value() as u64;
+
+    staking_queue
+        .lock()
+        .expect("Staking queue lock is poisoned")
+        .confirm(operation_id)
+        .chain_neon(&mut ctx, "Unable to confirm queued operation")?;
+
+    Ok(ctx.undefined())
+}
+
+/// Remove the failed operation and re-base the following ones on its nonce
+/// @arguments
+/// - id: staking queue id
+/// - operationId: id of the failed operation
+/// @return QueuedTransaction[] re-based transactions
+pub fn fail(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let staking_queue = staking_queue_argument(&mut ctx, 0)?;
+    let operation_id = ctx.argument::<JsNumber>(1)?.value() as u64;
+
+    let mut staking_queue = staking_queue
+        .lock()
+        .expect("Staking queue lock is poisoned");
+    let rebased = staking_queue
+        .fail(operation_id)
+        .chain_neon(&mut ctx, "Unable to fail queued operation")?;
+
+    pending_operations_to_js_array(&mut ctx, rebased)
+}
+
+/// Re-base the queue on the nonce of the staked state queried from the
+/// Tendermint node
+/// @arguments
+/// - id: staking queue id
+/// - tendermintAddress: string
+/// @return ids of the committed operations
+pub fn sync(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let staking_queue = staking_queue_argument(&mut ctx, 0)?;
+    let tendermint_address = ctx.argument::<JsString>(1)?.value();
+
+    let committed = staking_queue
+        .lock()
+        .expect("Staking queue lock is poisoned")
+        .sync(&tendermint_address)
+        .chain_neon(&mut ctx, "Unable to sync staking queue")?;
+
+    let js_committed = JsArray::new(&mut ctx, committed.len() as u32);
+    for (i, operation_id) in committed.iter().enumerate() {
+        let operation_id = ctx.number(*operation_id as f64);
+        js_committed.set(&mut ctx, i as u32, operation_id)?;
+    }
+
+    Ok(js_committed)
+}
+
+/// Returns the signed transactions of the pending operations in nonce order
+/// @arguments
+/// - id: staking queue id
+/// @return QueuedTransaction[]
+pub fn transactions(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let staking_queue = staking_queue_argument(&mut ctx, 0)?;
+
+    let staking_queue = staking_queue
+        .lock()
+        .expect("Staking queue lock is poisoned");
+
+    pending_operations_to_js_array(&mut ctx, staking_queue.pending())
+}
+
+/// Returns the nonce of the staked state once every committed operation is
+/// applied
+/// @arguments
+/// - id: staking queue id
+/// @return string
+pub fn committed_nonce(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let staking_queue = staking_queue_argument(&mut ctx, 0)?;
+
+    let committed_nonce = staking_queue
+        .lock()
+        .expect("Staking queue lock is poisoned")
+        .committed_nonce();
+
+    Ok(ctx.string(committed_nonce.to_string()))
+}
+
+fn pending_operations_to_js_array<'a>(
+    ctx: &mut FunctionContext<'a>,
+    pending: &[PendingOperation],
+) -> JsResult<'a, JsArray> {
+    let js_array = JsArray::new(ctx, pending.len() as u32);
+    for (i, pending) in pending.iter().enumerate() {
+        let js_transaction = pending_operation_to_js_object(ctx, pending)?;
+        js_array.set(ctx, i as u32, js_transaction)?;
+    }
+
+    Ok(js_array)
+}
+
+fn pending_operation_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    pending: &PendingOperation,
+) -> JsResult<'a, JsObject> {
+    let object = ctx.empty_object();
+
+    let id = ctx.number(pending.id as f64);
+    object
+        .set(ctx, "id", id)
+        .chain_neon(ctx, "Unable to set id of queued transaction")?;
+
+    let nonce = ctx.string(pending.nonce.to_string());
+    object
+        .set(ctx, "nonce", nonce)
+        .chain_neon(ctx, "Unable to set nonce of queued transaction")?;
+
+    let tx_id = ctx.string(hex::encode(pending.tx_id));
+    object
+        .set(ctx, "txId", tx_id)
+        .chain_neon(ctx, "Unable to set txId of queued transaction")?;
+
+    let tx_aux = pending.tx_aux.encode();
+    let mut tx_hex = ctx.buffer(tx_aux.len() as u32)?;
+    ctx.borrow_mut(&mut tx_hex, |data| {
+        data.as_mut_slice().copy_from_slice(&tx_aux);
+    });
+    object
+        .set(ctx, "txHex", tx_hex)
+        .chain_neon(ctx, "Unable to set txHex of queued transaction")?;
+
+    Ok(object)
+}
+
+pub fn register_staking_queue_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let create_fn = JsFunction::new(ctx, create)?;
+    js_object.set(ctx, "create", create_fn)?;
+
+    let free_fn = JsFunction::new(ctx, free)?;
+    js_object.set(ctx, "free", free_fn)?;
+
+    let push_fn = JsFunction::new(ctx, push)?;
+    js_object.set(ctx, "push", push_fn)?;
+
+    let confirm_fn = JsFunction::new(ctx, confirm)?;
+    js_object.set(ctx, "confirm", confirm_fn)?;
+
+    let fail_fn = JsFunction::new(ctx, fail)?;
+    js_object.set(ctx, "fail", fail_fn)?;
+
+    let sync_fn = JsFunction::new(ctx, sync)?;
+    js_object.set(ctx, "sync", sync_fn)?;
+
+    let transactions_fn = JsFunction::new(ctx, transactions)?;
+    js_object.set(ctx, "transactions", transactions_fn)?;
+
+    let committed_nonce_fn = JsFunction::new(ctx, committed_nonce)?;
+    js_object.set(ctx, "committedNonce", committed_nonce_fn)?;
+
+    ctx.export_value("stakingQueue", js_object)
+}
@@ -1,24 +1,37 @@
-use std::str::FromStr;
-
 use neon::prelude::*;
 
-use chain_core::state::account::{DepositBondTx, StakedStateAddress, StakedStateOpAttributes};
-use chain_core::tx::data::input::TxoPointer;
+use chain_core::state::account::DepositBondTx;
+use chain_core::tx::data::output::TxOut;
 use chain_core::tx::witness::TxInWitness;
 use chain_core::tx::TransactionId;
 use client_common::SignedTransaction;
-use parity_scale_codec::{Decode, Encode};
-
-use crate::common::Features;
+use cro_nodelib_core::codec::{decode_transaction, decode_witness};
+use cro_nodelib_core::staking_transaction::deposit::{
+    deposit_amounts, sign_deposit_input, verify_deposit_input_witness,
+    BuildDepositTransactionOptions,
+};
+use parity_scale_codec::Encode;
+
+use crate::common::features_argument;
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
+use crate::schema::types::{network_of_chain_hex_id, OutputOptions};
+use crate::schema::{object_argument, SchemaResultNeonExt};
 use crate::tx_aux::signed_transaction_to_hex;
 
 pub fn build_raw_deposit_transaction(mut ctx: FunctionContext) -> JsResult<JsObject> {
-    let options = BuildRawDepositTransactionOption::parse(&mut ctx)?;
-    let attributes = StakedStateOpAttributes::new(options.chain_hex_id);
+    let options: BuildDepositTransactionOptions = object_argument(&mut ctx, 0)?;
+    let spent_outputs = options.spent_outputs().or_throw(&mut ctx)?;
+
+    let tx = options.to_tx();
 
-    let tx = DepositBondTx::new(options.inputs, options.to_address, attributes);
+    let amounts = match (spent_outputs, &options.fee_config) {
+        (Some(ref spent_outputs), Some(ref fee_config)) => Some(
+            deposit_amounts(&tx, spent_outputs, &fee_config.to_fee_algorithm())
+                .chain_neon(&mut ctx, "Unable to compute deposit amount")?,
+        ),
+        _ => None,
+    };
 
     let raw_tx = tx.encode();
     let mut raw_tx_buffer = ctx.buffer(raw_tx.len() as u32)?;
@@ -37,15 +50,77 @@ pub fn build_raw_deposit_transaction(mut ctx: FunctionContext) -> JsResult<JsObj
     return_object
         .set(&mut ctx, "txId", tx_id)
         .chain_neon(&mut ctx, "Unable to set txId of return object")?;
+    if let Some(amounts) = amounts {
+        let input_amount = ctx.string(u64::from(amounts.input_amount).to_string());
+        return_object
+            .set(&mut ctx, "inputAmount", input_amount)
+            .chain_neon(&mut ctx, "Unable to set inputAmount of return object")?;
+        let fee = ctx.string(u64::from(amounts.fee).to_string());
+        return_object
+            .set(&mut ctx, "fee", fee)
+            .chain_neon(&mut ctx, "Unable to set fee of return object")?;
+        let deposit_amount = ctx.string(u64::from(amounts.deposit_amount).to_string());
+        return_object
+            .set(&mut ctx, "depositAmount", deposit_amount)
+            .chain_neon(&mut ctx, "Unable to set depositAmount of return object")?;
+    }
 
     Ok(return_object)
 }
 
+/// Sign an input of the deposit transaction with the provided KeyPair
+/// @arguments
+/// - unsignedRawTx: Buffer of the deposit transaction
+/// - prevOutput: (optional) output spent by the input, verified to belong to
+///   the key pair when provided
+/// - keyPair
+/// @return encoded TxInWitness Buffer
+pub fn sign_deposit_transaction_input(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
+    let deposit_bond_tx = deposit_bond_tx_argument(&mut ctx, 0)?;
+    let prev_output = prev_output_argument(&mut ctx, 1, &deposit_bond_tx)?;
+    let (private_key, public_key) = key_pair_argument(&mut ctx, 2)?;
+
+    let witness = sign_deposit_input(
+        &deposit_bond_tx,
+        prev_output.as_ref(),
+        private_key,
+        public_key,
+    )
+    .chain_neon(&mut ctx, "Unable to sign transaction")?
+    .encode();
+
+    let mut witness_buffer = ctx.buffer(witness.len() as u32)?;
+    ctx.borrow_mut(&mut witness_buffer, |data| {
+        let data = data.as_mut_slice();
+        data.copy_from_slice(&witness)
+    });
+
+    Ok(witness_buffer)
+}
+
+/// Verify the witness unlocks the output spent by an input of the deposit
+/// transaction
+/// @arguments
+/// - unsignedRawTx: Buffer of the deposit transaction
+/// - prevOutput: output spent by the input
+/// - witness: encoded TxInWitness Buffer
+pub fn verify_deposit_transaction_input_witness(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let deposit_bond_tx = deposit_bond_tx_argument(&mut ctx, 0)?;
+    let prev_output = prev_output_argument(&mut ctx, 1, &deposit_bond_tx)?
+        .chain_neon(&mut ctx, "Missing previous output of the input")?;
+    let witness = tx_in_witness_argument(&mut ctx, 2)?;
+
+    verify_deposit_input_witness(&deposit_bond_tx, &prev_output, &witness)
+        .chain_neon(&mut ctx, "Unable to verify input witness")?;
+
+    Ok(ctx.undefined())
+}
+
 pub fn deposit_transaction_to_hex(mut ctx: FunctionContext) -> JsResult<JsBuffer> {
     let deposit_bond_tx = deposit_bond_tx_argument(&mut ctx, 0)?;
     let tx_in_witness_vec = tx_in_witness_vec_argument(&mut ctx, 1)?;
     let tendermint_address = ctx.argument::<JsString>(2)?.value();
-    let features = Features::argument(&mut ctx, 3)?;
+    let features = features_argument(&mut ctx, 3)?;
 
     let witness = tx_in_witness_vec.into();
     let signed_transaction = SignedTransaction::DepositStakeTransaction(deposit_bond_tx, witness);
@@ -55,9 +130,9 @@ pub fn deposit_transaction_to_hex(mut ctx: FunctionContext) -> JsResult<JsBuffer
 
 pub fn deposit_bond_tx_argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<DepositBondTx> {
     let deposit_bond_tx = ctx.argument::<JsBuffer>(i)?;
-    let mut deposit_bond_tx = deposit_bond_tx.borrow(&ctx.lock()).as_slice();
+    let deposit_bond_tx = deposit_bond_tx.borrow(&ctx.lock()).as_slice();
 
-    DepositBondTx::decode(&mut deposit_bond_tx)
+    decode_transaction(deposit_bond_tx, "raw transaction")
         .chain_neon(ctx, "Unable to decode raw transaction bytes")
 }
 
@@ -72,51 +147,36 @@ pub fn tx_in_witness_vec_argument(
             let tx_in_witness = tx_in_witness
                 .downcast_or_throw::<JsBuffer, FunctionContext>(ctx)
                 .chain_neon(ctx, "Unable to downcast witness")?;
-            let mut tx_in_witness = tx_in_witness.borrow(&ctx.lock()).as_slice();
+            let tx_in_witness = tx_in_witness.borrow(&ctx.lock()).as_slice();
 
-            TxInWitness::decode(&mut tx_in_witness).chain_neon(ctx, "Unable to decode witness")
+            decode_witness(tx_in_witness, "witness").chain_neon(ctx, "Unable to decode witness")
         })
         .collect()
 }
 
-struct BuildRawDepositTransactionOption {
-    inputs: Vec<TxoPointer>,
-    to_address: StakedStateAddress,
-    chain_hex_id: u8,
+fn tx_in_witness_argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<TxInWitness> {
+    let tx_in_witness = ctx.argument::<JsBuffer>(i)?;
+    let tx_in_witness = tx_in_witness.borrow(&ctx.lock()).as_slice();
+
+    decode_witness(tx_in_witness, "witness").chain_neon(ctx, "Unable to decode witness")
 }
 
-impl BuildRawDepositTransactionOption {
-    fn parse(ctx: &mut FunctionContext) -> NeonResult<BuildRawDepositTransactionOption> {
-        let options = ctx
-            .argument::<JsObject>(0)
-            .chain_neon(ctx, "Unable to deserialize options object")?;
-
-        let inputs = options
-            .get(ctx, "inputs")?
-            .downcast_or_throw::<JsArray, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast inputs")?
-            .to_vec(ctx)?;
-        let inputs = parse_prev_output_pointer_vec(ctx, inputs)?;
-
-        let to_address = options
-            .get(ctx, "toAddress")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast toAddress")?
-            .value();
-        let to_address = StakedStateAddress::from_str(&to_address)
-            .chain_neon(ctx, "Unable to deserialize staking address")?;
-
-        let chain_hex_id = options
-            .get(ctx, "chainHexId")?
-            .downcast_or_throw::<JsBuffer, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast chainHexId")?;
-        let chain_hex_id = ctx.borrow(&chain_hex_id, |data| data.as_slice::<u8>());
-        let chain_hex_id = chain_hex_id_from_vec(ctx, chain_hex_id.to_vec())?;
-
-        Ok(BuildRawDepositTransactionOption {
-            inputs,
-            to_address,
-            chain_hex_id,
+/// Parse the optional previous output argument with the network of the
+/// deposit transaction
+fn prev_output_argument(
+    ctx: &mut FunctionContext,
+    i: i32,
+    deposit_bond_tx: &DepositBondTx,
+) -> NeonResult<Option<TxOut>> {
+    let prev_output: Option<OutputOptions> = object_argument(ctx, i)?;
+
+    prev_output
+        .map(|prev_output| {
+            let network =
+                network_of_chain_hex_id(deposit_bond_tx.attributes.chain_hex_id, "prevOutput")?;
+
+            prev_output.to_tx_out(&network, "prevOutput")
         })
-    }
+        .transpose()
+        .or_throw(ctx)
 }
@@ -1,10 +1,15 @@
 mod deposit_transaction;
+mod staked_state;
 mod unbond_transaction;
 mod withdraw_unbonded_transaction;
 
 use neon::prelude::*;
 
-use deposit_transaction::{build_raw_deposit_transaction, deposit_transaction_to_hex};
+use deposit_transaction::{
+    build_raw_deposit_transaction, deposit_transaction_to_hex, sign_deposit_transaction_input,
+    verify_deposit_transaction_input_witness,
+};
+use staked_state::query_staked_state;
 use unbond_transaction::{build_raw_unbond_transaction, unbond_transaction_to_hex};
 use withdraw_unbonded_transaction::{
     build_raw_withdraw_unbonded_transaction, estimate_withdraw_unbonded_transaction_fee,
@@ -22,6 +27,21 @@ pub fn register_staking_transaction_module(ctx: &mut ModuleContext) -> NeonResul
         build_raw_deposit_transaction_fn,
     )?;
 
+    let sign_deposit_transaction_input_fn = JsFunction::new(ctx, sign_deposit_transaction_input)?;
+    js_object.set(
+        ctx,
+        "signDepositTransactionInput",
+        sign_deposit_transaction_input_fn,
+    )?;
+
+    let verify_deposit_transaction_input_witness_fn =
+        JsFunction::new(ctx, verify_deposit_transaction_input_witness)?;
+    js_object.set(
+        ctx,
+        "verifyDepositTransactionInputWitness",
+        verify_deposit_transaction_input_witness_fn,
+    )?;
+
     let deposit_transaction_to_hex_fn = JsFunction::new(ctx, deposit_transaction_to_hex)?;
     js_object.set(
         ctx,
@@ -29,6 +49,9 @@ pub fn register_staking_transaction_module(ctx: &mut ModuleContext) -> NeonResul
         deposit_transaction_to_hex_fn,
     )?;
 
+    let query_staked_state_fn = JsFunction::new(ctx, query_staked_state)?;
+    js_object.set(ctx, "queryStakedState", query_staked_state_fn)?;
+
     let build_raw_unbond_transaction_fn = JsFunction::new(ctx, build_raw_unbond_transaction)?;
     js_object.set(
         ctx,
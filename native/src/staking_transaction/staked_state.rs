@@ -0,0 +1,72 @@
+use chain_core::state::account::StakedState;
+use cro_nodelib_core::staking_transaction;
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+
+/// Query the staked state of a staking address from the Tendermint node.
+/// Returns `null` when the staking address has no staked state.
+pub fn query_staked_state(mut ctx: FunctionContext) -> JsResult<JsValue> {
+    let staking_address = staking_address_argument(&mut ctx, 0)?;
+    let tendermint_address = ctx.argument::<JsString>(1)?.value();
+
+    let staked_state =
+        staking_transaction::query_staked_state(&staking_address, &tendermint_address)
+            .chain_neon(&mut ctx, "Unable to query staked state")?;
+
+    match staked_state {
+        Some(staked_state) => {
+            staked_state_to_js_object(&mut ctx, &staked_state).map(|object| object.upcast())
+        }
+        None => Ok(ctx.null().upcast()),
+    }
+}
+
+fn staked_state_to_js_object<'a, C>(
+    ctx: &mut C,
+    staked_state: &StakedState,
+) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let object = ctx.empty_object();
+
+    let nonce = ctx.number(staked_state.nonce as f64);
+    object
+        .set(ctx, "nonce", nonce)
+        .chain_neon(ctx, "Unable to set nonce of staked state")?;
+
+    let bonded = ctx.string(u64::from(staked_state.bonded).to_string());
+    object
+        .set(ctx, "bonded", bonded)
+        .chain_neon(ctx, "Unable to set bonded of staked state")?;
+
+    let unbonded = ctx.string(u64::from(staked_state.unbonded).to_string());
+    object
+        .set(ctx, "unbonded", unbonded)
+        .chain_neon(ctx, "Unable to set unbonded of staked state")?;
+
+    let unbonded_from = ctx.number(staked_state.unbonded_from as f64);
+    object
+        .set(ctx, "unbondedFrom", unbonded_from)
+        .chain_neon(ctx, "Unable to set unbondedFrom of staked state")?;
+
+    let address = ctx.string(staked_state.address.to_string());
+    object
+        .set(ctx, "address", address)
+        .chain_neon(ctx, "Unable to set address of staked state")?;
+
+    let jailed_until = staked_state
+        .validator
+        .as_ref()
+        .and_then(|validator| validator.jailed_until);
+    if let Some(jailed_until) = jailed_until {
+        let jailed_until = ctx.number(jailed_until as f64);
+        object
+            .set(ctx, "jailedUntil", jailed_until)
+            .chain_neon(ctx, "Unable to set jailedUntil of staked state")?;
+    }
+
+    Ok(object)
+}
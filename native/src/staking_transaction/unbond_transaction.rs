@@ -1,29 +1,25 @@
-use std::str::FromStr;
-
 use neon::prelude::*;
 
-use chain_core::init::coin::Coin;
-use chain_core::state::account::{
-    Nonce, StakedStateAddress, StakedStateOpAttributes, StakedStateOpWitness, UnbondTx,
+use chain_core::state::account::UnbondTx;
+use chain_core::tx::TransactionId;
+use cro_nodelib_core::codec::decode_transaction;
+use cro_nodelib_core::staking_transaction::unbond::{
+    sign_unbond_transaction, BuildUnbondTransactionOptions,
 };
-use chain_core::tx::{TransactionId, TxAux, TxPublicAux};
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::Encode;
 
 use crate::error::ClientErrorNeonExt;
 use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
 use crate::signer::KeyPairSigner;
 use crate::tx_aux::tx_aux_to_hex;
 
 pub fn build_raw_unbond_transaction(mut ctx: FunctionContext) -> JsResult<JsObject> {
-    let options = BuildUnbondTransactionOptions::parse(&mut ctx)?;
-    let attributes = StakedStateOpAttributes::new(options.chain_hex_id);
+    let options: BuildUnbondTransactionOptions = object_argument(&mut ctx, 0)?;
+    let amount = options.unbond_amount().or_throw(&mut ctx)?;
+    let unbonded_from = options.unbonded_from().or_throw(&mut ctx)?;
 
-    let tx = UnbondTx::new(
-        options.staking_address,
-        options.nonce,
-        options.amount,
-        attributes,
-    );
+    let tx = options.to_tx(amount);
 
     let raw_tx = tx.encode();
     let mut raw_tx_buffer = ctx.buffer(raw_tx.len() as u32)?;
@@ -43,6 +39,16 @@ pub fn build_raw_unbond_transaction(mut ctx: FunctionContext) -> JsResult<JsObje
         .set(&mut ctx, "txId", tx_id)
         .chain_neon(&mut ctx, "Unable to set txId of return object")?;
 
+    let amount = ctx.string(u64::from(amount).to_string());
+    return_object
+        .set(&mut ctx, "amount", amount)
+        .chain_neon(&mut ctx, "Unable to set amount of return object")?;
+
+    let unbonded_from = ctx.number(unbonded_from as f64);
+    return_object
+        .set(&mut ctx, "unbondedFrom", unbonded_from)
+        .chain_neon(&mut ctx, "Unable to set unbondedFrom of return object")?;
+
     Ok(return_object)
 }
 
@@ -52,70 +58,16 @@ pub fn unbond_transaction_to_hex(mut ctx: FunctionContext) -> JsResult<JsBuffer>
     let signer = KeyPairSigner::new(key_pair.0, key_pair.1)
         .chain_neon(&mut ctx, "Unable to create KeyPair signer")?;
 
-    let signature = signer
-        .sign(&unbond_tx.id())
-        .map(StakedStateOpWitness::new)
+    let tx_aux = sign_unbond_transaction(unbond_tx, &signer)
         .chain_neon(&mut ctx, "Error when signing transaction")?;
 
-    let tx_aux = TxAux::PublicTx(TxPublicAux::UnbondStakeTx(unbond_tx, signature));
-
     tx_aux_to_hex(&mut ctx, tx_aux)
 }
 
 fn unbond_tx_argument(ctx: &mut FunctionContext, i: i32) -> NeonResult<UnbondTx> {
     let unbond_tx = ctx.argument::<JsBuffer>(i)?;
-    let mut unbond_tx = unbond_tx.borrow(&ctx.lock()).as_slice();
-
-    UnbondTx::decode(&mut unbond_tx).chain_neon(ctx, "Unable to decode raw transaction bytes")
-}
-
-struct BuildUnbondTransactionOptions {
-    staking_address: StakedStateAddress,
-    nonce: Nonce,
-    amount: Coin,
-    chain_hex_id: u8,
-}
-
-impl BuildUnbondTransactionOptions {
-    fn parse(ctx: &mut FunctionContext) -> NeonResult<BuildUnbondTransactionOptions> {
-        let options = ctx
-            .argument::<JsObject>(0)
-            .chain_neon(ctx, "Unable to deserialize options object")?;
-
-        let staking_address = options
-            .get(ctx, "stakingAddress")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast stakingAddress")?
-            .value();
-        let staking_address = StakedStateAddress::from_str(&staking_address)
-            .chain_neon(ctx, "Unable to deserialize stakingAddress")?;
-
-        let nonce = options
-            .get(ctx, "nonce")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast stakingAddress")?
-            .value();
-        let nonce = parse_account_nonce(ctx, nonce)?;
-
-        let amount = options
-            .get(ctx, "amount")?
-            .downcast_or_throw::<JsString, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast amount")?
-            .value();
-        let amount = Coin::from_str(&amount).chain_neon(ctx, "Unable to deserialize amount")?;
-
-        let chain_hex_id = options
-            .get(ctx, "chainHexId")?
-            .downcast_or_throw::<JsBuffer, FunctionContext>(ctx)
-            .chain_neon(ctx, "Unable to downcast chainHexId")?;
-        let chain_hex_id = chain_hex_id.borrow(&ctx.lock()).as_slice().to_vec();
-        let chain_hex_id = chain_hex_id_from_vec(ctx, chain_hex_id)?;
+    let unbond_tx = unbond_tx.borrow(&ctx.lock()).as_slice();
 
-        Ok(BuildUnbondTransactionOptions {
-            staking_address,
-            nonce,
-            amount,
-            chain_hex_id,
-        })
-    }
+    decode_transaction(unbond_tx, "raw transaction")
+        .chain_neon(ctx, "Unable to decode raw transaction bytes")
 }
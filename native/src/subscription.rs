@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use cro_nodelib_core::subscription::{
+    EventFilter, Subscription, SubscriptionEvent, ViewKeyDecryption,
+};
+use lazy_static::lazy_static;
+use neon::event::EventHandler;
+use neon::prelude::*;
+
+use crate::common::features_argument;
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+
+lazy_static! {
+    /// Running subscriptions by id. JS only holds the id, the subscription
+    /// is closed when removed.
+    static ref SUBSCRIPTIONS: Mutex<HashMap<u32, Arc<Subscription>>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_SUBSCRIPTION_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Subscribe to the new blocks and transactions of the Tendermint node.
+/// Events are passed to the callback as they are received, the next ones
+/// once the callback returns. The last call has `closed` set. The view key
+/// pair and features are only read when transfer addresses are filtered.
+/// @arguments
+/// - filter: {
+///     blocks?: boolean,
+///     txs?: boolean,
+///     txIds?: string[],
+///     stakingAddresses?: string[],
+///     transferAddresses?: string[]
+///   }
+/// - tendermintAddress: string
+/// - callback: (err, { closed: boolean, events: SubscriptionEvent[] })
+/// - viewKeyPair: KeyPair decrypting the outputs of the transactions
+/// - features: string
+/// @return subscription id
+pub fn subscribe(mut ctx: FunctionContext) -> JsResult<JsNumber> {
+    let filter: EventFilter = object_argument(&mut ctx, 0)?;
+    filter.verify().or_throw(&mut ctx)?;
+    let tendermint_address = ctx.argument::<JsString>(1)?.value();
+    let callback = ctx.argument::<JsFunction>(2)?;
+    let decryption = if filter.transfer_addresses.is_empty() {
+        None
+    } else {
+        let (view_key, _) = key_pair_argument(&mut ctx, 3)?;
+        let features = features_argument(&mut ctx, 4)?;
+
+        Some(ViewKeyDecryption { view_key, features })
+    };
+    let this = ctx.this();
+    let handler = EventHandler::new(&ctx, this, callback);
+
+    let subscription = Subscription::start(&tendermint_address, filter, decryption)
+        .chain_neon(&mut ctx, "Unable to subscribe to Tendermint events")?;
+    let subscription = Arc::new(subscription);
+
+    let id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::SeqCst);
+    SUBSCRIPTIONS
+        .lock()
+        .expect("Subscriptions lock is poisoned")
+        .insert(id, subscription.clone());
+
+    thread::spawn(move || forward_events(id, &subscription, &handler));
+
+    Ok(ctx.number(id))
+}
+
+/// Pass the events of the subscription to the JS callback until it is
+/// closed. Waits for each call to return so that events are queued natively
+/// while JS is busy.
+fn forward_events(id: u32, subscription: &Subscription, handler: &EventHandler) {
+    let (returned, wait_return) = channel();
+
+    loop {
+        let events = subscription.wait_events();
+        let closed = events.is_none();
+
+        let returned = returned.clone();
+        handler.schedule_with(move |ctx, this, callback| {
+            // Exceptions thrown by the callback are ignored
+            if let Ok(result) = events_to_js_object(ctx, closed, events.unwrap_or_default()) {
+                let args: Vec<Handle<JsValue>> = vec![ctx.null().upcast(), result.upcast()];
+                let _ = callback.call(ctx, this, args);
+            }
+            let _ = returned.send(());
+        });
+
+        if closed {
+            break;
+        }
+        if wait_return.recv().is_err() {
+            break;
+        }
+    }
+
+    SUBSCRIPTIONS
+        .lock()
+        .expect("Subscriptions lock is poisoned")
+        .remove(&id);
+}
+
+/// Close the subscription. Events received so far are still passed to the
+/// callback.
+/// @arguments
+/// - id: subscription id
+pub fn unsubscribe(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let id = ctx.argument::<JsNumber>(0)?.value() as u32;
+
+    if let Some(subscription) = SUBSCRIPTIONS
+        .lock()
+        .expect("Subscriptions lock is poisoned")
+        .remove(&id)
+    {
+        subscription.close();
+    }
+
+    Ok(ctx.undefined())
+}
+
+fn events_to_js_object<'a>(
+    ctx: &mut TaskContext<'a>,
+    closed: bool,
+    events: Vec<SubscriptionEvent>,
+) -> JsResult<'a, JsObject> {
+    let js_object = JsObject::new(ctx);
+    let closed = ctx.boolean(closed);
+    js_object.set(ctx, "closed", closed)?;
+
+    let js_events = JsArray::new(ctx, events.len() as u32);
+    for (i, event) in events.iter().enumerate() {
+        let js_event = subscription_event_to_js_object(ctx, event)?;
+        js_events.set(ctx, i as u32, js_event)?;
+    }
+    js_object.set(ctx, "events", js_events)?;
+
+    Ok(js_object)
+}
+
+fn subscription_event_to_js_object<'a, C>(
+    ctx: &mut C,
+    event: &SubscriptionEvent,
+) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+{
+    let js_object = JsObject::new(ctx);
+
+    match event {
+        SubscriptionEvent::Block(block) => {
+            let event_type = ctx.string("block");
+            js_object.set(ctx, "type", event_type)?;
+            let height = ctx.string(block.height.to_string());
+            js_object.set(ctx, "height", height)?;
+            let time = ctx.number(block.time as f64);
+            js_object.set(ctx, "time", time)?;
+            let tx_count = ctx.number(block.tx_count as f64);
+            js_object.set(ctx, "txCount", tx_count)?;
+        }
+        SubscriptionEvent::Tx(tx) => {
+            let event_type = ctx.string("tx");
+            js_object.set(ctx, "type", event_type)?;
+            let height = ctx.string(tx.height.to_string());
+            js_object.set(ctx, "height", height)?;
+            let index = ctx.number(tx.index);
+            js_object.set(ctx, "index", index)?;
+            let tx_id = ctx.string(hex::encode(tx.tx_id));
+            js_object.set(ctx, "txId", tx_id)?;
+            let tx_type = ctx.string(tx.tx_type);
+            js_object.set(ctx, "txType", tx_type)?;
+            if let Some(staking_address) = tx.staking_address {
+                let staking_address = ctx.string(staking_address.to_string());
+                js_object.set(ctx, "stakingAddress", staking_address)?;
+            }
+            let code = ctx.number(tx.code);
+            js_object.set(ctx, "code", code)?;
+            let log = ctx.string(&tx.log);
+            js_object.set(ctx, "log", log)?;
+        }
+        SubscriptionEvent::Reconnecting { attempt, error } => {
+            let event_type = ctx.string("reconnecting");
+            js_object.set(ctx, "type", event_type)?;
+            let attempt = ctx.number(*attempt);
+            js_object.set(ctx, "attempt", attempt)?;
+            let error = ctx.string(error);
+            js_object.set(ctx, "error", error)?;
+        }
+        SubscriptionEvent::Resumed {
+            from_height,
+            to_height,
+        } => {
+            let event_type = ctx.string("resumed");
+            js_object.set(ctx, "type", event_type)?;
+            let from_height = ctx.string(from_height.to_string());
+            js_object.set(ctx, "fromHeight", from_height)?;
+            let to_height = ctx.string(to_height.to_string());
+            js_object.set(ctx, "toHeight", to_height)?;
+        }
+    }
+
+    Ok(js_object)
+}
+
+pub fn register_subscription_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let subscribe_fn = JsFunction::new(ctx, subscribe)?;
+    js_object.set(ctx, "subscribe", subscribe_fn)?;
+
+    let unsubscribe_fn = JsFunction::new(ctx, unsubscribe)?;
+    js_object.set(ctx, "unsubscribe", unsubscribe_fn)?;
+
+    ctx.export_value("subscription", js_object)
+}
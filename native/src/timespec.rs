@@ -0,0 +1,49 @@
+//! Conversion between Timespec and ISO-8601 date time strings
+//!
+//! Timespec is the number of seconds since UNIX epoch. Date times are
+//! formatted in UTC as `YYYY-MM-DDTHH:MM:SSZ` and parsed from the same format
+//! with an optional UTC offset in place of `Z`.
+use chain_core::common::Timespec;
+use cro_nodelib_core::timespec::{timespec_from_iso8601, timespec_to_iso8601};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+
+/// Format Timespec as ISO-8601 UTC date time
+/// @arguments
+/// - timespec: number
+/// @return string
+pub fn to_iso8601(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let timespec = ctx.argument::<JsNumber>(0)?.value();
+    if timespec < 0.0 || timespec.fract() != 0.0 {
+        return ctx.throw_error(format!("Invalid Timespec {}", timespec));
+    }
+    let date_time = timespec_to_iso8601(timespec as Timespec)
+        .chain_neon(&mut ctx, "Unable to format ISO-8601 date time")?;
+
+    Ok(ctx.string(date_time))
+}
+
+/// Parse ISO-8601 date time into Timespec
+/// @arguments
+/// - dateTime: string
+/// @return number
+pub fn from_iso8601(mut ctx: FunctionContext) -> JsResult<JsNumber> {
+    let date_time = ctx.argument::<JsString>(0)?.value();
+    let timespec = timespec_from_iso8601(&date_time)
+        .chain_neon(&mut ctx, "Unable to parse ISO-8601 date time")?;
+
+    Ok(ctx.number(timespec as f64))
+}
+
+pub fn register_timespec_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let to_iso8601_fn = JsFunction::new(ctx, to_iso8601)?;
+    js_object.set(ctx, "toISO8601", to_iso8601_fn)?;
+
+    let from_iso8601_fn = JsFunction::new(ctx, from_iso8601)?;
+    js_object.set(ctx, "fromISO8601", from_iso8601_fn)?;
+
+    ctx.export_value("timespec", js_object)
+}
@@ -0,0 +1,90 @@
+use cro_nodelib_core::network::network_by_chain_hex_id;
+use cro_nodelib_core::transfer_transaction::{IncompleteInspection, InspectIncompleteOptions};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+
+/// Report the witness status and signer requirements of each input
+/// @arguments
+/// - options: {
+///     incompleteHex: Buffer,
+///     feeConfig: FeeConfig,
+///     addressParams?: AddressParams[]
+///   }
+/// @return {
+///     txId: string,
+///     inputs: Array<{
+///         prevTxId, prevIndex, prevOutput: { address, value, validFrom? },
+///         addressLeaves, addressParams?, hasWitness, isWitnessValid
+///     }>
+/// }
+pub fn inspect_incomplete_hex(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let options: InspectIncompleteOptions = object_argument(&mut ctx, 0)?;
+    options.verify().or_throw(&mut ctx)?;
+
+    let inspection = options.inspect().chain_neon(
+        &mut ctx,
+        "Unable to inspect incomplete transfer transaction",
+    )?;
+
+    inspection_to_js_object(&mut ctx, &inspection)
+}
+
+fn inspection_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    inspection: &IncompleteInspection,
+) -> JsResult<'a, JsObject> {
+    let network = network_by_chain_hex_id(inspection.chain_hex_id)
+        .chain_neon(ctx, "Unable to find network of the transaction")?;
+
+    let js_inputs = JsArray::new(ctx, inspection.inputs.len() as u32);
+    for (i, input) in inspection.inputs.iter().enumerate() {
+        let js_input = JsObject::new(ctx);
+
+        let prev_tx_id = ctx.string(hex::encode(input.prev_tx_id));
+        js_input.set(ctx, "prevTxId", prev_tx_id)?;
+        let prev_index = ctx.number(input.prev_index);
+        js_input.set(ctx, "prevIndex", prev_index)?;
+
+        let js_prev_output = JsObject::new(ctx);
+        let address = network
+            .encode_transfer_address(&input.prev_output.address)
+            .chain_neon(ctx, "Unable to encode transfer address")?;
+        let address = ctx.string(address);
+        js_prev_output.set(ctx, "address", address)?;
+        let value = ctx.string(u64::from(input.prev_output.value).to_string());
+        js_prev_output.set(ctx, "value", value)?;
+        if let Some(valid_from) = input.prev_output.valid_from {
+            let valid_from = ctx.number(valid_from as f64);
+            js_prev_output.set(ctx, "validFrom", valid_from)?;
+        }
+        js_input.set(ctx, "prevOutput", js_prev_output)?;
+
+        let address_leaves = ctx.number(input.address_leaves);
+        js_input.set(ctx, "addressLeaves", address_leaves)?;
+
+        if let Some(ref address_params) = input.address_params {
+            let js_address_params = JsObject::new(ctx);
+            let required_signers = ctx.number(address_params.required_signers as f64);
+            js_address_params.set(ctx, "requiredSigners", required_signers)?;
+            let total_signers = ctx.number(address_params.total_signers as f64);
+            js_address_params.set(ctx, "totalSigners", total_signers)?;
+            js_input.set(ctx, "addressParams", js_address_params)?;
+        }
+
+        let has_witness = ctx.boolean(input.has_witness);
+        js_input.set(ctx, "hasWitness", has_witness)?;
+        let is_witness_valid = ctx.boolean(input.is_witness_valid);
+        js_input.set(ctx, "isWitnessValid", is_witness_valid)?;
+
+        js_inputs.set(ctx, i as u32, js_input)?;
+    }
+
+    let js_object = JsObject::new(ctx);
+    let tx_id = ctx.string(hex::encode(inspection.tx_id));
+    js_object.set(ctx, "txId", tx_id)?;
+    js_object.set(ctx, "inputs", js_inputs)?;
+
+    Ok(js_object)
+}
@@ -0,0 +1,111 @@
+//! Assertion based verification of transactions prepared by other parties
+use chain_core::tx::TxAux;
+use client_common::SignedTransaction;
+use cro_nodelib_core::codec::decode_transaction;
+use cro_nodelib_core::tx_assertion::{
+    assert_signed_withdraw_unbonded_tx, assert_tx_aux, TxAssertions, TxVerification,
+};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::schema::object_argument;
+
+fn verification_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    verification: &TxVerification,
+) -> JsResult<'a, JsObject> {
+    let mismatches = verification.report.mismatches();
+    let js_mismatches = JsArray::new(ctx, mismatches.len() as u32);
+    for (i, mismatch) in mismatches.iter().enumerate() {
+        let js_mismatch = JsObject::new(ctx);
+
+        let field = ctx.string(&mismatch.field);
+        js_mismatch.set(ctx, "field", field)?;
+        let expected = ctx.string(&mismatch.expected);
+        js_mismatch.set(ctx, "expected", expected)?;
+        let actual = ctx.string(&mismatch.actual);
+        js_mismatch.set(ctx, "actual", actual)?;
+
+        js_mismatches.set(ctx, i as u32, js_mismatch)?;
+    }
+
+    let unverifiable = verification.report.unverifiable();
+    let js_unverifiable = JsArray::new(ctx, unverifiable.len() as u32);
+    for (i, field) in unverifiable.iter().enumerate() {
+        let field = ctx.string(field);
+        js_unverifiable.set(ctx, i as u32, field)?;
+    }
+
+    let js_object = JsObject::new(ctx);
+    let tx_type = ctx.string(verification.tx_type);
+    js_object.set(ctx, "txType", tx_type)?;
+    let tx_id = ctx.string(hex::encode(verification.tx_id));
+    js_object.set(ctx, "txId", tx_id)?;
+    let valid = ctx.boolean(verification.report.is_valid());
+    js_object.set(ctx, "valid", valid)?;
+    js_object.set(ctx, "mismatches", js_mismatches)?;
+    js_object.set(ctx, "unverifiable", js_unverifiable)?;
+
+    Ok(js_object)
+}
+
+/// Verify a broadcast-able transaction against the expected fields
+/// @arguments
+/// - txAux: Buffer of the encoded TxAux
+/// - assertions: expected fields
+/// @return { txType, txId, valid, mismatches: Array<{ field, expected, actual }>,
+///   unverifiable: string[] }
+pub fn verify_tx_aux(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let tx_aux = ctx.argument::<JsBuffer>(0)?;
+    let tx_aux = tx_aux.borrow(&ctx.lock()).as_slice();
+    let tx_aux: TxAux = decode_transaction(tx_aux, "transaction")
+        .chain_neon(&mut ctx, "Unable to decode transaction bytes")?;
+    let assertions: TxAssertions = object_argument(&mut ctx, 1)?;
+
+    let verification =
+        assert_tx_aux(&tx_aux, &assertions).chain_neon(&mut ctx, "Unable to verify transaction")?;
+
+    verification_to_js_object(&mut ctx, &verification)
+}
+
+/// Verify a signed withdraw unbonded transaction in plain form against the
+/// expected fields
+/// @arguments
+/// - signedPlainTx: Buffer from `withdrawUnbondedTransactionToSignedPlainHex`
+/// - assertions: expected fields
+/// @return { txType, txId, valid, mismatches: Array<{ field, expected, actual }>,
+///   unverifiable: string[] }
+pub fn verify_signed_withdraw_unbonded_tx(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let signed_transaction = ctx.argument::<JsBuffer>(0)?;
+    let signed_transaction = signed_transaction.borrow(&ctx.lock()).as_slice();
+    let signed_transaction: SignedTransaction =
+        decode_transaction(signed_transaction, "signed transaction")
+            .chain_neon(&mut ctx, "Unable to decode transaction bytes")?;
+    let (tx, witness) = match signed_transaction {
+        SignedTransaction::WithdrawUnbondedStakeTransaction(tx, witness) => (tx, witness),
+        _ => return ctx.throw_error("Transaction is not a withdraw unbonded transaction"),
+    };
+    let assertions: TxAssertions = object_argument(&mut ctx, 1)?;
+
+    let verification = assert_signed_withdraw_unbonded_tx(&tx, &witness, &assertions)
+        .chain_neon(&mut ctx, "Unable to verify transaction")?;
+
+    verification_to_js_object(&mut ctx, &verification)
+}
+
+pub fn register_tx_assertion_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let verify_tx_aux_fn = JsFunction::new(ctx, verify_tx_aux)?;
+    js_object.set(ctx, "verifyTxAux", verify_tx_aux_fn)?;
+
+    let verify_signed_withdraw_unbonded_tx_fn =
+        JsFunction::new(ctx, verify_signed_withdraw_unbonded_tx)?;
+    js_object.set(
+        ctx,
+        "verifySignedWithdrawUnbondedTx",
+        verify_signed_withdraw_unbonded_tx_fn,
+    )?;
+
+    ctx.export_value("txAssertion", js_object)
+}
@@ -0,0 +1,99 @@
+//! JS bindings of Tendermint validator consensus keys
+use cro_nodelib_core::validator_key::{
+    consensus_address, consensus_public_key_from_base64, ValidatorKey,
+};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+
+pub fn generate_validator_key(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let validator_key =
+        ValidatorKey::generate().chain_neon(&mut ctx, "Unable to generate validator key")?;
+
+    validator_key_to_js_object(&mut ctx, &validator_key)
+}
+
+pub fn validator_key_from_seed(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let seed = u8_buffer_argument(&mut ctx, 0)?;
+    let validator_key =
+        ValidatorKey::from_seed(&seed).chain_neon(&mut ctx, "Unable to import validator key")?;
+
+    validator_key_to_js_object(&mut ctx, &validator_key)
+}
+
+pub fn validator_key_from_priv_validator_key(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let json = ctx.argument::<JsString>(0)?.value();
+    let validator_key = ValidatorKey::from_priv_validator_key_json(&json)
+        .chain_neon(&mut ctx, "Unable to import priv_validator_key.json")?;
+
+    validator_key_to_js_object(&mut ctx, &validator_key)
+}
+
+pub fn get_consensus_address(mut ctx: FunctionContext) -> JsResult<JsString> {
+    let consensus_public_key = ctx.argument::<JsString>(0)?.value();
+    let consensus_public_key = consensus_public_key_from_base64(&consensus_public_key)
+        .chain_neon(&mut ctx, "Invalid consensus public key")?;
+
+    Ok(ctx.string(consensus_address(&consensus_public_key)))
+}
+
+fn validator_key_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    validator_key: &ValidatorKey,
+) -> JsResult<'a, JsObject> {
+    let priv_validator_key = validator_key
+        .to_priv_validator_key_json()
+        .chain_neon(ctx, "Unable to export priv_validator_key.json")?;
+
+    let seed = validator_key.seed();
+    let mut seed_buffer = ctx.buffer(seed.len() as u32)?;
+    ctx.borrow_mut(&mut seed_buffer, |data| {
+        data.as_mut_slice().copy_from_slice(seed);
+    });
+
+    let object = ctx.empty_object();
+    object
+        .set(ctx, "seed", seed_buffer)
+        .chain_neon(ctx, "Unable to set seed of validator key")?;
+
+    let consensus_public_key = ctx.string(validator_key.consensus_public_key_base64());
+    object
+        .set(ctx, "consensusPublicKey", consensus_public_key)
+        .chain_neon(ctx, "Unable to set consensusPublicKey of validator key")?;
+
+    let consensus_address = ctx.string(validator_key.consensus_address());
+    object
+        .set(ctx, "consensusAddress", consensus_address)
+        .chain_neon(ctx, "Unable to set consensusAddress of validator key")?;
+
+    let priv_validator_key = ctx.string(priv_validator_key);
+    object
+        .set(ctx, "privValidatorKey", priv_validator_key)
+        .chain_neon(ctx, "Unable to set privValidatorKey of validator key")?;
+
+    Ok(object)
+}
+
+pub fn register_validator_key_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let generate_validator_key_fn = JsFunction::new(ctx, generate_validator_key)?;
+    js_object.set(ctx, "generateValidatorKey", generate_validator_key_fn)?;
+
+    let validator_key_from_seed_fn = JsFunction::new(ctx, validator_key_from_seed)?;
+    js_object.set(ctx, "validatorKeyFromSeed", validator_key_from_seed_fn)?;
+
+    let validator_key_from_priv_validator_key_fn =
+        JsFunction::new(ctx, validator_key_from_priv_validator_key)?;
+    js_object.set(
+        ctx,
+        "validatorKeyFromPrivValidatorKey",
+        validator_key_from_priv_validator_key_fn,
+    )?;
+
+    let get_consensus_address_fn = JsFunction::new(ctx, get_consensus_address)?;
+    js_object.set(ctx, "getConsensusAddress", get_consensus_address_fn)?;
+
+    ctx.export_value("validatorKey", js_object)
+}
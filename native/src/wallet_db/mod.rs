@@ -0,0 +1,362 @@
+use chain_core::tx::data::output::TxOut;
+use cro_nodelib_core::network::{network_by_chain_hex_id, NetworkDefinition};
+use cro_nodelib_core::schema::de::h256_from_hex;
+use cro_nodelib_core::wallet_db::{
+    AddAddressOptions, IngestBlockOptions, IngestTransactionOptions, IngestionSummary,
+    OutputStatus, TrackedOutput, WalletDb, WalletStoreOptions, WalletTransferOptions,
+};
+use neon::prelude::*;
+
+use crate::error::ClientErrorNeonExt;
+use crate::function_types::*;
+use crate::schema::{object_argument, SchemaResultNeonExt};
+use crate::tx_aux::bytes_to_buffer;
+
+/// Create the wallet database file of the chain
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// - chainHexId: Buffer
+pub fn create(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+    let chain_hex_id = chain_hex_id_argument(&mut ctx, 1)?;
+
+    store
+        .create(chain_hex_id)
+        .chain_neon(&mut ctx, "Unable to create wallet database")?;
+
+    Ok(ctx.undefined())
+}
+
+/// Record the transfer address as owned by the wallet
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// - options: { address: string, addressParams?: AddressParams }
+/// @return true when the address is new
+pub fn add_address(mut ctx: FunctionContext) -> JsResult<JsBoolean> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+    let options: AddAddressOptions = object_argument(&mut ctx, 1)?;
+
+    let mut db = open_wallet_db(&mut ctx, &store)?;
+    let network = network_of_wallet_db(&mut ctx, &db)?;
+    let (address, address_params) = options.to_address(&network).or_throw(&mut ctx)?;
+
+    let added = db.add_address(address, address_params);
+    save_wallet_db(&mut ctx, &store, &db)?;
+
+    Ok(ctx.boolean(added))
+}
+
+/// Ingest the decrypted raw transactions of the block
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// - block: {
+///     height: string,
+///     time: number,
+///     txs: Array<{ kind: 'transfer' | 'deposit' | 'withdrawUnbonded', rawTx: Buffer }>
+///   }
+/// @return { received: number, spent: number }
+pub fn ingest_block(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+    let block: IngestBlockOptions = object_argument(&mut ctx, 1)?;
+
+    let txs = block
+        .to_raw_transactions()
+        .chain_neon(&mut ctx, "Unable to decode block transactions")?;
+    let mut db = open_wallet_db(&mut ctx, &store)?;
+    let summary = db
+        .ingest_block(block.height, block.time, &txs)
+        .chain_neon(&mut ctx, "Unable to ingest block")?;
+    save_wallet_db(&mut ctx, &store, &db)?;
+
+    ingestion_summary_to_js_object(&mut ctx, &summary)
+}
+
+/// Ingest a decrypted raw transaction on its own, e.g. before its block
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// - tx: {
+///     kind: 'transfer' | 'deposit' | 'withdrawUnbonded',
+///     rawTx: Buffer,
+///     height?: string
+///   }
+/// @return { received: number, spent: number }
+pub fn ingest_transaction(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+    let options: IngestTransactionOptions = object_argument(&mut ctx, 1)?;
+
+    let tx = options
+        .to_raw_transaction()
+        .chain_neon(&mut ctx, "Unable to decode transaction")?;
+    let mut db = open_wallet_db(&mut ctx, &store)?;
+    let summary = db
+        .ingest_transaction(&tx, options.height)
+        .chain_neon(&mut ctx, "Unable to ingest transaction")?;
+    save_wallet_db(&mut ctx, &store, &db)?;
+
+    ingestion_summary_to_js_object(&mut ctx, &summary)
+}
+
+/// Returns the tracked outputs of the wallet
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// @return Array<{
+///     prevTxId, prevIndex, prevOutput: { address, value, validFrom? },
+///     addressParams, height?: string,
+///     status: 'unspent' | 'pending' | 'spent', spentBy?: string
+/// }>
+pub fn get_outputs(mut ctx: FunctionContext) -> JsResult<JsArray> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+
+    let db = open_wallet_db(&mut ctx, &store)?;
+    let network = network_of_wallet_db(&mut ctx, &db)?;
+
+    let js_outputs = JsArray::new(&mut ctx, db.outputs().len() as u32);
+    for (i, tracked) in db.outputs().iter().enumerate() {
+        let js_output = tracked_output_to_js_object(&mut ctx, &db, &network, tracked)?;
+
+        if let Some(height) = tracked.height {
+            let height = ctx.string(height.to_string());
+            js_output.set(&mut ctx, "height", height)?;
+        }
+        let (status, spent_by) = match tracked.status {
+            OutputStatus::Unspent => ("unspent", None),
+            OutputStatus::Pending(tx_id) => ("pending", Some(tx_id)),
+            OutputStatus::Spent(tx_id) => ("spent", Some(tx_id)),
+        };
+        let status = ctx.string(status);
+        js_output.set(&mut ctx, "status", status)?;
+        if let Some(spent_by) = spent_by {
+            let spent_by = ctx.string(hex::encode(spent_by));
+            js_output.set(&mut ctx, "spentBy", spent_by)?;
+        }
+
+        js_outputs.set(&mut ctx, i as u32, js_output)?;
+    }
+
+    Ok(js_outputs)
+}
+
+/// Returns the balance and the last ingested block height of the wallet
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// @return {
+///     available: string,
+///     locked: string,
+///     pending: string,
+///     lastHeight: string
+/// }
+pub fn get_balance(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+
+    let db = open_wallet_db(&mut ctx, &store)?;
+    let balance = db
+        .balance()
+        .chain_neon(&mut ctx, "Unable to calculate wallet balance")?;
+
+    let return_object = ctx.empty_object();
+    let fields = [
+        ("available", u64::from(balance.available)),
+        ("locked", u64::from(balance.locked)),
+        ("pending", u64::from(balance.pending)),
+        ("lastHeight", db.last_height()),
+    ];
+    for (key, value) in fields.iter() {
+        let value = ctx.string(value.to_string());
+        return_object
+            .set(&mut ctx, *key, value)
+            .chain_neon(&mut ctx, "Unable to set balance of return object")?;
+    }
+
+    Ok(return_object)
+}
+
+/// Build a transfer transaction from the unspent outputs of the wallet. The
+/// spent outputs are pending until the transaction is ingested or released.
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// - options: {
+///     outputs: Output[],
+///     viewKeys?: Buffer[],
+///     changeAddress?: string,
+///     feeConfig: FeeConfig
+///   }
+/// @return {
+///     incompleteHex: Buffer,
+///     txId: string,
+///     fee: string,
+///     inputs: Input[],
+///     change?: Output
+/// }
+pub fn build_transfer(mut ctx: FunctionContext) -> JsResult<JsObject> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+    let options: WalletTransferOptions = object_argument(&mut ctx, 1)?;
+
+    let mut db = open_wallet_db(&mut ctx, &store)?;
+    let network = network_of_wallet_db(&mut ctx, &db)?;
+    let transfer_options = options.to_transfer_options(&network).or_throw(&mut ctx)?;
+
+    let transfer = db
+        .build_transfer(&transfer_options, options.fee_config.to_fee_algorithm())
+        .chain_neon(&mut ctx, "Unable to build transfer from wallet")?;
+    save_wallet_db(&mut ctx, &store, &db)?;
+
+    let return_object = ctx.empty_object();
+
+    let incomplete_hex = bytes_to_buffer(&mut ctx, &transfer.builder.to_incomplete())?;
+    return_object.set(&mut ctx, "incompleteHex", incomplete_hex)?;
+    let tx_id = ctx.string(hex::encode(transfer.builder.tx_id()));
+    return_object.set(&mut ctx, "txId", tx_id)?;
+    let fee = ctx.string(u64::from(transfer.fee).to_string());
+    return_object.set(&mut ctx, "fee", fee)?;
+
+    let js_inputs = JsArray::new(&mut ctx, transfer.inputs.len() as u32);
+    for (i, input) in transfer.inputs.iter().enumerate() {
+        let js_input = tracked_output_to_js_object(&mut ctx, &db, &network, input)?;
+        js_inputs.set(&mut ctx, i as u32, js_input)?;
+    }
+    return_object.set(&mut ctx, "inputs", js_inputs)?;
+
+    if let Some(ref change) = transfer.change {
+        let js_change = output_to_js_object(&mut ctx, &network, change)?;
+        return_object.set(&mut ctx, "change", js_change)?;
+    }
+
+    Ok(return_object)
+}
+
+/// Release the outputs pending for the transaction, e.g. when it is
+/// rejected
+/// @arguments
+/// - store: { path: string, passphrase?: string }
+/// - txId: string
+/// @return number of released outputs
+pub fn release_pending(mut ctx: FunctionContext) -> JsResult<JsNumber> {
+    let store: WalletStoreOptions = object_argument(&mut ctx, 0)?;
+    let tx_id = ctx.argument::<JsString>(1)?.value();
+    let tx_id = h256_from_hex(&tx_id).chain_neon(&mut ctx, "Unable to parse tx id")?;
+
+    let mut db = open_wallet_db(&mut ctx, &store)?;
+    let released = db.release_pending(&tx_id);
+    save_wallet_db(&mut ctx, &store, &db)?;
+
+    Ok(ctx.number(released as f64))
+}
+
+fn open_wallet_db(ctx: &mut FunctionContext, store: &WalletStoreOptions) -> NeonResult<WalletDb> {
+    store
+        .open()
+        .chain_neon(ctx, "Unable to open wallet database")
+}
+
+fn save_wallet_db(
+    ctx: &mut FunctionContext,
+    store: &WalletStoreOptions,
+    db: &WalletDb,
+) -> NeonResult<()> {
+    store
+        .save(db)
+        .chain_neon(ctx, "Unable to save wallet database")
+}
+
+fn network_of_wallet_db(ctx: &mut FunctionContext, db: &WalletDb) -> NeonResult<NetworkDefinition> {
+    network_by_chain_hex_id(db.chain_hex_id())
+        .chain_neon(ctx, "Unable to find network of the wallet database")
+}
+
+fn ingestion_summary_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    summary: &IngestionSummary,
+) -> JsResult<'a, JsObject> {
+    let js_object = JsObject::new(ctx);
+
+    let received = ctx.number(summary.received as f64);
+    js_object.set(ctx, "received", received)?;
+    let spent = ctx.number(summary.spent as f64);
+    js_object.set(ctx, "spent", spent)?;
+
+    Ok(js_object)
+}
+
+/// Tracked output in the shape of a transfer input
+fn tracked_output_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    db: &WalletDb,
+    network: &NetworkDefinition,
+    tracked: &TrackedOutput,
+) -> JsResult<'a, JsObject> {
+    let js_input = JsObject::new(ctx);
+
+    let prev_tx_id = ctx.string(hex::encode(tracked.pointer.id));
+    js_input.set(ctx, "prevTxId", prev_tx_id)?;
+    let prev_index = ctx.number(tracked.pointer.index);
+    js_input.set(ctx, "prevIndex", prev_index)?;
+    let js_prev_output = output_to_js_object(ctx, network, &tracked.output)?;
+    js_input.set(ctx, "prevOutput", js_prev_output)?;
+
+    if let Some(owned) = db
+        .addresses()
+        .iter()
+        .find(|owned| owned.address == tracked.output.address)
+    {
+        let js_address_params = JsObject::new(ctx);
+        let required_signers = ctx.number(owned.required_signers as f64);
+        js_address_params.set(ctx, "requiredSigners", required_signers)?;
+        let total_signers = ctx.number(owned.total_signers as f64);
+        js_address_params.set(ctx, "totalSigners", total_signers)?;
+        js_input.set(ctx, "addressParams", js_address_params)?;
+    }
+
+    Ok(js_input)
+}
+
+fn output_to_js_object<'a>(
+    ctx: &mut FunctionContext<'a>,
+    network: &NetworkDefinition,
+    output: &TxOut,
+) -> JsResult<'a, JsObject> {
+    let js_output = JsObject::new(ctx);
+
+    let address = network
+        .encode_transfer_address(&output.address)
+        .chain_neon(ctx, "Unable to encode transfer address")?;
+    let address = ctx.string(address);
+    js_output.set(ctx, "address", address)?;
+    let value = ctx.string(u64::from(output.value).to_string());
+    js_output.set(ctx, "value", value)?;
+    if let Some(valid_from) = output.valid_from {
+        let valid_from = ctx.number(valid_from as f64);
+        js_output.set(ctx, "validFrom", valid_from)?;
+    }
+
+    Ok(js_output)
+}
+
+pub fn register_wallet_db_module(ctx: &mut ModuleContext) -> NeonResult<()> {
+    let js_object = JsObject::new(ctx);
+
+    let create_fn = JsFunction::new(ctx, create)?;
+    js_object.set(ctx, "create", create_fn)?;
+
+    let add_address_fn = JsFunction::new(ctx, add_address)?;
+    js_object.set(ctx, "addAddress", add_address_fn)?;
+
+    let ingest_block_fn = JsFunction::new(ctx, ingest_block)?;
+    js_object.set(ctx, "ingestBlock", ingest_block_fn)?;
+
+    let ingest_transaction_fn = JsFunction::new(ctx, ingest_transaction)?;
+    js_object.set(ctx, "ingestTransaction", ingest_transaction_fn)?;
+
+    let get_outputs_fn = JsFunction::new(ctx, get_outputs)?;
+    js_object.set(ctx, "getOutputs", get_outputs_fn)?;
+
+    let get_balance_fn = JsFunction::new(ctx, get_balance)?;
+    js_object.set(ctx, "getBalance", get_balance_fn)?;
+
+    let build_transfer_fn = JsFunction::new(ctx, build_transfer)?;
+    js_object.set(ctx, "buildTransfer", build_transfer_fn)?;
+
+    let release_pending_fn = JsFunction::new(ctx, release_pending)?;
+    js_object.set(ctx, "releasePending", release_pending_fn)?;
+
+    ctx.export_value("walletDb", js_object)
+}
@@ -0,0 +1,74 @@
+use std::str::FromStr;
+
+use chain_core::state::account::StakedStateAddress;
+use chain_core::tx::data::address::ExtendedAddr;
+use cro_nodelib_core::address::{
+    staking_address_from_public_key, transfer_address_from_public_key,
+};
+use cro_nodelib_core::network::decode_transfer_address;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+
+#[wasm_bindgen]
+pub struct Address {}
+
+#[wasm_bindgen]
+impl Address {
+    #[wasm_bindgen(js_name = getTransferAddressFromPublicKey)]
+    pub fn get_transfer_address_from_public_key(
+        public_key: &[u8],
+        network: &str,
+    ) -> JsResult<String> {
+        let public_key = public_key_argument(public_key)?;
+        let network = network_argument(network)?;
+
+        transfer_address_from_public_key(&network, &public_key)
+            .chain_js("Unable to create transfer address")
+    }
+
+    #[wasm_bindgen(js_name = getStakingAddressFromPublicKey)]
+    pub fn get_staking_address_from_public_key(public_key: &[u8]) -> JsResult<String> {
+        let public_key = public_key_argument(public_key)?;
+
+        Ok(staking_address_from_public_key(&public_key).to_string())
+    }
+
+    #[wasm_bindgen(js_name = isTransferAddressValid)]
+    pub fn is_transfer_address_valid(address: &str, network: &str) -> JsResult<bool> {
+        let network = network_argument(network)?;
+
+        Ok(network.decode_transfer_address(address).is_ok())
+    }
+
+    #[wasm_bindgen(js_name = isStakingAddressValid)]
+    pub fn is_staking_address_valid(address: &str) -> bool {
+        StakedStateAddress::from_str(address).is_ok()
+    }
+
+    #[wasm_bindgen(js_name = parseTransferAddress)]
+    pub fn parse_transfer_address(address: &str) -> JsResult<JsValue> {
+        let (network, extended_addr) =
+            decode_transfer_address(address).chain_js("Invalid transfer address")?;
+        let ExtendedAddr::OrTree(root_hash) = extended_addr;
+
+        js_object(&[
+            ("network", JsValue::from_str(&network.name)),
+            ("addressPrefix", JsValue::from_str(&network.address_prefix)),
+            ("rootHash", bytes_to_js(&root_hash)),
+        ])
+    }
+
+    #[wasm_bindgen(js_name = convertTransferAddress)]
+    pub fn convert_transfer_address(address: &str, network: &str) -> JsResult<String> {
+        let network = network_argument(network)?;
+
+        let (_, extended_addr) =
+            decode_transfer_address(address).chain_js("Invalid transfer address")?;
+
+        network
+            .encode_transfer_address(&extended_addr)
+            .chain_js("Unable to convert to CRO address")
+    }
+}
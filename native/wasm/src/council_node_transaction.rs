@@ -0,0 +1,84 @@
+use chain_core::state::account::UnjailTx;
+use chain_core::state::tendermint::TendermintValidatorPubKey;
+use chain_core::state::validator::NodeJoinRequestTx;
+use chain_core::tx::TransactionId;
+use cro_nodelib_core::council_node_transaction::node_join::{
+    sign_node_join_transaction, BuildNodeJoinTransactionOptions,
+};
+use cro_nodelib_core::council_node_transaction::unjail::{
+    sign_unjail_transaction, UnjailTxOptions,
+};
+use parity_scale_codec::Encode;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+use crate::schema::{from_js_value, SchemaResultJsExt};
+use crate::tx_aux::tx_aux_to_hex;
+
+#[wasm_bindgen]
+pub struct CouncilNodeTransaction {}
+
+#[wasm_bindgen]
+impl CouncilNodeTransaction {
+    #[wasm_bindgen(js_name = buildRawNodeJoinTransaction)]
+    pub fn build_raw_node_join_transaction(options: JsValue) -> JsResult<JsValue> {
+        let options: BuildNodeJoinTransactionOptions = from_js_value(&options)?;
+        let council_node = options.council_node().or_throw()?;
+
+        let TendermintValidatorPubKey::Ed25519(consensus_public_key) =
+            &council_node.consensus_pubkey;
+        let consensus_public_key = base64::encode(consensus_public_key);
+
+        let tx = options.to_tx(council_node);
+
+        js_object(&[
+            ("unsignedRawTx", bytes_to_js(&tx.encode())),
+            ("txId", JsValue::from_str(&hex::encode(tx.id()))),
+            (
+                "consensusPublicKey",
+                JsValue::from_str(&consensus_public_key),
+            ),
+        ])
+    }
+
+    #[wasm_bindgen(js_name = nodeJoinTransactionToHex)]
+    pub fn node_join_transaction_to_hex(
+        unsigned_raw_tx: &[u8],
+        key_pair: JsValue,
+    ) -> JsResult<JsValue> {
+        let node_join_request_tx: NodeJoinRequestTx = raw_tx_argument(unsigned_raw_tx)?;
+        let signer = signer_argument(&key_pair)?;
+
+        let tx_aux = sign_node_join_transaction(node_join_request_tx, &signer)
+            .chain_js("Error when signing transaction")?;
+
+        Ok(tx_aux_to_hex(&tx_aux))
+    }
+
+    #[wasm_bindgen(js_name = buildRawUnjailTransaction)]
+    pub fn build_raw_unjail_transaction(options: JsValue) -> JsResult<JsValue> {
+        let options: UnjailTxOptions = from_js_value(&options)?;
+
+        let tx = options.to_tx();
+
+        js_object(&[
+            ("unsignedRawTx", bytes_to_js(&tx.encode())),
+            ("txId", JsValue::from_str(&hex::encode(tx.id()))),
+        ])
+    }
+
+    #[wasm_bindgen(js_name = unjailTransactionToHex)]
+    pub fn unjail_transaction_to_hex(
+        unsigned_raw_tx: &[u8],
+        key_pair: JsValue,
+    ) -> JsResult<JsValue> {
+        let unjail_tx: UnjailTx = raw_tx_argument(unsigned_raw_tx)?;
+        let signer = signer_argument(&key_pair)?;
+
+        let tx_aux = sign_unjail_transaction(unjail_tx, &signer)
+            .chain_js("Error when signing transaction")?;
+
+        Ok(tx_aux_to_hex(&tx_aux))
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use wasm_bindgen::JsValue;
+
+pub type JsResult<T> = Result<T, JsValue>;
+
+pub trait ClientErrorJsExt<T> {
+    /// Adds given message to source error and converts it to a JS error
+    fn chain_js<M>(self, message: M) -> JsResult<T>
+    where
+        M: Display;
+}
+
+impl<T, E> ClientErrorJsExt<T> for Result<T, E>
+where
+    E: Display,
+{
+    #[inline]
+    fn chain_js<M>(self, message: M) -> JsResult<T>
+    where
+        M: Display,
+    {
+        self.map_err(|err| js_error(format!("{}: {}", message, err)))
+    }
+}
+
+impl<T> ClientErrorJsExt<T> for Option<T> {
+    #[inline]
+    fn chain_js<M>(self, message: M) -> JsResult<T>
+    where
+        M: Display,
+    {
+        self.ok_or_else(|| js_error(message))
+    }
+}
+
+/// JS `Error` of the message
+#[inline]
+pub fn js_error<M>(message: M) -> JsValue
+where
+    M: Display,
+{
+    js_sys::Error::new(&message.to_string()).into()
+}
@@ -0,0 +1,40 @@
+use cro_nodelib_core::fee_estimation::EstimateFeeOptions;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::js_object;
+use crate::schema::{from_js_value, SchemaResultJsExt};
+
+#[wasm_bindgen]
+pub struct FeeEstimation {}
+
+#[wasm_bindgen]
+impl FeeEstimation {
+    #[wasm_bindgen(js_name = estimateFee)]
+    pub fn estimate_fee(options: JsValue) -> JsResult<JsValue> {
+        let options: EstimateFeeOptions = from_js_value(&options)?;
+        options.verify().or_throw()?;
+
+        let estimation = options
+            .estimate()
+            .chain_js("Unable to estimate transaction fee")?;
+
+        let size = js_object(&[
+            (
+                "transaction",
+                JsValue::from(estimation.size.transaction as f64),
+            ),
+            ("witness", JsValue::from(estimation.size.witness as f64)),
+            ("overhead", JsValue::from(estimation.size.overhead() as f64)),
+            ("total", JsValue::from(estimation.size.total as f64)),
+        ])?;
+
+        js_object(&[
+            (
+                "fee",
+                JsValue::from_str(&u64::from(estimation.fee).to_string()),
+            ),
+            ("size", size),
+        ])
+    }
+}
@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use chain_core::common::{H256, HASH_SIZE_256};
+use chain_core::state::account::StakedStateOpWitness;
+use chain_core::tx::witness::TxInWitness;
+use client_common::{PrivateKey, PublicKey};
+use cro_nodelib_core::codec::{decode_transaction, decode_witness};
+use cro_nodelib_core::fee::FeeSchedule;
+use cro_nodelib_core::network::{network_by_name, NetworkDefinition};
+use cro_nodelib_core::signer::KeyPairSigner;
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use parity_scale_codec::{Decode, Encode};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::error::{js_error, ClientErrorJsExt, JsResult};
+use crate::schema::from_js_value;
+use crate::schema::types::{FeeConfigOptions, KeyPairOptions};
+
+#[inline]
+pub fn h256_str_argument(hash_str: &str) -> JsResult<H256> {
+    let decoded_hash_array =
+        hex::decode(hash_str).chain_js("input hash is not a valid hex string")?;
+
+    h256_buffer_argument(&decoded_hash_array)
+}
+
+#[inline]
+pub fn h256_buffer_argument(buffer: &[u8]) -> JsResult<H256> {
+    if buffer.len() != HASH_SIZE_256 {
+        return Err(js_error(format!(
+            "input hash should be a hex string of 32 bytes, {:?} is {} bytes",
+            buffer,
+            buffer.len()
+        )));
+    }
+
+    let mut h256_hash: H256 = [0; HASH_SIZE_256];
+    h256_hash.copy_from_slice(buffer);
+
+    Ok(h256_hash)
+}
+
+#[inline]
+pub fn public_key_argument(public_key: &[u8]) -> JsResult<PublicKey> {
+    PublicKey::deserialize_from(public_key).chain_js("Unable to deserialize public key")
+}
+
+#[inline]
+pub fn public_key_vector_argument(public_keys: &Array) -> JsResult<Vec<PublicKey>> {
+    public_keys
+        .iter()
+        .map(|public_key| {
+            let public_key = public_key
+                .dyn_into::<Uint8Array>()
+                .ok()
+                .chain_js("Unable to downcast public key")?
+                .to_vec();
+            let public_key = hex::encode_upper(public_key);
+
+            PublicKey::from_str(&public_key).chain_js("Unable to deserialize public key")
+        })
+        .collect()
+}
+
+#[inline]
+pub fn private_key_argument(private_key: &[u8]) -> JsResult<PrivateKey> {
+    PrivateKey::deserialize_from(private_key).chain_js("Unable to deserialize private key")
+}
+
+#[inline]
+pub fn key_pair_argument(key_pair: &JsValue) -> JsResult<(PrivateKey, PublicKey)> {
+    let key_pair: KeyPairOptions = from_js_value(key_pair)?;
+
+    Ok((key_pair.private_key, key_pair.public_key))
+}
+
+#[inline]
+pub fn signer_argument(key_pair: &JsValue) -> JsResult<KeyPairSigner> {
+    let (private_key, public_key) = key_pair_argument(key_pair)?;
+
+    KeyPairSigner::new(private_key, public_key).chain_js("Unable to create KeyPair signer")
+}
+
+#[inline]
+pub fn network_argument(network: &str) -> JsResult<NetworkDefinition> {
+    network_by_name(network).chain_js("Invalid network")
+}
+
+#[inline]
+pub fn fee_config_argument(fee_config: &JsValue) -> JsResult<FeeSchedule> {
+    let fee_config: FeeConfigOptions = from_js_value(fee_config)?;
+
+    Ok(fee_config.to_fee_algorithm())
+}
+
+/// Decode the raw transaction bytes returned by a `buildRaw*` function
+#[inline]
+pub fn raw_tx_argument<T>(raw_tx: &[u8]) -> JsResult<T>
+where
+    T: Decode + Encode,
+{
+    decode_transaction(raw_tx, "raw transaction").chain_js("Unable to decode raw transaction bytes")
+}
+
+#[inline]
+pub fn parse_staked_state_op_witness(witness: &[u8]) -> JsResult<StakedStateOpWitness> {
+    decode_witness(witness, "witness").chain_js("Unable to decode staked state op witness")
+}
+
+/// Decode the input witness bytes returned by a `signInput` function
+#[inline]
+pub fn tx_in_witness_argument(witness: &[u8]) -> JsResult<TxInWitness> {
+    decode_witness(witness, "witness").chain_js("Unable to decode witness")
+}
+
+#[inline]
+pub fn bytes_to_js(bytes: &[u8]) -> JsValue {
+    Uint8Array::from(bytes).into()
+}
+
+/// JS object of the entries, in order
+pub fn js_object(entries: &[(&str, JsValue)]) -> JsResult<JsValue> {
+    let object = Object::new();
+    for (key, value) in entries.iter() {
+        Reflect::set(&object, &JsValue::from_str(key), value)?;
+    }
+
+    Ok(object.into())
+}
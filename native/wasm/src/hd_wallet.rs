@@ -0,0 +1,87 @@
+use cro_nodelib_core::hd_wallet::{
+    seed_from_mnemonic, AddressDerivation, DeriveAddressesOptions, DerivedAddress,
+};
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{js_error, ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+use crate::schema::{from_js_value, SchemaResultJsExt};
+
+#[wasm_bindgen]
+pub struct HdWallet {}
+
+#[wasm_bindgen]
+impl HdWallet {
+    #[wasm_bindgen(js_name = getSeedFromMnemonic)]
+    pub fn get_seed_from_mnemonic(mnemonic: &str) -> JsResult<JsValue> {
+        let seed = seed_from_mnemonic(mnemonic).chain_js("Unable to deserialize mnemonic")?;
+
+        Ok(bytes_to_js(&seed))
+    }
+
+    #[wasm_bindgen(js_name = deriveKeyPairFromSeed)]
+    pub fn derive_key_pair_from_seed(
+        seed: &[u8],
+        network: &str,
+        account: u32,
+        index: u32,
+    ) -> JsResult<JsValue> {
+        let network = network_argument(network)?;
+
+        let (public_key, private_key) = network
+            .derive_key_pair(seed, account, index)
+            .chain_js("Unable to derive key pair")?;
+
+        js_object(&[
+            ("publicKey", bytes_to_js(&public_key.serialize())),
+            (
+                "compressedPublicKey",
+                bytes_to_js(&public_key.serialize_compressed()),
+            ),
+            ("privateKey", bytes_to_js(&private_key.serialize())),
+        ])
+    }
+
+    /// Derive the addresses of a range of indexes. There is no worker
+    /// thread on WebAssembly, so large ranges should be split by the caller.
+    #[wasm_bindgen(js_name = deriveAddressesFromSeed)]
+    pub fn derive_addresses_from_seed(options: JsValue) -> JsResult<Array> {
+        let options: DeriveAddressesOptions = from_js_value(&options)?;
+        let derivation = AddressDerivation::new(options).or_throw()?;
+
+        let derived_addresses = derivation.derive().map_err(js_error)?;
+
+        derived_addresses
+            .iter()
+            .map(derived_address_to_js_object)
+            .collect()
+    }
+}
+
+fn derived_address_to_js_object(derived_address: &DerivedAddress) -> JsResult<JsValue> {
+    let mut entries = vec![
+        ("index", JsValue::from(derived_address.index)),
+        (
+            "publicKey",
+            bytes_to_js(&derived_address.public_key.serialize()),
+        ),
+        (
+            "compressedPublicKey",
+            bytes_to_js(&derived_address.public_key.serialize_compressed()),
+        ),
+    ];
+    if let Some(ref private_key) = derived_address.private_key {
+        entries.push(("privateKey", bytes_to_js(&private_key.serialize())));
+    }
+    entries.push((
+        "transferAddress",
+        JsValue::from_str(&derived_address.transfer_address),
+    ));
+    entries.push((
+        "stakingAddress",
+        JsValue::from_str(&derived_address.staking_address),
+    ));
+
+    js_object(&entries)
+}
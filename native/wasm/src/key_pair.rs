@@ -0,0 +1,58 @@
+use client_common::{PrivateKey, PublicKey};
+use cro_nodelib_core::key_pair;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+
+#[wasm_bindgen]
+pub struct KeyPair {}
+
+#[wasm_bindgen]
+impl KeyPair {
+    #[wasm_bindgen(js_name = verifyPublicKey)]
+    pub fn verify_public_key(public_key: &[u8]) -> JsResult<()> {
+        public_key_argument(public_key).map(|_| ())
+    }
+
+    #[wasm_bindgen(js_name = verifyPrivateKey)]
+    pub fn verify_private_key(private_key: &[u8]) -> JsResult<()> {
+        private_key_argument(private_key).map(|_| ())
+    }
+
+    #[wasm_bindgen(js_name = getPublicKeysFromPrivateKey)]
+    pub fn get_public_keys_from_private_key(private_key: &[u8]) -> JsResult<JsValue> {
+        let private_key = private_key_argument(private_key)?;
+
+        public_keys_to_js_object(&PublicKey::from(&private_key))
+    }
+
+    #[wasm_bindgen(js_name = getPublicKeysFromAnyPublicKey)]
+    pub fn get_public_keys_from_any_public_key(public_key: &[u8]) -> JsResult<JsValue> {
+        let public_key = public_key_argument(public_key)?;
+
+        public_keys_to_js_object(&public_key)
+    }
+
+    #[wasm_bindgen(js_name = newPrivateKey)]
+    pub fn new_private_key() -> JsResult<JsValue> {
+        let private_key = PrivateKey::new().chain_js("Unable to create new private key")?;
+
+        Ok(bytes_to_js(&private_key.serialize()))
+    }
+
+    #[wasm_bindgen(js_name = isValidViewKey)]
+    pub fn is_valid_view_key(view_key: &[u8]) -> bool {
+        key_pair::is_valid_view_key(view_key)
+    }
+}
+
+fn public_keys_to_js_object(public_key: &PublicKey) -> JsResult<JsValue> {
+    js_object(&[
+        ("publicKey", bytes_to_js(&public_key.serialize())),
+        (
+            "compressedPublicKey",
+            bytes_to_js(&public_key.serialize_compressed()),
+        ),
+    ])
+}
@@ -0,0 +1,28 @@
+//! WebAssembly bindings of the node library core for browser and extension
+//! wallets
+//!
+//! Only the offline capabilities are exposed: key pairs, HD wallet,
+//! addresses, raw transaction builders, signing, multi-signature sessions
+//! and fee estimation. Each module of the neon bindings is exported as a
+//! class of static functions with the same function names and arguments,
+//! e.g. `MultiSig.newSession`.
+//!
+//! Transactions sent to the enclave are obfuscated by the host instead of a
+//! Tendermint websocket client. The `obfuscate` callback receives the
+//! encoded signed transaction as a `Uint8Array` and returns, or resolves to,
+//! the encoded `TxAux`, which is checked to be the obfuscation of the signed
+//! transaction. `mockObfuscate` can be passed as the callback on networks
+//! without enclave.
+mod address;
+mod council_node_transaction;
+mod error;
+mod fee_estimation;
+mod function_types;
+mod hd_wallet;
+mod key_pair;
+mod multi_sig;
+mod network;
+mod schema;
+mod staking_transaction;
+mod transfer_transaction;
+mod tx_aux;
@@ -0,0 +1,184 @@
+use chain_core::common::H256;
+use cro_nodelib_core::multi_sig::{multi_sig_transfer_address, verify_signature, MultiSigBuilder};
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+
+/// Multi-signature session, restored from and saved to its incomplete bytes
+/// between each round
+#[wasm_bindgen]
+pub struct MultiSig {}
+
+#[wasm_bindgen]
+impl MultiSig {
+    /// Create new multisig address of `required_signers` out of the public
+    /// keys
+    #[wasm_bindgen(js_name = createAddress)]
+    pub fn create_address(
+        public_keys: Array,
+        self_public_key: &[u8],
+        required_signers: usize,
+        network: &str,
+    ) -> JsResult<String> {
+        let public_keys = public_key_vector_argument(&public_keys)?;
+        let self_public_key = public_key_argument(self_public_key)?;
+        let network = network_argument(network)?;
+
+        multi_sig_transfer_address(&network, public_keys, self_public_key, required_signers)
+            .chain_js("Unable to create MultiSig address")
+    }
+
+    /// Create new multisig session of the hex message, returns the
+    /// incomplete session
+    #[wasm_bindgen(js_name = newSession)]
+    pub fn new_session(
+        message: &str,
+        signer_public_keys: Array,
+        self_public_key: &[u8],
+        self_private_key: &[u8],
+    ) -> JsResult<JsValue> {
+        let message = h256_str_argument(message)?;
+        let signer_public_keys = public_key_vector_argument(&signer_public_keys)?;
+        let self_public_key = public_key_argument(self_public_key)?;
+        let self_private_key = private_key_argument(self_private_key)?;
+
+        let session = MultiSigBuilder::new(
+            message,
+            signer_public_keys,
+            self_public_key,
+            self_private_key,
+        )
+        .chain_js("Unable to create new MultiSigBuilder")?;
+
+        Ok(bytes_to_js(&session.to_incomplete()))
+    }
+
+    /// Generate nonce commitment, returns `{ incompleteSession,
+    /// nonceCommitment }`
+    #[wasm_bindgen(js_name = generateNonceCommitment)]
+    pub fn generate_nonce_commitment(incomplete_session: &[u8]) -> JsResult<JsValue> {
+        let mut session = session_argument(incomplete_session)?;
+
+        let nonce_commitment = session
+            .nonce_commitment()
+            .chain_js("Unable to process nonce commitment")?;
+
+        js_object(&[
+            ("incompleteSession", bytes_to_js(&session.to_incomplete())),
+            ("nonceCommitment", bytes_to_js(&nonce_commitment)),
+        ])
+    }
+
+    /// Add nonce commitment of another signer to session
+    #[wasm_bindgen(js_name = addNonceCommitment)]
+    pub fn add_nonce_commitment(
+        incomplete_session: &[u8],
+        public_key: &[u8],
+        nonce_commitment: &[u8],
+    ) -> JsResult<JsValue> {
+        let mut session = session_argument(incomplete_session)?;
+        let public_key = public_key_argument(public_key)?;
+        let nonce_commitment = h256_buffer_argument(nonce_commitment)?;
+
+        session
+            .add_nonce_commitment(&public_key, nonce_commitment)
+            .chain_js("Unable to add nonce commitment")?;
+
+        Ok(bytes_to_js(&session.to_incomplete()))
+    }
+
+    /// Generate nonce, returns `{ incompleteSession, nonce }`. Throws if any
+    /// required signer's nonce commitment is missing
+    #[wasm_bindgen(js_name = generateNonce)]
+    pub fn generate_nonce(incomplete_session: &[u8]) -> JsResult<JsValue> {
+        let mut session = session_argument(incomplete_session)?;
+
+        let nonce = session.nonce().chain_js("Unable to process nonce")?;
+
+        js_object(&[
+            ("incompleteSession", bytes_to_js(&session.to_incomplete())),
+            ("nonce", bytes_to_js(&nonce)),
+        ])
+    }
+
+    /// Add nonce of another signer to session
+    #[wasm_bindgen(js_name = addNonce)]
+    pub fn add_nonce(
+        incomplete_session: &[u8],
+        public_key: &[u8],
+        nonce: &[u8],
+    ) -> JsResult<JsValue> {
+        let mut session = session_argument(incomplete_session)?;
+        let public_key = public_key_argument(public_key)?;
+        let nonce = h256_buffer_argument(nonce)?;
+
+        session
+            .add_nonce(&public_key, &nonce)
+            .chain_js("Unable to add nonce")?;
+
+        Ok(bytes_to_js(&session.to_incomplete()))
+    }
+
+    /// Partially sign the message, returns `{ incompleteSession,
+    /// partialSignature }`
+    #[wasm_bindgen(js_name = partialSign)]
+    pub fn partial_sign(incomplete_session: &[u8]) -> JsResult<JsValue> {
+        let mut session = session_argument(incomplete_session)?;
+
+        let partial_signature: H256 = session
+            .partial_signature()
+            .chain_js("Unable to partially sign")?;
+
+        js_object(&[
+            ("incompleteSession", bytes_to_js(&session.to_incomplete())),
+            ("partialSignature", bytes_to_js(&partial_signature)),
+        ])
+    }
+
+    /// Add partial signature of another signer to session
+    #[wasm_bindgen(js_name = addPartialSignature)]
+    pub fn add_partial_signature(
+        incomplete_session: &[u8],
+        public_key: &[u8],
+        partial_signature: &[u8],
+    ) -> JsResult<JsValue> {
+        let mut session = session_argument(incomplete_session)?;
+        let public_key = public_key_argument(public_key)?;
+        let partial_signature = h256_buffer_argument(partial_signature)?;
+
+        session
+            .add_partial_signature(&public_key, partial_signature)
+            .chain_js("Unable to add partial signature")?;
+
+        Ok(bytes_to_js(&session.to_incomplete()))
+    }
+
+    /// Final signature of the session
+    pub fn sign(incomplete_session: &[u8]) -> JsResult<JsValue> {
+        let session = session_argument(incomplete_session)?;
+
+        let signature = session
+            .signature()
+            .chain_js("Unable to create signature")?
+            .serialize_default();
+
+        Ok(bytes_to_js(&signature))
+    }
+
+    /// Verify the final signature of the hex message against all co-signers'
+    /// public keys
+    pub fn verify(signature: &[u8], message: &str, public_keys: Array) -> JsResult<bool> {
+        let message = h256_str_argument(message)?;
+        let public_keys = public_key_vector_argument(&public_keys)?;
+
+        verify_signature(signature, &message, public_keys).chain_js("Unable to verify signature")
+    }
+}
+
+#[inline]
+fn session_argument(incomplete_session: &[u8]) -> JsResult<MultiSigBuilder> {
+    MultiSigBuilder::from_incomplete_insecure(incomplete_session.to_vec())
+        .chain_js("Unable to restore the incomplete session")
+}
@@ -0,0 +1,61 @@
+//! JS bindings of the network registry
+use cro_nodelib_core::network::{
+    insert_network, network_by_chain_hex_id, registered_networks, NetworkDefinition,
+};
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{js_error, ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+use crate::schema::from_js_value;
+
+#[wasm_bindgen]
+pub struct Network {}
+
+#[wasm_bindgen]
+impl Network {
+    #[wasm_bindgen(js_name = registerNetwork)]
+    pub fn register_network(network: JsValue) -> JsResult<()> {
+        let network: NetworkDefinition = from_js_value(&network)?;
+
+        insert_network(network).chain_js("Unable to register network")
+    }
+
+    #[wasm_bindgen(js_name = getNetworkByChainHexId)]
+    pub fn get_network_by_chain_hex_id(chain_hex_id: &[u8]) -> JsResult<JsValue> {
+        if chain_hex_id.len() != 1 {
+            return Err(js_error("Chain hex id must be 8 bit long"));
+        }
+
+        let network =
+            network_by_chain_hex_id(chain_hex_id[0]).chain_js("Unable to find network")?;
+
+        network_to_js_object(&network)
+    }
+
+    #[wasm_bindgen(js_name = getNetworkByName)]
+    pub fn get_network_by_name(network: &str) -> JsResult<JsValue> {
+        let network = network_argument(network)?;
+
+        network_to_js_object(&network)
+    }
+
+    #[wasm_bindgen(js_name = listNetworks)]
+    pub fn list_networks() -> JsResult<Array> {
+        registered_networks()
+            .iter()
+            .map(network_to_js_object)
+            .collect()
+    }
+}
+
+fn network_to_js_object(network: &NetworkDefinition) -> JsResult<JsValue> {
+    let mut entries = vec![("name", JsValue::from_str(&network.name))];
+    if let Some(chain_hex_id) = network.chain_hex_id {
+        entries.push(("chainHexId", bytes_to_js(&[chain_hex_id])));
+    }
+    entries.push(("addressPrefix", JsValue::from_str(&network.address_prefix)));
+    entries.push(("bip44CoinType", JsValue::from(network.bip44_coin_type)));
+
+    js_object(&entries)
+}
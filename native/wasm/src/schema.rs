@@ -0,0 +1,115 @@
+//! Declarative conversion of JavaScript option objects into Rust structs
+//!
+//! Same as the neon bindings, JS values are first converted to a
+//! `serde_json::Value`, with `Uint8Array` (and so Node.js `Buffer`) as
+//! objects tagged with `de::BUFFER_TAG`, and then deserialized with the
+//! path of the offending field on failure.
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use wasm_bindgen::{JsCast, JsValue};
+
+use cro_nodelib_core::schema::{de, from_js_json_value, SchemaError, SchemaResult};
+
+use crate::error::{js_error, JsResult};
+
+pub use cro_nodelib_core::schema::types;
+
+/// Maximum nesting level of a JS value accepted as options
+const MAX_DEPTH: usize = 32;
+
+pub trait SchemaResultJsExt<T> {
+    /// Converts the schema error to a JS error
+    fn or_throw(self) -> JsResult<T>;
+}
+
+impl<T> SchemaResultJsExt<T> for SchemaResult<T> {
+    #[inline]
+    fn or_throw(self) -> JsResult<T> {
+        self.map_err(js_error)
+    }
+}
+
+/// Deserialize a JS value into `T` and throw an error with the path of the
+/// offending field on failure
+#[inline]
+pub fn from_js_value<T>(value: &JsValue) -> JsResult<T>
+where
+    T: DeserializeOwned,
+{
+    let value = to_json_value(value, "", 0)?;
+
+    from_js_json_value(value).or_throw()
+}
+
+fn to_json_value(value: &JsValue, path: &str, depth: usize) -> JsResult<Value> {
+    if depth > MAX_DEPTH {
+        return Err(js_error(SchemaError::new(
+            path,
+            "value is nested too deeply",
+        )));
+    }
+
+    if value.is_null() || value.is_undefined() {
+        Ok(Value::Null)
+    } else if let Some(boolean) = value.as_bool() {
+        Ok(Value::Bool(boolean))
+    } else if let Some(number) = value.as_f64() {
+        Ok(number_to_json_value(number))
+    } else if let Some(string) = value.as_string() {
+        Ok(Value::String(string))
+    } else if value.is_instance_of::<Uint8Array>() {
+        let bytes = Uint8Array::new(value).to_vec();
+
+        let mut tagged = Map::with_capacity(1);
+        tagged.insert(de::BUFFER_TAG.to_owned(), Value::String(hex::encode(bytes)));
+
+        Ok(Value::Object(tagged))
+    } else if Array::is_array(value) {
+        Array::from(value)
+            .iter()
+            .enumerate()
+            .map(|(i, item)| to_json_value(&item, &format!("{}[{}]", path, i), depth + 1))
+            .collect::<JsResult<Vec<Value>>>()
+            .map(Value::Array)
+    } else if value.is_function() {
+        Err(js_error(SchemaError::new(
+            path,
+            "functions are not supported",
+        )))
+    } else if value.is_object() {
+        let keys = Object::keys(value.unchecked_ref::<Object>());
+
+        let mut map = Map::with_capacity(keys.length() as usize);
+        for key in keys.iter() {
+            let item = Reflect::get(value, &key)?;
+            let key = match key.as_string() {
+                Some(key) => key,
+                None => continue,
+            };
+            let item_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+
+            map.insert(key, to_json_value(&item, &item_path, depth + 1)?);
+        }
+
+        Ok(Value::Object(map))
+    } else {
+        Err(js_error(SchemaError::new(path, "unsupported value type")))
+    }
+}
+
+fn number_to_json_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number >= 0.0 && number <= u64::max_value() as f64 {
+        Value::Number(Number::from(number as u64))
+    } else if number.fract() == 0.0 && number < 0.0 && number >= i64::min_value() as f64 {
+        Value::Number(Number::from(number as i64))
+    } else {
+        Number::from_f64(number)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
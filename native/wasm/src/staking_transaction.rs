@@ -0,0 +1,258 @@
+use chain_core::state::account::{DepositBondTx, UnbondTx, WithdrawUnbondedTx};
+use chain_core::tx::data::output::TxOut;
+use chain_core::tx::witness::TxInWitness;
+use chain_core::tx::TransactionId;
+use client_common::SignedTransaction;
+use cro_nodelib_core::staking_transaction::deposit::{
+    deposit_amounts, sign_deposit_input, verify_deposit_input_witness,
+    BuildDepositTransactionOptions,
+};
+use cro_nodelib_core::staking_transaction::staked_state_op_witness;
+use cro_nodelib_core::staking_transaction::unbond::{
+    sign_unbond_transaction, BuildUnbondTransactionOptions,
+};
+use cro_nodelib_core::staking_transaction::withdraw_unbonded::{
+    estimate_withdraw_unbonded_fee, BuildWithdrawUnbondedTransactionOptions,
+};
+use js_sys::{Array, Function, Uint8Array};
+use parity_scale_codec::Encode;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+use crate::schema::types::{network_of_chain_hex_id, OutputOptions};
+use crate::schema::{from_js_value, SchemaResultJsExt};
+use crate::tx_aux::{signed_transaction_to_hex, tx_aux_to_hex};
+
+#[wasm_bindgen]
+pub struct StakingTransaction {}
+
+#[wasm_bindgen]
+impl StakingTransaction {
+    #[wasm_bindgen(js_name = buildRawDepositTransaction)]
+    pub fn build_raw_deposit_transaction(options: JsValue) -> JsResult<JsValue> {
+        let options: BuildDepositTransactionOptions = from_js_value(&options)?;
+        let spent_outputs = options.spent_outputs().or_throw()?;
+
+        let tx = options.to_tx();
+
+        let mut entries = vec![
+            ("unsignedRawTx", bytes_to_js(&tx.encode())),
+            ("txId", JsValue::from_str(&hex::encode(tx.id()))),
+        ];
+        if let (Some(ref spent_outputs), Some(ref fee_config)) =
+            (spent_outputs, &options.fee_config)
+        {
+            let amounts = deposit_amounts(&tx, spent_outputs, &fee_config.to_fee_algorithm())
+                .chain_js("Unable to compute deposit amount")?;
+
+            entries.push((
+                "inputAmount",
+                JsValue::from_str(&u64::from(amounts.input_amount).to_string()),
+            ));
+            entries.push((
+                "fee",
+                JsValue::from_str(&u64::from(amounts.fee).to_string()),
+            ));
+            entries.push((
+                "depositAmount",
+                JsValue::from_str(&u64::from(amounts.deposit_amount).to_string()),
+            ));
+        }
+
+        js_object(&entries)
+    }
+
+    /// Sign an input of the deposit transaction with the provided KeyPair.
+    /// The previous output spent by the input is optional and verified to
+    /// belong to the key pair when provided.
+    #[wasm_bindgen(js_name = signDepositTransactionInput)]
+    pub fn sign_deposit_transaction_input(
+        unsigned_raw_tx: &[u8],
+        prev_output: JsValue,
+        key_pair: JsValue,
+    ) -> JsResult<JsValue> {
+        let deposit_bond_tx: DepositBondTx = raw_tx_argument(unsigned_raw_tx)?;
+        let prev_output = prev_output_argument(&prev_output, &deposit_bond_tx)?;
+        let (private_key, public_key) = key_pair_argument(&key_pair)?;
+
+        let witness = sign_deposit_input(
+            &deposit_bond_tx,
+            prev_output.as_ref(),
+            private_key,
+            public_key,
+        )
+        .chain_js("Unable to sign transaction")?;
+
+        Ok(bytes_to_js(&witness.encode()))
+    }
+
+    /// Verify the witness unlocks the output spent by an input of the
+    /// deposit transaction
+    #[wasm_bindgen(js_name = verifyDepositTransactionInputWitness)]
+    pub fn verify_deposit_transaction_input_witness(
+        unsigned_raw_tx: &[u8],
+        prev_output: JsValue,
+        witness: &[u8],
+    ) -> JsResult<()> {
+        let deposit_bond_tx: DepositBondTx = raw_tx_argument(unsigned_raw_tx)?;
+        let prev_output = prev_output_argument(&prev_output, &deposit_bond_tx)?
+            .chain_js("Missing previous output of the input")?;
+        let witness = tx_in_witness_argument(witness)?;
+
+        verify_deposit_input_witness(&deposit_bond_tx, &prev_output, &witness)
+            .chain_js("Unable to verify input witness")
+    }
+
+    /// Finish the deposit transaction with the witnesses of its inputs and
+    /// export to broadcast-able hex, obfuscated by the host callback
+    #[wasm_bindgen(js_name = depositTransactionToHex)]
+    pub async fn deposit_transaction_to_hex(
+        unsigned_raw_tx: Vec<u8>,
+        witnesses: Array,
+        obfuscate: Function,
+    ) -> JsResult<JsValue> {
+        let deposit_bond_tx: DepositBondTx = raw_tx_argument(&unsigned_raw_tx)?;
+        let witnesses = tx_in_witness_vec_argument(&witnesses)?;
+
+        let signed_transaction =
+            SignedTransaction::DepositStakeTransaction(deposit_bond_tx, witnesses.into());
+
+        signed_transaction_to_hex(signed_transaction, &obfuscate).await
+    }
+
+    #[wasm_bindgen(js_name = buildRawUnbondTransaction)]
+    pub fn build_raw_unbond_transaction(options: JsValue) -> JsResult<JsValue> {
+        let options: BuildUnbondTransactionOptions = from_js_value(&options)?;
+        let amount = options.unbond_amount().or_throw()?;
+        let unbonded_from = options.unbonded_from().or_throw()?;
+
+        let tx = options.to_tx(amount);
+
+        js_object(&[
+            ("unsignedRawTx", bytes_to_js(&tx.encode())),
+            ("txId", JsValue::from_str(&hex::encode(tx.id()))),
+            ("amount", JsValue::from_str(&u64::from(amount).to_string())),
+            ("unbondedFrom", JsValue::from(unbonded_from as f64)),
+        ])
+    }
+
+    #[wasm_bindgen(js_name = unbondTransactionToHex)]
+    pub fn unbond_transaction_to_hex(
+        unsigned_raw_tx: &[u8],
+        key_pair: JsValue,
+    ) -> JsResult<JsValue> {
+        let unbond_tx: UnbondTx = raw_tx_argument(unsigned_raw_tx)?;
+        let signer = signer_argument(&key_pair)?;
+
+        let tx_aux = sign_unbond_transaction(unbond_tx, &signer)
+            .chain_js("Error when signing transaction")?;
+
+        Ok(tx_aux_to_hex(&tx_aux))
+    }
+
+    #[wasm_bindgen(js_name = buildRawWithdrawUnbondedTransaction)]
+    pub fn build_raw_withdraw_unbonded_transaction(options: JsValue) -> JsResult<JsValue> {
+        let options: BuildWithdrawUnbondedTransactionOptions = from_js_value(&options)?;
+        let tx = options.to_tx().or_throw()?;
+
+        js_object(&[
+            ("unsignedRawTx", bytes_to_js(&tx.encode())),
+            ("txId", JsValue::from_str(&hex::encode(tx.id()))),
+        ])
+    }
+
+    #[wasm_bindgen(js_name = estimateWithdrawUnbondedTransactionFee)]
+    pub fn estimate_withdraw_unbonded_transaction_fee(
+        unsigned_raw_tx: &[u8],
+        fee_config: JsValue,
+    ) -> JsResult<String> {
+        let withdraw_unbonded_tx: WithdrawUnbondedTx = raw_tx_argument(unsigned_raw_tx)?;
+        let fee_config = fee_config_argument(&fee_config)?;
+
+        let estimated_fee = estimate_withdraw_unbonded_fee(withdraw_unbonded_tx, &fee_config)
+            .chain_js("Unable to estimate transaction fee")?;
+
+        Ok(u64::from(estimated_fee).to_string())
+    }
+
+    #[wasm_bindgen(js_name = withdrawUnbondedTransactionToWitness)]
+    pub fn withdraw_unbonded_transaction_to_witness(
+        unsigned_raw_tx: &[u8],
+        key_pair: JsValue,
+    ) -> JsResult<JsValue> {
+        let withdraw_unbonded_tx: WithdrawUnbondedTx = raw_tx_argument(unsigned_raw_tx)?;
+        let signer = signer_argument(&key_pair)?;
+
+        let witness = staked_state_op_witness(&signer, &withdraw_unbonded_tx.id())
+            .chain_js("Error when signing transaction")?;
+
+        Ok(bytes_to_js(&witness.encode()))
+    }
+
+    #[wasm_bindgen(js_name = withdrawUnbondedTransactionToSignedPlainHex)]
+    pub fn withdraw_unbonded_transaction_to_signed_plain_hex(
+        unsigned_raw_tx: &[u8],
+        witness: &[u8],
+    ) -> JsResult<JsValue> {
+        let withdraw_unbonded_tx: WithdrawUnbondedTx = raw_tx_argument(unsigned_raw_tx)?;
+        let witness = parse_staked_state_op_witness(witness)?;
+
+        let signed_transaction =
+            SignedTransaction::WithdrawUnbondedStakeTransaction(withdraw_unbonded_tx, witness);
+
+        Ok(bytes_to_js(&signed_transaction.encode()))
+    }
+
+    /// Finish the withdraw unbonded transaction with its witness and export
+    /// to broadcast-able hex, obfuscated by the host callback
+    #[wasm_bindgen(js_name = withdrawUnbondedTransactionToObfuscatedHex)]
+    pub async fn withdraw_unbonded_transaction_to_obfuscated_hex(
+        unsigned_raw_tx: Vec<u8>,
+        witness: Vec<u8>,
+        obfuscate: Function,
+    ) -> JsResult<JsValue> {
+        let withdraw_unbonded_tx: WithdrawUnbondedTx = raw_tx_argument(&unsigned_raw_tx)?;
+        let witness = parse_staked_state_op_witness(&witness)?;
+
+        let signed_transaction =
+            SignedTransaction::WithdrawUnbondedStakeTransaction(withdraw_unbonded_tx, witness);
+
+        signed_transaction_to_hex(signed_transaction, &obfuscate).await
+    }
+}
+
+fn tx_in_witness_vec_argument(witnesses: &Array) -> JsResult<Vec<TxInWitness>> {
+    witnesses
+        .iter()
+        .map(|witness| {
+            let witness = witness
+                .dyn_into::<Uint8Array>()
+                .ok()
+                .chain_js("Unable to downcast witness")?
+                .to_vec();
+
+            tx_in_witness_argument(&witness)
+        })
+        .collect()
+}
+
+/// Parse the optional previous output argument with the network of the
+/// deposit transaction
+fn prev_output_argument(
+    prev_output: &JsValue,
+    deposit_bond_tx: &DepositBondTx,
+) -> JsResult<Option<TxOut>> {
+    let prev_output: Option<OutputOptions> = from_js_value(prev_output)?;
+
+    prev_output
+        .map(|prev_output| {
+            let network =
+                network_of_chain_hex_id(deposit_bond_tx.attributes.chain_hex_id, "prevOutput")?;
+
+            prev_output.to_tx_out(&network, "prevOutput")
+        })
+        .transpose()
+        .or_throw()
+}
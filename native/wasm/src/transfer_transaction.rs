@@ -0,0 +1,131 @@
+use chain_core::tx::witness::TxInWitness;
+use client_common::SignedTransaction;
+use cro_nodelib_core::codec::decode_witness;
+use cro_nodelib_core::transfer_transaction::{
+    self, build_incomplete, signed_transfer_transaction, BuilderOptions,
+    FeeScheduleRawTransferTransactionBuilder, IncompleteBuilderOptions,
+};
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ClientErrorJsExt, JsResult};
+use crate::function_types::*;
+use crate::schema::{from_js_value, SchemaResultJsExt};
+use crate::tx_aux::signed_transaction_to_hex;
+
+#[wasm_bindgen]
+pub struct TransferTransaction {}
+
+#[wasm_bindgen]
+impl TransferTransaction {
+    /// Create a basic transfer transaction builder without witnesses
+    #[wasm_bindgen(js_name = buildIncompleteHex)]
+    pub fn build_incomplete_hex(options: JsValue) -> JsResult<JsValue> {
+        let options: BuilderOptions = from_js_value(&options)?;
+        let raw_tx_options = options.to_raw_tx_options().or_throw()?;
+
+        let builder = build_incomplete(&raw_tx_options, options.fee_config.to_fee_algorithm())
+            .chain_js("Unable to build incomplete transaction")?;
+
+        Ok(bytes_to_js(&builder.to_incomplete()))
+    }
+
+    /// Add witness to a particular input
+    #[wasm_bindgen(js_name = addInputWitness)]
+    pub fn add_input_witness(
+        incomplete_builder: JsValue,
+        input_index: usize,
+        witness: &[u8],
+    ) -> JsResult<JsValue> {
+        let mut builder = incomplete_builder_argument(&incomplete_builder)?;
+        let witness: TxInWitness =
+            decode_witness(witness, "witness").chain_js("Unable to decode raw witness bytes")?;
+
+        builder
+            .add_witness(input_index, witness)
+            .chain_js("Unable to add witness to input")?;
+
+        Ok(bytes_to_js(&builder.to_incomplete()))
+    }
+
+    /// Sign a particular input with the provided KeyPair
+    #[wasm_bindgen(js_name = signInput)]
+    pub fn sign_input(
+        incomplete_builder: JsValue,
+        input_index: usize,
+        key_pair: JsValue,
+    ) -> JsResult<JsValue> {
+        let mut builder = incomplete_builder_argument(&incomplete_builder)?;
+        let signer = signer_argument(&key_pair)?;
+
+        transfer_transaction::sign_input(&mut builder, input_index, &signer)
+            .chain_js("Unable to sign input")?;
+
+        Ok(bytes_to_js(&builder.to_incomplete()))
+    }
+
+    /// Determine if all the transaction inputs have signature
+    #[wasm_bindgen(js_name = isCompleted)]
+    pub fn is_completed(incomplete_builder: JsValue) -> JsResult<bool> {
+        let builder = incomplete_builder_argument(&incomplete_builder)?;
+
+        Ok(builder.is_completed())
+    }
+
+    /// Returns transaction Id of builder
+    #[wasm_bindgen(js_name = txId)]
+    pub fn tx_id(incomplete_builder: JsValue) -> JsResult<String> {
+        let builder = incomplete_builder_argument(&incomplete_builder)?;
+
+        Ok(hex::encode(builder.tx_id()))
+    }
+
+    /// Returns the estimated fee of builder
+    #[wasm_bindgen(js_name = estimateFee)]
+    pub fn estimate_fee(incomplete_builder: JsValue) -> JsResult<String> {
+        let builder = incomplete_builder_argument(&incomplete_builder)?;
+
+        let estimated_fee = builder
+            .estimate_fee()
+            .chain_js("Unable to estimate transaction fee")?;
+
+        Ok(u64::from(estimated_fee.to_coin()).to_string())
+    }
+
+    /// Verify the provided incomplete RawTransferTransaction hex is a valid
+    /// transaction to be broadcasted
+    pub fn verify(incomplete_builder: JsValue) -> JsResult<()> {
+        let builder = incomplete_builder_argument(&incomplete_builder)?;
+
+        builder
+            .verify()
+            .chain_js("Error when trying to verify raw transfer transaction")
+    }
+
+    /// Finish the transaction and export to broadcast-able hex, obfuscated
+    /// by the host callback
+    #[wasm_bindgen(js_name = toHex)]
+    pub async fn to_hex(incomplete_builder: JsValue, obfuscate: Function) -> JsResult<JsValue> {
+        let builder = incomplete_builder_argument(&incomplete_builder)?;
+
+        let (tx, witness) =
+            signed_transfer_transaction(&builder).chain_js("Unable to finish transaction")?;
+
+        signed_transaction_to_hex(
+            SignedTransaction::TransferTransaction(tx, witness),
+            &obfuscate,
+        )
+        .await
+    }
+}
+
+#[inline]
+fn incomplete_builder_argument(
+    incomplete_builder: &JsValue,
+) -> JsResult<FeeScheduleRawTransferTransactionBuilder> {
+    let incomplete_builder: IncompleteBuilderOptions = from_js_value(incomplete_builder)?;
+
+    incomplete_builder
+        .to_builder()
+        .chain_js("Unable to deserialize raw transfer transaction hex")
+}
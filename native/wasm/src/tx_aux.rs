@@ -0,0 +1,60 @@
+use chain_core::tx::TxAux;
+use client_common::SignedTransaction;
+use cro_nodelib_core::codec::decode_transaction;
+use cro_nodelib_core::tx_aux::{host_obfuscated_tx_aux, signed_transaction_to_tx_aux, Features};
+use js_sys::{Function, Promise, Uint8Array};
+use parity_scale_codec::Encode;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::error::{js_error, ClientErrorJsExt, JsResult};
+use crate::function_types::bytes_to_js;
+
+/// Obfuscate the signed transaction with the host callback and export it to
+/// broadcast-able hex
+pub async fn signed_transaction_to_hex(
+    signed_transaction: SignedTransaction,
+    obfuscate: &Function,
+) -> JsResult<JsValue> {
+    let tx_aux = obfuscate_with_host(&signed_transaction, obfuscate).await?;
+
+    Ok(tx_aux_to_hex(&tx_aux))
+}
+
+async fn obfuscate_with_host(
+    signed_transaction: &SignedTransaction,
+    obfuscate: &Function,
+) -> JsResult<TxAux> {
+    let request = bytes_to_js(&signed_transaction.encode());
+    let response = obfuscate.call1(&JsValue::NULL, &request)?;
+    let response = match response.dyn_into::<Promise>() {
+        Ok(promise) => JsFuture::from(promise).await?,
+        Err(response) => response,
+    };
+    let tx_aux = response
+        .dyn_into::<Uint8Array>()
+        .map_err(|_| js_error("Obfuscate callback should return the TxAux bytes"))?
+        .to_vec();
+
+    host_obfuscated_tx_aux(signed_transaction, &tx_aux).chain_js("Unable to encrypt transaction")
+}
+
+#[inline]
+pub fn tx_aux_to_hex(tx_aux: &TxAux) -> JsValue {
+    bytes_to_js(&tx_aux.encode())
+}
+
+/// Obfuscate callback keeping the signed transaction in plain, for networks
+/// without enclave
+#[wasm_bindgen(js_name = mockObfuscate)]
+pub fn mock_obfuscate(signed_transaction: &[u8]) -> JsResult<JsValue> {
+    let signed_transaction: SignedTransaction =
+        decode_transaction(signed_transaction, "signed transaction")
+            .chain_js("Unable to decode signed transaction bytes")?;
+
+    let tx_aux = signed_transaction_to_tx_aux(signed_transaction, "", Features::MockObfuscation)
+        .chain_js("Unable to encrypt transaction")?;
+
+    Ok(tx_aux_to_hex(&tx_aux))
+}